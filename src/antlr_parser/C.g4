@@ -149,6 +149,7 @@ constantExpression
 declaration
     :   declarationSpecifiers initDeclaratorList ';'
     |   staticAssertDeclaration
+    |   declarationSpecifiers ';' // struct S {...}; enum E {...};
     ;
 
 declarationSpecifiers
@@ -492,12 +493,11 @@ forMidExpression
     ;
 
 jumpStatement
-    // :   ('goto' Identifier
     :   continueStatement
     |   breakStatement
     |   returnStatement
+    |   'goto' Identifier ';'
     // |   'goto' unaryExpression // GCC extension
-    // )
     ;
 
 breakpointStatement
@@ -4112,13 +4112,8 @@ where
 
 			recog.base.set_state(389);
 			recog.err_handler.sync(&mut recog.base)?;
-			match recog.base.input.la(1) {
-			 T__0 | T__1 | T__2 | T__3 | T__4 | T__5 | T__6 | T__7 | Auto | Char |
-			 Const | Double | Enum | Extern | Float | Inline | Int | Long | Register |
-			 Restrict | Short | Signed | Static | Struct | Typedef | Union | Unsigned |
-			 Void | Volatile | Alignas | Atomic | Bool | Complex | Noreturn | ThreadLocal |
-			 Identifier 
-				=> {
+			match  recog.interpreter.adaptive_predict(22,&mut recog.base)? {
+				1 =>{
 					//recog.base.enter_outer_alt(_localctx.clone(), 1);
 					recog.base.enter_outer_alt(None, 1);
 					{
@@ -4135,9 +4130,8 @@ where
 
 					}
 				}
-
-			 StaticAssert 
-				=> {
+			,
+				2 =>{
 					//recog.base.enter_outer_alt(_localctx.clone(), 2);
 					recog.base.enter_outer_alt(None, 2);
 					{
@@ -4147,8 +4141,22 @@ where
 
 					}
 				}
+			,
+				3 =>{
+					//recog.base.enter_outer_alt(_localctx.clone(), 3);
+					recog.base.enter_outer_alt(None, 3);
+					{
+					/*InvokeRule declarationSpecifiers*/
+					recog.base.set_state(1026);
+					recog.declarationSpecifiers()?;
 
-				_ => Err(ANTLRError::NoAltError(NoViableAltError::new(&mut recog.base)))?
+					recog.base.set_state(1027);
+					recog.base.match_token(Semi,&mut recog.err_handler)?;
+
+					}
+				}
+
+				_ => {}
 			}
 			Ok(())
 		})();
@@ -11297,7 +11305,7 @@ where
 			recog.base.set_state(873);
 			recog.err_handler.sync(&mut recog.base)?;
 			_la = recog.base.input.la(1);
-			while (((_la) & !0x3f) == 0 && ((1usize << _la) & ((1usize << T__0) | (1usize << T__1) | (1usize << T__2) | (1usize << T__3) | (1usize << T__4) | (1usize << T__5) | (1usize << T__6) | (1usize << T__7) | (1usize << T__13) | (1usize << T__14) | (1usize << Auto) | (1usize << Break) | (1usize << Case) | (1usize << Char) | (1usize << Const) | (1usize << Continue) | (1usize << Default) | (1usize << Do) | (1usize << Double) | (1usize << Enum) | (1usize << Extern) | (1usize << Float) | (1usize << For))) != 0) || ((((_la - 32)) & !0x3f) == 0 && ((1usize << (_la - 32)) & ((1usize << (If - 32)) | (1usize << (Breakpoint - 32)) | (1usize << (Inline - 32)) | (1usize << (Int - 32)) | (1usize << (Long - 32)) | (1usize << (Register - 32)) | (1usize << (Restrict - 32)) | (1usize << (Return - 32)) | (1usize << (Short - 32)) | (1usize << (Signed - 32)) | (1usize << (Sizeof - 32)) | (1usize << (Static - 32)) | (1usize << (Struct - 32)) | (1usize << (Switch - 32)) | (1usize << (Typedef - 32)) | (1usize << (Union - 32)) | (1usize << (Unsigned - 32)) | (1usize << (Void - 32)) | (1usize << (Volatile - 32)) | (1usize << (While - 32)) | (1usize << (Alignas - 32)) | (1usize << (Alignof - 32)) | (1usize << (Atomic - 32)) | (1usize << (Bool - 32)) | (1usize << (Complex - 32)) | (1usize << (Noreturn - 32)) | (1usize << (StaticAssert - 32)) | (1usize << (ThreadLocal - 32)) | (1usize << (LeftParen - 32)))) != 0) || ((((_la - 66)) & !0x3f) == 0 && ((1usize << (_la - 66)) & ((1usize << (LeftBrace - 66)) | (1usize << (Plus - 66)) | (1usize << (PlusPlus - 66)) | (1usize << (Minus - 66)) | (1usize << (MinusMinus - 66)) | (1usize << (Star - 66)) | (1usize << (And - 66)) | (1usize << (Not - 66)) | (1usize << (Tilde - 66)) | (1usize << (Semi - 66)))) != 0) || ((((_la - 108)) & !0x3f) == 0 && ((1usize << (_la - 108)) & ((1usize << (Identifier - 108)) | (1usize << (Constant - 108)) | (1usize << (DigitSequence - 108)) | (1usize << (StringLiteral - 108)))) != 0) {
+			while (((_la) & !0x3f) == 0 && ((1usize << _la) & ((1usize << T__0) | (1usize << T__1) | (1usize << T__2) | (1usize << T__3) | (1usize << T__4) | (1usize << T__5) | (1usize << T__6) | (1usize << T__7) | (1usize << T__13) | (1usize << T__14) | (1usize << Auto) | (1usize << Break) | (1usize << Case) | (1usize << Char) | (1usize << Const) | (1usize << Continue) | (1usize << Default) | (1usize << Do) | (1usize << Double) | (1usize << Enum) | (1usize << Extern) | (1usize << Float) | (1usize << For) | (1usize << Goto))) != 0) || ((((_la - 32)) & !0x3f) == 0 && ((1usize << (_la - 32)) & ((1usize << (If - 32)) | (1usize << (Breakpoint - 32)) | (1usize << (Inline - 32)) | (1usize << (Int - 32)) | (1usize << (Long - 32)) | (1usize << (Register - 32)) | (1usize << (Restrict - 32)) | (1usize << (Return - 32)) | (1usize << (Short - 32)) | (1usize << (Signed - 32)) | (1usize << (Sizeof - 32)) | (1usize << (Static - 32)) | (1usize << (Struct - 32)) | (1usize << (Switch - 32)) | (1usize << (Typedef - 32)) | (1usize << (Union - 32)) | (1usize << (Unsigned - 32)) | (1usize << (Void - 32)) | (1usize << (Volatile - 32)) | (1usize << (While - 32)) | (1usize << (Alignas - 32)) | (1usize << (Alignof - 32)) | (1usize << (Atomic - 32)) | (1usize << (Bool - 32)) | (1usize << (Complex - 32)) | (1usize << (Noreturn - 32)) | (1usize << (StaticAssert - 32)) | (1usize << (ThreadLocal - 32)) | (1usize << (LeftParen - 32)))) != 0) || ((((_la - 66)) & !0x3f) == 0 && ((1usize << (_la - 66)) & ((1usize << (LeftBrace - 66)) | (1usize << (Plus - 66)) | (1usize << (PlusPlus - 66)) | (1usize << (Minus - 66)) | (1usize << (MinusMinus - 66)) | (1usize << (Star - 66)) | (1usize << (And - 66)) | (1usize << (Not - 66)) | (1usize << (Tilde - 66)) | (1usize << (Semi - 66)))) != 0) || ((((_la - 108)) & !0x3f) == 0 && ((1usize << (_la - 108)) & ((1usize << (Identifier - 108)) | (1usize << (Constant - 108)) | (1usize << (DigitSequence - 108)) | (1usize << (StringLiteral - 108)))) != 0) {
 				{
 				{
 				/*InvokeRule blockItem*/
@@ -13121,6 +13129,21 @@ fn breakStatement(&self) -> Option<Rc<BreakStatementContextAll<'input>>> where S
 fn returnStatement(&self) -> Option<Rc<ReturnStatementContextAll<'input>>> where Self:Sized{
 	self.child_of_type(0)
 }
+/// Retrieves first TerminalNode corresponding to token Goto
+/// Returns `None` if there is no child corresponding to token Goto
+fn Goto(&self) -> Option<Rc<TerminalNode<'input,CParserContextType>>> where Self:Sized{
+	self.get_token(Goto, 0)
+}
+/// Retrieves first TerminalNode corresponding to token Identifier
+/// Returns `None` if there is no child corresponding to token Identifier
+fn Identifier(&self) -> Option<Rc<TerminalNode<'input,CParserContextType>>> where Self:Sized{
+	self.get_token(Identifier, 0)
+}
+/// Retrieves first TerminalNode corresponding to token Semi
+/// Returns `None` if there is no child corresponding to token Semi
+fn Semi(&self) -> Option<Rc<TerminalNode<'input,CParserContextType>>> where Self:Sized{
+	self.get_token(Semi, 0)
+}
 
 }
 
@@ -13179,6 +13202,23 @@ where
 					}
 				}
 
+			 Goto 
+				=> {
+					//recog.base.enter_outer_alt(_localctx.clone(), 4);
+					recog.base.enter_outer_alt(None, 4);
+					{
+					recog.base.set_state(1022);
+					recog.base.match_token(Goto,&mut recog.err_handler)?;
+
+					recog.base.set_state(1023);
+					recog.base.match_token(Identifier,&mut recog.err_handler)?;
+
+					recog.base.set_state(1024);
+					recog.base.match_token(Semi,&mut recog.err_handler)?;
+
+					}
+				}
+
 				_ => Err(ANTLRError::NoAltError(NoViableAltError::new(&mut recog.base)))?
 			}
 			Ok(())
@@ -14459,7 +14499,7 @@ lazy_static! {
 
 const _serializedATN:&'static str =
 	"\x03\u{608b}\u{a72a}\u{8133}\u{b9ed}\u{417c}\u{3be7}\u{7786}\u{5964}\x03\
-	\x78\u{400}\x04\x02\x09\x02\x04\x03\x09\x03\x04\x04\x09\x04\x04\x05\x09\
+	\x78\u{407}\x04\x02\x09\x02\x04\x03\x09\x03\x04\x04\x09\x04\x04\x05\x09\
 	\x05\x04\x06\x09\x06\x04\x07\x09\x07\x04\x08\x09\x08\x04\x09\x09\x09\x04\
 	\x0a\x09\x0a\x04\x0b\x09\x0b\x04\x0c\x09\x0c\x04\x0d\x09\x0d\x04\x0e\x09\
 	\x0e\x04\x0f\x09\x0f\x04\x10\x09\x10\x04\x11\x09\x11\x04\x12\x09\x12\x04\
@@ -14585,232 +14625,233 @@ const _serializedATN:&'static str =
 	\u{3e4}\x0a\x5c\x03\x5c\x03\x5c\x03\x5d\x05\x5d\u{3e9}\x0a\x5d\x03\x5d\x03\
 	\x5d\x03\x5e\x06\x5e\u{3ee}\x0a\x5e\x0d\x5e\x0e\x5e\u{3ef}\x03\x5f\x03\x5f\
 	\x03\x5f\x05\x5f\u{3f5}\x0a\x5f\x03\x60\x03\x60\x03\x60\x03\x60\x03\x61\
-	\x06\x61\u{3fc}\x0a\x61\x0d\x61\x0e\x61\u{3fd}\x03\x61\x02\x05\x0a\x62\x78\
-	\x62\x02\x04\x06\x08\x0a\x0c\x0e\x10\x12\x14\x16\x18\x1a\x1c\x1e\x20\x22\
-	\x24\x26\x28\x2a\x2c\x2e\x30\x32\x34\x36\x38\x3a\x3c\x3e\x40\x42\x44\x46\
-	\x48\x4a\x4c\x4e\x50\x52\x54\x56\x58\x5a\x5c\x5e\x60\x62\x64\x66\x68\x6a\
-	\x6c\x6e\x70\x72\x74\x76\x78\x7a\x7c\x7e\u{80}\u{82}\u{84}\u{86}\u{88}\u{8a}\
-	\u{8c}\u{8e}\u{90}\u{92}\u{94}\u{96}\u{98}\u{9a}\u{9c}\u{9e}\u{a0}\u{a2}\
-	\u{a4}\u{a6}\u{a8}\u{aa}\u{ac}\u{ae}\u{b0}\u{b2}\u{b4}\u{b6}\u{b8}\u{ba}\
-	\u{bc}\u{be}\u{c0}\x02\x14\x03\x02\x6b\x6c\x04\x02\x2c\x2c\x37\x37\x05\x02\
-	\x4c\x50\x53\x53\x58\x59\x03\x02\x50\x52\x04\x02\x4c\x4c\x4e\x4e\x03\x02\
-	\x4a\x4b\x03\x02\x46\x49\x03\x02\x69\x6a\x03\x02\x5e\x68\x08\x02\x13\x13\
-	\x1e\x1e\x27\x27\x2d\x2d\x30\x30\x3f\x3f\x03\x02\x03\x05\x04\x02\x2e\x2e\
-	\x31\x31\x06\x02\x17\x17\x28\x28\x34\x34\x38\x38\x04\x02\x09\x09\x0b\x0f\
-	\x03\x02\x40\x41\x04\x02\x50\x50\x57\x57\x03\x02\x10\x11\x04\x02\x12\x12\
-	\x34\x34\x02\u{446}\x02\u{cd}\x03\x02\x02\x02\x04\u{cf}\x03\x02\x02\x02\
-	\x06\u{d6}\x03\x02\x02\x02\x08\u{e0}\x03\x02\x02\x02\x0a\u{e5}\x03\x02\x02\
-	\x02\x0c\u{105}\x03\x02\x02\x02\x0e\u{110}\x03\x02\x02\x02\x10\u{112}\x03\
-	\x02\x02\x02\x12\u{114}\x03\x02\x02\x02\x14\u{116}\x03\x02\x02\x02\x16\u{11e}\
-	\x03\x02\x02\x02\x18\u{126}\x03\x02\x02\x02\x1a\u{12e}\x03\x02\x02\x02\x1c\
-	\u{136}\x03\x02\x02\x02\x1e\u{13e}\x03\x02\x02\x02\x20\u{146}\x03\x02\x02\
-	\x02\x22\u{14e}\x03\x02\x02\x02\x24\u{156}\x03\x02\x02\x02\x26\u{15e}\x03\
-	\x02\x02\x02\x28\u{166}\x03\x02\x02\x02\x2a\u{174}\x03\x02\x02\x02\x2c\u{176}\
-	\x03\x02\x02\x02\x2e\u{178}\x03\x02\x02\x02\x30\u{180}\x03\x02\x02\x02\x32\
-	\u{187}\x03\x02\x02\x02\x34\u{18a}\x03\x02\x02\x02\x36\u{18f}\x03\x02\x02\
-	\x02\x38\u{198}\x03\x02\x02\x02\x3a\u{19a}\x03\x02\x02\x02\x3c\u{1a2}\x03\
-	\x02\x02\x02\x3e\u{1a7}\x03\x02\x02\x02\x40\u{1c4}\x03\x02\x02\x02\x42\u{1d1}\
-	\x03\x02\x02\x02\x44\u{1d3}\x03\x02\x02\x02\x46\u{1d6}\x03\x02\x02\x02\x48\
-	\u{1e2}\x03\x02\x02\x02\x4a\u{1e6}\x03\x02\x02\x02\x4c\u{1eb}\x03\x02\x02\
-	\x02\x4e\u{1f9}\x03\x02\x02\x02\x50\u{208}\x03\x02\x02\x02\x52\u{20a}\x03\
-	\x02\x02\x02\x54\u{212}\x03\x02\x02\x02\x56\u{217}\x03\x02\x02\x02\x58\u{219}\
-	\x03\x02\x02\x02\x5a\u{21e}\x03\x02\x02\x02\x5c\u{228}\x03\x02\x02\x02\x5e\
-	\u{22a}\x03\x02\x02\x02\x60\u{233}\x03\x02\x02\x02\x62\u{248}\x03\x02\x02\
-	\x02\x64\u{26a}\x03\x02\x02\x02\x66\u{273}\x03\x02\x02\x02\x68\u{27a}\x03\
-	\x02\x02\x02\x6a\u{27f}\x03\x02\x02\x02\x6c\u{283}\x03\x02\x02\x02\x6e\u{288}\
-	\x03\x02\x02\x02\x70\u{297}\x03\x02\x02\x02\x72\u{299}\x03\x02\x02\x02\x74\
-	\u{2a1}\x03\x02\x02\x02\x76\u{2aa}\x03\x02\x02\x02\x78\u{2cf}\x03\x02\x02\
-	\x02\x7a\u{2f9}\x03\x02\x02\x02\x7c\u{303}\x03\x02\x02\x02\x7e\u{306}\x03\
-	\x02\x02\x02\u{80}\u{313}\x03\x02\x02\x02\u{82}\u{317}\x03\x02\x02\x02\u{84}\
-	\u{321}\x03\x02\x02\x02\u{86}\u{323}\x03\x02\x02\x02\u{88}\u{355}\x03\x02\
-	\x02\x02\u{8a}\u{362}\x03\x02\x02\x02\u{8c}\u{364}\x03\x02\x02\x02\u{8e}\
-	\u{36b}\x03\x02\x02\x02\u{90}\u{370}\x03\x02\x02\x02\u{92}\u{373}\x03\x02\
-	\x02\x02\u{94}\u{379}\x03\x02\x02\x02\u{96}\u{37b}\x03\x02\x02\x02\u{98}\
-	\u{384}\x03\x02\x02\x02\u{9a}\u{38d}\x03\x02\x02\x02\u{9c}\u{38f}\x03\x02\
-	\x02\x02\u{9e}\u{395}\x03\x02\x02\x02\u{a0}\u{39b}\x03\x02\x02\x02\u{a2}\
-	\u{3a3}\x03\x02\x02\x02\u{a4}\u{3ac}\x03\x02\x02\x02\u{a6}\u{3b4}\x03\x02\
-	\x02\x02\u{a8}\u{3b6}\x03\x02\x02\x02\u{aa}\u{3b8}\x03\x02\x02\x02\u{ac}\
-	\u{3bd}\x03\x02\x02\x02\u{ae}\u{3d4}\x03\x02\x02\x02\u{b0}\u{3d6}\x03\x02\
-	\x02\x02\u{b2}\u{3db}\x03\x02\x02\x02\u{b4}\u{3de}\x03\x02\x02\x02\u{b6}\
-	\u{3e1}\x03\x02\x02\x02\u{b8}\u{3e8}\x03\x02\x02\x02\u{ba}\u{3ed}\x03\x02\
-	\x02\x02\u{bc}\u{3f4}\x03\x02\x02\x02\u{be}\u{3f6}\x03\x02\x02\x02\u{c0}\
-	\u{3fb}\x03\x02\x02\x02\u{c2}\u{ce}\x07\x6e\x02\x02\u{c3}\u{ce}\x07\x6f\
-	\x02\x02\u{c4}\u{c6}\x07\x71\x02\x02\u{c5}\u{c4}\x03\x02\x02\x02\u{c6}\u{c7}\
-	\x03\x02\x02\x02\u{c7}\u{c5}\x03\x02\x02\x02\u{c7}\u{c8}\x03\x02\x02\x02\
-	\u{c8}\u{ce}\x03\x02\x02\x02\u{c9}\u{ca}\x07\x40\x02\x02\u{ca}\u{cb}\x05\
-	\x2e\x18\x02\u{cb}\u{cc}\x07\x41\x02\x02\u{cc}\u{ce}\x03\x02\x02\x02\u{cd}\
-	\u{c2}\x03\x02\x02\x02\u{cd}\u{c3}\x03\x02\x02\x02\u{cd}\u{c5}\x03\x02\x02\
-	\x02\u{cd}\u{c9}\x03\x02\x02\x02\u{ce}\x03\x03\x02\x02\x02\u{cf}\u{d0}\x07\
-	\x3b\x02\x02\u{d0}\u{d1}\x07\x40\x02\x02\u{d1}\u{d2}\x05\x2a\x16\x02\u{d2}\
-	\u{d3}\x07\x5d\x02\x02\u{d3}\u{d4}\x05\x06\x04\x02\u{d4}\u{d5}\x07\x41\x02\
-	\x02\u{d5}\x05\x03\x02\x02\x02\u{d6}\u{db}\x05\x08\x05\x02\u{d7}\u{d8}\x07\
-	\x5d\x02\x02\u{d8}\u{da}\x05\x08\x05\x02\u{d9}\u{d7}\x03\x02\x02\x02\u{da}\
-	\u{dd}\x03\x02\x02\x02\u{db}\u{d9}\x03\x02\x02\x02\u{db}\u{dc}\x03\x02\x02\
-	\x02\u{dc}\x07\x03\x02\x02\x02\u{dd}\u{db}\x03\x02\x02\x02\u{de}\u{e1}\x05\
-	\x74\x3b\x02\u{df}\u{e1}\x07\x19\x02\x02\u{e0}\u{de}\x03\x02\x02\x02\u{e0}\
-	\u{df}\x03\x02\x02\x02\u{e1}\u{e2}\x03\x02\x02\x02\u{e2}\u{e3}\x07\x5b\x02\
-	\x02\u{e3}\u{e4}\x05\x2a\x16\x02\u{e4}\x09\x03\x02\x02\x02\u{e5}\u{e6}\x08\
-	\x06\x01\x02\u{e6}\u{e7}\x05\x02\x02\x02\u{e7}\u{f9}\x03\x02\x02\x02\u{e8}\
-	\u{f5}\x0c\x04\x02\x02\u{e9}\u{ea}\x07\x42\x02\x02\u{ea}\u{eb}\x05\x2e\x18\
-	\x02\u{eb}\u{ec}\x07\x43\x02\x02\u{ec}\u{f6}\x03\x02\x02\x02\u{ed}\u{ee}\
-	\x07\x40\x02\x02\u{ee}\u{ef}\x05\x0c\x07\x02\u{ef}\u{f0}\x07\x41\x02\x02\
-	\u{f0}\u{f6}\x03\x02\x02\x02\u{f1}\u{f2}\x09\x02\x02\x02\u{f2}\u{f6}\x07\
-	\x6e\x02\x02\u{f3}\u{f6}\x07\x4d\x02\x02\u{f4}\u{f6}\x07\x4f\x02\x02\u{f5}\
-	\u{e9}\x03\x02\x02\x02\u{f5}\u{ed}\x03\x02\x02\x02\u{f5}\u{f1}\x03\x02\x02\
-	\x02\u{f5}\u{f3}\x03\x02\x02\x02\u{f5}\u{f4}\x03\x02\x02\x02\u{f6}\u{f8}\
-	\x03\x02\x02\x02\u{f7}\u{e8}\x03\x02\x02\x02\u{f8}\u{fb}\x03\x02\x02\x02\
-	\u{f9}\u{f7}\x03\x02\x02\x02\u{f9}\u{fa}\x03\x02\x02\x02\u{fa}\x0b\x03\x02\
-	\x02\x02\u{fb}\u{f9}\x03\x02\x02\x02\u{fc}\u{101}\x05\x2a\x16\x02\u{fd}\
-	\u{fe}\x07\x5d\x02\x02\u{fe}\u{100}\x05\x2a\x16\x02\u{ff}\u{fd}\x03\x02\
-	\x02\x02\u{100}\u{103}\x03\x02\x02\x02\u{101}\u{ff}\x03\x02\x02\x02\u{101}\
-	\u{102}\x03\x02\x02\x02\u{102}\u{106}\x03\x02\x02\x02\u{103}\u{101}\x03\
-	\x02\x02\x02\u{104}\u{106}\x03\x02\x02\x02\u{105}\u{fc}\x03\x02\x02\x02\
-	\u{105}\u{104}\x03\x02\x02\x02\u{106}\x0d\x03\x02\x02\x02\u{107}\u{111}\
-	\x05\x0a\x06\x02\u{108}\u{109}\x05\x10\x09\x02\u{109}\u{10a}\x05\x12\x0a\
-	\x02\u{10a}\u{111}\x03\x02\x02\x02\u{10b}\u{10c}\x09\x03\x02\x02\u{10c}\
-	\u{10d}\x07\x40\x02\x02\u{10d}\u{10e}\x05\x74\x3b\x02\u{10e}\u{10f}\x07\
-	\x41\x02\x02\u{10f}\u{111}\x03\x02\x02\x02\u{110}\u{107}\x03\x02\x02\x02\
-	\u{110}\u{108}\x03\x02\x02\x02\u{110}\u{10b}\x03\x02\x02\x02\u{111}\x0f\
-	\x03\x02\x02\x02\u{112}\u{113}\x09\x04\x02\x02\u{113}\x11\x03\x02\x02\x02\
-	\u{114}\u{115}\x05\x0e\x08\x02\u{115}\x13\x03\x02\x02\x02\u{116}\u{11b}\
-	\x05\x12\x0a\x02\u{117}\u{118}\x09\x05\x02\x02\u{118}\u{11a}\x05\x12\x0a\
-	\x02\u{119}\u{117}\x03\x02\x02\x02\u{11a}\u{11d}\x03\x02\x02\x02\u{11b}\
-	\u{119}\x03\x02\x02\x02\u{11b}\u{11c}\x03\x02\x02\x02\u{11c}\x15\x03\x02\
-	\x02\x02\u{11d}\u{11b}\x03\x02\x02\x02\u{11e}\u{123}\x05\x14\x0b\x02\u{11f}\
-	\u{120}\x09\x06\x02\x02\u{120}\u{122}\x05\x14\x0b\x02\u{121}\u{11f}\x03\
-	\x02\x02\x02\u{122}\u{125}\x03\x02\x02\x02\u{123}\u{121}\x03\x02\x02\x02\
-	\u{123}\u{124}\x03\x02\x02\x02\u{124}\x17\x03\x02\x02\x02\u{125}\u{123}\
-	\x03\x02\x02\x02\u{126}\u{12b}\x05\x16\x0c\x02\u{127}\u{128}\x09\x07\x02\
-	\x02\u{128}\u{12a}\x05\x16\x0c\x02\u{129}\u{127}\x03\x02\x02\x02\u{12a}\
-	\u{12d}\x03\x02\x02\x02\u{12b}\u{129}\x03\x02\x02\x02\u{12b}\u{12c}\x03\
-	\x02\x02\x02\u{12c}\x19\x03\x02\x02\x02\u{12d}\u{12b}\x03\x02\x02\x02\u{12e}\
-	\u{133}\x05\x18\x0d\x02\u{12f}\u{130}\x09\x08\x02\x02\u{130}\u{132}\x05\
-	\x18\x0d\x02\u{131}\u{12f}\x03\x02\x02\x02\u{132}\u{135}\x03\x02\x02\x02\
-	\u{133}\u{131}\x03\x02\x02\x02\u{133}\u{134}\x03\x02\x02\x02\u{134}\x1b\
-	\x03\x02\x02\x02\u{135}\u{133}\x03\x02\x02\x02\u{136}\u{13b}\x05\x1a\x0e\
-	\x02\u{137}\u{138}\x09\x09\x02\x02\u{138}\u{13a}\x05\x1a\x0e\x02\u{139}\
-	\u{137}\x03\x02\x02\x02\u{13a}\u{13d}\x03\x02\x02\x02\u{13b}\u{139}\x03\
-	\x02\x02\x02\u{13b}\u{13c}\x03\x02\x02\x02\u{13c}\x1d\x03\x02\x02\x02\u{13d}\
-	\u{13b}\x03\x02\x02\x02\u{13e}\u{143}\x05\x1c\x0f\x02\u{13f}\u{140}\x07\
-	\x53\x02\x02\u{140}\u{142}\x05\x1c\x0f\x02\u{141}\u{13f}\x03\x02\x02\x02\
-	\u{142}\u{145}\x03\x02\x02\x02\u{143}\u{141}\x03\x02\x02\x02\u{143}\u{144}\
-	\x03\x02\x02\x02\u{144}\x1f\x03\x02\x02\x02\u{145}\u{143}\x03\x02\x02\x02\
-	\u{146}\u{14b}\x05\x1e\x10\x02\u{147}\u{148}\x07\x57\x02\x02\u{148}\u{14a}\
-	\x05\x1e\x10\x02\u{149}\u{147}\x03\x02\x02\x02\u{14a}\u{14d}\x03\x02\x02\
-	\x02\u{14b}\u{149}\x03\x02\x02\x02\u{14b}\u{14c}\x03\x02\x02\x02\u{14c}\
-	\x21\x03\x02\x02\x02\u{14d}\u{14b}\x03\x02\x02\x02\u{14e}\u{153}\x05\x20\
-	\x11\x02\u{14f}\u{150}\x07\x54\x02\x02\u{150}\u{152}\x05\x20\x11\x02\u{151}\
-	\u{14f}\x03\x02\x02\x02\u{152}\u{155}\x03\x02\x02\x02\u{153}\u{151}\x03\
-	\x02\x02\x02\u{153}\u{154}\x03\x02\x02\x02\u{154}\x23\x03\x02\x02\x02\u{155}\
-	\u{153}\x03\x02\x02\x02\u{156}\u{15b}\x05\x22\x12\x02\u{157}\u{158}\x07\
-	\x55\x02\x02\u{158}\u{15a}\x05\x22\x12\x02\u{159}\u{157}\x03\x02\x02\x02\
-	\u{15a}\u{15d}\x03\x02\x02\x02\u{15b}\u{159}\x03\x02\x02\x02\u{15b}\u{15c}\
-	\x03\x02\x02\x02\u{15c}\x25\x03\x02\x02\x02\u{15d}\u{15b}\x03\x02\x02\x02\
-	\u{15e}\u{163}\x05\x24\x13\x02\u{15f}\u{160}\x07\x56\x02\x02\u{160}\u{162}\
-	\x05\x24\x13\x02\u{161}\u{15f}\x03\x02\x02\x02\u{162}\u{165}\x03\x02\x02\
-	\x02\u{163}\u{161}\x03\x02\x02\x02\u{163}\u{164}\x03\x02\x02\x02\u{164}\
-	\x27\x03\x02\x02\x02\u{165}\u{163}\x03\x02\x02\x02\u{166}\u{16c}\x05\x26\
-	\x14\x02\u{167}\u{168}\x07\x5a\x02\x02\u{168}\u{169}\x05\x2e\x18\x02\u{169}\
-	\u{16a}\x07\x5b\x02\x02\u{16a}\u{16b}\x05\x28\x15\x02\u{16b}\u{16d}\x03\
-	\x02\x02\x02\u{16c}\u{167}\x03\x02\x02\x02\u{16c}\u{16d}\x03\x02\x02\x02\
-	\u{16d}\x29\x03\x02\x02\x02\u{16e}\u{175}\x05\x28\x15\x02\u{16f}\u{170}\
-	\x05\x0e\x08\x02\u{170}\u{171}\x05\x2c\x17\x02\u{171}\u{172}\x05\x2a\x16\
-	\x02\u{172}\u{175}\x03\x02\x02\x02\u{173}\u{175}\x07\x70\x02\x02\u{174}\
-	\u{16e}\x03\x02\x02\x02\u{174}\u{16f}\x03\x02\x02\x02\u{174}\u{173}\x03\
-	\x02\x02\x02\u{175}\x2b\x03\x02\x02\x02\u{176}\u{177}\x09\x0a\x02\x02\u{177}\
-	\x2d\x03\x02\x02\x02\u{178}\u{17d}\x05\x2a\x16\x02\u{179}\u{17a}\x07\x5d\
-	\x02\x02\u{17a}\u{17c}\x05\x2a\x16\x02\u{17b}\u{179}\x03\x02\x02\x02\u{17c}\
-	\u{17f}\x03\x02\x02\x02\u{17d}\u{17b}\x03\x02\x02\x02\u{17d}\u{17e}\x03\
-	\x02\x02\x02\u{17e}\x2f\x03\x02\x02\x02\u{17f}\u{17d}\x03\x02\x02\x02\u{180}\
-	\u{181}\x05\x28\x15\x02\u{181}\x31\x03\x02\x02\x02\u{182}\u{183}\x05\x34\
-	\x1b\x02\u{183}\u{184}\x05\x3a\x1e\x02\u{184}\u{185}\x07\x5c\x02\x02\u{185}\
-	\u{188}\x03\x02\x02\x02\u{186}\u{188}\x05\u{86}\x44\x02\u{187}\u{182}\x03\
-	\x02\x02\x02\u{187}\u{186}\x03\x02\x02\x02\u{188}\x33\x03\x02\x02\x02\u{189}\
-	\u{18b}\x05\x38\x1d\x02\u{18a}\u{189}\x03\x02\x02\x02\u{18b}\u{18c}\x03\
-	\x02\x02\x02\u{18c}\u{18a}\x03\x02\x02\x02\u{18c}\u{18d}\x03\x02\x02\x02\
-	\u{18d}\x35\x03\x02\x02\x02\u{18e}\u{190}\x05\x38\x1d\x02\u{18f}\u{18e}\
-	\x03\x02\x02\x02\u{190}\u{191}\x03\x02\x02\x02\u{191}\u{18f}\x03\x02\x02\
-	\x02\u{191}\u{192}\x03\x02\x02\x02\u{192}\x37\x03\x02\x02\x02\u{193}\u{199}\
-	\x05\x3e\x20\x02\u{194}\u{199}\x05\x40\x21\x02\u{195}\u{199}\x05\x5a\x2e\
-	\x02\u{196}\u{199}\x05\x5c\x2f\x02\u{197}\u{199}\x05\x5e\x30\x02\u{198}\
-	\u{193}\x03\x02\x02\x02\u{198}\u{194}\x03\x02\x02\x02\u{198}\u{195}\x03\
-	\x02\x02\x02\u{198}\u{196}\x03\x02\x02\x02\u{198}\u{197}\x03\x02\x02\x02\
-	\u{199}\x39\x03\x02\x02\x02\u{19a}\u{19f}\x05\x3c\x1f\x02\u{19b}\u{19c}\
-	\x07\x5d\x02\x02\u{19c}\u{19e}\x05\x3c\x1f\x02\u{19d}\u{19b}\x03\x02\x02\
-	\x02\u{19e}\u{1a1}\x03\x02\x02\x02\u{19f}\u{19d}\x03\x02\x02\x02\u{19f}\
-	\u{1a0}\x03\x02\x02\x02\u{1a0}\x3b\x03\x02\x02\x02\u{1a1}\u{19f}\x03\x02\
-	\x02\x02\u{1a2}\u{1a5}\x05\x60\x31\x02\u{1a3}\u{1a4}\x07\x5e\x02\x02\u{1a4}\
-	\u{1a6}\x05\x7c\x3f\x02\u{1a5}\u{1a3}\x03\x02\x02\x02\u{1a5}\u{1a6}\x03\
-	\x02\x02\x02\u{1a6}\x3d\x03\x02\x02\x02\u{1a7}\u{1a8}\x09\x0b\x02\x02\u{1a8}\
-	\x3f\x03\x02\x02\x02\u{1a9}\u{1c5}\x07\x33\x02\x02\u{1aa}\u{1c5}\x07\x16\
-	\x02\x02\u{1ab}\u{1c5}\x07\x2a\x02\x02\u{1ac}\u{1c5}\x07\x25\x02\x02\u{1ad}\
-	\u{1c5}\x07\x26\x02\x02\u{1ae}\u{1c5}\x07\x1f\x02\x02\u{1af}\u{1c5}\x07\
-	\x1b\x02\x02\u{1b0}\u{1c5}\x07\x2b\x02\x02\u{1b1}\u{1c5}\x07\x32\x02\x02\
-	\u{1b2}\u{1c5}\x07\x39\x02\x02\u{1b3}\u{1c5}\x07\x3a\x02\x02\u{1b4}\u{1c5}\
-	\x07\x03\x02\x02\u{1b5}\u{1c5}\x07\x04\x02\x02\u{1b6}\u{1c5}\x07\x05\x02\
-	\x02\u{1b7}\u{1b8}\x07\x06\x02\x02\u{1b8}\u{1b9}\x07\x40\x02\x02\u{1b9}\
-	\u{1ba}\x09\x0c\x02\x02\u{1ba}\u{1c5}\x07\x41\x02\x02\u{1bb}\u{1c5}\x05\
-	\x58\x2d\x02\u{1bc}\u{1c5}\x05\x42\x22\x02\u{1bd}\u{1c5}\x05\x50\x29\x02\
-	\u{1be}\u{1c5}\x05\x7a\x3e\x02\u{1bf}\u{1c0}\x07\x07\x02\x02\u{1c0}\u{1c1}\
-	\x07\x40\x02\x02\u{1c1}\u{1c2}\x05\x30\x19\x02\u{1c2}\u{1c3}\x07\x41\x02\
-	\x02\u{1c3}\u{1c5}\x03\x02\x02\x02\u{1c4}\u{1a9}\x03\x02\x02\x02\u{1c4}\
-	\u{1aa}\x03\x02\x02\x02\u{1c4}\u{1ab}\x03\x02\x02\x02\u{1c4}\u{1ac}\x03\
-	\x02\x02\x02\u{1c4}\u{1ad}\x03\x02\x02\x02\u{1c4}\u{1ae}\x03\x02\x02\x02\
-	\u{1c4}\u{1af}\x03\x02\x02\x02\u{1c4}\u{1b0}\x03\x02\x02\x02\u{1c4}\u{1b1}\
-	\x03\x02\x02\x02\u{1c4}\u{1b2}\x03\x02\x02\x02\u{1c4}\u{1b3}\x03\x02\x02\
-	\x02\u{1c4}\u{1b4}\x03\x02\x02\x02\u{1c4}\u{1b5}\x03\x02\x02\x02\u{1c4}\
-	\u{1b6}\x03\x02\x02\x02\u{1c4}\u{1b7}\x03\x02\x02\x02\u{1c4}\u{1bb}\x03\
-	\x02\x02\x02\u{1c4}\u{1bc}\x03\x02\x02\x02\u{1c4}\u{1bd}\x03\x02\x02\x02\
-	\u{1c4}\u{1be}\x03\x02\x02\x02\u{1c4}\u{1bf}\x03\x02\x02\x02\u{1c5}\x41\
-	\x03\x02\x02\x02\u{1c6}\u{1c8}\x05\x44\x23\x02\u{1c7}\u{1c9}\x07\x6e\x02\
-	\x02\u{1c8}\u{1c7}\x03\x02\x02\x02\u{1c8}\u{1c9}\x03\x02\x02\x02\u{1c9}\
-	\u{1ca}\x03\x02\x02\x02\u{1ca}\u{1cb}\x07\x44\x02\x02\u{1cb}\u{1cc}\x05\
-	\x46\x24\x02\u{1cc}\u{1cd}\x07\x45\x02\x02\u{1cd}\u{1d2}\x03\x02\x02\x02\
-	\u{1ce}\u{1cf}\x05\x44\x23\x02\u{1cf}\u{1d0}\x07\x6e\x02\x02\u{1d0}\u{1d2}\
-	\x03\x02\x02\x02\u{1d1}\u{1c6}\x03\x02\x02\x02\u{1d1}\u{1ce}\x03\x02\x02\
-	\x02\u{1d2}\x43\x03\x02\x02\x02\u{1d3}\u{1d4}\x09\x0d\x02\x02\u{1d4}\x45\
-	\x03\x02\x02\x02\u{1d5}\u{1d7}\x05\x48\x25\x02\u{1d6}\u{1d5}\x03\x02\x02\
-	\x02\u{1d7}\u{1d8}\x03\x02\x02\x02\u{1d8}\u{1d6}\x03\x02\x02\x02\u{1d8}\
-	\u{1d9}\x03\x02\x02\x02\u{1d9}\x47\x03\x02\x02\x02\u{1da}\u{1db}\x05\x4a\
-	\x26\x02\u{1db}\u{1dc}\x05\x4c\x27\x02\u{1dc}\u{1dd}\x07\x5c\x02\x02\u{1dd}\
-	\u{1e3}\x03\x02\x02\x02\u{1de}\u{1df}\x05\x4a\x26\x02\u{1df}\u{1e0}\x07\
-	\x5c\x02\x02\u{1e0}\u{1e3}\x03\x02\x02\x02\u{1e1}\u{1e3}\x05\u{86}\x44\x02\
-	\u{1e2}\u{1da}\x03\x02\x02\x02\u{1e2}\u{1de}\x03\x02\x02\x02\u{1e2}\u{1e1}\
-	\x03\x02\x02\x02\u{1e3}\x49\x03\x02\x02\x02\u{1e4}\u{1e7}\x05\x40\x21\x02\
-	\u{1e5}\u{1e7}\x05\x5a\x2e\x02\u{1e6}\u{1e4}\x03\x02\x02\x02\u{1e6}\u{1e5}\
-	\x03\x02\x02\x02\u{1e7}\u{1e9}\x03\x02\x02\x02\u{1e8}\u{1ea}\x05\x4a\x26\
-	\x02\u{1e9}\u{1e8}\x03\x02\x02\x02\u{1e9}\u{1ea}\x03\x02\x02\x02\u{1ea}\
-	\x4b\x03\x02\x02\x02\u{1eb}\u{1f0}\x05\x4e\x28\x02\u{1ec}\u{1ed}\x07\x5d\
-	\x02\x02\u{1ed}\u{1ef}\x05\x4e\x28\x02\u{1ee}\u{1ec}\x03\x02\x02\x02\u{1ef}\
-	\u{1f2}\x03\x02\x02\x02\u{1f0}\u{1ee}\x03\x02\x02\x02\u{1f0}\u{1f1}\x03\
-	\x02\x02\x02\u{1f1}\x4d\x03\x02\x02\x02\u{1f2}\u{1f0}\x03\x02\x02\x02\u{1f3}\
-	\u{1fa}\x05\x60\x31\x02\u{1f4}\u{1f6}\x05\x60\x31\x02\u{1f5}\u{1f4}\x03\
-	\x02\x02\x02\u{1f5}\u{1f6}\x03\x02\x02\x02\u{1f6}\u{1f7}\x03\x02\x02\x02\
-	\u{1f7}\u{1f8}\x07\x5b\x02\x02\u{1f8}\u{1fa}\x05\x30\x19\x02\u{1f9}\u{1f3}\
-	\x03\x02\x02\x02\u{1f9}\u{1f5}\x03\x02\x02\x02\u{1fa}\x4f\x03\x02\x02\x02\
-	\u{1fb}\u{1fd}\x07\x1d\x02\x02\u{1fc}\u{1fe}\x07\x6e\x02\x02\u{1fd}\u{1fc}\
-	\x03\x02\x02\x02\u{1fd}\u{1fe}\x03\x02\x02\x02\u{1fe}\u{1ff}\x03\x02\x02\
-	\x02\u{1ff}\u{200}\x07\x44\x02\x02\u{200}\u{202}\x05\x52\x2a\x02\u{201}\
-	\u{203}\x07\x5d\x02\x02\u{202}\u{201}\x03\x02\x02\x02\u{202}\u{203}\x03\
-	\x02\x02\x02\u{203}\u{204}\x03\x02\x02\x02\u{204}\u{205}\x07\x45\x02\x02\
-	\u{205}\u{209}\x03\x02\x02\x02\u{206}\u{207}\x07\x1d\x02\x02\u{207}\u{209}\
-	\x07\x6e\x02\x02\u{208}\u{1fb}\x03\x02\x02\x02\u{208}\u{206}\x03\x02\x02\
-	\x02\u{209}\x51\x03\x02\x02\x02\u{20a}\u{20f}\x05\x54\x2b\x02\u{20b}\u{20c}\
-	\x07\x5d\x02\x02\u{20c}\u{20e}\x05\x54\x2b\x02\u{20d}\u{20b}\x03\x02\x02\
-	\x02\u{20e}\u{211}\x03\x02\x02\x02\u{20f}\u{20d}\x03\x02\x02\x02\u{20f}\
-	\u{210}\x03\x02\x02\x02\u{210}\x53\x03\x02\x02\x02\u{211}\u{20f}\x03\x02\
-	\x02\x02\u{212}\u{215}\x05\x56\x2c\x02\u{213}\u{214}\x07\x5e\x02\x02\u{214}\
-	\u{216}\x05\x30\x19\x02\u{215}\u{213}\x03\x02\x02\x02\u{215}\u{216}\x03\
-	\x02\x02\x02\u{216}\x55\x03\x02\x02\x02\u{217}\u{218}\x07\x6e\x02\x02\u{218}\
-	\x57\x03\x02\x02\x02\u{219}\u{21a}\x07\x38\x02\x02\u{21a}\u{21b}\x07\x40\
-	\x02\x02\u{21b}\u{21c}\x05\x74\x3b\x02\u{21c}\u{21d}\x07\x41\x02\x02\u{21d}\
-	\x59\x03\x02\x02\x02\u{21e}\u{21f}\x09\x0e\x02\x02\u{21f}\x5b\x03\x02\x02\
-	\x02\u{220}\u{229}\x07\x24\x02\x02\u{221}\u{229}\x07\x3d\x02\x02\u{222}\
-	\u{229}\x07\x08\x02\x02\u{223}\u{229}\x07\x09\x02\x02\u{224}\u{225}\x07\
-	\x0a\x02\x02\u{225}\u{226}\x07\x40\x02\x02\u{226}\u{227}\x07\x6e\x02\x02\
-	\u{227}\u{229}\x07\x41\x02\x02\u{228}\u{220}\x03\x02\x02\x02\u{228}\u{221}\
-	\x03\x02\x02\x02\u{228}\u{222}\x03\x02\x02\x02\u{228}\u{223}\x03\x02\x02\
-	\x02\u{228}\u{224}\x03\x02\x02\x02\u{229}\x5d\x03\x02\x02\x02\u{22a}\u{22b}\
+	\x06\x61\u{3fc}\x0a\x61\x0d\x61\x0e\x61\u{3fd}\x03\x61\x03\x57\x03\x57\x03\
+	\x57\x03\x57\x03\x1a\x03\x1a\x03\x1a\x02\x05\x0a\x62\x78\x62\x02\x04\x06\
+	\x08\x0a\x0c\x0e\x10\x12\x14\x16\x18\x1a\x1c\x1e\x20\x22\x24\x26\x28\x2a\
+	\x2c\x2e\x30\x32\x34\x36\x38\x3a\x3c\x3e\x40\x42\x44\x46\x48\x4a\x4c\x4e\
+	\x50\x52\x54\x56\x58\x5a\x5c\x5e\x60\x62\x64\x66\x68\x6a\x6c\x6e\x70\x72\
+	\x74\x76\x78\x7a\x7c\x7e\u{80}\u{82}\u{84}\u{86}\u{88}\u{8a}\u{8c}\u{8e}\
+	\u{90}\u{92}\u{94}\u{96}\u{98}\u{9a}\u{9c}\u{9e}\u{a0}\u{a2}\u{a4}\u{a6}\
+	\u{a8}\u{aa}\u{ac}\u{ae}\u{b0}\u{b2}\u{b4}\u{b6}\u{b8}\u{ba}\u{bc}\u{be}\
+	\u{c0}\x02\x14\x03\x02\x6b\x6c\x04\x02\x2c\x2c\x37\x37\x05\x02\x4c\x50\x53\
+	\x53\x58\x59\x03\x02\x50\x52\x04\x02\x4c\x4c\x4e\x4e\x03\x02\x4a\x4b\x03\
+	\x02\x46\x49\x03\x02\x69\x6a\x03\x02\x5e\x68\x08\x02\x13\x13\x1e\x1e\x27\
+	\x27\x2d\x2d\x30\x30\x3f\x3f\x03\x02\x03\x05\x04\x02\x2e\x2e\x31\x31\x06\
+	\x02\x17\x17\x28\x28\x34\x34\x38\x38\x04\x02\x09\x09\x0b\x0f\x03\x02\x40\
+	\x41\x04\x02\x50\x50\x57\x57\x03\x02\x10\x11\x04\x02\x12\x12\x34\x34\x02\
+	\u{44f}\x02\u{cd}\x03\x02\x02\x02\x04\u{cf}\x03\x02\x02\x02\x06\u{d6}\x03\
+	\x02\x02\x02\x08\u{e0}\x03\x02\x02\x02\x0a\u{e5}\x03\x02\x02\x02\x0c\u{105}\
+	\x03\x02\x02\x02\x0e\u{110}\x03\x02\x02\x02\x10\u{112}\x03\x02\x02\x02\x12\
+	\u{114}\x03\x02\x02\x02\x14\u{116}\x03\x02\x02\x02\x16\u{11e}\x03\x02\x02\
+	\x02\x18\u{126}\x03\x02\x02\x02\x1a\u{12e}\x03\x02\x02\x02\x1c\u{136}\x03\
+	\x02\x02\x02\x1e\u{13e}\x03\x02\x02\x02\x20\u{146}\x03\x02\x02\x02\x22\u{14e}\
+	\x03\x02\x02\x02\x24\u{156}\x03\x02\x02\x02\x26\u{15e}\x03\x02\x02\x02\x28\
+	\u{166}\x03\x02\x02\x02\x2a\u{174}\x03\x02\x02\x02\x2c\u{176}\x03\x02\x02\
+	\x02\x2e\u{178}\x03\x02\x02\x02\x30\u{180}\x03\x02\x02\x02\x32\u{187}\x03\
+	\x02\x02\x02\x34\u{18a}\x03\x02\x02\x02\x36\u{18f}\x03\x02\x02\x02\x38\u{198}\
+	\x03\x02\x02\x02\x3a\u{19a}\x03\x02\x02\x02\x3c\u{1a2}\x03\x02\x02\x02\x3e\
+	\u{1a7}\x03\x02\x02\x02\x40\u{1c4}\x03\x02\x02\x02\x42\u{1d1}\x03\x02\x02\
+	\x02\x44\u{1d3}\x03\x02\x02\x02\x46\u{1d6}\x03\x02\x02\x02\x48\u{1e2}\x03\
+	\x02\x02\x02\x4a\u{1e6}\x03\x02\x02\x02\x4c\u{1eb}\x03\x02\x02\x02\x4e\u{1f9}\
+	\x03\x02\x02\x02\x50\u{208}\x03\x02\x02\x02\x52\u{20a}\x03\x02\x02\x02\x54\
+	\u{212}\x03\x02\x02\x02\x56\u{217}\x03\x02\x02\x02\x58\u{219}\x03\x02\x02\
+	\x02\x5a\u{21e}\x03\x02\x02\x02\x5c\u{228}\x03\x02\x02\x02\x5e\u{22a}\x03\
+	\x02\x02\x02\x60\u{233}\x03\x02\x02\x02\x62\u{248}\x03\x02\x02\x02\x64\u{26a}\
+	\x03\x02\x02\x02\x66\u{273}\x03\x02\x02\x02\x68\u{27a}\x03\x02\x02\x02\x6a\
+	\u{27f}\x03\x02\x02\x02\x6c\u{283}\x03\x02\x02\x02\x6e\u{288}\x03\x02\x02\
+	\x02\x70\u{297}\x03\x02\x02\x02\x72\u{299}\x03\x02\x02\x02\x74\u{2a1}\x03\
+	\x02\x02\x02\x76\u{2aa}\x03\x02\x02\x02\x78\u{2cf}\x03\x02\x02\x02\x7a\u{2f9}\
+	\x03\x02\x02\x02\x7c\u{303}\x03\x02\x02\x02\x7e\u{306}\x03\x02\x02\x02\u{80}\
+	\u{313}\x03\x02\x02\x02\u{82}\u{317}\x03\x02\x02\x02\u{84}\u{321}\x03\x02\
+	\x02\x02\u{86}\u{323}\x03\x02\x02\x02\u{88}\u{355}\x03\x02\x02\x02\u{8a}\
+	\u{362}\x03\x02\x02\x02\u{8c}\u{364}\x03\x02\x02\x02\u{8e}\u{36b}\x03\x02\
+	\x02\x02\u{90}\u{370}\x03\x02\x02\x02\u{92}\u{373}\x03\x02\x02\x02\u{94}\
+	\u{379}\x03\x02\x02\x02\u{96}\u{37b}\x03\x02\x02\x02\u{98}\u{384}\x03\x02\
+	\x02\x02\u{9a}\u{38d}\x03\x02\x02\x02\u{9c}\u{38f}\x03\x02\x02\x02\u{9e}\
+	\u{395}\x03\x02\x02\x02\u{a0}\u{39b}\x03\x02\x02\x02\u{a2}\u{3a3}\x03\x02\
+	\x02\x02\u{a4}\u{3ac}\x03\x02\x02\x02\u{a6}\u{3b4}\x03\x02\x02\x02\u{a8}\
+	\u{3b6}\x03\x02\x02\x02\u{aa}\u{3b8}\x03\x02\x02\x02\u{ac}\u{3bd}\x03\x02\
+	\x02\x02\u{ae}\u{3d4}\x03\x02\x02\x02\u{b0}\u{3d6}\x03\x02\x02\x02\u{b2}\
+	\u{3db}\x03\x02\x02\x02\u{b4}\u{3de}\x03\x02\x02\x02\u{b6}\u{3e1}\x03\x02\
+	\x02\x02\u{b8}\u{3e8}\x03\x02\x02\x02\u{ba}\u{3ed}\x03\x02\x02\x02\u{bc}\
+	\u{3f4}\x03\x02\x02\x02\u{be}\u{3f6}\x03\x02\x02\x02\u{c0}\u{3fb}\x03\x02\
+	\x02\x02\u{c2}\u{ce}\x07\x6e\x02\x02\u{c3}\u{ce}\x07\x6f\x02\x02\u{c4}\u{c6}\
+	\x07\x71\x02\x02\u{c5}\u{c4}\x03\x02\x02\x02\u{c6}\u{c7}\x03\x02\x02\x02\
+	\u{c7}\u{c5}\x03\x02\x02\x02\u{c7}\u{c8}\x03\x02\x02\x02\u{c8}\u{ce}\x03\
+	\x02\x02\x02\u{c9}\u{ca}\x07\x40\x02\x02\u{ca}\u{cb}\x05\x2e\x18\x02\u{cb}\
+	\u{cc}\x07\x41\x02\x02\u{cc}\u{ce}\x03\x02\x02\x02\u{cd}\u{c2}\x03\x02\x02\
+	\x02\u{cd}\u{c3}\x03\x02\x02\x02\u{cd}\u{c5}\x03\x02\x02\x02\u{cd}\u{c9}\
+	\x03\x02\x02\x02\u{ce}\x03\x03\x02\x02\x02\u{cf}\u{d0}\x07\x3b\x02\x02\u{d0}\
+	\u{d1}\x07\x40\x02\x02\u{d1}\u{d2}\x05\x2a\x16\x02\u{d2}\u{d3}\x07\x5d\x02\
+	\x02\u{d3}\u{d4}\x05\x06\x04\x02\u{d4}\u{d5}\x07\x41\x02\x02\u{d5}\x05\x03\
+	\x02\x02\x02\u{d6}\u{db}\x05\x08\x05\x02\u{d7}\u{d8}\x07\x5d\x02\x02\u{d8}\
+	\u{da}\x05\x08\x05\x02\u{d9}\u{d7}\x03\x02\x02\x02\u{da}\u{dd}\x03\x02\x02\
+	\x02\u{db}\u{d9}\x03\x02\x02\x02\u{db}\u{dc}\x03\x02\x02\x02\u{dc}\x07\x03\
+	\x02\x02\x02\u{dd}\u{db}\x03\x02\x02\x02\u{de}\u{e1}\x05\x74\x3b\x02\u{df}\
+	\u{e1}\x07\x19\x02\x02\u{e0}\u{de}\x03\x02\x02\x02\u{e0}\u{df}\x03\x02\x02\
+	\x02\u{e1}\u{e2}\x03\x02\x02\x02\u{e2}\u{e3}\x07\x5b\x02\x02\u{e3}\u{e4}\
+	\x05\x2a\x16\x02\u{e4}\x09\x03\x02\x02\x02\u{e5}\u{e6}\x08\x06\x01\x02\u{e6}\
+	\u{e7}\x05\x02\x02\x02\u{e7}\u{f9}\x03\x02\x02\x02\u{e8}\u{f5}\x0c\x04\x02\
+	\x02\u{e9}\u{ea}\x07\x42\x02\x02\u{ea}\u{eb}\x05\x2e\x18\x02\u{eb}\u{ec}\
+	\x07\x43\x02\x02\u{ec}\u{f6}\x03\x02\x02\x02\u{ed}\u{ee}\x07\x40\x02\x02\
+	\u{ee}\u{ef}\x05\x0c\x07\x02\u{ef}\u{f0}\x07\x41\x02\x02\u{f0}\u{f6}\x03\
+	\x02\x02\x02\u{f1}\u{f2}\x09\x02\x02\x02\u{f2}\u{f6}\x07\x6e\x02\x02\u{f3}\
+	\u{f6}\x07\x4d\x02\x02\u{f4}\u{f6}\x07\x4f\x02\x02\u{f5}\u{e9}\x03\x02\x02\
+	\x02\u{f5}\u{ed}\x03\x02\x02\x02\u{f5}\u{f1}\x03\x02\x02\x02\u{f5}\u{f3}\
+	\x03\x02\x02\x02\u{f5}\u{f4}\x03\x02\x02\x02\u{f6}\u{f8}\x03\x02\x02\x02\
+	\u{f7}\u{e8}\x03\x02\x02\x02\u{f8}\u{fb}\x03\x02\x02\x02\u{f9}\u{f7}\x03\
+	\x02\x02\x02\u{f9}\u{fa}\x03\x02\x02\x02\u{fa}\x0b\x03\x02\x02\x02\u{fb}\
+	\u{f9}\x03\x02\x02\x02\u{fc}\u{101}\x05\x2a\x16\x02\u{fd}\u{fe}\x07\x5d\
+	\x02\x02\u{fe}\u{100}\x05\x2a\x16\x02\u{ff}\u{fd}\x03\x02\x02\x02\u{100}\
+	\u{103}\x03\x02\x02\x02\u{101}\u{ff}\x03\x02\x02\x02\u{101}\u{102}\x03\x02\
+	\x02\x02\u{102}\u{106}\x03\x02\x02\x02\u{103}\u{101}\x03\x02\x02\x02\u{104}\
+	\u{106}\x03\x02\x02\x02\u{105}\u{fc}\x03\x02\x02\x02\u{105}\u{104}\x03\x02\
+	\x02\x02\u{106}\x0d\x03\x02\x02\x02\u{107}\u{111}\x05\x0a\x06\x02\u{108}\
+	\u{109}\x05\x10\x09\x02\u{109}\u{10a}\x05\x12\x0a\x02\u{10a}\u{111}\x03\
+	\x02\x02\x02\u{10b}\u{10c}\x09\x03\x02\x02\u{10c}\u{10d}\x07\x40\x02\x02\
+	\u{10d}\u{10e}\x05\x74\x3b\x02\u{10e}\u{10f}\x07\x41\x02\x02\u{10f}\u{111}\
+	\x03\x02\x02\x02\u{110}\u{107}\x03\x02\x02\x02\u{110}\u{108}\x03\x02\x02\
+	\x02\u{110}\u{10b}\x03\x02\x02\x02\u{111}\x0f\x03\x02\x02\x02\u{112}\u{113}\
+	\x09\x04\x02\x02\u{113}\x11\x03\x02\x02\x02\u{114}\u{115}\x05\x0e\x08\x02\
+	\u{115}\x13\x03\x02\x02\x02\u{116}\u{11b}\x05\x12\x0a\x02\u{117}\u{118}\
+	\x09\x05\x02\x02\u{118}\u{11a}\x05\x12\x0a\x02\u{119}\u{117}\x03\x02\x02\
+	\x02\u{11a}\u{11d}\x03\x02\x02\x02\u{11b}\u{119}\x03\x02\x02\x02\u{11b}\
+	\u{11c}\x03\x02\x02\x02\u{11c}\x15\x03\x02\x02\x02\u{11d}\u{11b}\x03\x02\
+	\x02\x02\u{11e}\u{123}\x05\x14\x0b\x02\u{11f}\u{120}\x09\x06\x02\x02\u{120}\
+	\u{122}\x05\x14\x0b\x02\u{121}\u{11f}\x03\x02\x02\x02\u{122}\u{125}\x03\
+	\x02\x02\x02\u{123}\u{121}\x03\x02\x02\x02\u{123}\u{124}\x03\x02\x02\x02\
+	\u{124}\x17\x03\x02\x02\x02\u{125}\u{123}\x03\x02\x02\x02\u{126}\u{12b}\
+	\x05\x16\x0c\x02\u{127}\u{128}\x09\x07\x02\x02\u{128}\u{12a}\x05\x16\x0c\
+	\x02\u{129}\u{127}\x03\x02\x02\x02\u{12a}\u{12d}\x03\x02\x02\x02\u{12b}\
+	\u{129}\x03\x02\x02\x02\u{12b}\u{12c}\x03\x02\x02\x02\u{12c}\x19\x03\x02\
+	\x02\x02\u{12d}\u{12b}\x03\x02\x02\x02\u{12e}\u{133}\x05\x18\x0d\x02\u{12f}\
+	\u{130}\x09\x08\x02\x02\u{130}\u{132}\x05\x18\x0d\x02\u{131}\u{12f}\x03\
+	\x02\x02\x02\u{132}\u{135}\x03\x02\x02\x02\u{133}\u{131}\x03\x02\x02\x02\
+	\u{133}\u{134}\x03\x02\x02\x02\u{134}\x1b\x03\x02\x02\x02\u{135}\u{133}\
+	\x03\x02\x02\x02\u{136}\u{13b}\x05\x1a\x0e\x02\u{137}\u{138}\x09\x09\x02\
+	\x02\u{138}\u{13a}\x05\x1a\x0e\x02\u{139}\u{137}\x03\x02\x02\x02\u{13a}\
+	\u{13d}\x03\x02\x02\x02\u{13b}\u{139}\x03\x02\x02\x02\u{13b}\u{13c}\x03\
+	\x02\x02\x02\u{13c}\x1d\x03\x02\x02\x02\u{13d}\u{13b}\x03\x02\x02\x02\u{13e}\
+	\u{143}\x05\x1c\x0f\x02\u{13f}\u{140}\x07\x53\x02\x02\u{140}\u{142}\x05\
+	\x1c\x0f\x02\u{141}\u{13f}\x03\x02\x02\x02\u{142}\u{145}\x03\x02\x02\x02\
+	\u{143}\u{141}\x03\x02\x02\x02\u{143}\u{144}\x03\x02\x02\x02\u{144}\x1f\
+	\x03\x02\x02\x02\u{145}\u{143}\x03\x02\x02\x02\u{146}\u{14b}\x05\x1e\x10\
+	\x02\u{147}\u{148}\x07\x57\x02\x02\u{148}\u{14a}\x05\x1e\x10\x02\u{149}\
+	\u{147}\x03\x02\x02\x02\u{14a}\u{14d}\x03\x02\x02\x02\u{14b}\u{149}\x03\
+	\x02\x02\x02\u{14b}\u{14c}\x03\x02\x02\x02\u{14c}\x21\x03\x02\x02\x02\u{14d}\
+	\u{14b}\x03\x02\x02\x02\u{14e}\u{153}\x05\x20\x11\x02\u{14f}\u{150}\x07\
+	\x54\x02\x02\u{150}\u{152}\x05\x20\x11\x02\u{151}\u{14f}\x03\x02\x02\x02\
+	\u{152}\u{155}\x03\x02\x02\x02\u{153}\u{151}\x03\x02\x02\x02\u{153}\u{154}\
+	\x03\x02\x02\x02\u{154}\x23\x03\x02\x02\x02\u{155}\u{153}\x03\x02\x02\x02\
+	\u{156}\u{15b}\x05\x22\x12\x02\u{157}\u{158}\x07\x55\x02\x02\u{158}\u{15a}\
+	\x05\x22\x12\x02\u{159}\u{157}\x03\x02\x02\x02\u{15a}\u{15d}\x03\x02\x02\
+	\x02\u{15b}\u{159}\x03\x02\x02\x02\u{15b}\u{15c}\x03\x02\x02\x02\u{15c}\
+	\x25\x03\x02\x02\x02\u{15d}\u{15b}\x03\x02\x02\x02\u{15e}\u{163}\x05\x24\
+	\x13\x02\u{15f}\u{160}\x07\x56\x02\x02\u{160}\u{162}\x05\x24\x13\x02\u{161}\
+	\u{15f}\x03\x02\x02\x02\u{162}\u{165}\x03\x02\x02\x02\u{163}\u{161}\x03\
+	\x02\x02\x02\u{163}\u{164}\x03\x02\x02\x02\u{164}\x27\x03\x02\x02\x02\u{165}\
+	\u{163}\x03\x02\x02\x02\u{166}\u{16c}\x05\x26\x14\x02\u{167}\u{168}\x07\
+	\x5a\x02\x02\u{168}\u{169}\x05\x2e\x18\x02\u{169}\u{16a}\x07\x5b\x02\x02\
+	\u{16a}\u{16b}\x05\x28\x15\x02\u{16b}\u{16d}\x03\x02\x02\x02\u{16c}\u{167}\
+	\x03\x02\x02\x02\u{16c}\u{16d}\x03\x02\x02\x02\u{16d}\x29\x03\x02\x02\x02\
+	\u{16e}\u{175}\x05\x28\x15\x02\u{16f}\u{170}\x05\x0e\x08\x02\u{170}\u{171}\
+	\x05\x2c\x17\x02\u{171}\u{172}\x05\x2a\x16\x02\u{172}\u{175}\x03\x02\x02\
+	\x02\u{173}\u{175}\x07\x70\x02\x02\u{174}\u{16e}\x03\x02\x02\x02\u{174}\
+	\u{16f}\x03\x02\x02\x02\u{174}\u{173}\x03\x02\x02\x02\u{175}\x2b\x03\x02\
+	\x02\x02\u{176}\u{177}\x09\x0a\x02\x02\u{177}\x2d\x03\x02\x02\x02\u{178}\
+	\u{17d}\x05\x2a\x16\x02\u{179}\u{17a}\x07\x5d\x02\x02\u{17a}\u{17c}\x05\
+	\x2a\x16\x02\u{17b}\u{179}\x03\x02\x02\x02\u{17c}\u{17f}\x03\x02\x02\x02\
+	\u{17d}\u{17b}\x03\x02\x02\x02\u{17d}\u{17e}\x03\x02\x02\x02\u{17e}\x2f\
+	\x03\x02\x02\x02\u{17f}\u{17d}\x03\x02\x02\x02\u{180}\u{181}\x05\x28\x15\
+	\x02\u{181}\x31\x03\x02\x02\x02\u{182}\u{183}\x05\x34\x1b\x02\u{183}\u{184}\
+	\x05\x3a\x1e\x02\u{184}\u{185}\x07\x5c\x02\x02\u{185}\u{188}\x03\x02\x02\
+	\x02\u{186}\u{188}\x05\u{86}\x44\x02\u{187}\u{182}\x03\x02\x02\x02\u{187}\
+	\u{186}\x03\x02\x02\x02\u{188}\x33\x03\x02\x02\x02\u{189}\u{18b}\x05\x38\
+	\x1d\x02\u{18a}\u{189}\x03\x02\x02\x02\u{18b}\u{18c}\x03\x02\x02\x02\u{18c}\
+	\u{18a}\x03\x02\x02\x02\u{18c}\u{18d}\x03\x02\x02\x02\u{18d}\x35\x03\x02\
+	\x02\x02\u{18e}\u{190}\x05\x38\x1d\x02\u{18f}\u{18e}\x03\x02\x02\x02\u{190}\
+	\u{191}\x03\x02\x02\x02\u{191}\u{18f}\x03\x02\x02\x02\u{191}\u{192}\x03\
+	\x02\x02\x02\u{192}\x37\x03\x02\x02\x02\u{193}\u{199}\x05\x3e\x20\x02\u{194}\
+	\u{199}\x05\x40\x21\x02\u{195}\u{199}\x05\x5a\x2e\x02\u{196}\u{199}\x05\
+	\x5c\x2f\x02\u{197}\u{199}\x05\x5e\x30\x02\u{198}\u{193}\x03\x02\x02\x02\
+	\u{198}\u{194}\x03\x02\x02\x02\u{198}\u{195}\x03\x02\x02\x02\u{198}\u{196}\
+	\x03\x02\x02\x02\u{198}\u{197}\x03\x02\x02\x02\u{199}\x39\x03\x02\x02\x02\
+	\u{19a}\u{19f}\x05\x3c\x1f\x02\u{19b}\u{19c}\x07\x5d\x02\x02\u{19c}\u{19e}\
+	\x05\x3c\x1f\x02\u{19d}\u{19b}\x03\x02\x02\x02\u{19e}\u{1a1}\x03\x02\x02\
+	\x02\u{19f}\u{19d}\x03\x02\x02\x02\u{19f}\u{1a0}\x03\x02\x02\x02\u{1a0}\
+	\x3b\x03\x02\x02\x02\u{1a1}\u{19f}\x03\x02\x02\x02\u{1a2}\u{1a5}\x05\x60\
+	\x31\x02\u{1a3}\u{1a4}\x07\x5e\x02\x02\u{1a4}\u{1a6}\x05\x7c\x3f\x02\u{1a5}\
+	\u{1a3}\x03\x02\x02\x02\u{1a5}\u{1a6}\x03\x02\x02\x02\u{1a6}\x3d\x03\x02\
+	\x02\x02\u{1a7}\u{1a8}\x09\x0b\x02\x02\u{1a8}\x3f\x03\x02\x02\x02\u{1a9}\
+	\u{1c5}\x07\x33\x02\x02\u{1aa}\u{1c5}\x07\x16\x02\x02\u{1ab}\u{1c5}\x07\
+	\x2a\x02\x02\u{1ac}\u{1c5}\x07\x25\x02\x02\u{1ad}\u{1c5}\x07\x26\x02\x02\
+	\u{1ae}\u{1c5}\x07\x1f\x02\x02\u{1af}\u{1c5}\x07\x1b\x02\x02\u{1b0}\u{1c5}\
+	\x07\x2b\x02\x02\u{1b1}\u{1c5}\x07\x32\x02\x02\u{1b2}\u{1c5}\x07\x39\x02\
+	\x02\u{1b3}\u{1c5}\x07\x3a\x02\x02\u{1b4}\u{1c5}\x07\x03\x02\x02\u{1b5}\
+	\u{1c5}\x07\x04\x02\x02\u{1b6}\u{1c5}\x07\x05\x02\x02\u{1b7}\u{1b8}\x07\
+	\x06\x02\x02\u{1b8}\u{1b9}\x07\x40\x02\x02\u{1b9}\u{1ba}\x09\x0c\x02\x02\
+	\u{1ba}\u{1c5}\x07\x41\x02\x02\u{1bb}\u{1c5}\x05\x58\x2d\x02\u{1bc}\u{1c5}\
+	\x05\x42\x22\x02\u{1bd}\u{1c5}\x05\x50\x29\x02\u{1be}\u{1c5}\x05\x7a\x3e\
+	\x02\u{1bf}\u{1c0}\x07\x07\x02\x02\u{1c0}\u{1c1}\x07\x40\x02\x02\u{1c1}\
+	\u{1c2}\x05\x30\x19\x02\u{1c2}\u{1c3}\x07\x41\x02\x02\u{1c3}\u{1c5}\x03\
+	\x02\x02\x02\u{1c4}\u{1a9}\x03\x02\x02\x02\u{1c4}\u{1aa}\x03\x02\x02\x02\
+	\u{1c4}\u{1ab}\x03\x02\x02\x02\u{1c4}\u{1ac}\x03\x02\x02\x02\u{1c4}\u{1ad}\
+	\x03\x02\x02\x02\u{1c4}\u{1ae}\x03\x02\x02\x02\u{1c4}\u{1af}\x03\x02\x02\
+	\x02\u{1c4}\u{1b0}\x03\x02\x02\x02\u{1c4}\u{1b1}\x03\x02\x02\x02\u{1c4}\
+	\u{1b2}\x03\x02\x02\x02\u{1c4}\u{1b3}\x03\x02\x02\x02\u{1c4}\u{1b4}\x03\
+	\x02\x02\x02\u{1c4}\u{1b5}\x03\x02\x02\x02\u{1c4}\u{1b6}\x03\x02\x02\x02\
+	\u{1c4}\u{1b7}\x03\x02\x02\x02\u{1c4}\u{1bb}\x03\x02\x02\x02\u{1c4}\u{1bc}\
+	\x03\x02\x02\x02\u{1c4}\u{1bd}\x03\x02\x02\x02\u{1c4}\u{1be}\x03\x02\x02\
+	\x02\u{1c4}\u{1bf}\x03\x02\x02\x02\u{1c5}\x41\x03\x02\x02\x02\u{1c6}\u{1c8}\
+	\x05\x44\x23\x02\u{1c7}\u{1c9}\x07\x6e\x02\x02\u{1c8}\u{1c7}\x03\x02\x02\
+	\x02\u{1c8}\u{1c9}\x03\x02\x02\x02\u{1c9}\u{1ca}\x03\x02\x02\x02\u{1ca}\
+	\u{1cb}\x07\x44\x02\x02\u{1cb}\u{1cc}\x05\x46\x24\x02\u{1cc}\u{1cd}\x07\
+	\x45\x02\x02\u{1cd}\u{1d2}\x03\x02\x02\x02\u{1ce}\u{1cf}\x05\x44\x23\x02\
+	\u{1cf}\u{1d0}\x07\x6e\x02\x02\u{1d0}\u{1d2}\x03\x02\x02\x02\u{1d1}\u{1c6}\
+	\x03\x02\x02\x02\u{1d1}\u{1ce}\x03\x02\x02\x02\u{1d2}\x43\x03\x02\x02\x02\
+	\u{1d3}\u{1d4}\x09\x0d\x02\x02\u{1d4}\x45\x03\x02\x02\x02\u{1d5}\u{1d7}\
+	\x05\x48\x25\x02\u{1d6}\u{1d5}\x03\x02\x02\x02\u{1d7}\u{1d8}\x03\x02\x02\
+	\x02\u{1d8}\u{1d6}\x03\x02\x02\x02\u{1d8}\u{1d9}\x03\x02\x02\x02\u{1d9}\
+	\x47\x03\x02\x02\x02\u{1da}\u{1db}\x05\x4a\x26\x02\u{1db}\u{1dc}\x05\x4c\
+	\x27\x02\u{1dc}\u{1dd}\x07\x5c\x02\x02\u{1dd}\u{1e3}\x03\x02\x02\x02\u{1de}\
+	\u{1df}\x05\x4a\x26\x02\u{1df}\u{1e0}\x07\x5c\x02\x02\u{1e0}\u{1e3}\x03\
+	\x02\x02\x02\u{1e1}\u{1e3}\x05\u{86}\x44\x02\u{1e2}\u{1da}\x03\x02\x02\x02\
+	\u{1e2}\u{1de}\x03\x02\x02\x02\u{1e2}\u{1e1}\x03\x02\x02\x02\u{1e3}\x49\
+	\x03\x02\x02\x02\u{1e4}\u{1e7}\x05\x40\x21\x02\u{1e5}\u{1e7}\x05\x5a\x2e\
+	\x02\u{1e6}\u{1e4}\x03\x02\x02\x02\u{1e6}\u{1e5}\x03\x02\x02\x02\u{1e7}\
+	\u{1e9}\x03\x02\x02\x02\u{1e8}\u{1ea}\x05\x4a\x26\x02\u{1e9}\u{1e8}\x03\
+	\x02\x02\x02\u{1e9}\u{1ea}\x03\x02\x02\x02\u{1ea}\x4b\x03\x02\x02\x02\u{1eb}\
+	\u{1f0}\x05\x4e\x28\x02\u{1ec}\u{1ed}\x07\x5d\x02\x02\u{1ed}\u{1ef}\x05\
+	\x4e\x28\x02\u{1ee}\u{1ec}\x03\x02\x02\x02\u{1ef}\u{1f2}\x03\x02\x02\x02\
+	\u{1f0}\u{1ee}\x03\x02\x02\x02\u{1f0}\u{1f1}\x03\x02\x02\x02\u{1f1}\x4d\
+	\x03\x02\x02\x02\u{1f2}\u{1f0}\x03\x02\x02\x02\u{1f3}\u{1fa}\x05\x60\x31\
+	\x02\u{1f4}\u{1f6}\x05\x60\x31\x02\u{1f5}\u{1f4}\x03\x02\x02\x02\u{1f5}\
+	\u{1f6}\x03\x02\x02\x02\u{1f6}\u{1f7}\x03\x02\x02\x02\u{1f7}\u{1f8}\x07\
+	\x5b\x02\x02\u{1f8}\u{1fa}\x05\x30\x19\x02\u{1f9}\u{1f3}\x03\x02\x02\x02\
+	\u{1f9}\u{1f5}\x03\x02\x02\x02\u{1fa}\x4f\x03\x02\x02\x02\u{1fb}\u{1fd}\
+	\x07\x1d\x02\x02\u{1fc}\u{1fe}\x07\x6e\x02\x02\u{1fd}\u{1fc}\x03\x02\x02\
+	\x02\u{1fd}\u{1fe}\x03\x02\x02\x02\u{1fe}\u{1ff}\x03\x02\x02\x02\u{1ff}\
+	\u{200}\x07\x44\x02\x02\u{200}\u{202}\x05\x52\x2a\x02\u{201}\u{203}\x07\
+	\x5d\x02\x02\u{202}\u{201}\x03\x02\x02\x02\u{202}\u{203}\x03\x02\x02\x02\
+	\u{203}\u{204}\x03\x02\x02\x02\u{204}\u{205}\x07\x45\x02\x02\u{205}\u{209}\
+	\x03\x02\x02\x02\u{206}\u{207}\x07\x1d\x02\x02\u{207}\u{209}\x07\x6e\x02\
+	\x02\u{208}\u{1fb}\x03\x02\x02\x02\u{208}\u{206}\x03\x02\x02\x02\u{209}\
+	\x51\x03\x02\x02\x02\u{20a}\u{20f}\x05\x54\x2b\x02\u{20b}\u{20c}\x07\x5d\
+	\x02\x02\u{20c}\u{20e}\x05\x54\x2b\x02\u{20d}\u{20b}\x03\x02\x02\x02\u{20e}\
+	\u{211}\x03\x02\x02\x02\u{20f}\u{20d}\x03\x02\x02\x02\u{20f}\u{210}\x03\
+	\x02\x02\x02\u{210}\x53\x03\x02\x02\x02\u{211}\u{20f}\x03\x02\x02\x02\u{212}\
+	\u{215}\x05\x56\x2c\x02\u{213}\u{214}\x07\x5e\x02\x02\u{214}\u{216}\x05\
+	\x30\x19\x02\u{215}\u{213}\x03\x02\x02\x02\u{215}\u{216}\x03\x02\x02\x02\
+	\u{216}\x55\x03\x02\x02\x02\u{217}\u{218}\x07\x6e\x02\x02\u{218}\x57\x03\
+	\x02\x02\x02\u{219}\u{21a}\x07\x38\x02\x02\u{21a}\u{21b}\x07\x40\x02\x02\
+	\u{21b}\u{21c}\x05\x74\x3b\x02\u{21c}\u{21d}\x07\x41\x02\x02\u{21d}\x59\
+	\x03\x02\x02\x02\u{21e}\u{21f}\x09\x0e\x02\x02\u{21f}\x5b\x03\x02\x02\x02\
+	\u{220}\u{229}\x07\x24\x02\x02\u{221}\u{229}\x07\x3d\x02\x02\u{222}\u{229}\
+	\x07\x08\x02\x02\u{223}\u{229}\x07\x09\x02\x02\u{224}\u{225}\x07\x0a\x02\
+	\x02\u{225}\u{226}\x07\x40\x02\x02\u{226}\u{227}\x07\x6e\x02\x02\u{227}\
+	\u{229}\x07\x41\x02\x02\u{228}\u{220}\x03\x02\x02\x02\u{228}\u{221}\x03\
+	\x02\x02\x02\u{228}\u{222}\x03\x02\x02\x02\u{228}\u{223}\x03\x02\x02\x02\
+	\u{228}\u{224}\x03\x02\x02\x02\u{229}\x5d\x03\x02\x02\x02\u{22a}\u{22b}\
 	\x07\x36\x02\x02\u{22b}\u{22e}\x07\x40\x02\x02\u{22c}\u{22f}\x05\x74\x3b\
 	\x02\u{22d}\u{22f}\x05\x30\x19\x02\u{22e}\u{22c}\x03\x02\x02\x02\u{22e}\
 	\u{22d}\x03\x02\x02\x02\u{22f}\u{230}\x03\x02\x02\x02\u{230}\u{231}\x07\
@@ -15042,15 +15083,19 @@ const _serializedATN:&'static str =
 	\x05\x60\x31\x02\u{3f8}\u{3f9}\x05\u{8c}\x47\x02\u{3f9}\u{bf}\x03\x02\x02\
 	\x02\u{3fa}\u{3fc}\x05\x32\x1a\x02\u{3fb}\u{3fa}\x03\x02\x02\x02\u{3fc}\
 	\u{3fd}\x03\x02\x02\x02\u{3fd}\u{3fb}\x03\x02\x02\x02\u{3fd}\u{3fe}\x03\
-	\x02\x02\x02\u{3fe}\u{c1}\x03\x02\x02\x02\x73\u{c7}\u{cd}\u{db}\u{e0}\u{f5}\
-	\u{f9}\u{101}\u{105}\u{110}\u{11b}\u{123}\u{12b}\u{133}\u{13b}\u{143}\u{14b}\
-	\u{153}\u{15b}\u{163}\u{16c}\u{174}\u{17d}\u{187}\u{18c}\u{191}\u{198}\u{19f}\
-	\u{1a5}\u{1c4}\u{1c8}\u{1d1}\u{1d8}\u{1e2}\u{1e6}\u{1e9}\u{1f0}\u{1f5}\u{1f9}\
-	\u{1fd}\u{202}\u{208}\u{20f}\u{215}\u{228}\u{22e}\u{233}\u{248}\u{24d}\u{250}\
-	\u{256}\u{262}\u{265}\u{267}\u{271}\u{273}\u{278}\u{27c}\u{281}\u{286}\u{28d}\
-	\u{295}\u{297}\u{29e}\u{2a3}\u{2a7}\u{2aa}\u{2b3}\u{2b6}\u{2bc}\u{2cc}\u{2cf}\
-	\u{2d4}\u{2d7}\u{2de}\u{2f1}\u{2f4}\u{2f6}\u{2ff}\u{303}\u{306}\u{30b}\u{310}\
-	\u{319}\u{321}\u{32a}\u{33e}\u{341}\u{349}\u{34c}\u{350}\u{355}\u{362}\u{36b}\
-	\u{370}\u{373}\u{379}\u{382}\u{38d}\u{3a6}\u{3aa}\u{3ae}\u{3b2}\u{3b4}\u{3bd}\
-	\u{3c3}\u{3ce}\u{3d4}\u{3d9}\u{3e3}\u{3e8}\u{3ef}\u{3f4}\u{3fd}";
+	\x02\x02\x02\u{3fe}\u{c1}\x03\x02\x02\x02\u{3bd}\u{400}\x03\x02\x02\x02\
+	\u{400}\u{401}\x07\x21\x02\x02\u{401}\u{402}\x07\x6e\x02\x02\u{402}\u{403}\
+	\x07\x5c\x02\x02\u{403}\u{3be}\x03\x02\x02\x02\u{187}\u{404}\x03\x02\x02\
+	\x02\u{404}\u{405}\x05\x34\x1b\x02\u{405}\u{406}\x07\x5c\x02\x02\u{406}\
+	\u{188}\x03\x02\x02\x02\x73\u{c7}\u{cd}\u{db}\u{e0}\u{f5}\u{f9}\u{101}\u{105}\
+	\u{110}\u{11b}\u{123}\u{12b}\u{133}\u{13b}\u{143}\u{14b}\u{153}\u{15b}\u{163}\
+	\u{16c}\u{174}\u{17d}\u{187}\u{18c}\u{191}\u{198}\u{19f}\u{1a5}\u{1c4}\u{1c8}\
+	\u{1d1}\u{1d8}\u{1e2}\u{1e6}\u{1e9}\u{1f0}\u{1f5}\u{1f9}\u{1fd}\u{202}\u{208}\
+	\u{20f}\u{215}\u{228}\u{22e}\u{233}\u{248}\u{24d}\u{250}\u{256}\u{262}\u{265}\
+	\u{267}\u{271}\u{273}\u{278}\u{27c}\u{281}\u{286}\u{28d}\u{295}\u{297}\u{29e}\
+	\u{2a3}\u{2a7}\u{2aa}\u{2b3}\u{2b6}\u{2bc}\u{2cc}\u{2cf}\u{2d4}\u{2d7}\u{2de}\
+	\u{2f1}\u{2f4}\u{2f6}\u{2ff}\u{303}\u{306}\u{30b}\u{310}\u{319}\u{321}\u{32a}\
+	\u{33e}\u{341}\u{349}\u{34c}\u{350}\u{355}\u{362}\u{36b}\u{370}\u{373}\u{379}\
+	\u{382}\u{38d}\u{3a6}\u{3aa}\u{3ae}\u{3b2}\u{3b4}\u{3bd}\u{3c3}\u{3ce}\u{3d4}\
+	\u{3d9}\u{3e3}\u{3e8}\u{3ef}\u{3f4}\u{3fd}";
 
@@ -29,7 +29,8 @@ impl<'input, S> CListener<'input> for TerminalOnlyListener<S> {}
 pub struct TerminalRuleListener<S> {
     pub st:S, // status passing through the tree
     // pub visit_term_f: Box<dyn FnMut(& ParserContext,&mut S)->()>,
-    pub enter_rule_f:Box<dyn FnMut(&ParserContext, &mut S, bool, usize) -> ()>,
+    /// 最后一个参数是这个节点第一个 token 的 (行号, 列号) ，行号从 1 开始，列号从 0 开始
+    pub enter_rule_f:Box<dyn FnMut(&ParserContext, &mut S, bool, usize, (isize, isize)) -> ()>,
     pub exit_rule_f:Box<dyn FnMut(&ParserContext, &mut S, bool, usize) -> ()>,
 }
 impl<'input, S> ParseTreeListener<'input, CParserContextType> for TerminalRuleListener<S> {
@@ -37,12 +38,12 @@ impl<'input, S> ParseTreeListener<'input, CParserContextType> for TerminalRuleLi
         // println!("token type  {}", ctx.symbol.get_token_type(),);
         // 如果 token type = -1 那么，这是个 EOF 标记
         if ctx.symbol.get_token_type() != -1 {
-            (*self.enter_rule_f)(ctx, &mut self.st, true, (ctx.symbol.get_token_type()) as usize);
+            (*self.enter_rule_f)(ctx, &mut self.st, true, (ctx.symbol.get_token_type()) as usize, (ctx.symbol.get_line(), ctx.symbol.get_column()));
             (*self.exit_rule_f)(ctx, &mut self.st, true, (ctx.symbol.get_token_type()) as usize);
         }
     }
     fn visit_error_node(&mut self, _node:&antlr_rust::tree::ErrorNode<'input, CParserContextType>) {}
-    fn enter_every_rule(&mut self, ctx:&<CParserContextType as ParserNodeType>::Type) { (*self.enter_rule_f)(ctx, &mut self.st, false, ctx.get_rule_index()); }
+    fn enter_every_rule(&mut self, ctx:&<CParserContextType as ParserNodeType>::Type) { (*self.enter_rule_f)(ctx, &mut self.st, false, ctx.get_rule_index(), (ctx.start().get_line(), ctx.start().get_column())); }
     fn exit_every_rule(&mut self, ctx:&<CParserContextType as ParserNodeType>::Type) { (*self.exit_rule_f)(ctx, &mut self.st, false, ctx.get_rule_index()); }
 }
 impl<'input, S> CListener<'input> for TerminalRuleListener<S> {}
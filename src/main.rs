@@ -14,10 +14,10 @@ use std::fmt::Debug;
 use antlr_parser::cparser::{RULE_compoundStatement, RULE_functionDefinition};
 use clap::Parser;
 
-use passes::{ast2cfg_pass::Ast2CfgPass, ast2et_debug_pass::Ast2EtDebugPass, ast2st_pass::Ast2StPass, call_graph_pass::CallGraphPass, cfg2lpt_pass::Cfg2LptPass, cfg2ncfg_pass::Cfg2NcfgPass, chi_mu_insertion_pass::ChiMuInsertionPass, code2ast_pass::Code2AstPass, dead_code_elimination_pass::{self, DeadCodeEliminationPass}, gvngcm_pass::GvnGcmPass, nhwc2et_pass::Nhwc2EtPass, nhwc2riscv_pass::Nhwc2RiscvPass, nhwc_dump_pass::NhwcDumpPass, ssa_deconstruction_pass::SsaDeconstructionPass, symtab_debug_pass::SymtabDebugPass, untrack_insertion_pass::{self, UntrackInsertionPass}};
+use passes::{ast2cfg_pass::Ast2CfgPass, cfg_loop_opt_pass::LoopOptPass, ast2et_debug_pass::Ast2EtDebugPass, ast2st_pass::Ast2StPass, call_graph_pass::CallGraphPass, cfg2lpt_pass::Cfg2LptPass, cfg2ncfg_pass::Cfg2NcfgPass, chi_mu_insertion_pass::ChiMuInsertionPass, code2ast_pass::Code2AstPass, preprocess_pass::PreprocessPass, semantic_check_pass::SemanticCheckPass, warning_pass::WarningPass, dead_code_elimination_pass::{self, DeadCodeEliminationPass}, gvngcm_pass::GvnGcmPass, nhwc2et_pass::Nhwc2EtPass, nhwc2riscv_pass::Nhwc2RiscvPass, nhwc_dump_pass::NhwcDumpPass, ssa_deconstruction_pass::SsaDeconstructionPass, symtab_debug_pass::SymtabDebugPass, untrack_insertion_pass::{self, UntrackInsertionPass}};
 use toolkit::symtab::SymIdx;
 
-use crate::{passes::{cfg_debug_pass::CfgDebugPass, def_use_chain_debug_pass::DefUseChainPass, mem_alloc_pass::MemAllocPass, ncfg2djg_pass::Ncfg2DjgPass, simulator_debug_pass::SimulatorDebugPass, ssa_pass::SsaPass}, toolkit::{pass_manager::PassManager}};
+use crate::{passes::{cfg_debug_pass::CfgDebugPass, def_use_chain_debug_pass::DefUseChainPass, mem_alloc_pass::MemAllocPass, ncfg2djg_pass::Ncfg2DjgPass, simulator_debug_pass::SimulatorDebugPass, ssa_pass::SsaPass}, toolkit::{pass_manager::{Pass, PassManager}}};
 #[derive(Parser, Clone, Default, Debug)]
 #[command(author, version, about)]
 pub struct Args {
@@ -30,9 +30,9 @@ pub struct Args {
     // output file path
     #[clap(short, long, default_value = "./testcase.s")]
     pub output: PathBuf,
-    /// Optimization level
-    #[arg(short='O', value_name = "optimize", default_value = "")]
-    pub optimize: String,
+    /// 优化等级，可选 -O0 -O1 -O2 -O3 -Os ，不指定时与 -O1 相同
+    #[arg(short='O', value_name = "optimize", default_value = "", value_parser = OptLevel::from_flag)]
+    pub optimize: OptLevel,
     // #[arg(short, long, default_value = "true")]
     // gen_png : bool
     #[arg(short, value_name = "debug", default_value = "false")]
@@ -46,28 +46,67 @@ pub struct Args {
 
     #[arg(short, value_name = "test", default_value = "false")]
     pub test:bool,
+
+    /// 头文件的查找路径，可以指定多次
+    #[arg(short='I', value_name = "include_dir")]
+    pub include_dirs:Vec<PathBuf>,
+
+    /// 预定义宏，形如 NAME 或者 NAME=VALUE ，可以指定多次
+    #[arg(short='D', value_name = "macro")]
+    pub defines:Vec<String>,
+
+    /// 打开警告，形如 -Wall -Wunused -Wno-unused -Werror ，可以指定多次
+    #[arg(short='W', value_name = "warning")]
+    pub warnings:Vec<String>,
+
+    /// 选择前端 parser ，antlr 使用 antlr 生成的 CParser ，native 使用手写的递归下降 parser ，check 两者都运行并逐节点比较
+    #[arg(long = "parser", value_name = "parser", default_value = "antlr")]
+    pub parser:String,
     
 }
 
+/// 优化等级，按启用的 pass 从少到多排列，因此可以直接比较大小  
+/// O0 只做到生成代码所必需的 SSA 构造与析构，O1 起加入 GvnGcm 与 DCE ，Os 与 O1 相同，不启用会增大代码体积的循环旋转  
+/// O2 起再加入循环旋转(LoopOptPass)与循环树的构建，目前没有只在 O3 启用的 pass ，O3 的 pass 列表与 O2 相同
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub enum OptLevel {
+    O0,
+    #[default]
+    O1,
+    Os,
+    O2,
+    O3,
+}
+impl OptLevel {
+    /// flag 是 -O 后面的部分，作为 clap 的 value_parser ，出错时由 clap 报告参数错误
+    pub fn from_flag(flag:&str) -> Result<Self, String> {
+        match flag {
+            "0" => Ok(OptLevel::O0),
+            "" | "1" => Ok(OptLevel::O1),
+            "s" => Ok(OptLevel::Os),
+            "2" => Ok(OptLevel::O2),
+            "3" => Ok(OptLevel::O3),
+            _ => Err(format!("未知的优化等级 -O{} ，可选 -O0 -O1 -O2 -O3 -Os", flag)),
+        }
+    }
+}
 
-fn main() {
-    // 读取命令选项，诸如 -c 表示代码文件地址
-    // 你也可以通过运行 cargo run -- --help 来查看所有可用选项
-    env_logger::init();
-    let args = Args::parse();
+/// 按优化等级与命令行选项生成要运行的 pass 列表
+fn build_pipeline(args:&Args) -> Vec<Box<dyn Pass>> {
     let debug = args.debug;
     let annotation = args.annotation;
-    // args.c_file_path = PathBuf::from_str("./demos/demo1.c").unwrap();
-    let mut pass_manager = PassManager::new(args);
+    let preprocess_pass = PreprocessPass::new(debug);
     let code2ast_pass = Code2AstPass::new(debug);
     let ast2cfg_pass = Ast2CfgPass::new(debug);
     let cfg2lpt_pass = Cfg2LptPass::new(debug);
     let cfg2ncfg_pass = Cfg2NcfgPass::new(debug,debug);
     let ast2et_debug_pass = Ast2EtDebugPass::new(debug);
     let ast2st_pass = Ast2StPass::new(debug);
+    let semantic_check_pass = SemanticCheckPass::new();
     let ncfg2djg_pass = Ncfg2DjgPass::new(debug);
     let ncfg2djg_pass2 = Ncfg2DjgPass::new(debug);
     let ssa_pass = SsaPass::new(debug, debug);
+    let warning_pass = WarningPass::new();
     let ssa_deconstruction_pass = SsaDeconstructionPass::new(debug, debug);
     let cfg_debug_pass1 = CfgDebugPass::new(debug);
     let cfg_debug_pass2 = CfgDebugPass::new(debug);
@@ -83,68 +122,102 @@ fn main() {
     let dce_pass = DeadCodeEliminationPass::new(debug,debug);
     let gvngcm_pass = GvnGcmPass::new(debug,debug);
     let untrack_insertion_pass = UntrackInsertionPass::new(debug,debug);
-    if pass_manager.ctx.args.test{
-        add_passes!(
-            code2ast_pass
-            then ast2st_pass
-            then ast2cfg_pass
-            then cfg2ncfg_pass
-            // then cfg2lpt_pass
-            then func_call_pass
-            then chi_mu_insertion_pass
-            then ncfg2djg_pass
-            then ssa_pass
-
-            then gvngcm_pass
-            then def_use_chain_pass
-            // then dce_pass
-            // then simulator_debug_pass
-            then ast2et_debug_pass
-            then symtab_debug_pass
-            // then nhwc2et_pass
-            then ssa_deconstruction_pass
-            then ncfg2djg_pass2
-            then untrack_insertion_pass
-            then nhwc_dump_pass
-            then mem_alloc_pass
-            then cfg_debug_pass2
-            then nhwc2riscv_pass
-            to pass_manager
-            
-        );
-    }else {
-        add_passes!(
-            code2ast_pass
-            then ast2st_pass
-            then ast2cfg_pass
-            then cfg2ncfg_pass
-            // then cfg2lpt_pass
-            then func_call_pass
-            then chi_mu_insertion_pass
-            then ncfg2djg_pass
-            then ssa_pass
-            then gvngcm_pass
-            then def_use_chain_pass
-            then dce_pass
-            // then simulator_debug_pass
-            then ast2et_debug_pass
-            then symtab_debug_pass
-            // then nhwc2et_pass
-            then ssa_deconstruction_pass
-            then ncfg2djg_pass2
-            then untrack_insertion_pass
-            then nhwc_dump_pass
-            then mem_alloc_pass
-            then cfg_debug_pass2
-            then nhwc2riscv_pass
-            to pass_manager
-            
-        );
-    };
+    let loop_opt_pass = LoopOptPass::new(debug);
+    let opt_level = args.optimize;
+    // -t 在任何优化等级下都关闭 DCE
+    let is_dce_enabled = opt_level >= OptLevel::O1 && !args.test;
+    // 每一行是 (是否启用, pass)，按顺序加入 pass_manager
+    let pipeline:Vec<(bool, Box<dyn Pass>)> = vec![
+        (true, Box::new(preprocess_pass)),
+        (true, Box::new(code2ast_pass)),
+        (true, Box::new(ast2st_pass)),
+        (true, Box::new(semantic_check_pass)),
+        (true, Box::new(ast2cfg_pass)),
+        (opt_level >= OptLevel::O2, Box::new(loop_opt_pass)),
+        (true, Box::new(cfg2ncfg_pass)),
+        (opt_level >= OptLevel::O2, Box::new(cfg2lpt_pass)),
+        (true, Box::new(func_call_pass)),
+        (true, Box::new(chi_mu_insertion_pass)),
+        (true, Box::new(ncfg2djg_pass)),
+        (true, Box::new(ssa_pass)),
+        (true, Box::new(warning_pass)),
+        (opt_level >= OptLevel::O1, Box::new(gvngcm_pass)),
+        // def use 图是分析，DCE 等后面的 pass 依赖它，所以每个等级都构建
+        (true, Box::new(def_use_chain_pass)),
+        (is_dce_enabled, Box::new(dce_pass)),
+        (true, Box::new(ast2et_debug_pass)),
+        (true, Box::new(symtab_debug_pass)),
+        (true, Box::new(ssa_deconstruction_pass)),
+        (true, Box::new(ncfg2djg_pass2)),
+        (true, Box::new(untrack_insertion_pass)),
+        (true, Box::new(nhwc_dump_pass)),
+        (true, Box::new(mem_alloc_pass)),
+        (true, Box::new(cfg_debug_pass2)),
+        (true, Box::new(nhwc2riscv_pass)),
+    ];
+    pipeline.into_iter().filter(|(is_enabled, _)| *is_enabled).map(|(_, pass)| pass).collect()
+}
+
+fn main() {
+    // 读取命令选项，诸如 -c 表示代码文件地址
+    // 你也可以通过运行 cargo run -- --help 来查看所有可用选项
+    env_logger::init();
+    let args = Args::parse();
+    // args.c_file_path = PathBuf::from_str("./demos/demo1.c").unwrap();
+    let pipeline = build_pipeline(&args);
+    let mut pass_manager = PassManager::new(args);
+    for pass in pipeline {
+        pass_manager.add_pass(pass);
+    }
     let rst = timeit!({ pass_manager.execute_passes() }, "all passed finish");
     timeit!({ pass_manager.await_all_io_tasks() }, "all io tasks finish");
-    if rst.is_err() {
-        panic!("pass manager execute failed");
+    if let Err(e) = rst {
+        // 诊断信息在各个 pass 中已经输出，这里只说明失败原因并返回非 0 的退出码
+        eprintln!("{}", e.root_cause());
+        std::process::exit(1);
     }
 }
 
+
+#[cfg(test)]
+mod test {
+    use super::{build_pipeline, Args, OptLevel};
+
+    fn pass_names_of(optimize:OptLevel, test:bool) -> Vec<String> {
+        build_pipeline(&Args { optimize, test, ..Default::default() }).iter().map(|pass| pass.get_pass_name()).collect()
+    }
+
+    /// 每个优化等级各自的 pass 列表
+    #[test]
+    fn test_pass_list_of_each_opt_level() {
+        let o0 = pass_names_of(OptLevel::O0, false);
+        let o1 = pass_names_of(OptLevel::O1, false);
+        let os = pass_names_of(OptLevel::Os, false);
+        let o2 = pass_names_of(OptLevel::O2, false);
+        let o3 = pass_names_of(OptLevel::O3, false);
+        for opt_names in [&o0, &o1, &os, &o2, &o3] {
+            for required_name in ["PreprocessPass", "Ast2CfgPass", "Cfg2NhwcCfgPass", "SSAPass", "SSADeconstructionPass", "Nhwc2RiscvPass"] {
+                assert!(opt_names.iter().any(|name| name == required_name), "{:?} 中缺少 {}", opt_names, required_name);
+            }
+        }
+        let has = |names:&Vec<String>, pass_name:&str| names.iter().any(|name| name == pass_name);
+        assert!(!has(&o0, "GvnGcmPass") && !has(&o0, "DeadCodeElimination Pass") && !has(&o0, "LoopOptPass"));
+        assert!(has(&o1, "GvnGcmPass") && has(&o1, "DeadCodeElimination Pass") && !has(&o1, "LoopOptPass") && !has(&o1, "Cfg2LptPass"));
+        assert_eq!(os, o1);
+        assert!(has(&o2, "GvnGcmPass") && has(&o2, "LoopOptPass") && has(&o2, "Cfg2LptPass"));
+        assert_eq!(o3, o2);
+        // 循环旋转只能在 cfg 还没有生成指令时进行
+        let position = |pass_name:&str| o2.iter().position(|name| name == pass_name).unwrap();
+        assert!(position("Ast2CfgPass") < position("LoopOptPass") && position("LoopOptPass") < position("Cfg2NhwcCfgPass"));
+        // -t 在任何等级下都关闭 DCE
+        assert!(!has(&pass_names_of(OptLevel::O2, true), "DeadCodeElimination Pass"));
+    }
+
+    #[test]
+    fn test_opt_level_from_flag() {
+        assert_eq!(OptLevel::from_flag(""), Ok(OptLevel::O1));
+        assert_eq!(OptLevel::from_flag("s"), Ok(OptLevel::Os));
+        assert_eq!(OptLevel::from_flag("3"), Ok(OptLevel::O3));
+        assert!(OptLevel::from_flag("4").is_err());
+    }
+}
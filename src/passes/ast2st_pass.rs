@@ -1,4 +1,4 @@
-use crate::toolkit::{context::NhwcCtx, dot::Config, etc::generate_png_by_graph_multi_tasks, gen_scope::parse_ast_to_scope, pass_manager::Pass};
+use crate::toolkit::{context::NhwcCtx, dot::Config, etc::generate_png_by_graph_multi_tasks, gen_scope::{parse_ast_to_scope, parse_typedefs_to_symtab}, pass_manager::Pass};
 use anyhow::Result;
 #[derive(Debug)]
 pub struct Ast2StPass {
@@ -12,6 +12,8 @@ impl Pass for Ast2StPass {
     // 运行这个pass
     fn run(&mut self, ctx:&mut NhwcCtx) -> Result<()> {
         parse_ast_to_scope(&ctx.ast_tree,&ctx.cfg_graph,&mut ctx.scope_tree,&mut ctx.ast2scope);
+        // typedef 名按作用域登记为类型符号，之后解析类型时沿 scope_tree 向外查找
+        parse_typedefs_to_symtab(&ctx.ast_tree,&ctx.scope_tree,&ctx.ast2scope,&mut ctx.symtab)?;
         // 3.1 生成对应的pn
         Ok(())
     }
@@ -1,7 +1,7 @@
 use crate::toolkit::{dot::Config};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-use crate::toolkit::{context::NhwcCtx, etc::generate_png_by_graph_multi_tasks, gen_nhwc_cfg::parse_cfg_into_nhwc_cfg, pass_manager::Pass};
+use crate::toolkit::{context::NhwcCtx, etc::generate_png_by_graph_multi_tasks, gen_nhwc_cfg::parse_cfg_into_nhwc_cfg, pass_manager::Pass, warning::collect_unreachable_warnings};
 #[derive(Debug)]
 pub struct Cfg2NcfgPass {
     is_gen_ncfg_png:bool,
@@ -27,6 +27,17 @@ impl Pass for Cfg2NcfgPass {
         // }
         // generate_png_by_graph(&et_tree, format!("et_tree"), &[Config::EdgeNoLabel, Config::Record, Config::Title("et_tree".to_string()),Config::NodeIndexLabel])?;
         rst?;
+        let errors = std::mem::take(ctx.symtab.get_mut_global_info().get_mut_diagnostics());
+        for error in errors.iter() {
+            eprintln!("{}", error.render());
+        }
+        if !errors.is_empty() {
+            return Err(anyhow!("生成 nhwc ir 时发现 {} 个错误", errors.len()));
+        }
+        // return 等语句之后的孤立节点会在 Ncfg2DjgPass 中被删掉，所以不可达代码要在这里收集
+        if !ctx.args.warnings.is_empty() {
+            ctx.diagnostics.extend(collect_unreachable_warnings(&ctx.cfg_graph));
+        }
         Ok(())
     }
     // 返回pass的描述，具体作用
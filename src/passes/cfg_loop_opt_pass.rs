@@ -1,6 +1,5 @@
-use crate::toolkit::{context::NhwcCtx, pass_manager::Pass};
+use crate::toolkit::{context::NhwcCtx, dot::Config, etc::generate_png_by_graph_multi_tasks, loop_optimize::rotate_while_loops, pass_manager::Pass};
 use anyhow::*;
-use syn::token::Loop;
 /// 定义额外的信息，这样我们就可以把 add_field 宏加入到符号表或者任何实现了 Fields trait 的地方
 /// 任何一个Pass 都有一个pass_run函数 来进行这个pass 相关的工作，比如说对于 SSAPass 我们要对 一个BasicBlock 中的ExprTree做出转换。
 /// 因为实际上 一个 ExprTree 最终会对应一个BasicBlock。
@@ -13,19 +12,20 @@ use syn::token::Loop;
 /// 总上，pass 的主要操作对象是 每个basic block 的expr_tree以及 cfg node。这点我们大概不会变
 /// 这个结构体，用于存储与Pass 相关的数据
 ///
+/// 目前只做循环旋转，在 Ast2Cfg 之后、 Cfg2Ncfg 之前运行，此时 cfg 中还没有指令
 #[derive(Debug)]
-pub struct LoopOptPass {}
+pub struct LoopOptPass {
+    is_gen_png:bool,
+}
 impl LoopOptPass {
-    pub fn new() -> Self { LoopOptPass {} }
+    pub fn new(is_gen_png:bool) -> Self { LoopOptPass { is_gen_png } }
 }
 
 impl Pass for LoopOptPass {
     // 运行这个pass
     fn run(&mut self, ctx:&mut NhwcCtx) -> Result<()> { 
-        // 1. find all while 
-
-        // 2. iter all phi node in cfg while node 
-        // 3. remove the phi def from any direct edge 
+        // 把 while 循环旋转为被 if 保护的 do while ，每次迭代少一次跳转
+        rotate_while_loops(&mut ctx.cfg_graph)?;
         Ok(()) 
     }
     // 返回pass的描述，具体作用
@@ -35,7 +35,11 @@ impl Pass for LoopOptPass {
     fn get_pass_name(&self) -> String { return "LoopOptPass".to_string(); }
     
     fn when_finish_or_panic(&mut self, ctx:&mut crate::toolkit::context::NhwcCtx) {
-        
+        if self.is_gen_png {
+            for cfg_node in ctx.cfg_graph.node_weights_mut() {
+                cfg_node.load_ast_node_text(&ctx.ast_tree);
+            }
+            generate_png_by_graph_multi_tasks(&ctx.cfg_graph.clone(), "rotated_cfg_graph".to_string(), &[Config::Record, Config::Rounded,Config::CfgBlock, Config::Title("rotated_cfg_graph".to_string()), Config::NodeIndexLabel],&mut ctx.io_task_list).unwrap();
+        }
     }
 }
-
@@ -1,6 +1,6 @@
 use std::{collections::HashSet, mem};
 
-use crate::{instr_mut, node, node_mut, toolkit::{call_node::CallGraph, cfg_node::{CfgGraph, InstrList}, etc, nhwc_instr::{InstrSlab, JumpOp, NhwcInstr, NhwcInstrType}, symtab::{SymTab, WithBorrow}}};
+use crate::{instr_mut, node, node_mut, toolkit::{call_node::CallGraph, cfg_node::{CfgGraph, InstrList}, etc, gen_nhwc_cfg::get_may_pointed_symidx_vec, nhwc_instr::{InstrSlab, JumpOp, NhwcInstr, NhwcInstrType}, symtab::{SymTab, WithBorrow}}};
 
 
 use ahash::{AHashSet, HashSetExt};
@@ -69,6 +69,13 @@ pub fn insert_additional_mu_chi_for_call_instr(cfg_graph:&mut CfgGraph, instr_sl
                             symtab.get_mut(&rc_func_symidx.as_ref_borrow()).get_mut_local_chi_set().insert(rc_may_def_symidx.clone());
                         }
                     },
+                    NhwcInstrType::Call { op_lhs:_, func_op } if func_op.is_indirect => {
+                        // 间接调用可能读写任何一个全局变量
+                        for rc_global_symidx in symtab.get_global_info().get_global_vars().clone(){
+                            symtab.get_mut(&rc_func_symidx.as_ref_borrow()).get_mut_global_mu_set().insert(rc_global_symidx.clone());
+                            symtab.get_mut(&rc_func_symidx.as_ref_borrow()).get_mut_global_chi_set().insert(rc_global_symidx);
+                        }
+                    },
                     _ => {
                         // do nothing
                     }
@@ -106,14 +113,21 @@ pub fn insert_additional_mu_chi_for_call_instr(cfg_graph:&mut CfgGraph, instr_sl
                     NhwcInstrType::Call { op_lhs: op_assigned_symidx, func_op } => {
                         new_instrs.push(instr);
                         flag = true;
-                        let mut chi_set = symtab.get(&func_op.rc_func_symidx.as_ref_borrow()).get_global_chi_set().clone();
-                        let mut mu_set = symtab.get(&func_op.rc_func_symidx.as_ref_borrow()).get_global_mu_set().clone();
-                        for para_symidx in &func_op.actual_arg_symidx_vec{
-                            let para_symidx = para_symidx.as_ref_borrow();
-                            if symtab.get(&para_symidx).has_pointed_symidx(){
-                                let pointed_symidx = symtab.get(&para_symidx).get_pointed_symidx().clone();
-                                chi_set.insert(pointed_symidx.clone());
-                                mu_set.insert(pointed_symidx.clone());
+                        // 间接调用的被调函数未知，认为它可能读写所有逃逸的内存，即全局变量与传入指针指向的变量
+                        let (mut chi_set, mut mu_set) = if func_op.is_indirect{
+                            let global_set:ahash::HashSet<_> = symtab.get_global_info().get_global_vars().iter().cloned().collect();
+                            (global_set.clone(), global_set)
+                        }else{
+                            (symtab.get(&func_op.rc_func_symidx.as_ref_borrow()).get_global_chi_set().clone(), symtab.get(&func_op.rc_func_symidx.as_ref_borrow()).get_global_mu_set().clone())
+                        };
+                        for rc_para_symidx in &func_op.actual_arg_symidx_vec{
+                            let para_symidx = rc_para_symidx.as_ref_borrow();
+                            // 传入指针时，被调函数可能读写指针指向的所有变量，函数指针指向的不是变量
+                            if (symtab.get(&para_symidx).has_pointed_symidx() || symtab.get(&para_symidx).get_type().is_ptr_64()) && !symtab.get(&para_symidx).get_type().is_fn_ptr(){
+                                for rc_pointed_symidx in get_may_pointed_symidx_vec(symtab, &rc_func_symidx, rc_para_symidx){
+                                    chi_set.insert(rc_pointed_symidx.clone());
+                                    mu_set.insert(rc_pointed_symidx);
+                                }
                             }
                         }
                         for rc_mu_symidx in mu_set{
@@ -1,6 +1,8 @@
-use crate::{ debug_info_blue, debug_info_green, debug_info_yellow, instr_mut, node, reg_field_for_struct, toolkit::{cfg_node::CFG_ROOT, context::NhwcCtx, dot::Config, etc::{_reverse_dfs_with_predicate, generate_png_by_graph_multi_tasks}, gen_dug::parse_dug, nhwc_instr::{JumpOp, NhwcInstr, NhwcInstrType}, pass_manager::Pass, symtab::{SymTab, SymTabEdge, SymTabGraph}}};
+use crate::{ debug_info_blue, debug_info_green, debug_info_yellow, direct_parent_nodes, instr_mut, node, reg_field_for_struct, toolkit::{cfg_node::{CfgGraph, CFG_ROOT}, context::NhwcCtx, dot::Config, etc::{_reverse_dfs_with_predicate, dfs, generate_png_by_graph_multi_tasks}, field::{TypeDiscriminants, Value}, gen_dug::parse_dug, gen_nhwc_cfg::find_label_of_cfg_node, nhwc_instr::{ArithOp, InstrSlab, JumpOp, NhwcInstr, NhwcInstrType, Trans}, pass_manager::Pass, symtab::{SymIdx, SymTab, SymTabEdge, SymTabGraph, WithBorrow}}};
 use anyhow::*;
+use ahash::HashSet;
 use itertools::Itertools;
+use petgraph::{graph::node_index, visit::EdgeRef};
 use crate::instr;
 #[derive(Debug)]
 pub struct DeadCodeEliminationPass {
@@ -25,10 +27,13 @@ impl Pass for DeadCodeEliminationPass {
         // 先建立一个图 
         let (instr_slab,cfg_graph,def_use_graph,symtab,dj_graph, call_graph)= (&mut ctx.nhwc_instr_slab,&mut ctx.cfg_graph,&mut ctx.def_use_graph,&mut ctx.symtab,&ctx.dj_graph, &ctx.call_graph);
         
+        // 0. remove cases of switch that can never be taken
+        prune_unreachable_switch_cases(cfg_graph, instr_slab, symtab);
+
         // 1. set all instruction that 
         // unrelevant to a ret instruction
         // && unrelevant to a external func call
-        // && unrelevant to br
+        // && unrelevant to br or switch
         // to nop 
         let mut relevant_dug_nodes = vec![];
         for dug_node_idx in def_use_graph.node_indices(){
@@ -44,6 +49,9 @@ impl Pass for DeadCodeEliminationPass {
                 NhwcInstrType::Jump { jump_op:JumpOp::Br { cond, t1, t2 } } => {
                     relevant_dug_nodes.push(*instr!(at instr in instr_slab).get_dug_cor_def_use_node());
                 }
+                NhwcInstrType::Jump { jump_op:JumpOp::Switch { cond:_, default:_, compared:_ } } => {
+                    relevant_dug_nodes.push(*instr!(at instr in instr_slab).get_dug_cor_def_use_node());
+                }
                 _ => {
                     
                 }
@@ -65,6 +73,8 @@ impl Pass for DeadCodeEliminationPass {
                 NhwcInstrType::DefineVar { var_symidx, vartype, op_value } => {},
                 NhwcInstrType::Alloc { var_symidx_vec: var_symidx, vartype } => {},
                 NhwcInstrType::Globl { var_symidx, vartype } => {},
+                // 已经随着不可达的 cfg node 一起删掉了
+                NhwcInstrType::Nope {  } => {},
                 _ => {
                     if instr!(at unvisited_instr in instr_slab).get_cfg_instr_idx().cfg_node!= CFG_ROOT{
                         debug_info_yellow!("set instr {} {:?} to nope ",unvisited_instr, instr!(at unvisited_instr in instr_slab));
@@ -103,3 +113,107 @@ impl Pass for DeadCodeEliminationPass {
         }
     }
 }
+
+/// 根据 switch cond 的定义推断它的取值范围，范围之外的 case 永远不会被跳到，从 switch instr 中删掉并删掉对应的 Case 边
+/// 如果 cond 是常量，那么 switch 只会剩下 default 这一个目标  
+/// 删边之后从 CFG_ROOT 不再可达的 cfg node 连同其中的 instr 一起删除，后继 phi 中来自被删边的 phi pair 也要去掉
+fn prune_unreachable_switch_cases(cfg_graph:&mut CfgGraph, instr_slab:&mut InstrSlab<NhwcInstr>, symtab:&SymTab){
+    let mut is_any_edge_removed = false;
+    for cfg_node in cfg_graph.node_indices().map(|x| x.index() as u32).collect_vec(){
+        let jump_instr = match node!(at cfg_node in cfg_graph).op_jump_instr{
+            Some(jump_instr) => jump_instr,
+            None => continue,
+        };
+        let (cond_ty, (lower, upper)) = match &instr!(at jump_instr in instr_slab).instr_type{
+            NhwcInstrType::Jump { jump_op:JumpOp::Switch { cond, default:_, compared:_ } } => {
+                let cond_ty = get_type_of_symidx(&cond.as_ref_borrow(), symtab);
+                match get_value_range_of_symidx(&cond.as_ref_borrow(), &cond_ty, instr_slab, symtab){
+                    Some(range) => (cond_ty, range),
+                    None => continue,
+                }
+            },
+            _ => continue,
+        };
+        let alive_labels = if let NhwcInstrType::Jump { jump_op:JumpOp::Switch { cond:_, default, compared } } = &mut instr_mut!(at jump_instr in instr_slab).instr_type{
+            compared.retain(|pair| {
+                let case_val = Value::from_string_with_specific_type(pair.compared.as_ref_borrow().symbol_name, &cond_ty.into()).as_i64();
+                lower <= case_val && case_val <= upper
+            });
+            if lower == upper{
+                if let Some(pair) = compared.pop(){
+                    *default = pair.label;
+                }
+                compared.clear();
+            }
+            compared.iter().map(|pair| pair.label.clone()).chain(std::iter::once(default.clone())).collect_vec()
+        }else { unreachable!() };
+        let case_edges = cfg_graph.edges_directed(node_index(cfg_node as usize), petgraph::Direction::Outgoing).filter(|e| e.weight().cfg_edge_type.is_case()).map(|e| e.id()).collect_vec();
+        for case_edge in case_edges{
+            let (_, cfg_case_node) = cfg_graph.edge_endpoints(case_edge).unwrap();
+            let case_label = find_label_of_cfg_node(cfg_case_node.index() as u32, cfg_graph, instr_slab);
+            if !alive_labels.contains(&case_label){
+                cfg_graph.remove_edge(case_edge);
+                is_any_edge_removed = true;
+            }
+        }
+    }
+    if !is_any_edge_removed{
+        return
+    }
+    let reachable_cfg_nodes:HashSet<u32> = dfs(cfg_graph, CFG_ROOT).into_iter().collect();
+    for cfg_node in cfg_graph.node_indices().map(|x| x.index() as u32).filter(|x| !reachable_cfg_nodes.contains(x)).collect_vec(){
+        for instr in node!(at cfg_node in cfg_graph).iter_all_instrs().cloned().collect_vec(){
+            debug_info_yellow!("set instr {} {:?} in unreachable cfg_node {} to nope ",instr, instr!(at instr in instr_slab), cfg_node);
+            *instr_mut!(at instr in instr_slab) = NhwcInstrType::Nope {  }.into();
+        }
+        cfg_graph.remove_node(node_index(cfg_node as usize));
+    }
+    for cfg_node in cfg_graph.node_indices().map(|x| x.index() as u32).collect_vec(){
+        let cfg_parent_nodes = direct_parent_nodes!(at cfg_node in cfg_graph);
+        for phi_instr in node!(at cfg_node in cfg_graph).phi_instrs.iter().cloned().collect_vec(){
+            if let NhwcInstrType::Phi { lhs:_, rhs } = &mut instr_mut!(at phi_instr in instr_slab).instr_type{
+                rhs.phi_pairs.retain(|phi_pair| cfg_parent_nodes.contains(&phi_pair.comming_cfg_node));
+            }
+        }
+    }
+}
+fn get_type_of_symidx(symidx:&SymIdx, symtab:&SymTab) -> TypeDiscriminants{
+    if symidx.is_literal(){
+        TypeDiscriminants::new_from_const_str(symidx.symbol_name)
+    }else{
+        symtab.get(&symidx.to_src_symidx()).get_type().into()
+    }
+}
+/// 返回 symidx 可能取值的闭区间，只识别几种简单的定义，其余都认为是 ty 的整个范围，ty 不是整型时返回 None
+fn get_value_range_of_symidx(symidx:&SymIdx, ty:&TypeDiscriminants, instr_slab:&InstrSlab<NhwcInstr>, symtab:&SymTab) -> Option<(i64,i64)>{
+    let (full_range, bits) = match ty{
+        TypeDiscriminants::I8 => ((i8::MIN as i64, i8::MAX as i64), 8),
+        TypeDiscriminants::I32 => ((i32::MIN as i64, i32::MAX as i64), 32),
+        TypeDiscriminants::U32 => ((0, u32::MAX as i64), 32),
+        TypeDiscriminants::I64 => ((i64::MIN, i64::MAX), 64),
+        _ => return None,
+    };
+    let value_of = |literal:&SymIdx| Value::from_string_with_specific_type(literal.symbol_name, &(*ty).into()).as_i64();
+    if symidx.is_literal(){
+        let val = value_of(symidx);
+        return Some((val, val))
+    }
+    let def_instr = match symtab.get(symidx).get_op_ssa_def_instr(){
+        Some(&def_instr) => def_instr,
+        None => return Some(full_range),
+    };
+    Some(match &instr!(at def_instr in instr_slab).instr_type{
+        NhwcInstrType::Arith { lhs:_, rhs:ArithOp::BitAnd { a, b, vartype:_ } } => {
+            // x & mask 且 mask 非负时，结果落在 [0, mask]
+            [a, b].iter().map(|x| x.as_ref_borrow()).filter(|x| x.is_literal())
+                .map(|x| value_of(&x)).filter(|&mask| mask >= 0)
+                .min().map(|mask| (0, mask.min(full_range.1))).unwrap_or(full_range)
+        },
+        NhwcInstrType::Arith { lhs:_, rhs:ArithOp::Ashr { a:_, b, vartype:_ } } if b.as_ref_borrow().is_literal() && *ty != TypeDiscriminants::U32 => {
+            let shift = value_of(&b.as_ref_borrow()) & (bits - 1);
+            if shift == 0 { full_range } else { (-(1i64 << (bits - 1 - shift)), (1i64 << (bits - 1 - shift)) - 1) }
+        },
+        NhwcInstrType::TranType { lhs:_, op:Trans::Zext { bool_symidx:_ } } => (0, 1),
+        _ => full_range,
+    })
+}
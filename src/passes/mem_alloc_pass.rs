@@ -1,4 +1,4 @@
-use crate::{add_symbol, debug_info_red, direct_child_nodes, node, node_mut, reg_field_for_struct, toolkit::{cfg_node::{CfgGraph, CFG_ROOT}, context::NhwcCtx, mem_layout::{self, MemLayout}, nhwc_instr::{InstrSlab, NhwcInstr}, pass_manager::Pass, scope_node::ST_ROOT, symbol::Symbol, symtab::{RcSymIdx, SymIdx, SymTab, WithBorrow}}};
+use crate::{add_symbol, debug_info_red, direct_child_nodes, node, node_mut, reg_field_for_struct, toolkit::{cfg_node::{CfgGraph, CFG_ROOT}, context::NhwcCtx, etc::dfs, gen_riscv_asm::get_vararg_stack_size_of_call, mem_layout::{self, MemLayout}, nhwc_instr::{InstrSlab, NhwcInstr}, pass_manager::Pass, scope_node::ST_ROOT, symbol::Symbol, symtab::{RcSymIdx, SymIdx, SymTab, WithBorrow}}};
 use anyhow::*;
 use itertools::Itertools;
 use crate::toolkit::field::Type;
@@ -45,7 +45,7 @@ impl Pass for MemAllocPass {
                 let mut overflowed_args = vec![];
                 for (idx,rc_symidx) in arg_symidx_vec.iter().enumerate(){
                     let symidx = rc_symidx.as_ref_borrow();
-                    if symtab.get(&symidx.to_src_symidx()).get_type().is_f_32(){
+                    if symtab.get(&symidx.to_src_symidx()).get_type().is_f_32() || symtab.get(&symidx.to_src_symidx()).get_type().is_f_64(){
                         if fpu_cnt >= 8{
                             overflowed_args.push((idx,rc_symidx.clone()));
                         }else{
@@ -68,7 +68,7 @@ impl Pass for MemAllocPass {
             node_mut!(at cfg_entry in cfg_graph).add_mem_layout(MemLayout::new());
             //  insert overflowed_args to mem_layout before ra and s0
             for (idx,arg) in &overflowed_args{
-                if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_array(){
+                if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_aggregate(){
                     alloc_stack_mem_for_cfg_entry(cfg_graph, cfg_entry, symtab,&arg)?;
                 }else {
                     for ssa_version in symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_ssa_versions().clone(){
@@ -79,7 +79,7 @@ impl Pass for MemAllocPass {
             alloc_stack_mem_for_cfg_entry(cfg_graph, cfg_entry, symtab, &ra_symidx)?;
             alloc_stack_mem_for_cfg_entry(cfg_graph, cfg_entry, symtab, &s0_symidx)?;
             for arg in reg_args{
-                if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_array(){
+                if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_aggregate(){
                     panic!("we will not alloc mem for array in stack arg")
                 }else {
                     for ssa_version in symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_ssa_versions().clone(){
@@ -94,7 +94,7 @@ impl Pass for MemAllocPass {
                     }
                     crate::toolkit::nhwc_instr::NhwcInstrType::Alloc { var_symidx_vec, vartype: _ } => {
                         for var_symidx in var_symidx_vec{
-                            if symtab.get(&var_symidx.as_ref_borrow().to_src_symidx()).get_type().is_array(){
+                            if symtab.get(&var_symidx.as_ref_borrow().to_src_symidx()).get_type().is_aggregate(){
                                 alloc_stack_mem_for_cfg_entry(cfg_graph, cfg_entry, symtab,&var_symidx.as_ref_borrow().to_src_symidx().as_rc())?;
                             }else if symtab.get(&var_symidx.as_ref_borrow().to_src_symidx()).get_type().is_ptr_64(){
                                 alloc_stack_mem_for_cfg_entry(cfg_graph, cfg_entry, symtab,&var_symidx.as_ref_borrow().to_src_symidx().as_rc())?;
@@ -115,6 +115,13 @@ impl Pass for MemAllocPass {
             }
             // align the mem by RISCV_STACK_MEM_ALIGN
             node_mut!(at cfg_entry in cfg_graph).get_mut_mem_layout().align_mem_with_blank(RISCV_STACK_MEM_ALIGN);
+            // 变长参数调用通过栈传递的实参放在栈帧底部，RISC-V 没有 red zone ，不能写到 sp 下方
+            let vararg_stack_size = dfs(cfg_graph, cfg_entry).into_iter().flat_map(|cfg_node| node!(at cfg_node in cfg_graph).iter_all_instrs().cloned().collect::<Vec<_>>())
+                .filter_map(|instr| match &instr!(at instr in instr_slab).instr_type{
+                    crate::toolkit::nhwc_instr::NhwcInstrType::Call { op_lhs:_, func_op } => Some(get_vararg_stack_size_of_call(func_op, symtab)),
+                    _ => None,
+                }).max().unwrap_or(0);
+            node_mut!(at cfg_entry in cfg_graph).get_mut_mem_layout().reserve_blank_at_bottom(vararg_stack_size);
             // we now can calculate offset2sp after mem_layout is calculated.
             calculate_mem_offset2sp(cfg_graph, cfg_entry, symtab, &ra_symidx)?;
             calculate_mem_offset2sp(cfg_graph, cfg_entry, symtab, &s0_symidx)?;
@@ -122,7 +129,7 @@ impl Pass for MemAllocPass {
                 match &instr!(at instr in instr_slab).instr_type{
                     crate::toolkit::nhwc_instr::NhwcInstrType::DefineFunc { func_symidx: _, ret_symidx: _, args } => {
                         for arg in args{
-                            if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_array(){
+                            if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_aggregate(){
                                 panic!("we will not alloc mem for array in stack arg")
                             }else if symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().is_ptr_64(){
                                 calculate_mem_offset2sp(cfg_graph, cfg_entry, symtab, &arg)?;
@@ -135,7 +142,7 @@ impl Pass for MemAllocPass {
                     },
                     crate::toolkit::nhwc_instr::NhwcInstrType::Alloc { var_symidx_vec, vartype: _ } => {
                         for var_symidx in var_symidx_vec{
-                            if symtab.get(&var_symidx.as_ref_borrow()).get_type().is_array(){
+                            if symtab.get(&var_symidx.as_ref_borrow()).get_type().is_aggregate(){
                                 calculate_mem_offset2sp(cfg_graph, cfg_entry, symtab, &var_symidx)?;
                             }else if symtab.get(&var_symidx.as_ref_borrow().to_src_symidx()).get_type().is_ptr_64(){
                                 calculate_mem_offset2sp(cfg_graph, cfg_entry, symtab, &var_symidx)?;
@@ -1,8 +1,11 @@
 pub mod ast2cfg_pass;
 pub mod ast2et_debug_pass;
 pub mod ast2st_pass;
+pub mod semantic_check_pass;
+pub mod warning_pass;
 pub mod cfg2ncfg_pass;
 pub mod code2ast_pass;
+pub mod preprocess_pass;
 pub mod nhwc2riscv_pass;
 pub mod pass_demo;
 pub mod riscv2binary_pass;
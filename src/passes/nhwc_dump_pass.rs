@@ -4,6 +4,7 @@ use std::rc::Rc;
 
 
 use crate::toolkit::cfg_node::CFG_ROOT;
+use crate::toolkit::field::Value;
 use crate::toolkit::gen_nhwc_cfg;
 use crate::toolkit::nhwc_instr::NhwcInstrType;
 use crate::toolkit::scope_node::ST_ROOT;
@@ -46,6 +47,7 @@ impl Pass for NhwcDumpPass {
             CfgEdgeType::Direct {  } => 2,
             CfgEdgeType::IfTrue {  } => 1,
             CfgEdgeType::BodyTail {  } => 1,
+            CfgEdgeType::Case {  } => 2,
         });
 
         let mut anonymous_label_count = 0;
@@ -96,6 +98,20 @@ impl Pass for NhwcDumpPass {
                                 node_mut!(at cfg_node in cfg_graph).op_jump_instr = Some(jump_instr);
                             }
                         },
+                        crate::toolkit::nhwc_instr::JumpOp::Switch { cond, default, compared } => {
+                            // 只剩 default 或者 cond 为常量时直接跳转
+                            let op_label = if compared.is_empty(){
+                                Some(default.clone())
+                            }else if cond.as_ref_borrow().is_literal(){
+                                let cond_val = Value::from_symidx(&cond.as_ref_borrow()).as_i32();
+                                Some(compared.iter().find(|pair| Value::from_symidx(&pair.compared.as_ref_borrow()).as_i32() == cond_val).map(|pair| pair.label.clone()).unwrap_or(default.clone()))
+                            }else { None };
+                            if let Some(label) = op_label{
+                                let jump_instr_struct = NhwcInstrType::new_jump(label).into();
+                                let jump_instr = instr_slab.insert_instr(jump_instr_struct);
+                                node_mut!(at cfg_node in cfg_graph).op_jump_instr = Some(jump_instr);
+                            }
+                        },
                         _ => {}
                     }
                 },
@@ -0,0 +1,65 @@
+use std::fs;
+use std::path::Path;
+
+use crate::toolkit::{context::NhwcCtx, pass_manager::Pass, preprocess::Preprocessor};
+use anyhow::Result;
+
+/// 每个程序都会隐式带上的运行时库声明，以 <built-in> 的名字参与预处理
+static SYSY_PRELUDE:&str = "int getint();
+int getch();
+int getarray(int zz_array[]);
+float getfloat();
+int getfarray(float zz_array[]);
+
+void putint(int zz_int );
+void putch(int zz_ch );
+void putarray(int x, int zz_array[]);
+void putfloat(float zz_float);
+void putfarray(int zz_len, float zz_farray[]);
+
+void putf(const char *zz_fmt, ...);
+
+#define starttime() _sysy_starttime(__LINE__)
+#define stoptime()  _sysy_stoptime(__LINE__)
+void _sysy_starttime(int zz_lineno);
+void _sysy_stoptime(int zz_lineno);
+
+void memset(void *zz_ptr, int zz_val, int zz_len);
+void memcpy(void *zz_dest, void *zz_ptr, int zz_len);
+";
+
+#[derive(Debug)]
+pub struct PreprocessPass {
+    is_gen_file:bool,
+}
+impl PreprocessPass {
+    pub fn new(is_gen_file:bool) -> Self { PreprocessPass { is_gen_file } }
+}
+
+impl Pass for PreprocessPass {
+    // 运行这个pass
+    fn run(&mut self, ctx:&mut NhwcCtx) -> Result<()> {
+        let mut preprocessor = Preprocessor::new(ctx.args.include_dirs.clone());
+        for def in ctx.args.defines.iter(){
+            preprocessor.define_from_cmdline(def)?;
+        }
+        if !ctx.args.no_header{
+            preprocessor.process_text(SYSY_PRELUDE, "<built-in>", None)?;
+        }
+        preprocessor.process_file(&ctx.args.input)?;
+        ctx.code = preprocessor.code;
+        ctx.line_map = preprocessor.line_map;
+        Ok(())
+    }
+    // 返回pass的描述，具体作用
+    fn get_desc(&self) -> String { return "pass PreprocessPass description".to_string(); }
+    // 返回pass的名称
+    fn get_pass_name(&self) -> String { return "PreprocessPass".to_string(); }
+
+    fn when_finish_or_panic(&mut self, ctx:&mut NhwcCtx) {
+        if self.is_gen_file {
+            let stem = Path::new(&ctx.args.output).file_stem().unwrap().to_string_lossy().to_string();
+            fs::write(stem + ".i", &ctx.code).unwrap();
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::toolkit::{context::NhwcCtx, diagnostic::Severity, pass_manager::Pass, semantic_check::check_semantics};
+use anyhow::{anyhow, Result};
+
+#[derive(Debug)]
+pub struct SemanticCheckPass {}
+impl SemanticCheckPass {
+    pub fn new() -> Self { SemanticCheckPass {} }
+}
+
+impl Pass for SemanticCheckPass {
+    // 运行这个pass
+    fn run(&mut self, ctx:&mut NhwcCtx) -> Result<()> {
+        let diagnostics = check_semantics(&ctx.ast_tree);
+        for diagnostic in diagnostics.iter() {
+            eprintln!("{}", diagnostic.render());
+        }
+        let error_count = diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Error).count();
+        if error_count > 0 {
+            return Err(anyhow!("语义检查发现 {} 个错误", error_count));
+        }
+        Ok(())
+    }
+    // 返回pass的描述，具体作用
+    fn get_desc(&self) -> String { return "pass SemanticCheckPass description".to_string(); }
+    // 返回pass的名称
+    fn get_pass_name(&self) -> String { return "SemanticCheckPass".to_string(); }
+
+    fn when_finish_or_panic(&mut self, _ctx:&mut NhwcCtx) {}
+}
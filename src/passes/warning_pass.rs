@@ -0,0 +1,34 @@
+use crate::toolkit::{context::NhwcCtx, pass_manager::Pass, warning::{collect_ssa_warnings, WarnConfig}};
+use anyhow::{anyhow, Result};
+
+#[derive(Debug)]
+pub struct WarningPass {}
+impl WarningPass {
+    pub fn new() -> Self { WarningPass {} }
+}
+
+impl Pass for WarningPass {
+    // 运行这个pass
+    fn run(&mut self, ctx:&mut NhwcCtx) -> Result<()> {
+        let warn_config = WarnConfig::from_flags(&ctx.args.warnings)?;
+        if !warn_config.is_any_enabled() {
+            return Ok(());
+        }
+        let mut diagnostics = std::mem::take(&mut ctx.diagnostics);
+        diagnostics.extend(collect_ssa_warnings(&ctx.cfg_graph, &ctx.nhwc_instr_slab, &ctx.symtab));
+        let diagnostics = warn_config.apply(diagnostics);
+        for diagnostic in diagnostics.iter() {
+            eprintln!("{}", diagnostic.render_line());
+        }
+        if warn_config.is_werror && !diagnostics.is_empty() {
+            return Err(anyhow!("-Werror 下有 {} 个警告被当作错误", diagnostics.len()));
+        }
+        Ok(())
+    }
+    // 返回pass的描述，具体作用
+    fn get_desc(&self) -> String { return "pass WarningPass description".to_string(); }
+    // 返回pass的名称
+    fn get_pass_name(&self) -> String { return "WarningPass".to_string(); }
+
+    fn when_finish_or_panic(&mut self, _ctx:&mut NhwcCtx) {}
+}
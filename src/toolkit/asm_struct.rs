@@ -3,7 +3,7 @@ use anyhow::*;
 use itertools::Itertools;
 use strum_macros::EnumIs;
 
-use crate::{debug_info_blue, debug_info_red};
+use crate::debug_info_blue;
 
 use super::{field::Value, rv64_instr::{Imm, RV64Instr}, symtab::{RcSymIdx, SymIdx}};
 
@@ -47,9 +47,6 @@ impl AsmSection{
     pub fn label(&mut self, imm:Imm){
         self.stmts.push(AsmAttr::Label  { imm }.into())
     }
-    pub fn double(&mut self, imm:Imm){
-        self.stmts.push(AsmAttr::Double { imm: imm }.into())
-    }
     pub fn word(&mut self, imm:Imm){
         self.stmts.push(AsmAttr::Word { imm: imm }.into())
     }
@@ -59,6 +56,10 @@ impl AsmSection{
     pub fn byte(&mut self, imm:Imm){
         self.stmts.push(AsmAttr::Byte { imm: imm }.into())
     }
+    /// 以 0 结尾的字符串，bytes 中不包含结尾的 0
+    pub fn asciz(&mut self, bytes:Vec<u8>){
+        self.stmts.push(AsmAttr::Asciz { bytes }.into())
+    }
     pub fn zero(&mut self, len:usize){
         if len >0 {
             self.stmts.push(AsmAttr::Zero { len }.into())
@@ -73,6 +74,13 @@ impl AsmSection{
     pub fn text(&mut self){
         self.stmts.push(AsmAttr::Text {  } .into())
     }
+    pub fn rodata(&mut self){
+        self.stmts.push(AsmAttr::Rodata {  } .into())
+    }
+    /// 用于跳转表等需要存放 8 字节地址的地方
+    pub fn dword(&mut self, imm:Imm){
+        self.stmts.push(AsmAttr::Dword { imm } .into())
+    }
     pub fn asm(&mut self, riscv_instr:RV64Instr){
         debug_info_blue!("asm:{:?}", riscv_instr);
         self.stmts.push(Asm::Riscv { instr: riscv_instr })
@@ -83,28 +91,19 @@ impl AsmSection{
         match val{
             Value::Array { value_map, dims: _, ele_ty:_ } => {
                 // if array
-                let mut offset_value_pairs = value_map.iter().collect_vec();
-                offset_value_pairs.sort_by_key(|x| x.0);
-
-                let mut cur = 0 ;
-                let mut last_offset = 0;
-                while cur < offset_value_pairs.len(){
-                    let (&offset,value) = offset_value_pairs[cur];
-                    if offset > last_offset{
-                        self.zero((offset - last_offset - 1)*val.get_ele_size());
-                    }
+                // 连续的非零元素逐个输出，中间和末尾没有初始化(或者值为 0)的元素合并成一个 .zero
+                let ele_size = val.get_ele_size();
+                let mut next_offset = 0;
+                for (&offset,value) in value_map.iter().filter(|(_,value)| !value.is_zero_bits()).sorted_by_key(|x| x.0){
+                    self.zero((offset - next_offset)*ele_size);
                     self.apply_value(value);
-                    last_offset = offset;
-                    cur +=1 ;
-                }
-                if last_offset < val.get_mem_len(){
-                    debug_info_red!("mem_len:{} last_offset:{}",val.get_mem_len(), last_offset);
-                    self.zero(val.get_mem_len() - last_offset*val.get_ele_size())
+                    next_offset = offset + 1;
                 }
+                self.zero(val.get_mem_len() - next_offset*ele_size);
             },
             _ => {
                 match val.get_ele_size(){
-                    8 => { self.double(Imm::new_literal(val.try_to_symidx().unwrap_or(SymIdx::new_verbose(0, "0", None,None)).as_rc())) }
+                    8 => { self.dword(Imm::new_literal(val.try_to_symidx().unwrap_or(SymIdx::new_verbose(0, "0", None,None)).as_rc())) }
                     4 => { self.word(Imm::new_literal(val.try_to_symidx().unwrap_or(SymIdx::new_verbose(0, "0", None,None)).as_rc())) }
                     2 => { self.half(Imm::new_literal(val.try_to_symidx().unwrap_or(SymIdx::new_verbose(0, "0", None,None)).as_rc())) }
                     1 => { self.byte(Imm::new_literal(val.try_to_symidx().unwrap_or(SymIdx::new_verbose(0, "0", None,None)).as_rc())) }
@@ -146,8 +145,11 @@ impl Debug for Asm{
                     AsmAttr::Text {  } => {
                         writeln!(f,"    .text")
                     },
-                    AsmAttr::Double { imm } => {
-                        writeln!(f,"    .double {:?}",imm)
+                    AsmAttr::Rodata {  } => {
+                        writeln!(f,"    .section .rodata")
+                    },
+                    AsmAttr::Dword { imm } => {
+                        writeln!(f,"    .dword {:?}",imm)
                     },
                     AsmAttr::Word { imm } => {
                         writeln!(f,"    .word {:?}",imm)
@@ -158,6 +160,11 @@ impl Debug for Asm{
                     AsmAttr::Byte { imm } => {
                         writeln!(f,"    .byte {:?}",imm)
                     },
+                    AsmAttr::Asciz { bytes } => {
+                        // 不可打印的字符以及引号和反斜杠都转成八进制转义
+                        let escaped:String = bytes.iter().map(|&b| if (0x20..0x7f).contains(&b) && b != b'"' && b != b'\\' { (b as char).to_string() } else { format!("\\{:03o}",b) }).collect();
+                        writeln!(f,"    .asciz \"{}\"",escaped)
+                    },
                     AsmAttr::Zero { len } => {
                         writeln!(f,"    .zero {:?}",len)
                     },
@@ -190,8 +197,8 @@ pub enum AsmAttr{
     Global{
         label:Imm
     },
-    Data{ },Text{},
-    Double{
+    Data{ },Text{},Rodata{},
+    Dword{
         imm:Imm
     },
     Word{
@@ -206,6 +213,9 @@ pub enum AsmAttr{
     Zero{
         len:usize
     },
+    Asciz{
+        bytes:Vec<u8>
+    },
     Label{
         imm:Imm
     },
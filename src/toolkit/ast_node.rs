@@ -1,12 +1,34 @@
-use std::{backtrace::{Backtrace, BacktraceStatus}, fmt::Debug};
+use std::{backtrace::{Backtrace, BacktraceStatus}, fmt::{Debug, Display}, sync::Arc};
 
 use petgraph::{
     stable_graph::{NodeIndex, StableDiGraph}, visit::Bfs
 };
 
 use petgraph::visit::{Dfs, Walker};
+
+use crate::antlr_parser::clexer::{And, Constant, Identifier, LeftParen, Star, Typedef};
+use crate::antlr_parser::cparser::{RULE_declaration, RULE_declarationSpecifier, RULE_declarationSpecifiers, RULE_declarator, RULE_directDeclarator, RULE_pointer, RULE_storageClassSpecifier, RULE_typeSpecifier, RULE_unaryExpression, RULE_unaryOperator};
+use crate::{direct_parent_node, find, find_nodes, node};
 pub type AstTree = StableDiGraph<AstNode, (), u32>;
 
+/// 源代码中的位置，已经通过预处理的行号映射换算回原始文件，行号和列号都从 1 开始
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SrcLoc {
+    pub file:Arc<str>,
+    pub line:usize,
+    pub col:usize,
+}
+impl SrcLoc {
+    pub fn new(file:Arc<str>, line:usize, col:usize) -> Self { SrcLoc { file, line, col } }
+    /// 用于报错信息，没有位置信息时返回 <unknown>
+    pub fn fmt_op(op_src_loc:&Option<SrcLoc>) -> String {
+        op_src_loc.as_ref().map_or("<unknown>".to_string(), |src_loc| src_loc.to_string())
+    }
+}
+impl Display for SrcLoc {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}:{}:{}", self.file, self.line, self.col) }
+}
+
 #[derive(Clone)]
 pub struct AstNode {
     pub rule_id:usize, // grammar id
@@ -14,9 +36,11 @@ pub struct AstNode {
     pub op_text:Option<String>,
     pub is_terminal:bool,
     pub child_vec:Vec<u32>,
+    /// 对于非终结符是它第一个 token 的位置
+    pub src_loc:Option<SrcLoc>,
 }
 impl AstNode {
-    pub fn new(rule_id:usize, op_text:Option<String>, is_terminal:bool) -> Self { Self { rule_id, node_index:0, op_text: op_text, is_terminal, child_vec: vec![] } }
+    pub fn new(rule_id:usize, op_text:Option<String>, is_terminal:bool) -> Self { Self { rule_id, node_index:0, op_text: op_text, is_terminal, child_vec: vec![], src_loc:None } }
 }
 
 impl Debug for AstNode {
@@ -29,6 +53,92 @@ impl Debug for AstNode {
     }
 }
 
+/// 统计 declarator 下 pointer 中 `*` 的个数，即声明的指针层数
+pub fn get_ptr_depth_of_declarator(ast_tree:&AstTree, declarator_node:u32) -> usize {
+    match find!(rule RULE_pointer at declarator_node in ast_tree){
+        Some(pointer_node) => node!(at pointer_node in ast_tree).child_vec.iter().filter(|&&child| {
+            let child_node = node!(at child in ast_tree);
+            child_node.is_terminal && child_node.rule_id == Star as usize
+        }).count(),
+        None => 0,
+    }
+}
+/// 判断 directDeclarator 是否形如 `(*fp)(int)` ，即括号里的 declarator 后面跟着参数列表，这声明的是函数指针而不是函数
+pub fn is_fn_ptr_direct_decl(ast_tree:&AstTree, direct_decl_node:u32) -> bool {
+    find!(term LeftParen at direct_decl_node in ast_tree).is_some()
+        && find!(rule RULE_directDeclarator at direct_decl_node in ast_tree).is_some_and(|sub_direct_decl_node| find!(rule RULE_declarator at sub_direct_decl_node in ast_tree).is_some())
+}
+/// 如果标识符是在函数指针的 declarator 中声明的，返回带有参数列表的那一层 directDeclarator
+pub fn find_fn_ptr_direct_decl_of_ident(ast_tree:&AstTree, ident_node:u32) -> Option<u32> {
+    let mut cur_node = ident_node;
+    while let Some(parent_node) = direct_parent_node!(at cur_node in ast_tree ret_option) {
+        let rule_id = node!(at parent_node in ast_tree).rule_id;
+        if rule_id == RULE_directDeclarator && is_fn_ptr_direct_decl(ast_tree, parent_node) {
+            return Some(parent_node)
+        }else if rule_id != RULE_directDeclarator && rule_id != RULE_declarator {
+            return None
+        }
+        cur_node = parent_node;
+    }
+    None
+}
+/// 如果这个节点往下只是一条单链并且终点是 Constant ，则返回这个 Constant 的文本
+pub fn find_single_constant_text(ast_tree:&AstTree, ast_node:u32) -> Option<String> {
+    find_single_term_text(ast_tree, ast_node, Constant)
+}
+/// 如果这个节点往下只是一条单链并且终点是 Identifier ，则返回这个 Identifier 的文本
+pub fn find_single_identifier_text(ast_tree:&AstTree, ast_node:u32) -> Option<String> {
+    find_single_term_text(ast_tree, ast_node, Identifier)
+}
+fn find_single_term_text(ast_tree:&AstTree, ast_node:u32, term_id:isize) -> Option<String> {
+    let node_struct = node!(at ast_node in ast_tree);
+    if node_struct.is_terminal {
+        if node_struct.rule_id == term_id as usize { node_struct.op_text.clone() } else { None }
+    } else if node_struct.child_vec.len() == 1 {
+        find_single_term_text(ast_tree, node_struct.child_vec[0], term_id)
+    } else {
+        None
+    }
+}
+/// 判断 declaration 的说明符中是否有 typedef ，这样的声明只定义类型名，不产生任何变量
+pub fn is_typedef_decl(ast_tree:&AstTree, decl_node:u32) -> bool {
+    get_storage_class_of_decl(ast_tree, decl_node) == Some(Typedef)
+}
+/// 返回声明或函数定义中存储类说明符(typedef extern static 等)的 term id ，没有时返回 None
+pub fn get_storage_class_of_decl(ast_tree:&AstTree, decl_node:u32) -> Option<isize> {
+    find_nodes!(rule RULE_declarationSpecifiers finally RULE_declarationSpecifier at decl_node in ast_tree).into_iter()
+        .find_map(|specifier_node| find!(rule RULE_storageClassSpecifier at specifier_node in ast_tree))
+        .map(|storage_class_node| node!(at {node!(at storage_class_node in ast_tree).child_vec[0]} in ast_tree).rule_id as isize)
+}
+/// 返回声明、函数定义或参数声明中的第一个类型说明符，形如 unsigned int 的多个类型说明符以第一个为准  
+/// 注意 find! 返回的是最后一个子节点，不能用它来找类型说明符
+pub fn find_type_specifier_of_decl(ast_tree:&AstTree, decl_node:u32) -> Option<u32> {
+    find_nodes!(rule RULE_declarationSpecifiers finally RULE_declarationSpecifier at decl_node in ast_tree).into_iter()
+        .find_map(|specifier_node| find!(rule RULE_typeSpecifier at specifier_node in ast_tree))
+}
+/// 从说明符中的类型节点向上找到所在的 declaration 并返回它的存储类说明符，函数定义与参数声明中的类型节点返回 None
+pub fn get_storage_class_of_type_node(ast_tree:&AstTree, type_ast_node:u32) -> Option<isize> {
+    let mut cur_node = type_ast_node;
+    while node!(at cur_node in ast_tree).rule_id != RULE_declarationSpecifiers || node!(at cur_node in ast_tree).is_terminal {
+        cur_node = direct_parent_node!(at cur_node in ast_tree ret_option)?;
+    }
+    let decl_node = direct_parent_node!(at cur_node in ast_tree ret_option)?;
+    if node!(at decl_node in ast_tree).rule_id == RULE_declaration { get_storage_class_of_decl(ast_tree, decl_node) } else { None }
+}
+/// 判断 start 之下是否存在形如 `&name` 的取地址表达式  
+/// 只按名字匹配，因此内层同名变量被取地址时外层变量也会被认为取了地址，这是保守的
+pub fn is_identifier_addr_taken(ast_tree:&AstTree, start:u32, name:&str) -> bool {
+    find_dfs_rule_ast(ast_tree, start, RULE_unaryExpression).any(|unary_expr_node| {
+        let child_vec = &node!(at unary_expr_node in ast_tree).child_vec;
+        if child_vec.len() != 2 { return false }
+        let (unary_operator_node, cast_expr_node) = (child_vec[0], child_vec[1]);
+        let unary_operator = node!(at unary_operator_node in ast_tree);
+        !unary_operator.is_terminal && unary_operator.rule_id == RULE_unaryOperator
+            && unary_operator.child_vec.first().map_or(false, |&op_node| node!(at op_node in ast_tree).rule_id == And as usize)
+            && find_single_identifier_text(ast_tree, cast_expr_node).as_deref() == Some(name)
+    })
+}
+
 /// 返回 start 所有子节点以dfs序返回的特定rule_id 的迭代器
 pub fn find_dfs_rule_ast<'a>(ast_tree:&'a AstTree, start:u32, target_rule_id:usize) -> impl Iterator<Item = u32> + 'a {
     // let ast_tree = &*ast_tree_rc.borrow();
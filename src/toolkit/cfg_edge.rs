@@ -14,6 +14,8 @@ pub enum CfgEdgeType {
     IfTrue {},
     BodyHead {},
     BodyTail {},
+    /// switch 到各个 case (包括 default) 的边
+    Case {},
 }
 impl CfgEdge {
     pub fn load_ast_node_text(&mut self, _ast_tree:&AstTree) -> Result<()> {
@@ -24,6 +26,7 @@ impl CfgEdge {
     pub fn new_if_true() -> Self { CfgEdge { cfg_edge_type: CfgEdgeType::IfTrue {}  }}
     pub fn new_body_head() -> Self { CfgEdge { cfg_edge_type: CfgEdgeType::BodyHead {} }}
     pub fn new_body_tail() -> Self { CfgEdge { cfg_edge_type: CfgEdgeType::BodyTail {} }}
+    pub fn new_case() -> Self { CfgEdge { cfg_edge_type: CfgEdgeType::Case {} }}
 }
 impl Debug for CfgEdge {
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -33,6 +36,7 @@ impl Debug for CfgEdge {
             CfgEdgeType::Direct {} =>  "Direct",
             CfgEdgeType::BodyTail {} =>  "BodyTail",
             CfgEdgeType::BodyHead {} =>  "BodyHead",
+            CfgEdgeType::Case {} =>  "Case",
         })
     }
 }
@@ -63,6 +63,11 @@ pub enum CfgNodeType {
         // exit_node: Option<u32>,
         // body_node: Option<(u32, u32)>,
     },
+    /// do while 循环，循环条件在循环体之后求值
+    /// 前驱直接进入循环体，循环体尾部以 BodyTail 边连到本节点，本节点以 BodyHead 边跳回循环体，以 Direct 边跳出循环
+    DoWhileLoop {
+        ast_expr_node:u32,
+    },
     Gather {},
     BasicBlock {
         ast_nodes:Vec<u32>,
@@ -166,6 +171,17 @@ impl CfgNode {
         }
         instr
     }
+    /// 返回这个 cfg 节点对应的第一个 ast 节点，用于定位源代码
+    pub fn get_first_ast_node(&self) -> Option<u32> {
+        match &self.cfg_node_type {
+            CfgNodeType::Entry { ast_node, .. } | CfgNodeType::Exit { ast_node } => Some(*ast_node),
+            CfgNodeType::Branch { ast_expr_node } | CfgNodeType::Switch { ast_expr_node } | CfgNodeType::WhileLoop { ast_expr_node } | CfgNodeType::DoWhileLoop { ast_expr_node } => Some(*ast_expr_node),
+            CfgNodeType::ForLoop { ast_before_node, .. } => Some(*ast_before_node),
+            CfgNodeType::Gather {} => None,
+            CfgNodeType::BasicBlock { ast_nodes } => ast_nodes.first().cloned(),
+            CfgNodeType::Root { static_ast_nodes } => static_ast_nodes.first().cloned(),
+        }
+    }
     pub fn load_ast_node_text(&mut self, ast_tree:&AstTree)  {
         match self.cfg_node_type.clone() {
             CfgNodeType::Entry { ast_node, calls_in_func: _ } => {
@@ -223,7 +239,7 @@ impl CfgNode {
                 ast_expr_node,
                 // exit_node: _,
                 // body_node: _,
-            } => {
+            } | CfgNodeType::DoWhileLoop { ast_expr_node } => {
                 self.text += node!(at ast_expr_node in ast_tree).op_text.as_ref().unwrap().as_str();
                 self.text += "\n";
             }
@@ -286,6 +302,11 @@ impl CfgNode {
     ) -> Self {
         Self { instrs:InstrList::new(), text:String::new(), info:Fields::new(),cfg_node_type:CfgNodeType::WhileLoop { ast_expr_node, }, phi_instrs: InstrList::new(), op_label_instr:None, op_jump_instr: None, asms: InstrSlab::new(),loop_level }
     }
+    pub fn new_do_while(
+        ast_expr_node:u32,loop_level:usize
+    ) -> Self {
+        Self { instrs:InstrList::new(), text:String::new(), info:Fields::new(),cfg_node_type:CfgNodeType::DoWhileLoop { ast_expr_node, }, phi_instrs: InstrList::new(), op_label_instr:None, op_jump_instr: None, asms: InstrSlab::new(),loop_level }
+    }
     pub fn new_switch(ast_expr_node:u32,loop_level:usize) -> Self { Self { instrs:InstrList::new(), text:String::new(), info:Fields::new(),cfg_node_type:CfgNodeType::Switch { ast_expr_node ,}, phi_instrs: InstrList::new(), op_label_instr: None, op_jump_instr: None, asms: InstrSlab::new(),loop_level } }
     pub fn new_entry(ast_node:u32, _instr:usize,loop_level:usize) -> Self {
         Self { instrs:InstrList::new(), text:String::new(), info:Fields::new(),cfg_node_type:CfgNodeType::Entry { ast_node, calls_in_func:vec![] ,}, phi_instrs: InstrList::new(), op_label_instr: None, op_jump_instr: None, asms: InstrSlab::new(),loop_level }
@@ -354,6 +375,10 @@ impl Debug for CfgNode {
                 write!(f,  " #{} {} \n {} $ @ # {} \n {:#?} \n loop_level:{} $ ", "While\n", ast_expr_node, self.text, "Fields", self.info,self.loop_level)
             }
             // 5元  输出为4格 {{2 | 1} | { 1 | 1}}
+            CfgNodeType::DoWhileLoop { ast_expr_node } => {
+                write!(f,  " #{} {} \n {} $ @ # {} \n {:#?} \n loop_level:{} $ ", "DoWhile\n", ast_expr_node, self.text, "Fields", self.info,self.loop_level)
+            }
+            // 5元  输出为4格 {{2 | 1} | { 1 | 1}}
             CfgNodeType::Switch { ast_expr_node } => {
                 write!(f,  " #{} {} \n {} $ @ # {} \n {:#?} \n loop_level:{} $ ", "Switch\n", ast_expr_node, self.text, "Fields", self.info,self.loop_level)
             }
@@ -11,6 +11,8 @@ use crate::toolkit::symtab::WithBorrow;
 use super::cfg_node::InstrList;
 use super::asm_struct::AsmStructure;
 use super::loop_node::LoopTree;
+use super::preprocess::LineMap;
+use super::diagnostic::Diagnostic;
 use super::nhwc_instr::NhwcInstr;
 use super::call_node::CallGraph;
 use super::rv64_instr::RV64Instr;
@@ -24,6 +26,10 @@ pub type DjGraph = petgraph::stable_graph::StableDiGraph<DjNode, DjEdge, u32>;
 pub struct NhwcCtx {
     pub args:Args,
     pub code:String,
+    /// 预处理之后代码的行号到原始文件行号的映射
+    pub line_map:LineMap,
+    /// 各个 pass 中收集到的警告，由 WarningPass 统一过滤和输出
+    pub diagnostics:Vec<Diagnostic>,
     pub ast_tree:AstTree,
     pub cfg_graph:CfgGraph,
     pub symtab:SymTab,
@@ -56,9 +62,13 @@ impl NhwcCtx {
                     with_field TEMP_COUNTER:{1}
                     with_field EXTERNAL_FUNC_SYMIDX_VEC:{vec![]}
                     with_field GLOBAL_VARS:{vec![]}
+                    with_field STR_LITERALS:{vec![]}
+                    with_field DIAGNOSTICS:{vec![]}
                 to symtab);
                 symtab},
             code:String::new(),
+            line_map:LineMap::new(),
+            diagnostics:vec![],
             scope_tree:ScopeTree::new(),
             et_tree:EtTree::new(),
             ast2scope:HashMap::new(),
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+use std::fs;
+
+use super::ast_node::SrcLoc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+impl Display for Severity {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// 可以通过 `-W<name>` 单独打开的警告类别
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarnKind {
+    Unused,
+    Unreachable,
+    Uninitialized,
+    DivByZero,
+}
+impl WarnKind {
+    pub const ALL:[WarnKind; 4] = [WarnKind::Unused, WarnKind::Unreachable, WarnKind::Uninitialized, WarnKind::DivByZero];
+    /// 命令行中 `-W` 后面的名字
+    pub fn flag_name(&self) -> &'static str {
+        match self {
+            WarnKind::Unused => "unused",
+            WarnKind::Unreachable => "unreachable",
+            WarnKind::Uninitialized => "uninitialized",
+            WarnKind::DivByZero => "div-by-zero",
+        }
+    }
+    pub fn from_flag_name(name:&str) -> Option<WarnKind> { WarnKind::ALL.into_iter().find(|kind| kind.flag_name() == name) }
+}
+
+/// 编译过程中收集到的一条诊断信息，报错时不立刻退出，而是收集起来统一输出
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity:Severity,
+    pub src_loc:Option<SrcLoc>,
+    pub msg:String,
+    /// 警告所属的类别，错误没有类别
+    pub op_warn_kind:Option<WarnKind>,
+}
+impl Diagnostic {
+    pub fn new_error(src_loc:Option<SrcLoc>, msg:String) -> Self { Diagnostic { severity:Severity::Error, src_loc, msg, op_warn_kind:None } }
+    pub fn new_warning(warn_kind:WarnKind, src_loc:Option<SrcLoc>, msg:String) -> Self { Diagnostic { severity:Severity::Warning, src_loc, msg, op_warn_kind:Some(warn_kind) } }
+    /// 单行的 `file:line:col: warning: msg [-Wkind]` ，格式固定，方便 CI 用正则解析  
+    /// -Werror 时警告被当作错误，后缀变为 `[-Werror=kind]`
+    pub fn render_line(&self) -> String {
+        let suffix = match (self.op_warn_kind, self.severity) {
+            (Some(warn_kind), Severity::Warning) => format!(" [-W{}]", warn_kind.flag_name()),
+            (Some(warn_kind), Severity::Error) => format!(" [-Werror={}]", warn_kind.flag_name()),
+            (None, _) => String::new(),
+        };
+        format!("{}: {}: {}{}", SrcLoc::fmt_op(&self.src_loc), self.severity, self.msg, suffix)
+    }
+    /// 渲染成 `file:line:col: error: msg` 加上出错那一行的源码以及指向出错列的 `^`
+    pub fn render(&self) -> String {
+        let mut s = self.render_line();
+        if let Some(src_loc) = &self.src_loc {
+            if let Some(src_line) = fs::read_to_string(&*src_loc.file).ok().and_then(|text| text.lines().nth(src_loc.line.wrapping_sub(1)).map(|l| l.to_string())) {
+                // 保留行首的 tab ，这样 ^ 才能和源码对齐
+                let pad:String = src_line.chars().take(src_loc.col.saturating_sub(1)).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+                let gutter = " ".repeat(src_loc.line.to_string().len());
+                s += &format!("\n {} | {}\n {} | {}^", src_loc.line, src_line, gutter, pad);
+            }
+        }
+        s
+    }
+}
+impl Display for Diagnostic {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.render()) }
+}
@@ -5,7 +5,7 @@ use std::fmt::Debug;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::{mem, u32};
 
-use super::ast_node::AstTree;
+use super::ast_node::{AstTree, SrcLoc};
 use super::etc::dfs_with_predicate;
 use super::field::{Fields, Type};
 use super::symtab::{ RcSymIdx, SymIdx, WithBorrow};
@@ -45,7 +45,8 @@ impl Debug for EtEdge{
 
 #[derive(Clone,Hash)]
 pub enum DeclOrDefOrUse {
-    DeclDef { type_ast_node:u32 ,is_const:bool },
+    /// ptr_depth 为 declarator 中 `*` 的个数
+    DeclDef { type_ast_node:u32 ,is_const:bool ,ptr_depth:usize },
     Use,
     Def 
 }
@@ -102,6 +103,8 @@ pub struct EtNode {
     pub gvn_evaluated:bool,
     pub gvn_instr_generated:bool,
     pub equivalent_symidx_vec:Vec<RcSymIdx>, // for gvn
+    /// 由 ast 生成的节点记录对应的源代码位置，gvn 等过程中新建的节点为 None
+    pub src_loc:Option<SrcLoc>,
 }
 pub trait EtHash{
     fn update_hash(&mut self,et_node:u32) -> Result<()>;
@@ -193,11 +196,20 @@ impl EtHash for EtTree{
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
+                    ExprOp::Conditional => {
+                        assert!(child_nodes.len() == 3);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let h3 = node!(at {child_nodes[2]} in self).hash?;
+                        let hash = Some((h1*7+(h2^(h3*3))+52711) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
                     ExprOp::BitwiseOr => {
                         assert!(child_nodes.len() == 2);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
                         let h2 = node!(at {child_nodes[1]} in self).hash?;
-                        let hash = Some((h1|h2+8332) % ET_HASH_MODULUS);
+                        let hash = Some(((h1+h2)*7+8332) % ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
@@ -205,7 +217,7 @@ impl EtHash for EtTree{
                         assert!(child_nodes.len() == 2);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
                         let h2 = node!(at {child_nodes[1]} in self).hash?;
-                        let hash = Some((h1&h2+32481) % ET_HASH_MODULUS);
+                        let hash = Some(((h1+h2)*11+32481) % ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
@@ -213,15 +225,14 @@ impl EtHash for EtTree{
                         assert!(child_nodes.len() == 2);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
                         let h2 = node!(at {child_nodes[1]} in self).hash?;
-                        let hash = Some((h1+h2+32941123) % ET_HASH_MODULUS);
+                        let hash = Some(((h1+h2)*13+32941123) % ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
                     ExprOp::BitwiseNot => {
                         assert!(child_nodes.len() == 1);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
-                        let h2 = node!(at {child_nodes[1]} in self).hash?;
-                        let hash = Some((h1+h2+93910) % ET_HASH_MODULUS);
+                        let hash = Some(((h1^93910)*3+17) % ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
@@ -277,7 +288,7 @@ impl EtHash for EtTree{
                         assert!(child_nodes.len() == 2);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
                         let h2 = node!(at {child_nodes[1]} in self).hash?;
-                        let hash = Some((h1^h2+70000) % ET_HASH_MODULUS);
+                        let hash = Some((h1*5-h2+70000) % ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
@@ -285,7 +296,7 @@ impl EtHash for EtTree{
                         assert!(child_nodes.len() == 2);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
                         let h2 = node!(at {child_nodes[1]} in self).hash?;
-                        let hash = Some((h1^h2+80000) % ET_HASH_MODULUS);
+                        let hash = Some((h1*5-h2*3+80000) % ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
@@ -297,6 +308,62 @@ impl EtHash for EtTree{
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
+                    ExprOp::UDiv => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1*7-h2*5+61001) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::UMod => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1*7-h2*3+9011) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::LogicalRShift => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1*5-h2*7+80021) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::ULess => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1*3-h2+93451) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::UGreater => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1-h2*3+38227) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::ULEq => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1*3-h2*2+3851) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::UGEq => {
+                        assert!(child_nodes.len() == 2);
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let h2 = node!(at {child_nodes[1]} in self).hash?;
+                        let hash = Some((h1*2-h2*3+32957) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
                     ExprOp::Call => {
                         let hash = Some((et_node as isize + 234)*23425%ET_HASH_MODULUS);
                         node_mut!(at et_node in self).hash =hash;                     
@@ -352,7 +419,12 @@ impl EtHash for EtTree{
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     }
-                    ExprOp::AddrOf => todo!(),
+                    ExprOp::AddrOf => {
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let hash = Some((h1^1723841) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
                     ExprOp::Deref => {
                         debug_info_red!("find deref? {}",et_node);
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
@@ -379,9 +451,22 @@ impl EtHash for EtTree{
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
+                    ExprOp::DotMember | ExprOp::ArrowMember => {
+                        // 成员名存放在 text 中，instr_et 中的 struct getelementptr 则把偏移作为第二个子节点
+                        let mut h1 = 0;
+                        for &child_node in child_nodes.iter(){
+                            h1 = (h1*131 + node!(at child_node in self).hash?) % ET_HASH_MODULUS;
+                        }
+                        let mut hasher = DefaultHasher::new();
+                        if let EtNodeType::Operator { op, ast_node: _, text, op_rc_symidx: _ } = &node!(at et_node in self).et_node_type{
+                            op.is_arrow_member().hash(&mut hasher);
+                            text.hash(&mut hasher);
+                        }
+                        let hash = Some(((h1^2849131) + (hasher.finish() as isize) % ET_HASH_MODULUS) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
                     // no need to implement
-                    ExprOp::DotMember => todo!(),
-                    ExprOp::ArrowMember => todo!(),
                     ExprOp::LPlusPlus => todo!(),
                     ExprOp::RPlusPlus => todo!(),
                     ExprOp::LMinusMinus => todo!(),
@@ -396,6 +481,30 @@ impl EtHash for EtTree{
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     },
+                    ExprOp::TransToU32 => {
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let hash = Some(((h1^2493437)+33317) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::TransToI8 => {
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let hash = Some(((h1^5493467)+63329) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::TransToI64 => {
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let hash = Some(((h1^3493441)+43319) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
+                    ExprOp::TransToF64 => {
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let hash = Some(((h1^4493453)+53323) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    },
                     ExprOp::TransToF32 => {
                         let h1 = node!(at {child_nodes[0]} in self).hash?;
                         let hash = Some(((h1^239432)+73232) % ET_HASH_MODULUS);
@@ -408,6 +517,12 @@ impl EtHash for EtTree{
                         node_mut!(at et_node in self).hash =hash;
                         hash
                     }
+                    ExprOp::Bitcast => {
+                        let h1 = node!(at {child_nodes[0]} in self).hash?;
+                        let hash = Some(((h1^672931)+51287) % ET_HASH_MODULUS);
+                        node_mut!(at et_node in self).hash =hash;
+                        hash
+                    }
                 }
             },
             EtNodeType::Literal { rc_literal_symidx, ast_node, text } => {
@@ -468,6 +583,8 @@ pub enum ExprOp {
     LogicalOr,
     LogicalAnd,
     LogicalNot,
+    /// 条件运算符 c ? a : b ，三个子节点依次为 c a b
+    Conditional,
     BitwiseOr,
     BitwiseAnd,
     BitwiseXor,
@@ -481,6 +598,14 @@ pub enum ExprOp {
     LShift,
     RShift,
     Mod,
+    /// 以下几个只出现在 instr_et 中，对应 vartype 为 U32 的指令，避免 gvn 把有符号和无符号的运算当成同一个表达式
+    UDiv,
+    UMod,
+    LogicalRShift,
+    ULess,
+    UGreater,
+    ULEq,
+    UGEq,
     Cast,
     Call,
     Negative,
@@ -502,8 +627,13 @@ pub enum ExprOp {
     Store,
     Load,
     TransToI32,
+    TransToU32,
+    TransToI8,
+    TransToI64,
     TransToF32,
+    TransToF64,
     TransToI1,
+    Bitcast,
 }
 impl Debug for ExprOp {
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -516,6 +646,7 @@ impl Debug for ExprOp {
             Self::LogicalOr => write!(f, "||"),
             Self::LogicalAnd => write!(f, "&&"),
             Self::LogicalNot => write!(f, "!"),
+            Self::Conditional => write!(f, "?:"),
             Self::BitwiseOr => write!(f, "|"),
             Self::BitwiseAnd => write!(f, "&"),
             Self::BitwiseXor => write!(f, "^"),
@@ -529,6 +660,13 @@ impl Debug for ExprOp {
             Self::LShift => write!(f, "<<"),
             Self::RShift => write!(f, ">>"),
             Self::Mod => write!(f, "%"),
+            Self::UDiv => write!(f, "/u"),
+            Self::UMod => write!(f, "%u"),
+            Self::LogicalRShift => write!(f, ">>>"),
+            Self::ULess => write!(f, "<u"),
+            Self::UGreater => write!(f, ">u"),
+            Self::ULEq => write!(f, "<=u"),
+            Self::UGEq => write!(f, ">=u"),
             Self::Cast => write!(f, "Cast"),
             Self::Call => write!(f, "Call"),
             Self::Negative => write!(f, "-"),
@@ -550,15 +688,20 @@ impl Debug for ExprOp {
             Self::Store => write!(f,"Store"),
             Self::Load => write!(f,"Load"),
             Self::TransToI32 => write!(f,"TransI32"),
+            Self::TransToU32 => write!(f,"TransU32"),
+            Self::TransToI8 => write!(f,"TransI8"),
+            Self::TransToI64 => write!(f,"TransI64"),
+            Self::TransToF64 => write!(f,"TransF64"),
             Self::TransToF32 => write!(f,"TransF32"),
             Self::TransToI1 => write!(f,"TransBool"),
+            Self::Bitcast => write!(f,"Bitcast"),
             // Self::Chi => write!(f,"Chi"),
         }
     }
 }
 impl From<EtNodeType> for EtNode{
     fn from(et_node_type: EtNodeType) -> Self {
-        Self { et_node_type, hash: None, gen_nhwc_cached_rc_symidx: None, et_ret_symidx_vec: None ,common_eliminated:false, dims: None, ty: None,equivalent_symidx_vec:vec![], gvn_evaluated: false, gvn_instr_generated: false, can_be_common_eliminated: true }
+        Self { et_node_type, hash: None, gen_nhwc_cached_rc_symidx: None, et_ret_symidx_vec: None ,common_eliminated:false, dims: None, ty: None,equivalent_symidx_vec:vec![], gvn_evaluated: false, gvn_instr_generated: false, can_be_common_eliminated: true, src_loc:None }
     }
 }
 
@@ -570,12 +713,27 @@ impl EtNodeType {
             Type::I32 => {
                 EtNodeType::Operator { op: ExprOp::TransToI32, ast_node, text: String::new(), op_rc_symidx: None }
             },
+            Type::U32 => {
+                EtNodeType::Operator { op: ExprOp::TransToU32, ast_node, text: String::new(), op_rc_symidx: None }
+            },
+            Type::I8 => {
+                EtNodeType::Operator { op: ExprOp::TransToI8, ast_node, text: String::new(), op_rc_symidx: None }
+            },
+            Type::I64 => {
+                EtNodeType::Operator { op: ExprOp::TransToI64, ast_node, text: String::new(), op_rc_symidx: None }
+            },
             Type::F32 => {
                 EtNodeType::Operator { op: ExprOp::TransToF32, ast_node, text: String::new(), op_rc_symidx: None }
             },
+            Type::F64 => {
+                EtNodeType::Operator { op: ExprOp::TransToF64, ast_node, text: String::new(), op_rc_symidx: None }
+            },
             Type::I1 => {
                 EtNodeType::Operator { op: ExprOp::TransToI1, ast_node, text: String::new(), op_rc_symidx: None }
             },
+            Type::Ptr64 { ty:_ } => {
+                EtNodeType::Operator { op: ExprOp::Bitcast, ast_node, text: String::new(), op_rc_symidx: None }
+            },
             _ => {panic!()}
         }
     }
@@ -587,9 +745,15 @@ impl EtNodeType {
     pub fn new_op_mul(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Mul, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_assign(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Assign, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_sep(ast_node:u32) -> Self { EtNodeType::Separator { ast_node, text:String::new() } }
+    pub fn get_ast_node(&self) -> u32 {
+        match self {
+            EtNodeType::Operator { ast_node, .. } | EtNodeType::Literal { ast_node, .. } | EtNodeType::Symbol { ast_node, .. } | EtNodeType::Separator { ast_node, .. } => *ast_node,
+        }
+    }
     pub fn new_op_logical_or(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LogicalOr, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_logical_and(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LogicalAnd, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_logical_not(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LogicalNot, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_conditional(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Conditional, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_bitwise_or(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::BitwiseOr, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_bitwise_xor(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::BitwiseXor, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_bitwise_and(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::BitwiseAnd, ast_node, text:String::new() ,op_rc_symidx:None} }
@@ -613,14 +777,22 @@ impl EtNodeType {
     pub fn new_op_left_shift(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LShift, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_right_shift(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::RShift, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_mod(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Mod, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_unsigned_div(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::UDiv, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_unsigned_mod(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::UMod, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_logical_right_shift(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LogicalRShift, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_unsigned_less_than(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::ULess, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_unsigned_greater_than(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::UGreater, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_unsigned_less_than_or_equal(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::ULEq, ast_node, text:String::new() ,op_rc_symidx:None} }
+    pub fn new_op_unsigned_greater_than_or_equal(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::UGEq, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_cast(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Cast, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_call(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Call, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_negative(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Negative, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_positive(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Positive, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_addr_of(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::AddrOf, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_deref(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::Deref, ast_node, text:String::new() ,op_rc_symidx:None} }
-    pub fn new_op_dot_member(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::DotMember, ast_node, text:String::new() ,op_rc_symidx:None} }
-    pub fn new_op_arrow_member(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::ArrowMember, ast_node, text:String::new() ,op_rc_symidx:None} }
+    /// 成员访问的成员名存放在 text 中
+    pub fn new_op_dot_member(ast_node:u32, member_name:String) -> Self { EtNodeType::Operator { op:ExprOp::DotMember, ast_node, text:member_name ,op_rc_symidx:None} }
+    pub fn new_op_arrow_member(ast_node:u32, member_name:String) -> Self { EtNodeType::Operator { op:ExprOp::ArrowMember, ast_node, text:member_name ,op_rc_symidx:None} }
     pub fn new_op_left_plusplus(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LPlusPlus, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_right_plusplus(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::RPlusPlus, ast_node, text:String::new() ,op_rc_symidx:None} }
     pub fn new_op_left_minusminus(ast_node:u32) -> Self { EtNodeType::Operator { op:ExprOp::LMinusMinus, ast_node, text:String::new() ,op_rc_symidx:None} }
@@ -656,7 +828,7 @@ impl EtNodeType {
             let ast_node = *ast_node;
             let _ = mem::replace(text, node!(at ast_node in ast_tree).op_text.as_ref().unwrap().clone());
         } else if let EtNodeType::Symbol { rc_symidx: _, ast_node: _, text, decldef_def_or_use: def_or_use } = self {
-            if let DeclOrDefOrUse::DeclDef { type_ast_node, is_const } = def_or_use {
+            if let DeclOrDefOrUse::DeclDef { type_ast_node, is_const, ptr_depth: _ } = def_or_use {
                 let type_ast_node = *type_ast_node;
                 let _ = mem::replace(text, node!(at type_ast_node in ast_tree).op_text.as_ref().unwrap().clone());
             }
@@ -686,8 +858,8 @@ impl Debug for EtNodeType {
 impl Debug for DeclOrDefOrUse {
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            &DeclOrDefOrUse::DeclDef { type_ast_node: _, is_const } => {
-                write!(f, "decl {}",if is_const{"const"}else {""})
+            &DeclOrDefOrUse::DeclDef { type_ast_node: _, is_const, ptr_depth } => {
+                write!(f, "decl {}{}",if is_const{"const"}else {""},"*".repeat(ptr_depth))
             },
             DeclOrDefOrUse::Use => write!(f, "use"),
             DeclOrDefOrUse::Def => write!(f, "def"),
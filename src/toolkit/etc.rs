@@ -1,5 +1,5 @@
 use std::{
-    env, fmt::Debug, fs::File, io::{Read, Write}, process::Command, thread::{spawn, AccessError, JoinHandle}
+    env, fmt::Debug, fs::File, io::Write, process::Command, thread::{spawn, AccessError, JoinHandle}
 };
 use std::time::Instant;
 
@@ -76,12 +76,6 @@ pub fn generate_png_by_graph_multi_tasks<N:Debug, E:Debug, Ty:EdgeType>(g:&Stabl
     Ok(())
 }
 
-/// 从指定文件中读取所有文本
-pub fn read_file_content(path:String) -> String {
-    let mut buf = String::new();
-    File::open(path).expect("文件读取异常").read_to_string(&mut buf).expect("读取失败");
-    buf
-}
 pub fn dfs<N, E, Ty>(graph:&StableGraph<N, E, Ty, u32>, start_node:u32) -> Vec<u32>
 where
     Ty: EdgeType,
@@ -1,7 +1,7 @@
 
 use std::borrow::BorrowMut;
 
-use super::{et_node::{DeclOrDefOrUse, EtEdge, EtHash, EtNode, EtNodeType, EtTree, ExprOp}, etc::{self, dfs, rpo}, field::{Type, Value}, scope_node::ScopeTree, symtab::SymTab};
+use super::{ast_node::SrcLoc, et_node::{DeclOrDefOrUse, EtEdge, EtHash, EtNode, EtNodeType, EtTree, ExprOp}, etc::{self, dfs, rpo}, field::{Type, Value}, scope_node::ScopeTree, symtab::SymTab};
 use crate::{add_edge, add_node_with_edge, debug_info_blue, debug_info_green, debug_info_red, direct_child_node, direct_child_nodes, direct_parent_node, node, node_mut, toolkit::{dot::Config, et_node::EtEdgeType, etc::{dfs_with_predicate, generate_png_by_graph, generate_png_by_graph_multi_tasks, rpo_with_predicate}, gen_cfg::AST_ROOT, scope_node::ST_ROOT, symtab::{RcSymIdx, SymIdx, WithBorrow}}};
 use ahash::{HashMap, HashMapExt};
 use anyhow::Result;
@@ -84,6 +84,50 @@ fn _eval_et(et_tree:&mut EtTree, et_node:u32) {
     };
 
 }
+/// 条件运算符的条件是常量而分支不全是常量时，直接用被选中的分支替换条件运算符
+/// 只用于 gen_nhwc 前的 et 树，instr_et 中的每个节点都可能是一条指令的根，不能这样替换
+fn fold_const_conditional(et_tree:&mut EtTree, et_sep_node:u32){
+    for et_node in rpo_with_predicate(et_tree, et_sep_node, |e|!e.weight().et_edge_type.is_deleted()){
+        if !matches!(&node!(at et_node in et_tree).et_node_type, EtNodeType::Operator { op:ExprOp::Conditional, .. }){ continue; }
+        let et_nodes = direct_et_child_nodes!(at et_node in et_tree);
+        let op_picked_et_node = if let EtNodeType::Literal { rc_literal_symidx, ast_node:_, text:_ } = &node!(at {et_nodes[0]} in et_tree).et_node_type{
+            Some(if matches!(Value::from_symidx(&rc_literal_symidx.as_ref_borrow()).trans_to_specific_type(&Type::I1),Value::I1(Some(true))) { et_nodes[1] } else { et_nodes[2] })
+        }else { None };
+        if let Some(picked_et_node) = op_picked_et_node{
+            replace_et_node_in_parent(et_node, picked_et_node, et_tree);
+        }
+    }
+}
+/// 把 et_node 在唯一父节点中的位置换成 new_et_node ，保持父节点子节点的顺序不变，et_node 没有唯一父节点时不做替换并返回 false
+fn replace_et_node_in_parent(et_node:u32, new_et_node:u32, et_tree:&mut EtTree) -> bool{
+    let parent_et_node = if let Some(parent_et_node) = direct_et_parent_node!(at et_node in et_tree ret_option) { parent_et_node } else { return false };
+    let mut edge_weight_tuple_vec = vec![];
+    for edge_ref in et_tree.edges_directed(node_index(parent_et_node as usize), Outgoing).collect_vec().iter().rev(){
+        let mut weight = edge_ref.weight().clone();
+        let s = edge_ref.source().index() as u32;
+        let t = edge_ref.target().index() as u32;
+        if t == et_node{
+            weight = EtEdgeType::Deleted.into();
+            edge_weight_tuple_vec.push((EtEdgeType::Direct.into(),s,new_et_node));
+        }
+        edge_weight_tuple_vec.push((weight,s,t));
+    }
+    let edges = et_tree.edges_directed(node_index(parent_et_node as usize), Outgoing).map(|edge| edge.id()).collect_vec();
+    for edge in edges{
+        et_tree.remove_edge(edge);
+    }
+    for (weight,s,t) in edge_weight_tuple_vec{
+        add_edge!({weight} from s to t in et_tree);
+    }
+    // et_node 不再被使用，它的出边也标记为 Deleted ，避免子节点出现多个父节点
+    let edges = et_tree.edges_directed(node_index(et_node as usize), Outgoing).map(|edge| edge.id()).collect_vec();
+    for edge in edges{
+        et_tree.edge_weight_mut(edge).unwrap().et_edge_type = EtEdgeType::Deleted;
+    }
+    let equivalent_symidx_vec_to_move = std::mem::take(&mut node_mut!(at et_node in et_tree).equivalent_symidx_vec);
+    node_mut!(at new_et_node in et_tree).equivalent_symidx_vec.extend(equivalent_symidx_vec_to_move);
+    true
+}
 fn match_x_mul_1(et_nodes:&Vec<u32>,et_node:u32,et_tree:&mut EtTree)->bool{
     let et_symidx_vec = get_symidx_vec(et_node, et_tree);
     let (l,r) = if let (Some(l),Some(r)) = (&et_symidx_vec[0],&et_symidx_vec[1]){ (l.as_ref_borrow(),r.as_ref_borrow()) }else { return false };
@@ -326,6 +370,7 @@ fn recursive_replace_const_symbol(et_tree:&mut EtTree,et_node:u32,symtab:&SymTab
     debug_info_red!("dfs_et_nodes successfully");
     for et_node in dfs_et_nodes{
         // debug_info_green!("visit {}",et_node);
+        let op_src_loc = node!(at et_node in et_tree).src_loc.clone();
         match &node_mut!(at et_node in et_tree).et_node_type{
             EtNodeType::Symbol { rc_symidx: rc_init_symidx, ast_node, text, decldef_def_or_use } => {
                 // debug_info_blue!("cur scope {}",scope_node);
@@ -338,12 +383,12 @@ fn recursive_replace_const_symbol(et_tree:&mut EtTree,et_node:u32,symtab:&SymTab
                         if scope_node != ST_ROOT{
                             symidx.scope_node = direct_parent_node!(at scope_node in scope_tree );
                         }else{
-                            return Err(anyhow!("scope为{}符号表中未找到{:?}", initial_scope, symidx.symbol_name.clone()));
+                            return Err(anyhow!("{}: scope为{}符号表中未找到{:?}", SrcLoc::fmt_op(&op_src_loc), initial_scope, symidx.symbol_name.clone()));
                         }
                     }
                     // debug_info_green!("replace symidx {}",sym_idx);
                     // if the symidx have its corresponding const symidx
-                    if !symtab.get(&symidx).get_type().is_array() && symtab.get(&symidx).has_const_cor_literal_symidx() {
+                    if !symtab.get(&symidx).get_type().is_aggregate() && symtab.get(&symidx).has_const_cor_literal_symidx() {
                         let literal_symidx = symtab.get(&symidx).get_const_cor_literal_symidx().clone();
                         node_mut!(at et_node in et_tree).et_node_type = EtNodeType::new_literal(*ast_node, literal_symidx.as_rc())
                     }
@@ -355,6 +400,66 @@ fn recursive_replace_const_symbol(et_tree:&mut EtTree,et_node:u32,symtab:&SymTab
     Ok(())
 }
 
+/// 把下标都是字面量的 const 数组元素替换成它的初值，部分初始化的数组中没有给出初值的元素为 0  
+/// 返回是否有元素被替换
+fn fold_const_array_index(et_tree:&mut EtTree, et_sep_node:u32, symtab:&SymTab, scope_tree:&ScopeTree) -> bool{
+    let const_usize_of = |symidx:&SymIdx| -> Option<usize> {
+        symidx.symbol_name.parse().ok().or_else(|| symtab.try_get(symidx).ok().filter(|symbol| symbol.has_const_cor_literal_symidx()).and_then(|symbol| symbol.get_const_cor_literal_symidx().symbol_name.parse().ok()))
+    };
+    let mut folded = false;
+    for et_node in rpo_with_predicate(et_tree, et_sep_node, |e|!e.weight().et_edge_type.is_deleted()){
+        // 从最外层的下标往下找到数组本身，下标按从高维到低维的顺序排列
+        let mut op_idx_vec = vec![];
+        let mut base_et_node = et_node;
+        while matches!(node!(at base_et_node in et_tree).et_node_type, EtNodeType::Operator { op:ExprOp::ArrayIndex, .. }){
+            let et_nodes = direct_et_child_nodes!(at base_et_node in et_tree);
+            // 形参 a[] 这样的声明没有下标
+            let Some(&idx_et_node) = et_nodes.get(1) else { break };
+            op_idx_vec.push(match &node!(at idx_et_node in et_tree).et_node_type {
+                EtNodeType::Literal { rc_literal_symidx, .. } => rc_literal_symidx.as_ref_borrow().symbol_name.parse::<usize>().ok(),
+                _ => None,
+            });
+            base_et_node = et_nodes[0];
+        }
+        op_idx_vec.reverse();
+        // 只有被读取的数组元素才能替换，取地址或者赋值时数组符号是 Def
+        let EtNodeType::Symbol { rc_symidx, decldef_def_or_use:DeclOrDefOrUse::Use, .. } = &node!(at base_et_node in et_tree).et_node_type else { continue };
+        let mut symidx = rc_symidx.as_ref_borrow().clone();
+        while symtab.try_get(&symidx).is_err() && symidx.scope_node != ST_ROOT {
+            let scope_node = symidx.scope_node;
+            symidx.scope_node = direct_parent_node!(at scope_node in scope_tree);
+        }
+        let Ok(symbol) = symtab.try_get(&symidx) else { continue };
+        let (Type::Array { dims, ele_ty:_ }, true) = (symbol.get_type(), symbol.has_const_cor_literal_symidx()) else { continue };
+        let op_dims = dims.iter().map(|op_dim| op_dim.as_ref().and_then(|dim| const_usize_of(&dim.as_ref_borrow()))).collect_vec();
+        if op_idx_vec.len() != op_dims.len() { continue }
+        let mut offset = 0;
+        for (op_idx, op_dim) in op_idx_vec.iter().zip(op_dims.iter()){
+            match (op_idx, op_dim) {
+                (Some(idx), Some(dim)) if idx < dim => offset = offset * dim + idx,
+                _ => { offset = usize::MAX; break; }
+            }
+        }
+        if offset == usize::MAX { continue }
+        let literal_symidx = symbol.get_const_cor_literal_symidx();
+        let Ok(symbol) = symtab.try_get(literal_symidx) else { continue };
+        // 运行时才能求值的初值(例如局部 const 数组用变量初始化)不能替换
+        match symbol.get_value().index_array(offset) {
+            Ok(Value::Ref { .. }) | Err(_) => continue,
+            Ok(val) => {
+                let ast_node = node!(at et_node in et_tree).et_node_type.get_ast_node();
+                node_mut!(at et_node in et_tree).et_node_type = EtNodeType::new_literal(ast_node, val.to_symidx().as_rc());
+                let edges = et_tree.edges_directed(node_index(et_node as usize), Outgoing).map(|edge| edge.id()).collect_vec();
+                for edge in edges{
+                    et_tree.edge_weight_mut(edge).unwrap().et_edge_type = EtEdgeType::Deleted;
+                }
+                folded = true;
+            },
+        }
+    }
+    folded
+}
+
 /// compress the et_tree 
 /// 1. replace const (optional)
 /// 2. eval_et
@@ -363,6 +468,14 @@ pub fn compress_et_for_gen_nhwc(et_tree:&mut EtTree, et_sep_node:u32, can_elimin
     debug_info_red!("exec compress_et on {}",et_sep_node);
     if replace_const{ recursive_replace_const_symbol(et_tree, et_sep_node, symtab, scope_node, scope_tree)?;}
     eval_et(et_tree, et_sep_node);
+    if replace_const && fold_const_array_index(et_tree, et_sep_node, symtab, scope_tree){
+        // 替换出来的字面量可能让上层的表达式也可以求值
+        for et_node in dfs(et_tree, et_sep_node){
+            node_mut!(at et_node in et_tree).gvn_evaluated = false;
+        }
+        eval_et(et_tree, et_sep_node);
+    }
+    fold_const_conditional(et_tree, et_sep_node);
     et_tree.update_hash(et_sep_node)?;
     for et_node in direct_et_child_nodes!(at et_sep_node in et_tree ){
         debug_info_blue!("hash expr eliminate on {}",et_node);
@@ -554,12 +667,22 @@ impl ExprOp{
             ExprOp::LogicalAnd|
             ExprOp::BitwiseOr |
             ExprOp::BitwiseAnd|
+            ExprOp::BitwiseXor|
+            ExprOp::LShift |
+            ExprOp::RShift |
             ExprOp::NEq |
             ExprOp::Less |
             ExprOp::Greater |
             ExprOp::LEq |
             ExprOp::GEq |
-            ExprOp::Mod => {
+            ExprOp::Mod |
+            ExprOp::UDiv |
+            ExprOp::UMod |
+            ExprOp::LogicalRShift |
+            ExprOp::ULess |
+            ExprOp::UGreater |
+            ExprOp::ULEq |
+            ExprOp::UGEq => {
                 if vec.len()<2 {
                     panic!("can't eval with no enough symidx {:?}",vec);
                 }
@@ -575,6 +698,9 @@ impl ExprOp{
             ExprOp::LogicalAnd => Value::from_symidx( &vec[0].as_ref_borrow()).logical_and(&Value::from_symidx( &vec[1].as_ref_borrow())),
             ExprOp::BitwiseOr => Value::from_symidx( &vec[0].as_ref_borrow())|Value::from_symidx( &vec[1].as_ref_borrow()),
             ExprOp::BitwiseAnd => Value::from_symidx( &vec[0].as_ref_borrow())&Value::from_symidx( &vec[1].as_ref_borrow()),
+            ExprOp::BitwiseXor => Value::from_symidx( &vec[0].as_ref_borrow())^Value::from_symidx( &vec[1].as_ref_borrow()),
+            ExprOp::LShift => Value::from_symidx( &vec[0].as_ref_borrow())<<Value::from_symidx( &vec[1].as_ref_borrow()),
+            ExprOp::RShift => Value::from_symidx( &vec[0].as_ref_borrow())>>Value::from_symidx( &vec[1].as_ref_borrow()),
 
             ExprOp::NEq => {
                 if is_ssa_form_bool && vec[0] == vec[1]{
@@ -611,7 +737,6 @@ impl ExprOp{
                     (Value::from_symidx( &vec[0].as_ref_borrow()).greater_than_or_equal(&Value::from_symidx( &vec[1].as_ref_borrow())))
                 }
             },
-            // ExprOp::BitwiseXor => (Value::from_symidx( &vec[0].as_ref_borrow())?^Value::from_symidx( &vec[1].as_ref_borrow())?)?,
             ExprOp::Eq => {
                 if is_ssa_form_bool && vec[0] == vec[1]{
                     Value::I1(Some(true))
@@ -622,14 +747,37 @@ impl ExprOp{
             ExprOp::Mod => {
                 (Value::from_symidx( &vec[0].as_ref_borrow())%Value::from_symidx( &vec[1].as_ref_borrow()))
             },
-            // ExprOp::LShift => (Value::from_symidx( &vec[0].as_ref_borrow())?<<Value::from_symidx( &vec[1].as_ref_borrow())?)?,
-            // ExprOp::RShift => (Value::from_symidx( &vec[0].as_ref_borrow())?>>Value::from_symidx( &vec[1].as_ref_borrow())?)?,
-            ExprOp::BitwiseNot => !Value::from_symidx( &vec[0].as_ref_borrow()), //一元运算符
+            // 无符号运算先把两个操作数都当作 u32
+            ExprOp::UDiv | ExprOp::UMod | ExprOp::LogicalRShift | ExprOp::ULess | ExprOp::UGreater | ExprOp::ULEq | ExprOp::UGEq => {
+                let a = Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::U32);
+                let b = Value::from_symidx( &vec[1].as_ref_borrow()).trans_to_specific_type(&Type::U32);
+                match self{
+                    ExprOp::UDiv => a / b,
+                    ExprOp::UMod => a % b,
+                    ExprOp::LogicalRShift => a >> b,
+                    ExprOp::ULess => a.less_than(&b),
+                    ExprOp::UGreater => a.greater_than(&b),
+                    ExprOp::ULEq => a.less_than_or_equal(&b),
+                    _ => a.greater_than_or_equal(&b),
+                }
+            },
+            ExprOp::BitwiseNot => Value::from_symidx( &vec[0].as_ref_borrow()).bit_not(), //一元运算符
             ExprOp::LogicalNot => !Value::from_symidx( &vec[0].as_ref_borrow()), 
+            // 两侧按算术转换的规则统一类型
+            ExprOp::Conditional => {
+                let (a,b) = (Value::from_symidx( &vec[1].as_ref_borrow()),Value::from_symidx( &vec[2].as_ref_borrow()));
+                let ty = Type::arith_adapt(&a.to_type(), &b.to_type());
+                let picked = if matches!(Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::I1),Value::I1(Some(true))) { a } else { b };
+                picked.trans_to_specific_type(&ty)
+            },
             ExprOp::Negative => -Value::from_symidx( &vec[0].as_ref_borrow()),
             ExprOp::Positive => Value::from_symidx( &vec[0].as_ref_borrow()),
             ExprOp::TransToF32 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::F32),
             ExprOp::TransToI32 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::I32),
+            ExprOp::TransToU32 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::U32),
+            ExprOp::TransToI8 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::I8),
+            ExprOp::TransToI64 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::I64),
+            ExprOp::TransToF64 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::F64),
             ExprOp::TransToI1 => Value::from_symidx( &vec[0].as_ref_borrow()).trans_to_specific_type(&Type::I1),
             _ => {Err(anyhow!(format!("unsupported operator {:?} for {:?}",self,vec)))?},
         })
@@ -1,5 +1,5 @@
 use core::panic;
-use std::{any::Any, cell::RefCell, collections::{hash_map::Iter, HashMap}, fmt::Debug, ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Rem, Sub}, rc::Rc, vec };
+use std::{any::Any, cell::RefCell, collections::{hash_map::Iter, HashMap}, fmt::Debug, ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub}, rc::Rc, vec };
 
 use ahash::AHashMap;
 use itertools::Itertools;
@@ -9,11 +9,17 @@ use anyhow::*;
 use regex::{self, Regex};
 
 
-use super::{ast_node::AstTree, scope_node::ST_ROOT, symbol, symtab::{SymIdx, WithBorrow}};
+use crate::antlr_parser::clexer::{Identifier, Union};
+use crate::antlr_parser::cparser::{RULE_declarator, RULE_directDeclarator, RULE_assignmentExpression, RULE_enumSpecifier, RULE_specifierQualifierList, RULE_structDeclaration, RULE_structDeclarationList, RULE_structDeclarator, RULE_structDeclaratorList, RULE_structOrUnion, RULE_structOrUnionSpecifier, RULE_typeSpecifier, RULE_typedefName};
+use super::{ast_node::{find_single_constant_text, get_ptr_depth_of_declarator, AstTree}, mem_layout::AggregateLayout, scope_node::ST_ROOT, symbol, symtab::{SymIdx, WithBorrow}};
 use super::symtab::RcSymIdx;
-use crate::{debug_info_blue, debug_info_green, debug_info_red, node};
+use super::literal::canonical_literal_name;
+use crate::{debug_info_blue, debug_info_green, debug_info_red, find, find_nodes, node};
 
 pub type Fields = HashMap<*const u8, Box<dyn Field>>;
+/// 根据 typedef 名查找它所代表的类型，找不到(或者被同名变量遮盖)时返回 None  
+/// struct/union 的 tag 也以 tag_symbol_name 给出的名字通过它按作用域查找
+pub type TypedefResolver<'a> = &'a dyn Fn(&str) -> Option<Type>;
 pub static TARGET_POINTER_MEM_LEN:usize = 8;
 
 /// 你实现的类型必须继承这个 trait
@@ -64,7 +70,11 @@ impl<T:Clone+Any+Debug> Field for Option<T>{
 #[derive(Debug, Clone, PartialEq, PartialOrd, EnumIs)]
 pub enum Value {
     I32(Option<i32>),
+    I8(Option<i8>),
+    U32(Option<u32>),
+    I64(Option<i64>),
     F32(Option<f32>),
+    F64(Option<f64>),
     I1(Option<bool>),
     Ref{rc_symidx:RcSymIdx, ty:Type},
     Void,
@@ -117,6 +127,9 @@ impl ArrayEleMap{
         self.map.insert(offset as usize, val);
         Ok(())
     }
+    pub fn contains_offset(&self,offset:usize) -> bool{
+        self.map.contains_key(&offset)
+    }
     /// insert element by `usize` offset
     pub fn insert_ele(&mut self,offset:usize,val:Value) {
         self.map.insert(offset, val);
@@ -151,7 +164,15 @@ impl PartialOrd for ArrayEleMap{
 #[strum_discriminants(derive(EnumIs))]
 pub enum Type {
     I32,
+    /// char，占 1 字节，在寄存器中保持符号扩展
+    I8,
+    /// unsigned int，在寄存器中与 I32 一样保持符号扩展
+    U32,
+    /// long 与 long long ，占 8 字节
+    I64,
     F32,
+    /// double ，占 8 字节
+    F64,
     I1,
     Void,
     Label,
@@ -164,6 +185,15 @@ pub enum Type {
         ele_ty:Box<Type>,
     },
     Fn { arg_syms:Vec<RcSymIdx>, ret_sym:RcSymIdx },
+    /// members 按声明顺序排列，若 members 为空说明这是一个尚未补全的类型(例如结构体内指向自身的指针)
+    Struct{
+        tag:&'static str,
+        members:Vec<(&'static str,Type)>,
+    },
+    Union{
+        tag:&'static str,
+        members:Vec<(&'static str,Type)>,
+    },
     Unknown,
 }
 impl Clone for Box<dyn Field> {
@@ -174,8 +204,25 @@ impl Value {
     pub fn new_ref(rc_symidx:RcSymIdx, ty:Type) -> Self{
         Self::Ref { rc_symidx, ty }
     }
+    /// 逻辑运算只关心是否为 0 ，把 U32 按位转成 I32 ，I64 和 F64 转成与 0 比较的结果即可
+    fn int_as_i32_for_logic(&self) -> Value{
+        match self{
+            Value::U32(op_v) => Value::I32(op_v.map(|v| v as i32)),
+            Value::I8(op_v) => Value::I32(op_v.map(|v| v as i32)),
+            Value::I64(op_v) => Value::I32(op_v.map(|v| (v != 0) as i32)),
+            Value::F64(op_v) => Value::I32(op_v.map(|v| (v != 0.0) as i32)),
+            _ => self.clone(),
+        }
+    }
+    /// char 在比较前提升为 int
+    fn char_as_i32(&self) -> Value{
+        match self{
+            Value::I8(op_v) => Value::I32(op_v.map(|v| v as i32)),
+            _ => self.clone(),
+        }
+    }
     pub fn logical_or(&self,val:&Value) -> Value{
-        match (self,val){
+        match (&self.int_as_i32_for_logic(),&val.int_as_i32_for_logic()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1((*v1!=0)||(*v2!=0)),
             (Value::I32(Some(v1)), Value::I1(Some(v2))) => Value::new_i1((*v1!=0)||*v2),
             (Value::I1(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1||(*v2!=0)),
@@ -189,11 +236,28 @@ impl Value {
             }
         }
     }
+    /// 按位取反，注意 Not 实现的是逻辑非
+    pub fn bit_not(&self) -> Value{
+        match self{
+            Value::I32(Some(v1)) => Value::new_i32(!v1),
+            Value::U32(Some(v1)) => Value::new_u32(!v1),
+            Value::I8(Some(v1)) => Value::new_i32(!(*v1 as i32)),
+            Value::I64(Some(v1)) => Value::new_i64(!v1),
+            Value::I1(Some(v1)) => Value::new_i32(!(*v1 as i32)),
+            _ => {
+                panic!("can't bitwise not {self:?}")
+            }
+        }
+    }
     pub fn force_to_ty(&self,target_ty:&Type)-> Value{
         match target_ty{
             Type::I32 => {
                 match &self{
                     Value::I32(_) => self.clone(),
+                    Value::I8(op_c) => Value::I32(op_c.map(|c| c as i32)),
+                    Value::U32(op_u) => Value::I32(op_u.map(|u| u as i32)),
+                    Value::I64(op_l) => Value::I32(op_l.map(|l| l as i32)),
+                    Value::F64(op_d) => Value::I32(op_d.map(|d| d as i32)),
                     Value::F32(op_f) => match op_f {
                         Some(f) => {
                             Value::I32(Some(*f as i32))
@@ -203,6 +267,28 @@ impl Value {
                     _ => todo!()
                 }
             },
+            Type::U32 => {
+                match &self{
+                    Value::U32(_) => self.clone(),
+                    Value::I32(op_i) => Value::U32(op_i.map(|i| i as u32)),
+                    Value::I8(op_c) => Value::U32(op_c.map(|c| c as u32)),
+                    Value::I64(op_l) => Value::U32(op_l.map(|l| l as u32)),
+                    Value::F32(op_f) => Value::U32(op_f.map(|f| f as u32)),
+                    Value::F64(op_d) => Value::U32(op_d.map(|d| d as u32)),
+                    _ => todo!()
+                }
+            },
+            Type::I64 => {
+                match &self{
+                    Value::I64(_) => self.clone(),
+                    Value::I32(op_i) => Value::I64(op_i.map(|i| i as i64)),
+                    Value::I8(op_c) => Value::I64(op_c.map(|c| c as i64)),
+                    Value::U32(op_u) => Value::I64(op_u.map(|u| u as i64)),
+                    Value::F32(op_f) => Value::I64(op_f.map(|f| f as i64)),
+                    Value::F64(op_d) => Value::I64(op_d.map(|d| d as i64)),
+                    _ => todo!()
+                }
+            },
             Type::F32 => {
                 match &self{
                     Value::F32(_) => self.clone(),
@@ -212,6 +298,33 @@ impl Value {
                         },
                         None => {Value::F32(None)},
                     },
+                    Value::U32(op_u) => Value::F32(op_u.map(|u| u as f32)),
+                    Value::I8(op_c) => Value::F32(op_c.map(|c| c as f32)),
+                    Value::I64(op_l) => Value::F32(op_l.map(|l| l as f32)),
+                    Value::F64(op_d) => Value::F32(op_d.map(|d| d as f32)),
+                    _ => todo!()
+                }
+            },
+            Type::F64 => {
+                match &self{
+                    Value::F64(_) => self.clone(),
+                    Value::I32(op_i) => Value::F64(op_i.map(|i| i as f64)),
+                    Value::U32(op_u) => Value::F64(op_u.map(|u| u as f64)),
+                    Value::I8(op_c) => Value::F64(op_c.map(|c| c as f64)),
+                    Value::I64(op_l) => Value::F64(op_l.map(|l| l as f64)),
+                    Value::F32(op_f) => Value::F64(op_f.map(|f| f as f64)),
+                    _ => todo!()
+                }
+            },
+            Type::I8 => {
+                match &self{
+                    Value::I8(_) => self.clone(),
+                    Value::I32(op_i) => Value::I8(op_i.map(|i| i as i8)),
+                    Value::U32(op_u) => Value::I8(op_u.map(|u| u as i8)),
+                    Value::I64(op_l) => Value::I8(op_l.map(|l| l as i8)),
+                    Value::F32(op_f) => Value::I8(op_f.map(|f| f as i32 as i8)),
+                    Value::F64(op_d) => Value::I8(op_d.map(|d| d as i32 as i8)),
+                    Value::I1(op_b) => Value::I8(op_b.map(|b| b as i8)),
                     _ => todo!()
                 }
             },
@@ -219,7 +332,7 @@ impl Value {
         }
     }
     pub fn logical_and(&self,val:&Value) -> Value{
-        match (self,val){
+        match (&self.int_as_i32_for_logic(),&val.int_as_i32_for_logic()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1((*v1!=0)&&(*v2!=0)),
             (Value::I32(Some(v1)), Value::I1(Some(v2))) => Value::new_i1((*v1!=0)&&*v2),
             (Value::I1(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1&&(*v2!=0)),
@@ -232,7 +345,16 @@ impl Value {
         }
     }
     pub fn logical_eq(&self, val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1==v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1==v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1==v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1==*v2),
             (Value::I1(Some(v1)), Value::I1(Some(v2))) => Value::new_i1(*v1==*v2),
             (Value::I1(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1 as i32==*v2),
@@ -244,7 +366,16 @@ impl Value {
         }
     }
     pub fn logical_neq(&self, val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1!=v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1!=v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1!=v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1!=*v2),
             (Value::I1(Some(v1)), Value::I1(Some(v2))) => Value::new_i1(*v1!=*v2),
             (Value::I1(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1 as i32!=*v2),
@@ -256,7 +387,16 @@ impl Value {
         }
     }
     pub fn less_than(&self,val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1<v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1<v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1<v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1<*v2),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_i1((*v1 as f32)<*v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1<(*v2 as f32)),
@@ -267,7 +407,16 @@ impl Value {
         }
     }
     pub fn greater_than(&self,val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1>v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1>v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1>v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1>*v2),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_i1((*v1 as f32)>*v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1>(*v2 as f32)),
@@ -278,7 +427,16 @@ impl Value {
         }
     }
     pub fn less_than_or_equal(&self,val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1<=v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1<=v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1<=v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1<=*v2),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_i1((*v1 as f32)<=*v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1<=(*v2 as f32)),
@@ -289,7 +447,16 @@ impl Value {
         }
     }
     pub fn equal(&self,val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1==v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1==v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1==v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1==*v2),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_i1((*v1 as f32)==*v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1==(*v2 as f32)),
@@ -303,7 +470,16 @@ impl Value {
         }
     }
     pub fn greater_than_or_equal(&self,val:&Value) -> Value{
-        match (self,val){
+        if let Some((v1,v2)) = Value::as_f64_pair(self, val){
+            return Value::new_i1(v1>=v2);
+        }
+        if let Some((v1,v2)) = Value::as_i64_pair(self, val){
+            return Value::new_i1(v1>=v2);
+        }
+        if let Some((v1,v2)) = Value::as_unsigned_pair(self, val){
+            return Value::new_i1(v1>=v2);
+        }
+        match (&self.char_as_i32(),&val.char_as_i32()){
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1>=*v2),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_i1((*v1 as f32)>=*v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_i1(*v1>=(*v2 as f32)),
@@ -313,12 +489,52 @@ impl Value {
             }
         }
     }
+    /// 比较时只要有一边是 U32 ，两边都按 u32 比较(与 C 的 usual arithmetic conversions 一致)
+    fn as_unsigned_pair(v1:&Value, v2:&Value) -> Option<(u32,u32)>{
+        match (v1,v2){
+            (Value::U32(_), Value::F32(_)) | (Value::F32(_), Value::U32(_)) => None,
+            (Value::U32(_), _) | (_, Value::U32(_)) => match (v1.trans_to_specific_type(&Type::U32), v2.trans_to_specific_type(&Type::U32)){
+                (Value::U32(Some(v1)), Value::U32(Some(v2))) => Some((v1,v2)),
+                _ => panic!("can't compare {v1:?} with {v2:?}"),
+            },
+            _ => None,
+        }
+    }
+    /// 比较时只要有一边是 F64 ，两边都按 f64 比较，优先于其他所有类型
+    fn as_f64_pair(v1:&Value, v2:&Value) -> Option<(f64,f64)>{
+        match (v1,v2){
+            (Value::F64(_), _) | (_, Value::F64(_)) => match (v1.trans_to_specific_type(&Type::F64), v2.trans_to_specific_type(&Type::F64)){
+                (Value::F64(Some(v1)), Value::F64(Some(v2))) => Some((v1,v2)),
+                _ => panic!("can't compare {v1:?} with {v2:?}"),
+            },
+            _ => None,
+        }
+    }
+    /// 比较时只要有一边是 I64 (且另一边不是 F32)，两边都按 i64 比较，优先于 u32
+    fn as_i64_pair(v1:&Value, v2:&Value) -> Option<(i64,i64)>{
+        match (v1,v2){
+            (Value::I64(_), Value::F32(_)) | (Value::F32(_), Value::I64(_)) => None,
+            (Value::I64(_), _) | (_, Value::I64(_)) => match (v1.trans_to_specific_type(&Type::I64), v2.trans_to_specific_type(&Type::I64)){
+                (Value::I64(Some(v1)), Value::I64(Some(v2))) => Some((v1,v2)),
+                _ => panic!("can't compare {v1:?} with {v2:?}"),
+            },
+            _ => None,
+        }
+    }
     pub fn new_i32(value:i32) -> Self { Value::I32(Some(value)) }
+    pub fn new_u32(value:u32) -> Self { Value::U32(Some(value)) }
+    pub fn new_i8(value:i8) -> Self { Value::I8(Some(value)) }
+    pub fn new_i64(value:i64) -> Self { Value::I64(Some(value)) }
     pub fn new_f32(value:f32) -> Self { Value::F32(Some(value)) }
+    pub fn new_f64(value:f64) -> Self { Value::F64(Some(value)) }
     pub fn new_unsure_from_specific_type(specified_ty:&Type) -> Self {
         match specified_ty{
             Type::I32 => Value::I32(None),
+            Type::U32 => Value::U32(None),
+            Type::I8 => Value::I8(None),
+            Type::I64 => Value::I64(None),
             Type::F32 => Value::F32(None),
+            Type::F64 => Value::F64(None),
             Type::I1 => Value::I1(None),
             Type::Void => Value::Void,
             Type::Label => todo!(),
@@ -335,6 +551,10 @@ impl Value {
             Type::Ref => {
                 Value::Unknown
             },
+            Type::Struct { .. } | Type::Union { .. } => {
+                // struct 的值只会在内存中存在，simulator 不追踪其内容
+                Value::Unknown
+            },
         }
     }
     pub fn new_ptr64_from_array_with_offset(rc_array_symidx:RcSymIdx,pointed_ty:Type,offset:Value) -> Self{
@@ -343,10 +563,27 @@ impl Value {
     pub fn new_ptr64_to_variable(rc_pointed_symidx:RcSymIdx,pointed_ty:Type) -> Self{
         Value::Ptr64 { pointed_ty:Box::new(pointed_ty), op_pointed_symidx: Some(rc_pointed_symidx), offset:Box::new(Value::I32(None)) }
     }
+    /// 值确定并且内存中的每一位都是 0 ，这样的元素可以用 .zero 输出，-0.0 不算
+    pub fn is_zero_bits(&self) -> bool{
+        match self{
+            Value::I32(op) => *op == Some(0),
+            Value::U32(op) => *op == Some(0),
+            Value::I8(op) => *op == Some(0),
+            Value::I64(op) => *op == Some(0),
+            Value::F32(op) => op.map(|v| v.to_bits()) == Some(0),
+            Value::F64(op) => op.map(|v| v.to_bits()) == Some(0),
+            Value::I1(op) => *op == Some(false),
+            _ => false,
+        }
+    }
     pub fn is_unsure(&self)->Result<bool>{
         match self{
             Value::I32(op) => Ok(op.is_none()),
+            Value::U32(op) => Ok(op.is_none()),
+            Value::I8(op) => Ok(op.is_none()),
+            Value::I64(op) => Ok(op.is_none()),
             Value::F32(op) => Ok(op.is_none()),
+            Value::F64(op) => Ok(op.is_none()),
             Value::I1(op) => Ok(op.is_none()),
             _ => panic!("无法确认 value:{:?} 是否 unsure ",self),
         }
@@ -360,17 +597,56 @@ impl Value {
         match (&self,&ty) {
             (Value::I32(Some(v)), Type::I32) => Value::new_i32(*v as i32),
             (Value::I32(Some(v)), Type::F32) => Value::new_f32(*v as f32),
-            (Value::I32(Some(v)), Type::I1) => todo!(),
+            (Value::I32(Some(v)), Type::I1) => Value::new_i1(*v != 0),
             (Value::I32(Some(v)), Type::Void) => todo!(),
             (Value::I32(Some(v)), Type::Label) => todo!(),
             (Value::F32(Some(v)), Type::I32) => Value::new_i32(*v as i32),
             (Value::F32(Some(v)), Type::F32) => Value::new_f32(*v as f32),
-            (Value::F32(Some(v)), Type::I1) => todo!(),
+            (Value::F32(Some(v)), Type::I1) => Value::new_i1(*v != 0.0),
             (Value::F32(Some(v)), Type::Void) => todo!(),
             (Value::F32(Some(v)), Type::Label) => todo!(),
             (Value::I1(Some(v)), Type::I32) => Value::new_i32(Into::into(*v)),
             (Value::I1(Some(v)), Type::F32) => Value::new_f32((*v).into()),
             (Value::I1(Some(v)), Type::I1) => Value::new_i1((*v).into()),
+            // I32 与 U32 之间的转换只是重新解释比特位
+            (Value::I32(Some(v)), Type::U32) => Value::new_u32(*v as u32),
+            (Value::U32(Some(v)), Type::U32) => Value::new_u32(*v),
+            (Value::U32(Some(v)), Type::I32) => Value::new_i32(*v as i32),
+            (Value::U32(Some(v)), Type::F32) => Value::new_f32(*v as f32),
+            (Value::F32(Some(v)), Type::U32) => Value::new_u32(*v as u32),
+            (Value::I1(Some(v)), Type::U32) => Value::new_u32((*v).into()),
+            // I32 符号扩展，U32 零扩展，I64 转 I32/U32 时截断
+            (Value::I32(Some(v)), Type::I64) => Value::new_i64(*v as i64),
+            (Value::U32(Some(v)), Type::I64) => Value::new_i64(*v as i64),
+            (Value::I1(Some(v)), Type::I64) => Value::new_i64((*v).into()),
+            (Value::F32(Some(v)), Type::I64) => Value::new_i64(*v as i64),
+            (Value::I64(Some(v)), Type::I64) => Value::new_i64(*v),
+            (Value::I64(Some(v)), Type::I32) => Value::new_i32(*v as i32),
+            (Value::I64(Some(v)), Type::U32) => Value::new_u32(*v as u32),
+            (Value::I64(Some(v)), Type::F32) => Value::new_f32(*v as f32),
+            // F32 提升为 F64 是精确的，F64 转 F32 时按 IEEE 舍入
+            (Value::I32(Some(v)), Type::F64) => Value::new_f64(*v as f64),
+            (Value::U32(Some(v)), Type::F64) => Value::new_f64(*v as f64),
+            (Value::I64(Some(v)), Type::F64) => Value::new_f64(*v as f64),
+            (Value::I1(Some(v)), Type::F64) => Value::new_f64((*v as i32).into()),
+            (Value::F32(Some(v)), Type::F64) => Value::new_f64(*v as f64),
+            (Value::F64(Some(v)), Type::F64) => Value::new_f64(*v),
+            (Value::F64(Some(v)), Type::F32) => Value::new_f32(*v as f32),
+            (Value::F64(Some(v)), Type::I32) => Value::new_i32(*v as i32),
+            (Value::F64(Some(v)), Type::U32) => Value::new_u32(*v as u32),
+            (Value::F64(Some(v)), Type::I64) => Value::new_i64(*v as i64),
+            // char 参与运算前先符号扩展，转为 char 时截断到低 8 位
+            (Value::I8(Some(v)), Type::I8) => Value::new_i8(*v),
+            (Value::I8(Some(v)), Type::I32) => Value::new_i32(*v as i32),
+            (Value::I8(Some(v)), Type::U32) => Value::new_u32(*v as u32),
+            (Value::I8(Some(v)), Type::I64) => Value::new_i64(*v as i64),
+            (Value::I8(Some(v)), Type::F32) => Value::new_f32(*v as f32),
+            (Value::I8(Some(v)), Type::F64) => Value::new_f64(*v as f64),
+            (Value::I8(Some(v)), Type::I1) => Value::new_i1(*v != 0),
+            (Value::U32(Some(v)), Type::I1) => Value::new_i1(*v != 0),
+            (Value::I64(Some(v)), Type::I1) => Value::new_i1(*v != 0),
+            (Value::F64(Some(v)), Type::I1) => Value::new_i1(*v != 0.0),
+            (Value::I32(Some(_)) | Value::U32(Some(_)) | Value::I64(Some(_)) | Value::F32(Some(_)) | Value::F64(Some(_)) | Value::I1(Some(_)), Type::I8) => self.force_to_ty(&Type::I8),
             (Value::Void, t) => panic!("void 类型不能转化为 {:?} 类型",t),
             (Value::Fn { arg_syms: _, rc_ret_sym: _ }, _t) => todo!(),
             _ => panic!("不能将 {:?} 转化为 {:?}",self,ty ),
@@ -404,7 +680,35 @@ impl Value {
                 }
             ),
 
+            Type::I8 => Value::from_string_with_specific_type(s, &Type::I32).force_to_ty(&Type::I8),
+            Type::U32 => {
+                let s = s.trim_end_matches(['u','U']);
+                Value::new_u32(if s.starts_with("0x") || s.starts_with("0X"){
+                    u32::from_str_radix(&s[2..], 16).expect("err trans radix 16")
+                }else if s.starts_with("0") && s.len()>1{
+                    u32::from_str_radix(&s[1..], 8).expect("err trans radix 8")
+                }else {
+                    s.parse().with_context(||format!("when parsing {} to u32",s)).unwrap()
+                })
+            },
+            Type::I64 => {
+                let s = s.trim_end_matches(['l','L']);
+                let (is_neg, s) = match s.strip_prefix('-'){ Some(s) => (true, s), None => (false, s) };
+                // 十六进制和八进制按 u64 解析后重新解释比特位
+                let v = if s.starts_with("0x") || s.starts_with("0X"){
+                    u64::from_str_radix(&s[2..], 16).expect("err trans radix 16") as i64
+                }else if s.starts_with("0") && s.len()>1{
+                    u64::from_str_radix(&s[1..], 8).expect("err trans radix 8") as i64
+                }else {
+                    s.parse::<u64>().with_context(||format!("when parsing {} to i64",s)).unwrap() as i64
+                };
+                Value::new_i64(if is_neg { v.wrapping_neg() } else { v })
+            },
             Type::F32 => Value::new_f32(s.parse().with_context(||format!("when parsing {} to f32",s)).unwrap_or_else(|_ |Value::parse_hex_float(s))),
+            Type::F64 => {
+                let s = s.trim_end_matches(['d','D']);
+                Value::new_f64(s.parse().with_context(||format!("when parsing {} to f64",s)).unwrap_or_else(|_ |Value::parse_hex_float(s) as f64))
+            },
             Type::I1 => Value::new_i1(s.parse().with_context(||format!("when parsing {} to i1",s)).expect("")),
             Type::Void => panic!("不能从string 转化为 Void 类型的value"),
             Type::Label => panic!("不能从string 转化为 Label 类型的value"),
@@ -413,6 +717,7 @@ impl Value {
             Type::Array { dims, ele_ty} => Value::new_array(ArrayEleMap::new(), unwrap_vec(dims), *ele_ty.clone()),
             Type::Ptr64 { ty: _ } => panic!("不能从string 转化为 Ptr64 类型的value"),
             Type::Ref => panic!("不能从string 转化为 Ref 类型的value"),
+            Type::Struct { .. } | Type::Union { .. } => panic!("不能从string 转化为 {:?} 类型的value",ty),
             Type::Unknown => panic!("不能从string 转化为 Unknown 类型的value"),
         }
     }
@@ -470,14 +775,41 @@ impl Value {
             Value::I32(Some(num)) => {
                 *num
             },
+            Value::U32(Some(num)) => {
+                *num as i32
+            },
+            Value::I8(Some(num)) => {
+                *num as i32
+            },
+            Value::I64(Some(num)) => {
+                *num as i32
+            },
             _ => {panic!("{:?} can't cast to i32",self)}
         }
     }
+    pub fn as_i64(&self) -> i64{
+        match self{
+            Value::I32(Some(num)) => *num as i64,
+            Value::U32(Some(num)) => *num as i64,
+            Value::I8(Some(num)) => *num as i64,
+            Value::I64(Some(num)) => *num,
+            _ => {panic!("{:?} can't cast to i64",self)}
+        }
+    }
     pub fn as_usize(&self) -> usize{
         match &self{
             Value::I32(Some(num)) => {
                 *num as usize
             },
+            Value::U32(Some(num)) => {
+                *num as usize
+            },
+            Value::I8(Some(num)) => {
+                *num as usize
+            },
+            Value::I64(Some(num)) => {
+                *num as usize
+            },
             _ => {panic!("{:?} can't cast to u32",self)}
         }
     }
@@ -507,7 +839,11 @@ impl Value {
     pub fn to_type(&self)->Type{
         match self{
             Value::I32(_) => Type::I32,
+            Value::U32(_) => Type::U32,
+            Value::I8(_) => Type::I8,
+            Value::I64(_) => Type::I64,
             Value::F32(_) => Type::F32,
+            Value::F64(_) => Type::F64,
             Value::I1(_) => Type::I1,
             Value::Void => Type::Void,
             Value::Fn { arg_syms, rc_ret_sym: ret_sym } => Type::Fn { arg_syms: arg_syms.clone(), ret_sym: ret_sym.clone() },
@@ -537,6 +873,28 @@ impl Value {
                     panic!("i32 {:?} unsure 无法转化为 symidx",self)
                 }
             },
+            Value::I8(op_i8) => {
+                if let Some(i8_value) = op_i8{
+                    // char 字面量与 int 字面量共用同一种写法
+                    SymIdx::new(ST_ROOT,i8_value.to_string().leak())
+                }else{
+                    panic!("i8 {:?} unsure 无法转化为 symidx",self)
+                }
+            },
+            Value::U32(op_u32) => {
+                if let Some(u32_value) = op_u32{
+                    SymIdx::new(ST_ROOT,format!("{}u",u32_value).leak())
+                }else{
+                    panic!("u32 {:?} unsure 无法转化为 symidx",self)
+                }
+            },
+            Value::I64(op_i64) => {
+                if let Some(i64_value) = op_i64{
+                    SymIdx::new(ST_ROOT,format!("{}l",i64_value).leak())
+                }else{
+                    panic!("i64 {:?} unsure 无法转化为 symidx",self)
+                }
+            },
             Value::F32(op_f32) => {
                 if let Some(f32_value) = op_f32{
                     let mut f32_str = f32_value.to_string();
@@ -549,6 +907,13 @@ impl Value {
                 }
 
             },
+            Value::F64(op_f64) => {
+                if let Some(f64_value) = op_f64{
+                    SymIdx::new(ST_ROOT,Value::f64_to_literal_str(*f64_value).leak())
+                }else{
+                    panic!("f64 {:?} unsure 无法转化为 symidx",self)
+                }
+            },
             Value::Ref { rc_symidx, ty } => {
                 rc_symidx.as_ref_borrow().clone()
             },
@@ -578,6 +943,15 @@ impl Value {
                     None
                 }
             },
+            Value::I8(op_i8) => {
+                op_i8.map(|i8_value| SymIdx::new(ST_ROOT,i8_value.to_string().leak()))
+            },
+            Value::U32(op_u32) => {
+                op_u32.map(|u32_value| SymIdx::new(ST_ROOT,format!("{}u",u32_value).leak()))
+            },
+            Value::I64(op_i64) => {
+                op_i64.map(|i64_value| SymIdx::new(ST_ROOT,format!("{}l",i64_value).leak()))
+            },
             Value::F32(op_f32) => {
                 if let Some(f32_value) = op_f32{
                     let mut f32_str = f32_value.to_string();
@@ -590,6 +964,9 @@ impl Value {
                 }
 
             },
+            Value::F64(op_f64) => {
+                op_f64.map(|f64_value| SymIdx::new(ST_ROOT,Value::f64_to_literal_str(f64_value).leak()))
+            },
             Value::Ref { rc_symidx, ty } => {
                 Some(rc_symidx.as_ref_borrow().clone())
             },
@@ -607,13 +984,29 @@ impl Value {
                     panic!("f1 {:?} unsure 无法转化为 symidx",self)
                 }
             }
+            // 没有初始化的全局指针（包括函数指针）为空指针
+            Value::Ptr64 { pointed_ty:_, op_pointed_symidx:None, offset:_ } => None,
             _ => panic!("{:?}无法转化为 symidx",self)
         }
     }
+    /// f64 字面量以 d 结尾，与源码中的 f32 字面量区分开
+    fn f64_to_literal_str(f64_value:f64) -> String{
+        let mut f64_str = f64_value.to_string();
+        if !f64_str.contains("."){
+            f64_str.push('.');
+        }
+        f64_str.push('d');
+        f64_str
+    }
     pub fn index_array(&self,offset:usize) -> Result<Value> {
         match self{
-            Value::Array { value_map, dims: _, ele_ty: _ } => {
-                value_map.get_ele_at(offset).cloned()
+            Value::Array { value_map, dims: _, ele_ty } => {
+                // 部分初始化的数组中没有给出初值的元素为 0
+                if value_map.contains_offset(offset) || offset >= self.get_mem_len() / self.get_ele_size() {
+                    value_map.get_ele_at(offset).cloned()
+                }else {
+                    Ok(Value::from_string_with_specific_type("0", ele_ty))
+                }
             },
             _ => {
                 panic!("index_array 无法对 非数组类型 使用 {:?}",&self)
@@ -630,34 +1023,147 @@ impl Value {
 }
 impl Type {
     /// 这个函数接受一个ast_node 和 ast_tree 通过识别 ast_node 来完成基本类型的识别  
-    /// 但是无法识别数组类型(只能识别数组的元素类型)
-    pub fn new(ast_node:u32, ast_tree:&AstTree) -> Self {
+    /// 但是无法识别数组类型(只能识别数组的元素类型)，typedef 名则交给 typedef_of 按作用域查找
+    pub fn new(ast_node:u32, ast_tree:&AstTree, typedef_of:TypedefResolver) -> Self {
+        if !node!(at ast_node in ast_tree).is_terminal && node!(at ast_node in ast_tree).rule_id == RULE_structOrUnionSpecifier{
+            return Type::new_struct_or_union(ast_node, ast_tree, &mut vec![], typedef_of);
+        }
+        if !node!(at ast_node in ast_tree).is_terminal && node!(at ast_node in ast_tree).rule_id == RULE_typedefName{
+            let ident_node = find!(term Identifier at ast_node in ast_tree).unwrap();
+            let name = node!(at ident_node in ast_tree).op_text.as_ref().unwrap().as_str();
+            return typedef_of(name).unwrap_or_else(|| panic!("{} 不是一个类型名",name)).complete_typedef(&[], typedef_of);
+        }
+        // enum 类型的变量当作 int 处理
+        if !node!(at ast_node in ast_tree).is_terminal && node!(at ast_node in ast_tree).rule_id == RULE_enumSpecifier{
+            return Type::I32;
+        }
         // 在asttree中找到node的u32所在节点的类型,返回I32或F32
         let text = node!(at ast_node in ast_tree).op_text.as_ref().unwrap().as_str();
         match text {
             "int" => Type::I32,
+            "signed" => Type::I32,
+            "unsigned" => Type::U32,
+            "long" => Type::I64,
+            "char" => Type::I8,
             "float" => Type::F32,
             "bool" => Type::I1,
-            "double" => Type::F32,
+            "double" => Type::F64,
             "void" => Type::Void,
             _ => panic!("text中类型错误 找到不支持的类型 {}", text),
         }
     }
+    /// 识别 structOrUnionSpecifier ，如果只有 tag 而没有成员列表，则通过 typedef_of 从当前作用域向外查找同 tag 的定义  
+    /// 找不到定义时返回尚未补全的类型，例如指向之后才定义的 struct 的指针  
+    /// defining_tags 记录正在展开的 tag ，用来避免 struct 内指向自身的指针导致的无限递归
+    pub fn new_struct_or_union(struct_spec_node:u32, ast_tree:&AstTree, defining_tags:&mut Vec<&'static str>, typedef_of:TypedefResolver) -> Self {
+        let (is_union, tag) = get_struct_or_union_tag(struct_spec_node, ast_tree);
+        let decl_list_node = match find!(rule RULE_structDeclarationList at struct_spec_node in ast_tree){
+            Some(decl_list_node) => decl_list_node,
+            None => {
+                let incomplete_ty = if is_union { Type::Union { tag, members: vec![] } } else { Type::Struct { tag, members: vec![] } };
+                if defining_tags.contains(&tag){
+                    // 尚未补全的类型，只能出现在指针后面
+                    return incomplete_ty;
+                }
+                return typedef_of(&tag_symbol_name(is_union, tag)).unwrap_or(incomplete_ty);
+            }
+        };
+        defining_tags.push(tag);
+        let mut members = vec![];
+        for struct_decl_node in find_nodes!(rule RULE_structDeclaration at decl_list_node in ast_tree){
+            let spec_qual_list_node = find!(rule RULE_specifierQualifierList at struct_decl_node in ast_tree).unwrap_or_else(|| panic!("struct {} 中不支持 static_assert",tag));
+            let mut type_spec_nodes = vec![];
+            collect_type_specifiers_of_spec_qual_list(spec_qual_list_node, ast_tree, &mut type_spec_nodes);
+            match find!(rule RULE_structDeclaratorList at struct_decl_node in ast_tree){
+                Some(struct_declarator_list_node) => {
+                    let member_base_ty = Type::new_from_type_specifier(type_spec_nodes[0], ast_tree, defining_tags, typedef_of);
+                    for struct_declarator_node in find_nodes!(rule RULE_structDeclarator at struct_declarator_list_node in ast_tree){
+                        let declarator_node = find!(rule RULE_declarator at struct_declarator_node in ast_tree).unwrap_or_else(|| panic!("struct {} 中不支持匿名位域",tag));
+                        members.push(Type::new_from_declarator(member_base_ty.clone(), declarator_node, ast_tree));
+                    }
+                },
+                None => {
+                    // 形如 int x; 的成员可能被解析成 typeSpecifier(int) typeSpecifier(typedefName x)
+                    // unsigned int x; 则会多出一个 typeSpecifier ，成员名总是最后一个
+                    if type_spec_nodes.len() < 2 {
+                        panic!("无法识别 struct {} 中的成员声明 at ast_node {}",tag,struct_decl_node)
+                    }
+                    let member_base_ty = Type::new_from_type_specifier(type_spec_nodes[0], ast_tree, defining_tags, typedef_of);
+                    let typedef_spec_node = *type_spec_nodes.last().unwrap();
+                    let member_ident_node = find!(rule RULE_typedefName finally term Identifier at typedef_spec_node in ast_tree).unwrap();
+                    members.push((node!(at member_ident_node in ast_tree).op_text.clone().unwrap().leak(), member_base_ty));
+                }
+            }
+        }
+        defining_tags.pop();
+        if is_union { Type::Union { tag, members } } else { Type::Struct { tag, members } }
+    }
+    /// 接受 typeSpecifier 节点，识别基本类型， struct/union 类型或 typedef 名
+    pub fn new_from_type_specifier(type_spec_node:u32, ast_tree:&AstTree, defining_tags:&mut Vec<&'static str>, typedef_of:TypedefResolver) -> Self {
+        let type_ast_node = node!(at type_spec_node in ast_tree).child_vec[0];
+        if !node!(at type_ast_node in ast_tree).is_terminal && node!(at type_ast_node in ast_tree).rule_id == RULE_structOrUnionSpecifier{
+            Type::new_struct_or_union(type_ast_node, ast_tree, defining_tags, typedef_of)
+        }else if !node!(at type_ast_node in ast_tree).is_terminal && node!(at type_ast_node in ast_tree).rule_id == RULE_typedefName{
+            let ident_node = find!(term Identifier at type_ast_node in ast_tree).unwrap();
+            let name = node!(at ident_node in ast_tree).op_text.as_ref().unwrap().as_str();
+            typedef_of(name).unwrap_or_else(|| panic!("{} 不是一个类型名",name)).complete_typedef(defining_tags, typedef_of)
+        }else {
+            Type::new(type_ast_node, ast_tree, typedef_of)
+        }
+    }
+    /// 只有 tag 而没有成员列表的 structOrUnionSpecifier 返回尚未补全的类型，否则返回 None  
+    /// typedef 中保留尚未补全的类型，这样 typedef struct Node Node; 可以写在 struct Node 的定义之前
+    pub fn new_incomplete_struct_or_union(struct_spec_node:u32, ast_tree:&AstTree) -> Option<Self> {
+        if find!(rule RULE_structDeclarationList at struct_spec_node in ast_tree).is_some(){
+            return None
+        }
+        let (is_union, tag) = get_struct_or_union_tag(struct_spec_node, ast_tree);
+        Some(if is_union { Type::Union { tag, members: vec![] } } else { Type::Struct { tag, members: vec![] } })
+    }
+    /// typedef 所代表的类型如果是尚未补全的 struct/union ，并且不是正在展开的 tag ，就按作用域找到其定义并补全
+    fn complete_typedef(self, defining_tags:&[&'static str], typedef_of:TypedefResolver) -> Self {
+        match &self{
+            Type::Struct { tag, members } | Type::Union { tag, members } if members.is_empty() && !defining_tags.contains(tag) => {
+                typedef_of(&tag_symbol_name(self.is_union(), tag)).unwrap_or(self)
+            },
+            _ => self
+        }
+    }
+    /// 根据 declarator 中的 pointer 和数组维度在 base_ty 上构建完整的类型，同时返回 declarator 中的标识符  
+    /// 数组维度只支持整数字面量
+    pub fn new_from_declarator(base_ty:Type, declarator_node:u32, ast_tree:&AstTree) -> (&'static str, Self) {
+        let mut ty = base_ty;
+        for _ in 0..get_ptr_depth_of_declarator(ast_tree, declarator_node){
+            ty = Type::Ptr64 { ty: Box::new(ty) };
+        }
+        let mut direct_decl_node = find!(rule RULE_directDeclarator at declarator_node in ast_tree).unwrap();
+        let mut dims = vec![];
+        while let Some(sub_direct_decl_node) = find!(rule RULE_directDeclarator at direct_decl_node in ast_tree){
+            let assign_expr_node = find!(rule RULE_assignmentExpression at direct_decl_node in ast_tree).unwrap_or_else(|| panic!("declarator 中的数组维度必须给出 at ast_node {}",direct_decl_node));
+            let dim_text = find_single_constant_text(ast_tree, assign_expr_node).unwrap_or_else(|| panic!("declarator 中的数组维度只支持整数字面量 at ast_node {}",assign_expr_node));
+            let dim_name = canonical_literal_name(&dim_text, false).unwrap_or_else(|e| panic!("{} at ast_node {}",e,assign_expr_node));
+            dims.insert(0, SymIdx::from_str(dim_name).as_rc());
+            direct_decl_node = sub_direct_decl_node;
+        }
+        let ident_node = find!(term Identifier at direct_decl_node in ast_tree).unwrap();
+        let name:&'static str = node!(at ident_node in ast_tree).op_text.clone().unwrap().leak();
+        if dims.is_empty(){
+            (name, ty)
+        }else {
+            (name, Type::new_array_dims_known(ty, dims))
+        }
+    }
     /// 这个函数接受一个元素类型和各个维度的大小来构建一个数组类型
-    /// 但是禁止创建数组的数组
+    /// 但是禁止创建数组的数组，元素类型本身是数组时(例如 typedef 出来的数组类型)会把维度展开
     pub fn new_array_dims_known(ele_ty:Type,dims:Vec<RcSymIdx>)->Self{
-        match &ele_ty{
-            Type::Fn { arg_syms: _, ret_sym: _ } => panic!("无法新建函数类型的数组"),
-            _=>{}
-        }
-        Type::Array { dims:dims.into_iter().map(|x| Some(x)).collect_vec(), ele_ty: Box::new(ele_ty) }
+        Type::new_array_dims_may_unknown(ele_ty, dims.into_iter().map(|x| Some(x)).collect_vec())
     }
     pub fn new_array_dims_may_unknown(ele_ty:Type,dims:Vec<Option<RcSymIdx>>)->Self{
-        match &ele_ty{
+        match ele_ty{
             Type::Fn { arg_syms: _, ret_sym: _ } => panic!("无法新建函数类型的数组"),
-            _=>{}
+            Type::Array { dims:ele_dims, ele_ty } => Type::Array { dims:dims.into_iter().chain(ele_dims).collect_vec(), ele_ty },
+            _ => Type::Array { dims, ele_ty: Box::new(ele_ty) }
         }
-        Type::Array { dims, ele_ty: Box::new(ele_ty) }
     }
     pub fn new_array_dims_may_unknown_with_dims_2_pow(ele_ty:Type,mut dims:Vec<Option<RcSymIdx>>)->Result<Self>{
         match &ele_ty{
@@ -707,13 +1213,18 @@ impl Type {
     pub fn get_align(&self)->usize{
         match &self{
             Type::I32 => 4,
+            Type::U32 => 4,
+            Type::I8 => 1,
+            Type::I64 => 8,
             Type::F32 => 4,
+            Type::F64 => 8,
             Type::I1 => 1,
             Type::Void => panic!("can't get alignment of void type {:?}",self),
             Type::Label => panic!("can't get alignment of label type {:?}",self),
             Type::Array { dims: _, ele_ty: ty } => ty.get_align(),
             Type::Fn { arg_syms: _, ret_sym: _ } =>panic!("can't get alignment of func type {:?}",self),
             Type::Ptr64 { ty: _ } => 8,
+            Type::Struct { .. } | Type::Union { .. } => self.get_aggregate_layout().align,
             Type::Ref => panic!("can't get align of Ref ty"),
             Type::Unknown => panic!("can't get align of unknown"),
         }
@@ -725,22 +1236,30 @@ impl Type {
             Type::Array { dims: _, ele_ty } => {
                 ele_ty.get_mem_len()
             },
-            Type::Ptr64 { ty } => {
+            Type::Ptr64 { ty } if ty.is_array() => {
                 ty.get_ele_size()
             }
+            Type::Ptr64 { ty } => {
+                ty.get_mem_len()
+            }
             _ => self.get_mem_len(),
         }
     }
     pub fn get_size(&self) -> usize{
         match &self{
             Type::I32 => 4,
+            Type::U32 => 4,
+            Type::I8 => 1,
+            Type::I64 => 8,
             Type::F32 => 4,
+            Type::F64 => 8,
             Type::I1 => 1,
             Type::Void => panic!("can't get alignment of void type {:?}",self),
             Type::Label => panic!("can't get alignment of label type {:?}",self),
             Type::Array { dims: _, ele_ty: ty } => ty.get_align(),
             Type::Fn { arg_syms: _, ret_sym: _ } =>panic!("can't get alignment of func type {:?}",self),
             Type::Ptr64 { ty: _ } => 8,
+            Type::Struct { .. } | Type::Union { .. } => self.get_aggregate_layout().size,
             Type::Ref => panic!("can't get align of Ref ty"),
             Type::Unknown => panic!("can't get align of unknown"),
         }
@@ -757,14 +1276,77 @@ impl Type {
 
     }
 
+    /// array struct 与 union 都只存在于内存中，不能被放到寄存器里
+    pub fn is_aggregate(&self) -> bool{
+        self.is_array() || self.is_struct() || self.is_union()
+    }
+    /// 函数指针，通过它进行的调用是间接调用
+    pub fn is_fn_ptr(&self) -> bool{
+        matches!(self, Type::Ptr64 { ty } if ty.is_fn())
+    }
+    pub fn get_aggregate_layout(&self) -> AggregateLayout{
+        match self{
+            Type::Struct { tag, members } => {
+                if members.is_empty(){ panic!("struct {} 是不完整的类型，无法计算布局",tag) }
+                AggregateLayout::new_struct(members.iter().map(|(_,ty)| ty))
+            },
+            Type::Union { tag, members } => {
+                if members.is_empty(){ panic!("union {} 是不完整的类型，无法计算布局",tag) }
+                AggregateLayout::new_union(members.iter().map(|(_,ty)| ty))
+            },
+            _ => panic!("{:?} 不是 struct 或 union",self)
+        }
+    }
+    /// 对于尚未补全的 struct/union (例如 struct 内指向自身的指针所指的类型) ，通过 typedef_of 按作用域找到其定义
+    pub fn to_complete_type(&self, typedef_of:TypedefResolver) -> Type{
+        match self{
+            Type::Struct { tag, members } | Type::Union { tag, members } if members.is_empty() => {
+                typedef_of(&tag_symbol_name(self.is_union(), tag)).unwrap_or_else(|| panic!("找不到 {:?} 的定义",self))
+            },
+            _ => self.clone()
+        }
+    }
+    /// 返回成员相对于 struct/union 起始地址的偏移以及成员的类型
+    pub fn get_member(&self, member_name:&str) -> (usize, Type){
+        let members = match self{
+            Type::Struct { tag: _, members } | Type::Union { tag: _, members } => members,
+            _ => panic!("{:?} 不是 struct 或 union ，无法访问成员 {}",self,member_name)
+        };
+        let layout = self.get_aggregate_layout();
+        match members.iter().position(|(name,_)| *name == member_name){
+            Some(idx) => (layout.offsets[idx], members[idx].1.clone()),
+            None => panic!("{:?} 中没有成员 {}",self,member_name)
+        }
+    }
+    /// 把 struct/union/数组展开为其中的标量，返回每个标量相对于起始地址的偏移以及标量的类型，用于逐个成员复制  
+    /// union 只展开占用空间最大的成员，所有成员都从 0 开始，所以它覆盖了其他成员的所有字节
+    pub fn get_scalar_leaves(&self) -> Vec<(usize, Type)>{
+        match self{
+            Type::Struct { tag: _, members } => {
+                let layout = self.get_aggregate_layout();
+                members.iter().zip(layout.offsets).flat_map(|((_,ty),offset)| ty.get_scalar_leaves().into_iter().map(move |(leaf_offset,leaf_ty)| (offset + leaf_offset, leaf_ty))).collect()
+            },
+            Type::Union { tag, members } => {
+                let (_,widest_ty) = members.iter().rev().max_by_key(|(_,ty)| ty.get_mem_len()).unwrap_or_else(|| panic!("union {} 是不完整的类型，无法展开",tag));
+                widest_ty.get_scalar_leaves()
+            },
+            Type::Array { dims: _, ele_ty } => {
+                let ele_len = ele_ty.get_mem_len();
+                (0..self.get_mem_len() / ele_len).flat_map(|idx| ele_ty.get_scalar_leaves().into_iter().map(move |(leaf_offset,leaf_ty)| (idx * ele_len + leaf_offset, leaf_ty))).collect()
+            },
+            _ => vec![(0, self.clone())],
+        }
+    }
+
     pub fn push_dim(&mut self,dim_symidx:RcSymIdx){
         match self{
             Type::Fn { arg_syms: _, ret_sym: _ } => panic!("无法新建函数类型的数组"),
             Type::Void => panic!("无法新建void类型的数组"),
             Type::Label => panic!("无法新建label类型的数组"),
-            Type::I32 => *self = Type::new_array_dims_known(self.clone(), vec![dim_symidx]),
-            Type::F32 => *self =Type::new_array_dims_known(self.clone(), vec![dim_symidx]),
+            Type::I32 | Type::U32 | Type::I8 | Type::I64 => *self = Type::new_array_dims_known(self.clone(), vec![dim_symidx]),
+            Type::F32 | Type::F64 => *self =Type::new_array_dims_known(self.clone(), vec![dim_symidx]),
             Type::I1 => *self =Type::new_array_dims_known(self.clone(), vec![dim_symidx]),
+            Type::Struct { .. } | Type::Union { .. } => *self =Type::new_array_dims_known(self.clone(), vec![dim_symidx]),
             Type::Array { dims, ele_ty: _ty } => {
                 dims.push(Some(dim_symidx));
             },
@@ -806,16 +1388,7 @@ impl Type {
                             dims.insert(0, None);
                             dims
                         },
-                        Type::Ptr64{ ty } => {
-                            let arr = self.ptr2arr();
-                            match arr{
-                                Type::Array { mut dims, ele_ty } => {
-                                    dims.insert(0, None);
-                                    dims
-                                },
-                                _ => {panic!()}
-                            }
-                        }
+                        // 指向指针的指针，其元素就是指针本身
                         _=>{
                             vec![None]
                         }
@@ -873,6 +1446,8 @@ impl Type {
                 debug_info_blue!("weight vec calcuated is {:?}",weighted_dims);
                 weighted_dims
             },
+            // struct 的 getelementptr 只有一个以字节为单位的偏移
+            Self::Struct { .. } | Self::Union { .. } => vec![Value::new_i32(1).to_symidx()],
             _=> {panic!("get_array_dim_weight_vec 仅能对 array type 使用，无法根据给定type:{:?}给出",self)}
         }
     }
@@ -903,7 +1478,11 @@ impl Type {
     pub fn get_array_dim(&self)->Result<&Vec<Option<RcSymIdx>>>{
         match &self{
             Type::I32 => panic!("can't get dim from i32"),
+            Type::U32 => panic!("can't get dim from u32"),
+            Type::I8 => panic!("can't get dim from i8"),
+            Type::I64 => panic!("can't get dim from i64"),
             Type::F32 => panic!("can't get dim from f32"),
+            Type::F64 => panic!("can't get dim from f64"),
             Type::I1 => panic!("can't get dim from i1"),
             Type::Void => panic!("can't get dim from void"),
             Type::Label => panic!("can't get dim from label"),
@@ -911,6 +1490,7 @@ impl Type {
             Type::Ptr64 { ty } => panic!("can't get dim from ptr64"),
             Type::Array { dims, ele_ty } => Ok(dims),
             Type::Fn { arg_syms, ret_sym } => panic!("can't get dim from fn"),
+            Type::Struct { .. } | Type::Union { .. } => panic!("can't get dim from {:?}",self),
             Type::Unknown => panic!("can't get dim from unknown"),
         }
     }
@@ -924,6 +1504,9 @@ impl Type {
             "f32" => {
                 Ok(Type::F32)
             },
+            "f64" => {
+                Ok(Type::F64)
+            },
             _ => {
                 todo!()
                 // let re = Regex::new(r"^array:(\w+)((?:\[\d+\])+)").unwrap();
@@ -959,13 +1542,18 @@ impl Type {
     pub fn get_mem_len(&self)->usize{
         match &self{
             Type::I32 => 4,
+            Type::U32 => 4,
+            Type::I8 => 1,
+            Type::I64 => 8,
             Type::F32 => 4,
+            Type::F64 => 8,
             Type::I1 => 1,
             Type::Void => todo!(),
             Type::Label => todo!(),
             Type::Array { dims: _, ele_ty: ty } => self.get_ele_len()*self.get_ele_size(),
             Type::Fn { arg_syms: _, ret_sym: _ } => todo!(),
             Type::Ptr64 { ty: _ } => TARGET_POINTER_MEM_LEN,
+            Type::Struct { .. } | Type::Union { .. } => self.get_aggregate_layout().size,
             Type::Ref => panic!("can't get mem_len of Ref"),
             Type::Unknown =>panic!("can't get mem_len of Unknown"),
         }
@@ -981,6 +1569,21 @@ impl Type {
             (Type::I1, Type::I32) => Type::I32,
             (Type::I1, Type::F32) => Type::F32,
             (Type::I1, Type::I1) => Type::I1,
+            // long 能表示 unsigned int 的所有值，因此 I64 与 U32 提升为 I64
+            (Type::I64, Type::I32 | Type::U32 | Type::I64 | Type::I1) => Type::I64,
+            (Type::I32 | Type::U32 | Type::I1, Type::I64) => Type::I64,
+            (Type::I64, Type::F32) => Type::F32,
+            (Type::F32, Type::I64) => Type::F32,
+            (Type::U32, Type::I32 | Type::U32 | Type::I1) => Type::U32,
+            (Type::I32 | Type::I1, Type::U32) => Type::U32,
+            (Type::U32, Type::F32) => Type::F32,
+            (Type::F32, Type::U32) => Type::F32,
+            // 有一边是 double 时结果为 double
+            (Type::F64, Type::I32 | Type::U32 | Type::I64 | Type::F32 | Type::F64 | Type::I1) => Type::F64,
+            (Type::I32 | Type::U32 | Type::I64 | Type::F32 | Type::I1, Type::F64) => Type::F64,
+            // char 先提升为 int
+            (Type::I8, ty2) => Type::arith_adapt(&Type::I32, ty2),
+            (ty1, Type::I8) => Type::arith_adapt(ty1, &Type::I32),
             (Type::Ptr64 { ty:ty1 }, Type::Ptr64 { ty:ty2 }) => Type::arith_adapt(ty1, ty2),
             (Type::Ptr64 { ty:ty1 },  ty2) => Type::arith_adapt(ty1, ty2),
             (ty1, Type::Ptr64 { ty:ty2 }) => Type::arith_adapt(ty1, ty2),
@@ -992,13 +1595,18 @@ impl Type {
     pub fn to_ref_ptr_type(&self) -> Self{
         match self{
             Type::I32 => Type::Ptr64 { ty: Box::new(Type::I32)},
+            Type::U32 => Type::Ptr64 { ty: Box::new(Type::U32)},
+            Type::I8 => Type::Ptr64 { ty: Box::new(Type::I8)},
+            Type::I64 => Type::Ptr64 { ty: Box::new(Type::I64)},
             Type::F32 => Type::Ptr64 { ty: Box::new(Type::F32)},
+            Type::F64 => Type::Ptr64 { ty: Box::new(Type::F64)},
             Type::I1 => Type::Ptr64 { ty: Box::new(Type::I1)},
             Type::Void => Type::Ptr64 { ty: Box::new(Type::Void)},
             Type::Label => todo!(),
             Type::Ptr64 { ty: _ } => Type::Ptr64 { ty: Box::new(self.clone())},
             Type::Array { dims: _, ele_ty: _ty } => {let mut poped_array = self.clone();poped_array.pop_dim();Type::Ptr64 { ty: Box::new(poped_array)}},
             Type::Fn { arg_syms: _, ret_sym: _ } => Type::Ptr64 { ty: Box::new(self.clone())},
+            Type::Struct { .. } | Type::Union { .. } => Type::Ptr64 { ty: Box::new(self.clone())},
             Type::Ref => panic!("ref type can't trans to ptr type"),
             Type::Unknown => todo!(),
         }
@@ -1036,7 +1644,11 @@ impl Debug for Type {
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Type::I32 => write!(f, "i32"),
+            Type::U32 => write!(f, "u32"),
+            Type::I8 => write!(f, "i8"),
+            Type::I64 => write!(f, "i64"),
             Type::F32 => write!(f, "f32"),
+            Type::F64 => write!(f, "f64"),
             Type::I1 => write!(f, "i1"),
             Type::Fn { arg_syms: args_types, ret_sym: ret_type } => {
                 write!(f, "Fn{:?}->{:?}", args_types, ret_type)
@@ -1047,6 +1659,8 @@ impl Debug for Type {
             Type::Array { dims, ele_ty: ty } => write!(f,"Array:{:?}:{:?}",ty,dims),
             Type::Ptr64 { ty } => write!(f,"ptr->{:?}",ty),
             Type::Ref => write!(f,"ref"),
+            Type::Struct { tag, members: _ } => write!(f,"struct {}",tag),
+            Type::Union { tag, members: _ } => write!(f,"union {}",tag),
             Type::Unknown => write!(f,"unknown"),
         }
     }
@@ -1059,8 +1673,11 @@ impl Add for Value{
         let l_val=self.trans_to_specific_type(&pub_ty);
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
-            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1+v2),
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_add(v2)),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1.wrapping_add(v2)),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1.wrapping_add(v2)),
             (Value::F32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32(v1+v2),
+            (Value::F64(Some(v1)), Value::F64(Some(v2))) => Value::new_f64(v1+v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_f32(v1 + (v2 as f32)),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32((v1 as f32) + v2),
             (Value::I1(Some(_v1)), Value::I1(Some(_v2))) => panic!("I1 can't add"),
@@ -1077,8 +1694,11 @@ impl Sub for Value{
         let l_val=self.trans_to_specific_type(&pub_ty);
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
-            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 - v2),
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_sub(v2)),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1.wrapping_sub(v2)),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1.wrapping_sub(v2)),
             (Value::F32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32(v1 - v2),
+            (Value::F64(Some(v1)), Value::F64(Some(v2))) => Value::new_f64(v1 - v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_f32(v1 - (v2 as f32)),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32((v1 as f32 - v2)),
             (Value::I1(Some(_v1)), Value::I1(Some(_v2))) => panic!("I1 can't sub"),
@@ -1095,8 +1715,11 @@ impl Mul for Value{
         let l_val=self.trans_to_specific_type(&pub_ty);
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
-            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 * v2),
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_mul(v2)),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1.wrapping_mul(v2)),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1.wrapping_mul(v2)),
             (Value::F32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32(v1 * v2),
+            (Value::F64(Some(v1)), Value::F64(Some(v2))) => Value::new_f64(v1 * v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_f32(v1 * (v2 as f32)),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32((v1 as f32 * v2)),
             (Value::I1(Some(_v1)), Value::I1(Some(_v2))) => panic!("I1 can't mul"),
@@ -1113,8 +1736,11 @@ impl Div for Value{
         let l_val=self.trans_to_specific_type(&pub_ty);
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
-            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 / v2),
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_div(v2)),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1.wrapping_div(v2)),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1.wrapping_div(v2)),
             (Value::F32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32(v1 / v2),
+            (Value::F64(Some(v1)), Value::F64(Some(v2))) => Value::new_f64(v1 / v2),
             (Value::F32(Some(v1)), Value::I32(Some(v2))) => Value::new_f32(v1 / (v2 as f32)),
             (Value::I32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32((v1 as f32 / v2)),
             (Value::I1(Some(_v1)), Value::I1(Some(_v2))) => panic!("I1 can't div"),
@@ -1131,8 +1757,11 @@ impl Rem for Value{
         let l_val=self.trans_to_specific_type(&pub_ty);
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
-            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 % v2),
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_rem(v2)),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1.wrapping_rem(v2)),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1.wrapping_rem(v2)),
             (Value::F32(Some(v1)), Value::F32(Some(v2))) => Value::new_f32(v1 % v2),
+            (Value::F64(Some(v1)), Value::F64(Some(v2))) => Value::new_f64(v1 % v2),
             (Value::I1(Some(_v1)), Value::I1(Some(_v2))) => panic!("I1 can't Rem"),
             (Value::Void, Value::Void) => panic!("Void can't Rem"),
             (_,_) => panic!("can't rem"),
@@ -1147,6 +1776,8 @@ impl BitAnd for Value{
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 & v2),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1 & v2),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1 & v2),
             (Value::F32(Some(_v1)), Value::F32(Some(_v2))) => panic!("F32 can't bitand"),
             (Value::I1(Some(v1)), Value::I1(Some(v2))) => Value::new_i1(v1 & v2),
             (Value::Void, Value::Void) => panic!("Void can't bitand"),
@@ -1162,6 +1793,8 @@ impl BitOr for Value{
         let r_val=rhs.trans_to_specific_type(&pub_ty);
         match (l_val,r_val) {
             (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 | v2),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1 | v2),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1 | v2),
             (Value::F32(Some(_v1)), Value::F32(Some(_v2))) => panic!("F32 can't bitor"),
             (Value::I1(Some(v1)), Value::I1(Some(v2))) => Value::new_i1(v1 | v2),
             (Value::Void, Value::Void) => panic!("Void can't bitor"),
@@ -1169,6 +1802,55 @@ impl BitOr for Value{
         }
     }
 }
+impl BitXor for Value{
+    type Output = Value;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let pub_ty=self.adapt(&rhs);
+        let l_val=self.trans_to_specific_type(&pub_ty);
+        let r_val=rhs.trans_to_specific_type(&pub_ty);
+        match (l_val,r_val) {
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1 ^ v2),
+            (Value::U32(Some(v1)), Value::U32(Some(v2))) => Value::new_u32(v1 ^ v2),
+            (Value::I64(Some(v1)), Value::I64(Some(v2))) => Value::new_i64(v1 ^ v2),
+            (Value::F32(Some(_v1)), Value::F32(Some(_v2))) => panic!("F32 can't bitxor"),
+            (Value::I1(Some(v1)), Value::I1(Some(v2))) => Value::new_i1(v1 ^ v2),
+            (Value::Void, Value::Void) => panic!("Void can't bitxor"),
+            (_,_) => panic!("can't bitxor"),
+        }
+    }
+}
+/// 移位量和 riscv 的 sllw/sraw 一样只取低 5 位，U32 的右移是逻辑右移(srlw)  
+/// I64 和 sll/sra 一样取低 6 位
+impl Shl for Value{
+    type Output = Value;
+    fn shl(self, rhs: Self) -> Self::Output {
+        if let Value::U32(Some(v1)) = self{
+            return Value::new_u32(v1.wrapping_shl(rhs.as_i32() as u32));
+        }
+        if let Value::I64(Some(v1)) = self{
+            return Value::new_i64(v1.wrapping_shl(rhs.as_i64() as u32));
+        }
+        match (self.trans_to_specific_type(&Type::I32),rhs.trans_to_specific_type(&Type::I32)) {
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_shl(v2 as u32)),
+            (_,_) => panic!("can't shl"),
+        }
+    }
+}
+impl Shr for Value{
+    type Output = Value;
+    fn shr(self, rhs: Self) -> Self::Output {
+        if let Value::U32(Some(v1)) = self{
+            return Value::new_u32(v1.wrapping_shr(rhs.as_i32() as u32));
+        }
+        if let Value::I64(Some(v1)) = self{
+            return Value::new_i64(v1.wrapping_shr(rhs.as_i64() as u32));
+        }
+        match (self.trans_to_specific_type(&Type::I32),rhs.trans_to_specific_type(&Type::I32)) {
+            (Value::I32(Some(v1)), Value::I32(Some(v2))) => Value::new_i32(v1.wrapping_shr(v2 as u32)),
+            (_,_) => panic!("can't shr"),
+        }
+    }
+}
 impl Not for Value{
     type Output = Value;
     fn not(self) -> Self::Output {
@@ -1178,9 +1860,19 @@ impl Not for Value{
             Value::I32(Some(v1)) => Value::new_i32({
                 if *v1 == 0 { 1 }else{ 0 } }
             ),
+            Value::U32(Some(v1)) => Value::new_i32({
+                if *v1 == 0 { 1 }else{ 0 } }
+            ),
+            Value::I64(Some(v1)) => Value::new_i32({
+                if *v1 == 0 { 1 }else{ 0 } }
+            ),
+            Value::I8(Some(v1)) => Value::new_i32({
+                if *v1 == 0 { 1 }else{ 0 } }
+            ),
             Value::F32(Some(v1)) => Value::new_i1(
                 if *v1 == 0.0 { true }else{ false }
             ),
+            Value::F64(Some(v1)) => Value::new_i1(*v1 == 0.0),
             Value::I1(Some(v1)) => Value::new_i1(!v1),
             Value::Void => panic!("Void can't logical not"),
             _ => panic!("can't logical not"),
@@ -1192,8 +1884,12 @@ impl Neg for Value{
     
     fn neg(self) -> Self::Output {
         match &self {
-            Value::I32(Some(v1)) => Value::new_i32(-v1),
+            Value::I32(Some(v1)) => Value::new_i32(v1.wrapping_neg()),
+            Value::U32(Some(v1)) => Value::new_u32(v1.wrapping_neg()),
+            Value::I64(Some(v1)) => Value::new_i64(v1.wrapping_neg()),
+            Value::I8(Some(v1)) => Value::new_i32((*v1 as i32).wrapping_neg()),
             Value::F32(Some(v1)) => Value::new_f32(-v1),
+            Value::F64(Some(v1)) => Value::new_f64(-v1),
             Value::I1(Some(v1)) => panic!("I1 can't neg"),
             Value::Void => panic!("Void 类型无法进行按位非运算"),
             _ => panic!("其他类型无法进行按位非运算"),
@@ -1210,6 +1906,30 @@ impl Debug for UseCounter {
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.use_count) }
 }
 
+/// struct/union 的 tag 作为类型符号登记在符号表中时的名字，带上关键字就不会和同名的变量或 typedef 冲突
+pub fn tag_symbol_name(is_union:bool, tag:&str) -> String{
+    format!("{} {}",if is_union {"union"} else {"struct"},tag)
+}
+/// 返回 structOrUnionSpecifier 是否为 union 以及它的 tag ，匿名的 struct/union 用节点编号生成 tag
+pub fn get_struct_or_union_tag(struct_spec_node:u32, ast_tree:&AstTree) -> (bool, &'static str){
+    let struct_or_union_node = find!(rule RULE_structOrUnion at struct_spec_node in ast_tree).unwrap();
+    let is_union = find!(term Union at struct_or_union_node in ast_tree).is_some();
+    let tag:&'static str = match find!(term Identifier at struct_spec_node in ast_tree){
+        Some(ident_node) => node!(at ident_node in ast_tree).op_text.clone().unwrap().leak(),
+        None => format!("anon{}",struct_spec_node).leak(),
+    };
+    (is_union, tag)
+}
+/// specifierQualifierList 是右递归的，这里把其中所有的 typeSpecifier 按顺序收集起来
+fn collect_type_specifiers_of_spec_qual_list(spec_qual_list_node:u32, ast_tree:&AstTree, type_spec_nodes:&mut Vec<u32>){
+    if let Some(type_spec_node) = find!(rule RULE_typeSpecifier at spec_qual_list_node in ast_tree){
+        type_spec_nodes.push(type_spec_node);
+    }
+    if let Some(sub_spec_qual_list_node) = find!(rule RULE_specifierQualifierList at spec_qual_list_node in ast_tree){
+        collect_type_specifiers_of_spec_qual_list(sub_spec_qual_list_node, ast_tree, type_spec_nodes);
+    }
+}
+
 pub fn unwrap_vec<T:Clone>(v:&Vec<Option<T>>)  -> Vec<T>{
     v.clone().into_iter().map(|x| x.unwrap()).collect_vec()
 }
@@ -1217,8 +1937,17 @@ impl TypeDiscriminants{
     pub fn new_from_const_str(const_str:&str) -> Self {
         if const_str.contains("true") || const_str.contains("false"){
             TypeDiscriminants::I1
+        }else if const_str.contains(".") && (const_str.ends_with('d') || const_str.ends_with('D')) && const_str.chars().next().is_some_and(|x|x.is_numeric()|| x=='-' || x=='.') {
+            TypeDiscriminants::F64
         }else if const_str.contains(".") && (const_str.chars().next().map_or(false, |x|x.is_numeric()|| x=='-' || x=='.')) {
             TypeDiscriminants::F32
+        } else if const_str.chars().next().map_or(false, |x|x.is_numeric()) && (const_str.ends_with('u') || const_str.ends_with('U')){
+            TypeDiscriminants::U32
+        } else if const_str.chars().next().is_some_and(|x|x.is_numeric() || x=='-') && (const_str.ends_with('l') || const_str.ends_with('L')){
+            TypeDiscriminants::I64
+        } else if const_str.chars().all(|x|x.is_ascii_digit()) && const_str.parse::<i32>().is_err() && const_str.parse::<i64>().is_ok(){
+            // 放不下 int 的十进制字面量按 C 的规则是 long
+            TypeDiscriminants::I64
         } else if const_str.chars().next().map_or(false, |x|x.is_numeric() || x=='-') && (const_str.contains("e")|| const_str.contains("E")){
             TypeDiscriminants::F32
         }else if const_str.chars().next().map_or(false, |x|x.is_numeric() || x=='-'){
@@ -1234,7 +1963,11 @@ impl From<TypeDiscriminants> for Type{
     fn from(value: TypeDiscriminants) -> Self {
         match value {
             TypeDiscriminants::I32 => Type::I32,
+            TypeDiscriminants::U32 => Type::U32,
+            TypeDiscriminants::I8 => Type::I8,
+            TypeDiscriminants::I64 => Type::I64,
             TypeDiscriminants::F32 => Type::F32,
+            TypeDiscriminants::F64 => Type::F64,
             TypeDiscriminants::I1 => Type::I1,
             TypeDiscriminants::Void => Type::Void,
             TypeDiscriminants::Label => Type::Label,
@@ -1242,6 +1975,8 @@ impl From<TypeDiscriminants> for Type{
             TypeDiscriminants::Ptr64 => Type::Unknown,
             TypeDiscriminants::Array => Type::Array { dims: vec![], ele_ty: Box::new(Type::Unknown) },
             TypeDiscriminants::Fn => panic!(),
+            // struct/union 的 tag 与成员无法从 TypeDiscriminants 中恢复
+            TypeDiscriminants::Struct | TypeDiscriminants::Union => panic!("无法由 {:?} 得到完整的类型，struct/union 需要 tag 与成员",value),
             TypeDiscriminants::Unknown => Type::Unknown,
         }
     }
@@ -35,7 +35,7 @@ pub fn gcm(instr_et:&mut EtTree, cfg_graph:&mut CfgGraph
 
         for cfg_node in dfs(cfg_graph, cfg_entry){
             let instrs = node!(at cfg_node in cfg_graph).instrs.clone();
-            let mut instr_iter = instrs.iter();
+            let mut instr_iter = instrs.iter().peekable();
             while let Some(&instr) = instr_iter.next(){
                 let def_symidx_vec = instr!(at instr in instr_slab).get_ssa_direct_def_symidx_vec();
                 let mut use_symidx_vec = instr!(at instr in instr_slab).get_ssa_direct_use_symidx_vec();
@@ -47,42 +47,45 @@ pub fn gcm(instr_et:&mut EtTree, cfg_graph:&mut CfgGraph
                     its use symidx vec should also consider the array symidx (assert the next instr is mu)
                 */
                 // println!("visit instr {:?}",instr!(at instr in instr_slab));
-                let op_additional_instr = match &instr!(at instr in instr_slab).instr_type{
+                let additional_instr_vec = match &instr!(at instr in instr_slab).instr_type{
                     super::nhwc_instr::NhwcInstrType::Load { lhs, ptr_symidx, ptr_ty } => {
-                        if let Some(&mu_instr) = instr_iter.next(){
-                            if let NhwcInstrType::Mu { may_use_symidx, may_use_instr} = &instr!(at mu_instr in instr_slab).instr_type{
-                                let may_use_instr = *may_use_instr;
-                                if may_use_instr != instr {
-                                    panic!("the next mu instr  of load's may_use_instr not equal to load instr {:?} at cfg_node:{}",instr!(at instr in instr_slab), cfg_node);
-                                }
-                                use_symidx_vec.push(&may_use_symidx);
-                                Some(mu_instr)
-                            }else {
-                                panic!("the latter instr is not mu instr after {:?} at cfg_node:{}",instr!(at instr in instr_slab), cfg_node);
+                        // 通过指针 load 时可能有多个 mu ，它们都紧跟在 load 后面
+                        let mut mu_instr_vec = vec![];
+                        while let Some(&&mu_instr) = instr_iter.peek(){
+                            match &instr!(at mu_instr in instr_slab).instr_type{
+                                NhwcInstrType::Mu { may_use_symidx, may_use_instr} if *may_use_instr == instr => {
+                                    use_symidx_vec.push(may_use_symidx);
+                                    mu_instr_vec.push(mu_instr);
+                                    instr_iter.next();
+                                },
+                                _ => break,
                             }
-                        }else {
-                            panic!()
                         }
+                        if mu_instr_vec.is_empty(){
+                            panic!("the latter instr is not mu instr after {:?} at cfg_node:{}",instr!(at instr in instr_slab), cfg_node);
+                        }
+                        mu_instr_vec
                     },
                     super::nhwc_instr::NhwcInstrType::Store { val_symidx, value_ty, ptr_symidx, ptr_ty } => {
-                        if let Some(&chi_instr) = instr_iter.next(){
-                            if let NhwcInstrType::Chi { lhs, rhs, may_def_instr } = &instr!(at chi_instr in instr_slab).instr_type{
-                                let may_def_instr = *may_def_instr;
-                                if may_def_instr != instr {
-                                    panic!("the next mu instr  of load's may_use_instr not equal to load instr {:?} at cfg_node:{}",instr!(at instr in instr_slab), cfg_node);
-                                }
-                                use_symidx_vec.push(&rhs);
-                                Some(chi_instr)
-                            }else {
-                                panic!("the latter instr is not mu instr after {:?} at cfg_node:{}",instr!(at instr in instr_slab), cfg_node);
+                        let mut chi_instr_vec = vec![];
+                        while let Some(&&chi_instr) = instr_iter.peek(){
+                            match &instr!(at chi_instr in instr_slab).instr_type{
+                                NhwcInstrType::Chi { lhs, rhs, may_def_instr } if *may_def_instr == instr => {
+                                    use_symidx_vec.push(rhs);
+                                    chi_instr_vec.push(chi_instr);
+                                    instr_iter.next();
+                                },
+                                _ => break,
                             }
-                        }else {
-                            panic!()
                         }
+                        if chi_instr_vec.is_empty(){
+                            panic!("the latter instr is not chi instr after {:?} at cfg_node:{}",instr!(at instr in instr_slab), cfg_node);
+                        }
+                        chi_instr_vec
                     },
-                    super::nhwc_instr::NhwcInstrType::GetElementPtr { lhs, ptr_symidx, array_ty, idx_vec } => None,
-                    super::nhwc_instr::NhwcInstrType::Arith { lhs, rhs } => None,
-                    super::nhwc_instr::NhwcInstrType::SimpleAssign { lhs, rhs, vartype } => None,
+                    super::nhwc_instr::NhwcInstrType::GetElementPtr { lhs, ptr_symidx, array_ty, idx_vec } => vec![],
+                    super::nhwc_instr::NhwcInstrType::Arith { lhs, rhs } => vec![],
+                    super::nhwc_instr::NhwcInstrType::SimpleAssign { lhs, rhs, vartype } => vec![],
                     super::nhwc_instr::NhwcInstrType::Call { op_lhs, func_op } => {
                         // this instr should be fixed 
                         continue;
@@ -120,8 +123,8 @@ pub fn gcm(instr_et:&mut EtTree, cfg_graph:&mut CfgGraph
                         }
                     },
                     super::nhwc_instr::NhwcInstrType::Untrack { symidx } => {panic!("gcm pass should never appear untrack instr because it may change the liveness of variable")},
-                    super::nhwc_instr::NhwcInstrType::TranType { lhs, op } => None,
-                    super::nhwc_instr::NhwcInstrType::BreakPoint { symidx, breakpoint_args } => None,
+                    super::nhwc_instr::NhwcInstrType::TranType { lhs, op } => vec![],
+                    super::nhwc_instr::NhwcInstrType::BreakPoint { symidx, breakpoint_args } => vec![],
                     super::nhwc_instr::NhwcInstrType::Nope {  } => {continue;},
                     super::nhwc_instr::NhwcInstrType::Label { label_symidx } => {panic!()},
                     super::nhwc_instr::NhwcInstrType::DefineFunc { func_symidx, ret_symidx, args } => { continue; },
@@ -188,13 +191,10 @@ pub fn gcm(instr_et:&mut EtTree, cfg_graph:&mut CfgGraph
                     node_mut!(at cfg_node in cfg_graph).instrs.remove(idx);
                     // move_count -=1;
                     node_mut!(at selected_cfg_node in cfg_graph).instrs.push(instr);
-                    match op_additional_instr{
-                        Some(additional_instr) => {
-                            let idx = node_mut!(at cfg_node in cfg_graph).instrs.iter().enumerate().find(|(idx,x)| **x == additional_instr).unwrap().0;
-                            node_mut!(at cfg_node in cfg_graph).instrs.remove(idx);
-                            node_mut!(at selected_cfg_node in cfg_graph).instrs.push(additional_instr);
-                        },
-                        None => {},
+                    for additional_instr in additional_instr_vec{
+                        let idx = node_mut!(at cfg_node in cfg_graph).instrs.iter().enumerate().find(|(idx,x)| **x == additional_instr).unwrap().0;
+                        node_mut!(at cfg_node in cfg_graph).instrs.remove(idx);
+                        node_mut!(at selected_cfg_node in cfg_graph).instrs.push(additional_instr);
                     }
                     update_cfg_instr_idx_in_cfg_node(cfg_graph, selected_cfg_node,  instr_slab)?;
                     update_ssa_def_instr_of_entry(cfg_graph, symtab, instr_slab, cfg_entry)?;
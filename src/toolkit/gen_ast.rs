@@ -1,43 +1,192 @@
-use antlr_rust::{common_token_stream::CommonTokenStream, InputStream};
+use antlr_rust::errors::ANTLRError;
+use antlr_rust::error_listener::ErrorListener;
+use antlr_rust::recognizer::Recognizer;
+use antlr_rust::token::{Token, TOKEN_EOF};
+use antlr_rust::token_factory::TokenFactory;
+use antlr_rust::TokenSource;
+use antlr_rust::{common_token_stream::CommonTokenStream, InputStream, Parser};
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
 
 use petgraph::csr::NodeIndex;
 
 use crate::antlr_parser::rule_walkers::TerminalRuleListener;
-use crate::node_mut;
-use crate::toolkit::ast_node;
+use crate::{node, node_mut};
+use crate::toolkit::ast_node::AstTree;
 use crate::toolkit::gen_et::compress_ast;
 use crate::{
     antlr_parser::{
-        clexer::CLexer, cparser::{CParser, CTreeWalker}
+        clexer::{Assign, CLexer, Comma, Enum, Identifier, LeftBrace, LeftBracket, LeftParen, RightBrace, RightParen, Semi, Star, Struct, Typedef, Union}, cparser::{CParser, CTreeWalker, RULE_blockItem, RULE_statement}
     }, toolkit::ast_node::AstNode
 };
 
 use super::context::NhwcCtx;
+use super::ast_node::SrcLoc;
+use super::preprocess::LineMap;
+use super::sysy_parser::parse_by_native;
+use anyhow::{anyhow, Result};
+
+/// antlr 报错时把预处理之后的行号换算回原始文件的行号
+struct LineMapErrorListener {
+    line_map:LineMap,
+}
+impl<'a, T:Recognizer<'a>> ErrorListener<'a, T> for LineMapErrorListener {
+    fn syntax_error(&self, _recognizer:&T, _offending_symbol:Option<&<T::TF as TokenFactory<'a>>::Inner>, line:isize, column:isize, msg:&str, _e:Option<&ANTLRError>) {
+        match self.line_map.get_src_line(line as usize) {
+            Some((file, src_line)) => eprintln!("{}:{}:{} {}", file, src_line, column, msg),
+            None => eprintln!("line {}:{} {}", line, column, msg),
+        }
+    }
+}
 // use super::gen_et::compress_ast;
 
-/// 把代码生成为AST树 code 为代码文本的字符串
-pub fn parse_as_ast_tree(context:&mut NhwcCtx) {
-    let code = &context.code;
-    let ast_tree = &mut context.ast_tree;
+/// 预先扫描一遍 token ，收集 typedef 声明的类型名，parser 靠它区分 `T *p;` 是声明还是乘法表达式  
+/// typedef 到 ; 之间，不在 {} 和参数列表中的标识符都当作类型名，多收集到的基本类型中的 typedef 名本来就是类型名  
+/// tokens 是去掉隐藏 token 之后的 (token 类型, 文本)，antlr 和手写的 parser 共用
+pub fn scan_typedef_names(tokens:&[(isize, &str)]) -> HashSet<String> {
+    let mut typedef_names = HashSet::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        idx += 1;
+        if tokens[idx - 1].0 != Typedef { continue }
+        let (mut brace_depth, mut para_depth) = (0, 0);
+        while idx < tokens.len() && !(tokens[idx].0 == Semi && brace_depth == 0) {
+            match tokens[idx].0 {
+                LeftBrace => brace_depth += 1,
+                RightBrace => brace_depth -= 1,
+                // 跟在标识符或者 ) 后面的 ( 是参数列表
+                LeftParen if para_depth > 0 || [Identifier, RightParen].contains(&tokens[idx - 1].0) => para_depth += 1,
+                RightParen if para_depth > 0 => para_depth -= 1,
+                Identifier if brace_depth == 0 && para_depth == 0 && ![Struct, Union, Enum].contains(&tokens[idx - 1].0) => { typedef_names.insert(tokens[idx].1.to_string()); }
+                _ => {}
+            }
+            idx += 1;
+        }
+    }
+    typedef_names
+}
 
+/// 把代码生成为AST树 code 为代码文本的字符串，使用哪个 parser 由 --parser 决定
+pub fn parse_as_ast_tree(context:&mut NhwcCtx) -> Result<()> {
+    let (code, line_map, debug) = (&context.code, &context.line_map, context.args.debug);
+    context.ast_tree = match context.args.parser.as_str() {
+        "antlr" => parse_by_antlr(code, line_map, debug),
+        "native" => parse_by_native(code, line_map, debug)?,
+        "check" => {
+            let antlr_ast_tree = parse_by_antlr(code, line_map, debug);
+            let native_ast_tree = parse_by_native(code, line_map, debug)?;
+            cross_check_ast_tree(&antlr_ast_tree, &native_ast_tree)?;
+            antlr_ast_tree
+        }
+        parser => return Err(anyhow!("未知的 parser {} ，可选 antlr native check", parser)),
+    };
+    compress_ast(&mut context.ast_tree);
+    Ok(())
+}
+
+/// 逐节点比较两棵 AstTree ，报告第一个不同的节点
+pub fn cross_check_ast_tree(antlr_ast_tree:&AstTree, native_ast_tree:&AstTree) -> Result<()> {
+    let node_desc = |ast_node:&AstNode| format!("{:?} `{}` at {}", ast_node, ast_node.op_text.clone().unwrap_or_default(), SrcLoc::fmt_op(&ast_node.src_loc));
+    for node_idx in antlr_ast_tree.node_indices() {
+        let antlr_node = &antlr_ast_tree[node_idx];
+        let Some(native_node) = native_ast_tree.node_weight(node_idx) else {
+            return Err(anyhow!("手写 parser 缺少节点 {}", node_desc(antlr_node)))
+        };
+        if antlr_node.rule_id != native_node.rule_id || antlr_node.is_terminal != native_node.is_terminal || antlr_node.op_text != native_node.op_text
+            || antlr_node.child_vec != native_node.child_vec || antlr_node.src_loc != native_node.src_loc {
+            return Err(anyhow!("手写 parser 的节点 {} 与 antlr 的节点 {} 不同", node_desc(native_node), node_desc(antlr_node)))
+        }
+    }
+    if native_ast_tree.node_count() != antlr_ast_tree.node_count() {
+        return Err(anyhow!("手写 parser 生成了 {} 个节点，antlr 生成了 {} 个节点", native_ast_tree.node_count(), antlr_ast_tree.node_count()))
+    }
+    Ok(())
+}
+
+/// 用 antlr 生成的 CParser 解析代码，再遍历它的语法树生成 petgraph 的 AstTree  
+/// 形如 `T *p;` 的 blockItem 被 CParser 当作了乘法表达式，T 是 typedef 名时把这一段重新当作 declaration 解析，替换掉原来的 statement
+pub fn parse_by_antlr(code:&str, line_map:&LineMap, debug:bool) -> AstTree {
+    let tokens = lex_by_antlr(code);
+    let default_tokens = tokens.iter().filter(|token| token.2 == 0).map(|token| (token.0, token.1.as_str())).collect::<Vec<_>>();
+    let typedef_names = scan_typedef_names(&default_tokens);
+    let mut block_item_vec = vec![];
+    let ast_tree = walk_antlr_tree(code, line_map, debug, false, &mut block_item_vec);
+    let mut replaced_child_map = HashMap::new();
+    for (block_item_node, start_token_idx, stop_token_idx) in block_item_vec {
+        let is_stmt = node!(at block_item_node in ast_tree).child_vec.first().is_some_and(|&child| node!(at child in ast_tree).rule_id == RULE_statement);
+        let block_item_tokens = tokens[start_token_idx..=stop_token_idx].iter().filter(|token| token.2 == 0).collect::<Vec<_>>();
+        let la = |idx:usize| block_item_tokens.get(idx).map_or(TOKEN_EOF, |token| token.0);
+        if is_stmt && is_typedef_ptr_decl(la, block_item_tokens.first().map(|token| token.1.as_str()), &typedef_names) {
+            // 之前的代码换成空白，这样重新解析得到的行号与列号不变
+            let (start_char, stop_char) = (tokens[start_token_idx].3, tokens[stop_token_idx].4);
+            let decl_code = code.chars().take(stop_char + 1).enumerate().map(|(char_idx, c)| if char_idx < start_char && c != '\n' { ' ' } else { c }).collect::<String>();
+            let mut decl_ast_tree = walk_antlr_tree(&decl_code, line_map, debug, true, &mut vec![]);
+            // 作为起始规则时 declaration 的第一个 token 还没有读入，位置取它第一个孩子的，文本中也会带上前面的空白
+            let decl_node = 0u32;
+            let first_child = node!(at decl_node in decl_ast_tree).child_vec[0];
+            node_mut!(at decl_node in decl_ast_tree).src_loc = node!(at first_child in decl_ast_tree).src_loc.clone();
+            node_mut!(at decl_node in decl_ast_tree).op_text = node!(at decl_node in decl_ast_tree).op_text.as_ref().map(|text| text.trim_start().to_string());
+            replaced_child_map.insert(block_item_node, decl_ast_tree);
+        }
+    }
+    if replaced_child_map.is_empty() {
+        return ast_tree;
+    }
+    let mut new_ast_tree = AstTree::default();
+    copy_ast_subtree(&ast_tree, 0, &mut new_ast_tree, &replaced_child_map);
+    new_ast_tree
+}
+/// 形如 `T *p;` 的 blockItem 既可以是声明也可以是乘法表达式，T 是 typedef 名时当作声明  
+/// la(k) 是 blockItem 中第 k 个非隐藏 token 的类型，first_text 是第一个 token 的文本，antlr 和手写的 parser 共用
+pub fn is_typedef_ptr_decl(la:impl Fn(usize) -> isize, first_text:Option<&str>, typedef_names:&HashSet<String>) -> bool {
+    let mut idx = 1;
+    while la(idx) == Star { idx += 1 }
+    la(0) == Identifier && idx > 1 && la(idx) == Identifier && [Semi, Comma, Assign, LeftBracket].contains(&la(idx + 1))
+        && first_text.is_some_and(|text| typedef_names.contains(text))
+}
+/// 把 ast_tree 中以 ast_node 为根的子树按先序复制到 new_ast_tree 中，节点编号随之重排  
+/// replaced_child_map 中的节点的孩子换成对应 AstTree 的整棵树
+fn copy_ast_subtree(ast_tree:&AstTree, ast_node:u32, new_ast_tree:&mut AstTree, replaced_child_map:&HashMap<u32, AstTree>) -> u32 {
+    let mut new_ast_node = node!(at ast_node in ast_tree).clone();
+    new_ast_node.child_vec.clear();
+    new_ast_node.node_index = new_ast_tree.node_count() as u32;
+    let new_node_id = new_ast_tree.add_node(new_ast_node).index() as u32;
+    let new_child_vec = match replaced_child_map.get(&ast_node) {
+        Some(sub_ast_tree) => vec![copy_ast_subtree(sub_ast_tree, 0, new_ast_tree, &HashMap::new())],
+        None => node!(at ast_node in ast_tree).child_vec.iter().map(|&child| copy_ast_subtree(ast_tree, child, new_ast_tree, replaced_child_map)).collect(),
+    };
+    for &new_child in new_child_vec.iter() {
+        new_ast_tree.add_edge(NodeIndex::from(new_node_id), NodeIndex::from(new_child), ());
+    }
+    node_mut!(at new_node_id in new_ast_tree).child_vec = new_child_vec;
+    new_node_id
+}
+/// 遍历 antlr 的语法树生成 AstTree ，is_decl 为 true 时从 declaration 开始解析，否则从 compilationUnit 开始  
+/// 同时把每个 blockItem 的 (节点, 第一个 token 的下标, 最后一个 token 的下标) 放进 block_item_vec
+fn walk_antlr_tree(code:&str, line_map:&LineMap, debug:bool, is_decl:bool, block_item_vec:&mut Vec<(u32, usize, usize)>) -> AstTree {
+    let mut ast_tree = AstTree::default();
     // 由于 antlr 已经生成了一个 AST 树 但我们需要的是 petgraph 类型，因此我们需要重新遍历一次这个树，生成 petgraph的
     {
         let mut count = 0;
-        let debug =  Box::new(context.args.debug);
+        let debug =  Box::new(debug);
         let debug = Box::leak(debug);
         
+        let src_line_map = line_map.clone();
         let listener = TerminalRuleListener {
-            st:(Vec::<usize>::new(), false, ast_tree, &mut count),
-            enter_rule_f:Box::new(|ctx, s, is_terminal, type_idx| {
-                let (node_count_under_depth, is_last_wrap_drop, g, count) = s;
+            st:(Vec::<usize>::new(), false, &mut ast_tree, &mut count, block_item_vec),
+            enter_rule_f:Box::new(move |ctx, s, is_terminal, type_idx, (line, column)| {
+                let (node_count_under_depth, is_last_wrap_drop, g, count, block_item_vec) = s;
                 let mut ast_node = if is_terminal || *debug{
                     AstNode::new(type_idx, Some(ctx.get_text()), is_terminal)
                 }else {
                     AstNode::new(type_idx, None, is_terminal)
                 };
                 ast_node.node_index = **count;
+                ast_node.src_loc = src_line_map.get_src_line(line as usize).map(|(file, src_line)| SrcLoc::new(file.clone(), *src_line, column as usize + 1));
                 **count += 1;
                 let node_id = g.add_node(ast_node).index();
+                if !is_terminal && type_idx == RULE_blockItem {
+                    block_item_vec.push((node_id as u32, ctx.start().get_token_index() as usize, ctx.stop().get_token_index() as usize));
+                }
                 // println!("enter rule {} id {}",ctx.get_text(),node_id);
                 if node_id != 0 {
                     let parent_node = match is_last_wrap_drop {
@@ -59,23 +208,41 @@ pub fn parse_as_ast_tree(context:&mut NhwcCtx) {
                 *is_last_wrap_drop = false;
             }),
             exit_rule_f:Box::new(|_ctx, s, _is_terminal, _type_idx| {
-                let (node_count_under_depth, is_last_wrap_drop, _g, _count) = s;
+                let (node_count_under_depth, is_last_wrap_drop, _g, _count, _block_item_vec) = s;
                 node_count_under_depth.pop();
                 *is_last_wrap_drop = true;
                 // println!("exit rule {}  ",ctx.get_text());
             }),
         };
 
-        let lexer = CLexer::new(InputStream::new(code.as_str()));
+        let mut lexer = CLexer::new(InputStream::new(code));
+        lexer.remove_error_listeners();
+        lexer.add_error_listener(Box::new(LineMapErrorListener { line_map:line_map.clone() }));
         let token_source = CommonTokenStream::new(lexer);
         let mut parser = CParser::new(token_source);
+        parser.remove_error_listeners();
+        parser.add_error_listener(Box::new(LineMapErrorListener { line_map:line_map.clone() }));
         // let m = *parser;
-        let result = parser.compilationUnit();
-        let tree = result.expect("解析失败");
-        CTreeWalker::walk(Box::new(listener), &*tree);
+        if is_decl {
+            let tree = parser.declaration().expect("解析失败");
+            CTreeWalker::walk(Box::new(listener), &*tree);
+        }else {
+            let tree = parser.compilationUnit().expect("解析失败");
+            CTreeWalker::walk(Box::new(listener), &*tree);
+        }
     }
-
-    compress_ast(&mut context.ast_tree);
-    // println!("{:?}","finished compress ast");
-    // 更新 context 中的 ast_tree
+    ast_tree
+}
+/// 用 CLexer 切分出所有 token ，包括隐藏的 token ，下标与 CommonTokenStream 中的相同  
+/// 每个 token 是 (token 类型, 文本, channel, 第一个字符的下标, 最后一个字符的下标)
+fn lex_by_antlr(code:&str) -> Vec<(isize, String, isize, usize, usize)> {
+    let mut lexer = CLexer::new(InputStream::new(code));
+    lexer.remove_error_listeners();
+    let mut tokens = vec![];
+    loop {
+        let tok = lexer.next_token();
+        if tok.get_token_type() == TOKEN_EOF { break }
+        tokens.push((tok.get_token_type(), tok.get_text().to_string(), tok.get_channel(), tok.get_start() as usize, tok.get_stop() as usize));
+    }
+    tokens
 }
@@ -43,6 +43,10 @@ pub fn parse_func_call_graph(
         // 向func call graph 中添加Call节点
         symtab.get_mut(&rc_external_func_symidx.as_ref_borrow()).add_cor_func_call_node(caller_node);
     }
+    // 间接调用不知道被调用的是哪个函数，保守地认为它可能调用任何一个被取过地址的函数
+    let addr_taken_func_symidxs:Vec<RcSymIdx> = symtab.get_global_info().get_all_cfg_func_symidx_entry_tuples().iter().map(|(rc_func_symidx,_)| rc_func_symidx.clone())
+        .chain(symtab.get_global_info().get_external_func_symidx_vec().iter().cloned())
+        .filter(|rc_func_symidx| symtab.has_symbol(&rc_func_symidx.as_ref_borrow().to_globl_ptr())).collect();
     for (caller_func_symidx,cfg_entry) in symtab.get_global_info().get_all_cfg_func_symidx_entry_tuples().clone(){
         let caller_func_symidx = caller_func_symidx.as_ref_borrow();
         let mut callee_func_set = HashSet::new();
@@ -50,6 +54,9 @@ pub fn parse_func_call_graph(
             for &instr in node!(at cfg_node in cfg_graph).iter_all_instrs(){
                 match &instr!(at instr in instr_slab).instr_type{
                     // 只处理Call
+                    NhwcInstrType::Call { op_lhs:_, func_op } if func_op.is_indirect => {
+                        callee_func_set.extend(addr_taken_func_symidxs.iter().cloned());
+                    },
                     NhwcInstrType::Call { op_lhs:_, func_op } => {
                         callee_func_set.insert(func_op.rc_func_symidx.clone());
                     },
@@ -1,23 +1,29 @@
 use crate::antlr_parser::cparser::{
-    RULE_blockItem, RULE_blockItemList, RULE_breakpointStatement, RULE_declaration, RULE_expression, RULE_expressionStatement, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forCondition, RULE_forIterationStatement, RULE_forMidExpression, RULE_ifSelection, RULE_iterationStatement, RULE_jumpStatement, RULE_labeledStatement, RULE_selectionStatement, RULE_statement, RULE_switchSelection, RULE_whileIterationStatement
+    RULE_blockItem, RULE_blockItemList, RULE_breakpointStatement, RULE_declaration, RULE_expression, RULE_expressionStatement, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forCondition, RULE_forIterationStatement, RULE_constantExpression, RULE_forMidExpression, RULE_ifSelection, RULE_iterationStatement, RULE_jumpStatement, RULE_labeledStatement, RULE_selectionStatement, RULE_statement, RULE_switchSelection, RULE_whileIterationStatement, RULE_doWhileIterationStatement
 };
-use crate::toolkit::ast_node::AstTree;
+use crate::toolkit::ast_node::{AstTree, SrcLoc};
 use crate::toolkit::cfg_edge::CfgEdge;
-use crate::{add_edge, add_node, node, reg_field_for_struct, rule_id, RULE_compoundStatement, RULE_functionDefinition};
+use crate::antlr_parser::clexer::{Goto, Identifier};
+use crate::{add_edge, add_node, node, node_mut, reg_field_for_struct, rule_id, RULE_compoundStatement, RULE_functionDefinition};
 use crate::{find, find_nodes};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+use std::collections::HashMap;
 use petgraph::visit::EdgeRef;
 use syn::token::Loop;
 
 use super::cfg_node::{CfgGraph, CfgNode, CfgNodeType};
+use super::etc::dfs;
 
 
 use super::symtab::SymTab;
 // 这个文件中没有在命名中提到是哪一中图中的节点，那么统一是 scope_node
 
 reg_field_for_struct!(CfgNode{
-    // CFG_COR_GATHER:u32,
+    CFG_COR_GATHER:u32,
+    CASE_CONST_AST_NODE:Option<u32>,
+    GOTO_LABEL_AST_NODE:u32,
+    SRC_LOC:SrcLoc,
 } with_fields info);
 
 pub fn process_while(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymTab, current_while_node:u32,loop_level:usize) -> Result<Option<(u32, u32)>> {
@@ -40,12 +46,34 @@ pub fn process_while(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut Sym
     Ok(Some((cfg_while_node, cfg_while_node)))
 }
 
+/// do while 循环先进入循环体，循环体之后才是判断条件的 DoWhileLoop 节点，返回循环体的头和 DoWhileLoop 节点
+pub fn process_do_while(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymTab, current_do_while_node:u32,loop_level:usize) -> Result<Option<(u32, u32)>> {
+    let loop_level = loop_level+1;
+    let expr_node = find!(rule RULE_expression at current_do_while_node in ast_tree).unwrap();
+    let statement_node = find!(rule RULE_statement at current_do_while_node in ast_tree).unwrap();
+    let op_head_tail_nodes = process_stmt(cfg_graph, ast_tree, symtab, statement_node,loop_level)?;
+    let loop_struct = CfgNode::new_do_while(expr_node,loop_level);
+    let cfg_do_while_node = add_node!(loop_struct to cfg_graph);
+    let (st_head_node, st_tail_node) = match op_head_tail_nodes {
+        Some(head_tail) => head_tail,
+        None => {
+            let empty_bb_struct = CfgNode::new_bb(vec![],loop_level);
+            let empty_bb = add_node!(empty_bb_struct to cfg_graph);
+            (empty_bb, empty_bb)
+        }
+    };
+    add_edge!({CfgEdge::new_body_head()} from cfg_do_while_node to st_head_node in cfg_graph);
+    add_edge!({CfgEdge::new_body_tail()} from st_tail_node to cfg_do_while_node in cfg_graph);
+    Ok(Some((st_head_node, cfg_do_while_node)))
+}
+
 ///处理循环过程的cfg节点处理和连接，返回branch和statement的idx
 pub fn process_iteration(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymTab, current_iteration_node:u32,loop_level:usize) -> Result<Option<(u32, u32)>> {
     //处理branch的构造
     let which_iteration_node = node!(at current_iteration_node in ast_tree).child_vec[0];
     match (rule_id!(at which_iteration_node in ast_tree), which_iteration_node) {
         (RULE_whileIterationStatement, while_node) => Ok(process_while(cfg_graph, ast_tree, symtab, while_node,loop_level)?),
+        (RULE_doWhileIterationStatement, do_while_node) => Ok(process_do_while(cfg_graph, ast_tree, symtab, do_while_node,loop_level)?),
         (RULE_forIterationStatement, for_node) => Ok(process_for(cfg_graph, ast_tree, symtab, for_node,loop_level)?),
         (_, _) => panic!("循环不属于for或while循环"),
     }
@@ -94,15 +122,75 @@ pub fn process_stmt(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymT
             let cfg_basicblock_node = add_node!(bb_struct to cfg_graph);
             Ok(Some((cfg_basicblock_node, cfg_basicblock_node)))
         }
-        (RULE_labeledStatement, labeled_node) => {
-            let bb_struct = CfgNode::new_bb(vec![labeled_node],loop_level);
-            let cfg_basicblock_node = add_node!(bb_struct to cfg_graph);
-            Ok(Some((cfg_basicblock_node, cfg_basicblock_node)))
-        }
+        (RULE_labeledStatement, labeled_node) => Ok(process_goto_label(cfg_graph, ast_tree, symtab, labeled_node,loop_level)?),
         (RULE_declaration, declare_node) => Ok(process_declartion(cfg_graph, ast_tree, symtab, declare_node,loop_level)?),
         (_, _) => panic!("不属于任何statement下等语句,ast树有错误 node:{}", which_statement),
     }
 }
+/// goto 的标签单独开一个空的 bb 作为跳转目标，标签后面的语句接在这个 bb 后面
+/// 跳到标签的边要等整个函数处理完之后在 process_goto 中连上
+pub fn process_goto_label(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymTab, labeled_node:u32,loop_level:usize) -> Result<Option<(u32, u32)>> {
+    if find!(term Identifier at labeled_node in ast_tree).is_none(){
+        panic!("case 或 default 标签 {} 只能直接出现在 switch 的语句块中", labeled_node)
+    }
+    let mut label_struct = CfgNode::new_bb(vec![],loop_level);
+    label_struct.add_goto_label_ast_node(labeled_node);
+    let cfg_label_node = add_node!(label_struct to cfg_graph);
+    let statement_node = find!(rule RULE_statement at labeled_node in ast_tree).unwrap();
+    match process_stmt(cfg_graph, ast_tree, symtab, statement_node,loop_level)?{
+        Some((st_head_node, st_tail_node)) => {
+            let opt_unite_bb_node = try_unite(Some(cfg_label_node), Some(st_head_node), cfg_graph, ast_tree)?;
+            if opt_unite_bb_node.is_none(){
+                add_edge!({CfgEdge::new_direct()} from cfg_label_node to st_head_node in cfg_graph);
+            }
+            if opt_unite_bb_node.is_some() && st_head_node == st_tail_node{
+                Ok(Some((cfg_label_node, cfg_label_node)))
+            }else{
+                Ok(Some((cfg_label_node, st_tail_node)))
+            }
+        }
+        None => Ok(Some((cfg_label_node, cfg_label_node))),
+    }
+}
+/// 返回标签语句或 goto 语句中标签的名字
+pub fn get_label_name_of_ast_node(ast_node:u32, ast_tree:&AstTree) -> String{
+    let identifier_node = find!(term Identifier at ast_node in ast_tree).unwrap();
+    node!(at identifier_node in ast_tree).op_text.clone().unwrap()
+}
+/// 判断这个 ast_node 是不是 goto 语句
+pub fn is_goto_stmt(ast_node:u32, ast_tree:&AstTree) -> bool{
+    rule_id!(at ast_node in ast_tree) == RULE_jumpStatement && find!(term Goto at ast_node in ast_tree).is_some()
+}
+/// 整个函数的 cfg 建好之后，把 goto 所在 bb 原来的出边去掉，改为连到对应标签的 bb 上
+/// 这样得到的 cfg 不一定是结构化的，甚至可能是不可归约的
+pub fn process_goto(cfg_entry_node:u32, cfg_graph:&mut CfgGraph, ast_tree:&AstTree) -> Result<()>{
+    let cfg_nodes = dfs(cfg_graph, cfg_entry_node);
+    let mut label_name2cfg_node = HashMap::new();
+    for &cfg_node in cfg_nodes.iter(){
+        if node!(at cfg_node in cfg_graph).has_goto_label_ast_node(){
+            let label_name = get_label_name_of_ast_node(*node!(at cfg_node in cfg_graph).get_goto_label_ast_node(), ast_tree);
+            if label_name2cfg_node.insert(label_name.clone(), cfg_node).is_some(){
+                return Err(anyhow!("标签 {} 重复定义", label_name));
+            }
+        }
+    }
+    for &cfg_node in cfg_nodes.iter(){
+        let op_goto_node = match &node!(at cfg_node in cfg_graph).cfg_node_type{
+            CfgNodeType::BasicBlock { ast_nodes } => ast_nodes.last().filter(|&&ast_node| is_goto_stmt(ast_node, ast_tree)).cloned(),
+            _ => None,
+        };
+        if let Some(goto_node) = op_goto_node{
+            let label_name = get_label_name_of_ast_node(goto_node, ast_tree);
+            let &cfg_label_node = label_name2cfg_node.get(&label_name).ok_or(anyhow!("goto 的标签 {} 没有定义", label_name))?;
+            let edges:Vec<_> = cfg_graph.edges(NodeIndex::from(cfg_node)).map(|e| e.id()).collect();
+            for edge in edges{
+                cfg_graph.remove_edge(edge);
+            }
+            add_edge!({CfgEdge::new_direct()} from cfg_node to cfg_label_node in cfg_graph);
+        }
+    }
+    Ok(())
+}
 pub fn process_declartion(cfg_graph:&mut CfgGraph, _ast_tree:&AstTree, _symtab:&mut SymTab, current_declare_node:u32,loop_level:usize) -> Result<Option<(u32, u32)>> {
     let bb_struct = CfgNode::new_bb(vec![current_declare_node],loop_level);
     let cfg_basicblock_node = add_node!(bb_struct to cfg_graph);
@@ -188,32 +276,99 @@ pub fn process_if(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymTab
         panic!("不对, selectionStatement 下面不可能有两个以上的Statement");
     }
 }
+/// switch 的 body 会按照 case/default 标签切分成多个 bb ，每个标签处都会新开一个 bb 并从 switch 连一条 Case 边过去，
+/// 标签之间没有 break 的话前一段顺序 fallthrough 到下一段
 pub fn process_switch(cfg_graph:&mut CfgGraph, ast_tree:&AstTree, symtab:&mut SymTab, switch_node:u32,loop_level:usize) -> Result<Option<(u32, u32)>> {
-    //将expression做成branch
+    //将expression做成switch
     let expression_node = find!(rule RULE_expression at switch_node in ast_tree).unwrap();
 
-    let branch_struct = CfgNode::new_switch(expression_node,loop_level);
-    let cfg_branch_node = add_node!(branch_struct to cfg_graph);
+    let switch_struct = CfgNode::new_switch(expression_node,loop_level);
+    let cfg_switch_node = add_node!(switch_struct to cfg_graph);
 
     let cfg_gather_struct = CfgNode::new_gather(loop_level);
     let cfg_gather_node = add_node!(cfg_gather_struct to cfg_graph);
+    // break 需要知道跳到哪里
+    node_mut!(at cfg_switch_node in cfg_graph).add_cfg_cor_gather(cfg_gather_node);
 
     //处理statement
     let compound_stmt_node = find!(rule RULE_statement finally RULE_compoundStatement at switch_node in ast_tree).unwrap();
-    if let Some((st_head_node, st_tail_node)) = process_compound(cfg_graph, ast_tree, symtab, compound_stmt_node,loop_level)? {
-        add_edge!({CfgEdge::new_direct()} from cfg_branch_node to st_head_node in cfg_graph);
+    let blockitem_nodes:Vec<u32> = match find!(rule RULE_blockItemList at compound_stmt_node in ast_tree){
+        Some(blockitemlist_node) => find_nodes!(rule RULE_blockItem at blockitemlist_node in ast_tree),
+        None => vec![],
+    };
+    let mut opt_current_cfg_head_and_tail:Option<(u32,u32)> = None;
+    let mut has_default = false;
+    for blockitem_node in blockitem_nodes {
+        let mut declare_or_statement_node = node!(at blockitem_node in ast_tree).child_vec[0];
+        // 连续的标签 (例如 case 1: case 2: ) 每一个都单独开一个空的 bb
+        while let Some(labeled_node) = get_case_labeled_node(declare_or_statement_node, ast_tree){
+            let op_case_const_node = find!(rule RULE_constantExpression at labeled_node in ast_tree);
+            if op_case_const_node.is_none(){
+                if has_default { panic!("switch {} 中出现了多个 default", switch_node) }
+                has_default = true;
+            }
+            let mut case_struct = CfgNode::new_bb(vec![],loop_level);
+            case_struct.add_case_const_ast_node(op_case_const_node);
+            let cfg_case_node = add_node!(case_struct to cfg_graph);
+            add_edge!({CfgEdge::new_case()} from cfg_switch_node to cfg_case_node in cfg_graph);
+            opt_current_cfg_head_and_tail = match opt_current_cfg_head_and_tail{
+                Some((current_cfg_head, current_cfg_tail)) => {
+                    // fallthrough
+                    add_edge!({CfgEdge::new_direct()} from current_cfg_tail to cfg_case_node in cfg_graph);
+                    Some((current_cfg_head, cfg_case_node))
+                },
+                None => Some((cfg_case_node, cfg_case_node)),
+            };
+            declare_or_statement_node = find!(rule RULE_statement at labeled_node in ast_tree).unwrap();
+        }
+        let op_head_tail = match (rule_id!(at declare_or_statement_node in ast_tree), declare_or_statement_node) {
+            (RULE_statement, stmt_node) => process_stmt(cfg_graph, ast_tree, symtab, stmt_node,loop_level)?,
+            (RULE_declaration, declare_node) => process_declartion(cfg_graph, ast_tree, symtab, declare_node,loop_level)?,
+            _ => panic!("ast_tree 树错了  node:{}", declare_or_statement_node),
+        };
+        let (opt_stmt_head, opt_stmt_tail) = op_head_tail.unzip();
+        let (opt_current_cfg_head, opt_current_cfg_tail) = opt_current_cfg_head_and_tail.unzip();
+        let opt_unite_bb_node = try_unite(opt_current_cfg_tail, opt_stmt_head, cfg_graph, ast_tree)?;
+        if let (None, Some(stmt_head), Some(current_cfg_tail)) = (opt_unite_bb_node, opt_stmt_head, opt_current_cfg_tail) {
+            add_edge!({CfgEdge::new_direct()} from current_cfg_tail to stmt_head in cfg_graph);
+        }
+        opt_current_cfg_head_and_tail = match (opt_stmt_head, opt_stmt_tail) {
+            (Some(x), Some(y)) if x == y => Option::zip(opt_current_cfg_head.or(opt_unite_bb_node).or(opt_stmt_head), opt_unite_bb_node.or(opt_stmt_tail)),
+            _ => Option::zip(opt_current_cfg_head.or(opt_unite_bb_node).or(opt_stmt_head), opt_stmt_tail.or(opt_current_cfg_tail)),
+        };
+    }
+    if let Some((_, st_tail_node)) = opt_current_cfg_head_and_tail {
         add_edge!({CfgEdge::new_direct()} from st_tail_node to cfg_gather_node in cfg_graph);
     }
-    Ok(Some((cfg_branch_node, cfg_gather_node)))
+    if !has_default {
+        // 没有 default 的时候 所有case 都不匹配就直接跳到 gather
+        add_edge!({CfgEdge::new_case()} from cfg_switch_node to cfg_gather_node in cfg_graph);
+    }
+    Ok(Some((cfg_switch_node, cfg_gather_node)))
+}
+/// 如果这个 statement 是 case 或 default 标签语句，返回 labeledStatement 节点
+fn get_case_labeled_node(stmt_node:u32, ast_tree:&AstTree) -> Option<u32>{
+    if rule_id!(at stmt_node in ast_tree) != RULE_statement { return None }
+    let labeled_node = node!(at stmt_node in ast_tree).child_vec[0];
+    if rule_id!(at labeled_node in ast_tree) == RULE_labeledStatement && find!(term Identifier at labeled_node in ast_tree).is_none(){
+        Some(labeled_node)
+    }else {
+        None
+    }
 }
 
 /// 首先确保这两个节点直接相连,如果合并成功，返回合并后的 node ，如果合并失败，返回None
 /// 需要注意的是 一个gather 和一个空的 BasicBlock 不可以合并，其次我们需要保留边的属性
 /// 两个BaiscBlock 如果 前者的最后一个 ast_stmt 是 jumpStatement 那么也不可以合并
+/// 后者如果是 do while 循环体的头(有来自 DoWhileLoop 的回边)或者是 goto 的标签，合并会丢掉这些边，也不可以合并
 pub fn try_unite(opt_node1:Option<u32>, opt_node2:Option<u32>, cfg_graph:&mut CfgGraph, ast_tree:&AstTree) -> Result<Option<u32>> {
     // return Ok(None);
     match (opt_node1, opt_node2) {
         (Some(node1), Some(node2)) => {
+            let is_do_while_body_head = cfg_graph.edges_directed(NodeIndex::from(node2), petgraph::Direction::Incoming).any(|e| e.weight().cfg_edge_type.is_body_head() && node!(at {e.source().index() as u32} in cfg_graph).cfg_node_type.is_do_while_loop());
+            if is_do_while_body_head || node!(at node2 in cfg_graph).has_goto_label_ast_node(){
+                return Ok(None)
+            }
             let (node_struct1, node_struct2) = cfg_graph.index_twice_mut(NodeIndex::from(node1), NodeIndex::from(node2));
             match ( &mut node_struct1.cfg_node_type,&mut node_struct2.cfg_node_type) {
                 (CfgNodeType::BasicBlock { ast_nodes: ast_nodes1 }, CfgNodeType::BasicBlock { ast_nodes: ast_nodes2 }) => {
@@ -355,9 +510,21 @@ pub fn parse_ast_to_cfg(ast_tree:&AstTree, cfg_graph:&mut CfgGraph, symtab:&mut
                 add_edge!( {CfgEdge::new_direct()} from  cfg_entry_node to cfg_exit_node in cfg_graph);
             }
         }
+        process_goto(cfg_entry_node, cfg_graph, ast_tree)?;
     }
+    attach_src_loc_to_cfg(cfg_graph, ast_tree);
     Ok(())
 }
+/// 给每个 cfg 节点记录它第一个 ast 节点的源代码位置，goto 标签所在的空 bb 使用标签的位置
+fn attach_src_loc_to_cfg(cfg_graph:&mut CfgGraph, ast_tree:&AstTree){
+    for cfg_node in cfg_graph.node_indices().map(|n| n.index() as u32).collect::<Vec<_>>(){
+        let cfg_node_struct = node!(at cfg_node in cfg_graph);
+        let op_ast_node = cfg_node_struct.get_first_ast_node().or(cfg_node_struct.get_op_goto_label_ast_node().cloned());
+        if let Some(src_loc) = op_ast_node.and_then(|ast_node| node!(at ast_node in ast_tree).src_loc.clone()){
+            node_mut!(at cfg_node in cfg_graph).add_src_loc(src_loc);
+        }
+    }
+}
 
 // /// 这个函数用于将cfg生成为 bbcfg 意思是这个cfg 里面只有bb
 // pub fn parse_cfg2cfgbb(ast_tree:&AstTree,cfg_graph:&mut CfgGraph,symtab:&mut SymTab,scope_tree:&ScopeTree){
@@ -50,7 +50,7 @@ pub fn parse_dug(cfg_graph:&mut CfgGraph,instr_slab:&mut InstrSlab<NhwcInstr>,sy
                         match jump_op{
                             JumpOp::Ret { op_ret_sym: _} => {},
                             JumpOp::Br { cond: _, t1: _, t2: _ } => {},
-                            JumpOp::Switch { cond: _, default: _, compared: _ } => continue,
+                            JumpOp::Switch { cond: _, default: _, compared: _ } => {},
                             JumpOp::DirectJump { label_symidx: _ } => continue,
                         },
                     NhwcInstrType::Phi { lhs: _, rhs: _ } => {},
@@ -84,7 +84,7 @@ pub fn parse_dug(cfg_graph:&mut CfgGraph,instr_slab:&mut InstrSlab<NhwcInstr>,sy
                 match jump_op{
                     JumpOp::Ret { op_ret_sym: _} => {},
                     JumpOp::Br { cond: _, t1: _, t2: _ } => {},
-                    JumpOp::Switch { cond: _, default: _, compared: _ } => continue,
+                    JumpOp::Switch { cond: _, default: _, compared: _ } => {},
                     JumpOp::DirectJump { label_symidx: _ } => continue,
                 },
             NhwcInstrType::Phi { lhs: _, rhs: _ } => {},
@@ -159,7 +159,7 @@ pub fn parse_dug(cfg_graph:&mut CfgGraph,instr_slab:&mut InstrSlab<NhwcInstr>,sy
                         match jump_op{
                             JumpOp::Ret { op_ret_sym: _} => {},
                             JumpOp::Br { cond: _, t1: _, t2: _ } => {},
-                            JumpOp::Switch { cond: _, default: _, compared: _ } => continue,
+                            JumpOp::Switch { cond: _, default: _, compared: _ } => {},
                             JumpOp::DirectJump { label_symidx: _ } => continue,
                         },
                     NhwcInstrType::Phi { lhs: _, rhs: _ } => {},
@@ -226,7 +226,7 @@ pub fn parse_dug(cfg_graph:&mut CfgGraph,instr_slab:&mut InstrSlab<NhwcInstr>,sy
                             // here we consider it as 2 situation 
                             // 1. when it is an array we should add edge from last def to here because you can't change all things in array in one time
                             // 2. when it is a non-array variable we will not add this edge
-                            if symtab.get(&lhs.as_ref_borrow().to_src_symidx()).get_type().is_array() 
+                            if symtab.get(&lhs.as_ref_borrow().to_src_symidx()).get_type().is_aggregate() 
                             ||symtab.get(&lhs.as_ref_borrow().to_src_symidx()).get_type().is_ptr_64(){
                             let &dug_cor_node = instr!(at may_def_instr in instr_slab).get_dug_cor_def_use_node();
                             let &def_dug_node = instr!(at def_instr in instr_slab).get_dug_cor_def_use_node();
@@ -254,7 +254,7 @@ pub fn get_cor_br_instr_of_phi_instr(cfg_graph:&CfgGraph,instr_slab:&InstrSlab<N
     if node!(at phi_cfg_node in cfg_graph).cfg_node_type.is_gather(){
         let cfg_branch_node = find_branch_of_gather_upward(phi_cfg_node, cfg_graph);
         node!(at cfg_branch_node in cfg_graph).op_jump_instr.unwrap_or_else(||panic!("这个 cfg_node:{} 没有 jump_instr ",cfg_branch_node))
-    }else if node!(at phi_cfg_node in cfg_graph).cfg_node_type.is_while_loop(){
+    }else if node!(at phi_cfg_node in cfg_graph).cfg_node_type.is_while_loop() || node!(at phi_cfg_node in cfg_graph).cfg_node_type.is_do_while_loop(){
         node!(at phi_cfg_node in cfg_graph).op_jump_instr.unwrap_or_else(||panic!("这个 cfg_node:{} 没有 jump_instr ",phi_cfg_node))
     }else{
         panic!("这个 phi instr {:?} 没有对应的 jump_det ",phi_instr_struct)
@@ -5,20 +5,21 @@ use petgraph::graph::Edge;
 use std::time::Instant;
 
 use crate::antlr_parser::clexer::{
-    And, Arrow, Constant, Div, DivAssign, Dot, Equal, Greater, GreaterEqual, Identifier, LeftShift, Less, LessEqual, Minus, MinusAssign, MinusMinus, Mod, MulAssign, Not, NotEqual, Plus, PlusAssign, PlusPlus, RightShift, Star, StringLiteral, Tilde
+    And, Arrow, Constant, LeftBracket, Div, DivAssign, Dot, Equal, Greater, GreaterEqual, Identifier, LeftShift, Less, LessEqual, Minus, MinusAssign, MinusMinus, Mod, MulAssign, Not, NotEqual, Plus, PlusAssign, PlusPlus, RightShift, Star, StringLiteral, Tilde
 };
 use crate::antlr_parser::cparser::{
-    Assign, RULE_additiveExpression, RULE_andExpression, RULE_argumentExpressionList, RULE_assignmentExpression, RULE_assignmentOperator, RULE_castExpression, RULE_conditionalExpression, RULE_declaration, RULE_declarationSpecifier, RULE_declarationSpecifiers, RULE_declarator, RULE_directDeclarator, RULE_equalityExpression, RULE_exclusiveOrExpression, RULE_expression, RULE_expressionStatement, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forDeclaration, RULE_forMidExpression, RULE_inclusiveOrExpression, RULE_initDeclarator, RULE_initDeclaratorList, RULE_initializer, RULE_initializerList, RULE_logicalAndExpression, RULE_logicalOrExpression, RULE_multiplicativeExpression, RULE_parameterDeclaration, RULE_parameterList, RULE_parameterTypeList, RULE_postfixExpression, RULE_primaryExpression, RULE_relationalExpression, RULE_shiftExpression, RULE_typeName, RULE_typeQualifier, RULE_typeSpecifier, RULE_unaryExpression, RULE_unaryOperator
+    Assign, RULE_additiveExpression, RULE_andExpression, RULE_argumentExpressionList, RULE_assignmentExpression, RULE_assignmentOperator, RULE_castExpression, RULE_conditionalExpression, RULE_constantExpression, RULE_declaration, RULE_declarationSpecifier, RULE_declarationSpecifiers, RULE_declarator, RULE_directDeclarator, RULE_enumSpecifier, RULE_equalityExpression, RULE_exclusiveOrExpression, RULE_expression, RULE_expressionStatement, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forDeclaration, RULE_forMidExpression, RULE_inclusiveOrExpression, RULE_initDeclarator, RULE_initDeclaratorList, RULE_initializer, RULE_initializerList, RULE_logicalAndExpression, RULE_logicalOrExpression, RULE_multiplicativeExpression, RULE_parameterDeclaration, RULE_parameterList, RULE_parameterTypeList, RULE_postfixExpression, RULE_primaryExpression, RULE_relationalExpression, RULE_shiftExpression, RULE_structOrUnionSpecifier, RULE_typeName, RULE_typeQualifier, RULE_typeSpecifier, RULE_typedefName, RULE_unaryExpression, RULE_unaryOperator
 };
 
-use crate::{add_edge, add_node, add_node_with_edge, debug_info_blue, debug_info_red, debug_info_yellow, direct_child_nodes, find, find_nodes, node, rule_id, term_id, timeit};
+use crate::{add_edge, add_node, add_node_with_edge, debug_info_blue, debug_info_red, debug_info_yellow, direct_child_nodes, find, find_nodes, node, node_mut, rule_id, term_id, timeit};
 
-use super::et_node::{DeclOrDefOrUse, EtEdgeType, EtNodeType, EtTree};
+use super::et_node::{DeclOrDefOrUse, EtEdgeType, EtNode, EtNodeType, EtTree};
 use super::etc::dfs;
 use super::eval_et::{compress_et_for_gen_nhwc};
 use super::gen_cfg::AST_ROOT;
+use super::literal::canonical_literal_name;
 use super::symtab::SymIdx;
-use super::{ast_node::AstTree, scope_node::ScopeTree};
+use super::{ast_node::{find_single_constant_text, find_type_specifier_of_decl, get_ptr_depth_of_declarator, is_fn_ptr_direct_decl, AstTree}, scope_node::ScopeTree};
 
 pub fn compress_ast(ast_tree:&mut AstTree){
     let dfs_nodes = dfs(ast_tree, AST_ROOT);
@@ -68,6 +69,12 @@ pub fn process_any_stmt(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Scop
             process_expr(et_tree, ast_tree, scope_tree, any_stmt_node, scope_node, sep_node);
             sep_node
         }
+        RULE_constantExpression => {
+            // case 标签后面的常量表达式
+            let sep_node = add_node!({EtNodeType::new_sep(any_stmt_node).into()} to et_tree);
+            process_any_expr_inner_node(et_tree, ast_tree, scope_tree, node!(at any_stmt_node in ast_tree).child_vec[0], scope_node, sep_node);
+            sep_node
+        }
         RULE_declaration => {
             let sep_node = add_node!({EtNodeType::new_sep(any_stmt_node).into()} to et_tree);
             process_declaration(et_tree, ast_tree, scope_tree, any_stmt_node, scope_node, sep_node);
@@ -114,8 +121,18 @@ pub fn process_any_stmt(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Scop
     // let calcuate_expr_node = eval_et(et_tree, sep_node);
     // et_tree.remove_node(find_nodes!());
     // let _ = eval_et(et_tree, sep_node);
+    attach_src_loc_to_et(et_tree, ast_tree, sep_node);
     sep_node// } ,"parse_any_stmt finished")
 }
+/// 把 ast 节点上的源代码位置记录到由它生成的 et 节点上
+fn attach_src_loc_to_et(et_tree:&mut EtTree, ast_tree:&AstTree, sep_node:u32){
+    for et_node in dfs(et_tree, sep_node){
+        if node!(at et_node in et_tree).src_loc.is_none(){
+            let ast_node = node!(at et_node in et_tree).et_node_type.get_ast_node();
+            node_mut!(at et_node in et_tree).src_loc = node!(at ast_node in ast_tree).src_loc.clone();
+        }
+    }
+}
 fn is_any_expr_inner_node(ast_tree:&AstTree,ast_node:u32) -> bool{
     if node!(at ast_node in ast_tree).is_terminal{
         false
@@ -165,17 +182,11 @@ fn process_any_expr_inner_node(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tre
     }
 }
 fn process_parameter_declaration(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, parameter_decl_node:u32, scope_node:u32, parent_et_node:u32) {
-    let type_ast_node = find!(
-        rule RULE_declarationSpecifiers
-        // 这里我们假定只有一个 修饰符
-        then RULE_declarationSpecifier
-        finally RULE_typeSpecifier at parameter_decl_node in ast_tree
-    ).unwrap();
-    let direct_decl_node = find!(rule RULE_declarator 
-        finally RULE_directDeclarator
-        at parameter_decl_node in ast_tree
-    ).unwrap();
-    process_direct_decl(et_tree, ast_tree, scope_tree, direct_decl_node, type_ast_node,false, scope_node, parent_et_node);
+    let type_ast_node = find_type_specifier_of_decl(ast_tree, parameter_decl_node).unwrap();
+    let declarator_node = find!(rule RULE_declarator at parameter_decl_node in ast_tree).unwrap();
+    let direct_decl_node = find!(rule RULE_directDeclarator at declarator_node in ast_tree).unwrap();
+    let ptr_depth = get_ptr_depth_of_declarator(ast_tree, declarator_node);
+    process_direct_decl(et_tree, ast_tree, scope_tree, direct_decl_node, type_ast_node,false, ptr_depth, scope_node, parent_et_node);
 }
 fn process_declaration(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, decl_node:u32, scope_node:u32, parent_et_node:u32) {
     // only consider variable declaration
@@ -191,7 +202,8 @@ fn process_declaration(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Scope
         let temp_node = node!(at specifier_node in ast_tree).child_vec[0];
         if rule_id!(at temp_node in ast_tree) == RULE_typeQualifier{
             is_const = true;
-        }else {
+        }else if rule_id!(at temp_node in ast_tree) == RULE_typeSpecifier && op_type_ast_node.is_none() {
+            // 形如 unsigned int 的多个类型说明符以第一个为准，typedef 等存储类说明符不影响类型
             op_type_ast_node = Some(temp_node);
         }
     }
@@ -218,6 +230,7 @@ fn process_init_declarator(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&S
 
     // 考虑到direct_decl_node 可能有 数组运算符，所以要进行进一步解析
     let direct_decl_node = find!(rule RULE_directDeclarator at declarator_node in ast_tree).unwrap_or_else(||panic!("error when try unwrap its son as direct_declaration_node {}", declarator_node));
+    let ptr_depth = get_ptr_depth_of_declarator(ast_tree, declarator_node);
 
     let op_assign_node = find!(term Assign at init_decl_node in ast_tree);
 
@@ -226,37 +239,59 @@ fn process_init_declarator(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&S
         Some(assign_node) => {
             // 这里手动生成 et_assign_node 然后在它的左边放上新定义的变量
             let et_assign_node = add_node_with_edge!({EtNodeType::new_op_assign(assign_node).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree );
-            process_direct_decl(et_tree, ast_tree, scope_tree, direct_decl_node, type_ast_node, is_const,scope_node, et_assign_node);
+            process_direct_decl(et_tree, ast_tree, scope_tree, direct_decl_node, type_ast_node, is_const, ptr_depth,scope_node, et_assign_node);
             let initializer_node = find!(rule RULE_initializer at init_decl_node in ast_tree).unwrap();
-            process_initializer(et_tree, ast_tree, scope_tree, initializer_node, scope_node, et_assign_node);
+            match (find!(term LeftBracket at direct_decl_node in ast_tree), find_str_literal_of_initializer(ast_tree, initializer_node)){
+                (Some(_), Some(primary_expr_node)) => {
+                    // char s[] = "abc" 等价于 char s[] = {'a','b','c',0}
+                    let mut bytes = unescape_c_literal(&concat_str_literals(ast_tree, primary_expr_node));
+                    bytes.push(0);
+                    // 没有写长度的数组由字符串长度决定
+                    let et_array_decl_node = direct_child_nodes!(at et_assign_node in et_tree)[0];
+                    if direct_child_nodes!(at et_array_decl_node in et_tree).len() == 1{
+                        add_node_with_edge!({EtNodeType::new_literal(primary_expr_node, SymIdx::new(scope_node, bytes.len().to_string().leak()).as_rc()).into()} with_edge {EtEdgeType::Direct.into()} from et_array_decl_node in et_tree);
+                    }
+                    let et_array_wrapper_node = add_node_with_edge!({EtNodeType::new_op_array_wrapper(initializer_node).into()} with_edge {EtEdgeType::Direct.into()} from et_assign_node in et_tree);
+                    for b in bytes{
+                        add_node_with_edge!({EtNodeType::new_literal(primary_expr_node, SymIdx::new(scope_node, (b as i8).to_string().leak()).as_rc()).into()} with_edge {EtEdgeType::Direct.into()} from et_array_wrapper_node in et_tree);
+                    }
+                }
+                _ => process_initializer(et_tree, ast_tree, scope_tree, initializer_node, scope_node, et_assign_node),
+            }
         }
         None => {
             // 说明这是一个 类似于 int a; 或者 int a[3][3]; 的形式，没有初始值，因此只需要把变量放在 parent et node 下面就行了
-            process_direct_decl(et_tree, ast_tree, scope_tree, direct_decl_node, type_ast_node,is_const, scope_node, parent_et_node);
+            process_direct_decl(et_tree, ast_tree, scope_tree, direct_decl_node, type_ast_node,is_const, ptr_depth, scope_node, parent_et_node);
         }
     }
 }
 
-fn process_direct_decl(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, direct_decl_node:u32, type_ast_node:u32,is_const:bool, scope_node:u32, parent_et_node:u32) {
-    if let Some(_para_type_list_node) = find!(rule RULE_parameterTypeList at direct_decl_node in ast_tree) {
+fn process_direct_decl(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, direct_decl_node:u32, type_ast_node:u32,is_const:bool, ptr_depth:usize, scope_node:u32, parent_et_node:u32) {
+    if is_fn_ptr_direct_decl(ast_tree, direct_decl_node) {
+        // 形如 (*fp)(int) 的函数指针，声明的是括号里的 declarator ，它的类型在 gen_nhwc 中由 ast 推导
+        let sub_direct_decl_node = find!(rule RULE_directDeclarator at direct_decl_node in ast_tree).unwrap();
+        let declarator_node = find!(rule RULE_declarator at sub_direct_decl_node in ast_tree).unwrap();
+        let inner_direct_decl_node = find!(rule RULE_directDeclarator at declarator_node in ast_tree).unwrap();
+        process_direct_decl(et_tree, ast_tree, scope_tree, inner_direct_decl_node, type_ast_node, is_const, get_ptr_depth_of_declarator(ast_tree, declarator_node), scope_node, parent_et_node);
+    } else if let Some(_para_type_list_node) = find!(rule RULE_parameterTypeList at direct_decl_node in ast_tree) {
         // 这说明这是一个至少有一个参数的 函数 声明
     } else if let Some(ident_node) = find!(term Identifier at direct_decl_node in ast_tree) {
         // 这说明这只是一个简单的 ident
         let type_ast_node  = node!(at type_ast_node in ast_tree).child_vec[0];
-        if !node!(at type_ast_node in ast_tree).is_terminal{
+        if !node!(at type_ast_node in ast_tree).is_terminal && node!(at type_ast_node in ast_tree).rule_id != RULE_structOrUnionSpecifier && node!(at type_ast_node in ast_tree).rule_id != RULE_enumSpecifier && node!(at type_ast_node in ast_tree).rule_id != RULE_typedefName{
             panic!()
         }
-        process_ident(et_tree, ast_tree, scope_tree, ident_node, scope_node, parent_et_node, DeclOrDefOrUse::DeclDef { type_ast_node, is_const});
+        process_ident(et_tree, ast_tree, scope_tree, ident_node, scope_node, parent_et_node, DeclOrDefOrUse::DeclDef { type_ast_node, is_const, ptr_depth});
     } else if let Some(assign_expr_node) = find!(rule RULE_assignmentExpression at direct_decl_node in ast_tree) {
         // 说明这是一个数组，其中索引有表达式 assign_expr_node
         let et_direct_decl_node = add_node_with_edge!({EtNodeType::new_op_array_idx(direct_decl_node).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
         let sub_direct_decl_node = find!(rule RULE_directDeclarator at direct_decl_node in ast_tree).unwrap();
-        process_direct_decl(et_tree, ast_tree, scope_tree, sub_direct_decl_node, type_ast_node,is_const, scope_node, et_direct_decl_node);
+        process_direct_decl(et_tree, ast_tree, scope_tree, sub_direct_decl_node, type_ast_node,is_const, ptr_depth, scope_node, et_direct_decl_node);
         process_any_expr_inner_node(et_tree, ast_tree, scope_tree, assign_expr_node, scope_node, et_direct_decl_node);
     } else if let Some(sub_direct_decl) = find!(rule RULE_directDeclarator at direct_decl_node in ast_tree) {
         // implies that it is a parameter declarator in func args
         let et_direct_decl_node = add_node_with_edge!({EtNodeType::new_op_array_idx(direct_decl_node).into()} with_edge {EtEdgeType::Direct.into()}from parent_et_node in et_tree);
-        process_direct_decl(et_tree, ast_tree, scope_tree, sub_direct_decl, type_ast_node,is_const, scope_node, et_direct_decl_node);
+        process_direct_decl(et_tree, ast_tree, scope_tree, sub_direct_decl, type_ast_node,is_const, ptr_depth, scope_node, et_direct_decl_node);
     }
 }
 
@@ -346,7 +381,15 @@ fn process_assign_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Scope
 }
 fn process_cond_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, cond_expr_node:u32, scope_node:u32, parent_et_node:u32) {
     let logical_or_node = find!(rule RULE_logicalOrExpression at cond_expr_node in ast_tree).unwrap_or_else(||panic!("cond_node_id {}", cond_expr_node));
-    process_any_expr_inner_node(et_tree, ast_tree, scope_tree, logical_or_node, scope_node, parent_et_node);
+    // 形如 c ? a : b ，三个子节点依次为 c a b
+    if let (Some(expr_node),Some(else_cond_expr_node)) = (find!(rule RULE_expression at cond_expr_node in ast_tree),find!(rule RULE_conditionalExpression at cond_expr_node in ast_tree)){
+        let cond_et_node = add_node_with_edge!({EtNodeType::new_op_conditional(cond_expr_node).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
+        process_any_expr_inner_node(et_tree, ast_tree, scope_tree, logical_or_node, scope_node, cond_et_node);
+        process_expr(et_tree, ast_tree, scope_tree, expr_node, scope_node, cond_et_node);
+        process_cond_expr(et_tree, ast_tree, scope_tree, else_cond_expr_node, scope_node, cond_et_node);
+    }else{
+        process_any_expr_inner_node(et_tree, ast_tree, scope_tree, logical_or_node, scope_node, parent_et_node);
+    }
 }
 fn process_logical_or_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, logical_or_expr_node:u32, scope_node:u32, parent_et_node:u32) {
     let mut logical_and_expr_nodes = node!(at logical_or_expr_node in ast_tree).child_vec.iter().rev();
@@ -559,8 +602,8 @@ fn process_shift_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeT
         process_any_expr_inner_node(et_tree, ast_tree, scope_tree, first, scope_node, parent_et_node);
         return;
     }
-
-    let additive_expr_nodes = find_nodes!(rule RULE_additiveExpression at shift_expr_node in ast_tree);
+    // 子节点是 additiveExpression 和操作符交替出现的，移位是左结合的，处理方式和 additive expr 相同
+    let additive_expr_nodes = &node!(at shift_expr_node in ast_tree).child_vec;
     let get_expr_node_of_op_node = |op_node_index| {
         let op_node = additive_expr_nodes[op_node_index];
         match term_id!(at op_node in ast_tree) {
@@ -573,20 +616,21 @@ fn process_shift_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeT
     let mut op_last_ep_additive_node = None;
     for (index, &additive_node) in additive_expr_nodes.iter().enumerate() {
         if index%2 == 1{ continue;}
-        if index != additive_expr_nodes.len() - 1 {
-            if op_last_ep_additive_node.is_none() {
-                // 由于是第一个节点，我们需要基于第一个操作符创建节点
-                op_last_ep_additive_node = Some(add_node_with_edge!({get_expr_node_of_op_node(1)} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree));
-            } else {
-                let last_ep_additive_node = op_last_ep_additive_node.unwrap();
-                op_last_ep_additive_node = Some(add_node_with_edge!({get_expr_node_of_op_node(index+1)} with_edge {EtEdgeType::Direct.into()} from last_ep_additive_node in et_tree));
-            }
+        if index == 0 {
+            op_last_ep_additive_node = Some(add_node!({get_expr_node_of_op_node(1)} to et_tree));
             process_any_expr_inner_node(et_tree, ast_tree, scope_tree, additive_node, scope_node, op_last_ep_additive_node.unwrap());
         } else {
-            // 最后一个节点使用同样的方法连接
             process_any_expr_inner_node(et_tree, ast_tree, scope_tree, additive_node, scope_node, op_last_ep_additive_node.unwrap());
+            let last_ep_additive_node = op_last_ep_additive_node.unwrap();
+            if index != additive_expr_nodes.len()-1{
+                let added_shift_node = add_node!({get_expr_node_of_op_node(index+1)} to et_tree);
+                add_edge!({EtEdgeType::Direct.into()} from added_shift_node to last_ep_additive_node in et_tree);
+                op_last_ep_additive_node = Some(added_shift_node);
+            }
         }
     }
+    let last_ep_additive_node = op_last_ep_additive_node.unwrap();
+    add_edge!({EtEdgeType::Direct.into()} from parent_et_node to last_ep_additive_node in et_tree);
 }
 
 fn process_additive_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&ScopeTree, additive_expr_node:u32, scope_node:u32, parent_et_node:u32) {
@@ -647,12 +691,15 @@ fn process_multiplicative_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tre
 
     let get_expr_node_of_op_node = |node_idx| {
         let node = cast_expr_nodes[node_idx];
-        match term_id!(at node in ast_tree) {
+        let mut op_et_node:EtNode = match term_id!(at node in ast_tree) {
             Star => EtNodeType::new_op_mul(multiplicative_expr_node).into(),
             Div => EtNodeType::new_op_div(multiplicative_expr_node).into(),
             Mod => EtNodeType::new_op_mod(multiplicative_expr_node).into(),
             _ => panic!("Unexpected operator in multiplicative expression"),
-        }
+        };
+        // 记录运算符本身的位置，-Wdiv-by-zero 指向 / 或 % 而不是整个表达式的开头
+        op_et_node.src_loc = node!(at node in ast_tree).src_loc.clone();
+        op_et_node
     };
 
     let mut op_last_ep_cast_node = None;
@@ -708,19 +755,33 @@ pub fn process_unary_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Sc
         PlusPlus => EtNodeType::new_op_left_plusplus(unary_expr_node).into(),
         MinusMinus => EtNodeType::new_op_left_minusminus(unary_expr_node).into(),
         Minus => EtNodeType::new_op_negative(unary_expr_node).into(),
-        Tilde => EtNodeType::new_op_logical_not(unary_expr_node).into(),
+        Tilde => EtNodeType::new_op_bitwise_not(unary_expr_node).into(),
         Not => EtNodeType::new_op_logical_not(unary_expr_node).into(),
         _ => panic!("Unexpected unary operator"),
     };
     // 检查是否存在一元操作符
     if let Some(unary_operator_node) = find!(rule RULE_unaryOperator at unary_expr_node in ast_tree) {
         let unary_operator_term_node = node!(at unary_operator_node in ast_tree).child_vec[0];
+        let child_cast_expr_node = find!(rule RULE_castExpression at unary_expr_node in ast_tree).unwrap();
+        // 负号后面直接跟数值字面量时合并成一个负的字面量，这样 -2147483648 才是 int 而不是对 long 取负
+        if let (Minus, Some(const_text)) = (term_id!(at unary_operator_term_node in ast_tree), find_single_constant_text(ast_tree, child_cast_expr_node)) {
+            if !const_text.starts_with('\'') {
+                let sym_name = canonical_literal_name(&const_text, true).unwrap_or_else(|e| panic!("{}", e));
+                add_node_with_edge!({EtNodeType::new_literal(unary_expr_node, SymIdx::new(scope_node, sym_name).as_rc()).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
+                return;
+            }
+        }
         // .unwrap_or_else(||panic!("在 unaryOperator {} 下找不到 unaryOpertor term",unary_operator_node));
         let op_node = add_node_with_edge!({get_expr_node_of_unary_op_node(unary_operator_term_node)} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
 
         // 一元操作符后跟的是 unaryExpression
-        let child_cast_expr_node = find!(rule RULE_castExpression at unary_expr_node in ast_tree).unwrap();
-        process_cast_expr(et_tree, ast_tree, scope_tree, child_cast_expr_node, scope_node, op_node);
+        if matches!(term_id!(at unary_operator_term_node in ast_tree), And) {
+            // & 需要的是操作数的地址而不是值，因此与赋值号左边一样按 Def 处理
+            let child_unary_expr_node = find!(rule RULE_unaryExpression at child_cast_expr_node in ast_tree).unwrap();
+            process_unary_expr(et_tree, ast_tree, scope_tree, child_unary_expr_node, scope_node, op_node, DeclOrDefOrUse::Def);
+        }else{
+            process_cast_expr(et_tree, ast_tree, scope_tree, child_cast_expr_node, scope_node, op_node);
+        }
     } else if let Some(postfix_expr_node) = find!(rule RULE_postfixExpression at unary_expr_node in ast_tree) {
         // 如果是 postfixExpression，直接处理
         process_postfix_expr(et_tree, ast_tree, scope_tree, postfix_expr_node, scope_node, parent_et_node,def_or_use);
@@ -744,18 +805,20 @@ fn process_postfix_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Scop
         process_arg_expr_list(et_tree, ast_tree, scope_tree, arg_expr_list_node, scope_node, et_call_node);
     } else if let Some(_dot_node) = find!(term Dot at postfix_expr_node in ast_tree) {
         //说明这是个结构体成员访问的语法 dot member access
-        let et_dot_member_node = add_node_with_edge!({EtNodeType::new_op_dot_member( postfix_expr_node).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
-        let postfix_expr_node = find!(rule RULE_postfixExpression at postfix_expr_node in ast_tree).unwrap();
+        // 成员名不是一个符号，因此直接存放在 et_node 的 text 中
         let ident_node = find!(term Identifier at postfix_expr_node in ast_tree).unwrap();
+        let member_name = node!(at ident_node in ast_tree).op_text.clone().unwrap();
+        let et_dot_member_node = add_node_with_edge!({EtNodeType::new_op_dot_member( postfix_expr_node,member_name).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
+        let postfix_expr_node = find!(rule RULE_postfixExpression at postfix_expr_node in ast_tree).unwrap();
         process_postfix_expr(et_tree, ast_tree, scope_tree, postfix_expr_node, scope_node, et_dot_member_node,def_or_use);
-        process_ident(et_tree, ast_tree, scope_tree, ident_node, scope_node, et_dot_member_node, DeclOrDefOrUse::Use);
     } else if let Some(_arrow_node) = find!(term Arrow at postfix_expr_node in ast_tree) {
         //说明这是个结构体成员访问的语法 arrow member access
-        let et_arrow_member_node = add_node_with_edge!({EtNodeType::new_op_arrow_member( postfix_expr_node).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
-        let postfix_expr_node = find!(rule RULE_postfixExpression at postfix_expr_node in ast_tree).unwrap();
         let ident_node = find!(term Identifier at postfix_expr_node in ast_tree).unwrap();
-        process_postfix_expr(et_tree, ast_tree, scope_tree, postfix_expr_node, scope_node, et_arrow_member_node,def_or_use);
-        process_ident(et_tree, ast_tree, scope_tree, ident_node, scope_node, et_arrow_member_node, DeclOrDefOrUse::Use);
+        let member_name = node!(at ident_node in ast_tree).op_text.clone().unwrap();
+        let et_arrow_member_node = add_node_with_edge!({EtNodeType::new_op_arrow_member( postfix_expr_node,member_name).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
+        let postfix_expr_node = find!(rule RULE_postfixExpression at postfix_expr_node in ast_tree).unwrap();
+        // -> 左边的指针本身只会被读取，即使整个表达式被赋值
+        process_postfix_expr(et_tree, ast_tree, scope_tree, postfix_expr_node, scope_node, et_arrow_member_node,DeclOrDefOrUse::Use);
     } else if let Some(_string_node) = find!(term PlusPlus at postfix_expr_node in ast_tree) {
         //说明这是个++的语法
         let et_plusplus_node = add_node_with_edge!({EtNodeType::new_op_right_plusplus( postfix_expr_node).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
@@ -787,7 +850,32 @@ fn process_primary_expr(et_tree:&mut EtTree, ast_tree:&AstTree, scope_tree:&Scop
         // println!("expr found under {}",primary_expr_node );
         process_expr(et_tree, ast_tree, scope_tree, expr_node, scope_node, parent_et_node);
     } else if let Some(string_node) = find!(term StringLiteral at primary_expr_node in ast_tree) {
-        process_literal(et_tree, ast_tree, scope_tree, string_node, scope_node, parent_et_node);
+        let literal_symidx = SymIdx::new(scope_node, format!("\"{}\"",concat_str_literals(ast_tree, primary_expr_node)).leak());
+        add_node_with_edge!({EtNodeType::new_literal(string_node, literal_symidx.as_rc()).into()} with_edge {EtEdgeType::Direct.into()} from parent_et_node in et_tree);
+    }
+}
+
+/// 相邻的字符串字面量 "ab" "cd" 拼接成一个，返回去掉引号(以及编码前缀)后未转义的内容
+fn concat_str_literals(ast_tree:&AstTree, primary_expr_node:u32) -> String{
+    find_nodes!(term StringLiteral at primary_expr_node in ast_tree).iter().map(|&string_node| {
+        let text = node!(at string_node in ast_tree).op_text.clone().unwrap();
+        let text = &text[text.find('"').unwrap()+1..];
+        text[..text.len()-1].to_string()
+    }).collect()
+}
+/// 如果 initializer 只是一个字符串字面量，返回它所在的 primaryExpression
+fn find_str_literal_of_initializer(ast_tree:&AstTree, initializer_node:u32) -> Option<u32>{
+    let mut cur_node = initializer_node;
+    loop{
+        let child_vec = &node!(at cur_node in ast_tree).child_vec;
+        let first_child = child_vec.first().copied().unwrap_or(cur_node);
+        if !node!(at cur_node in ast_tree).is_terminal && rule_id!(at cur_node in ast_tree) == RULE_primaryExpression{
+            return if find!(term StringLiteral at cur_node in ast_tree).is_some() { Some(cur_node) } else { None };
+        }else if child_vec.len() == 1 && !node!(at first_child in ast_tree).is_terminal{
+            cur_node = first_child;
+        }else{
+            return None;
+        }
     }
 }
 
@@ -820,6 +908,14 @@ fn process_ident(et_tree:&mut EtTree, ast_tree:&AstTree, _scope_tree:&ScopeTree,
 }
 fn process_literal(et_tree:&mut EtTree, ast_tree:&AstTree, _scope_tree:&ScopeTree, literal_node:u32, scope_node:u32, parent_et_node:u32) {
     let sym_name = node!(at literal_node in ast_tree).op_text.clone().unwrap().leak();
+    // 字符常量 'a' 的类型是 int ，直接换成它的数值
+    let sym_name:&'static str = if sym_name.starts_with('\'') {
+        let bytes = unescape_c_literal(&sym_name[1..sym_name.len()-1]);
+        (bytes[0] as i8 as i32).to_string().leak()
+    }else{
+        // 数值字面量换成规范名，这样 0x10 与 16 是同一个字面量
+        canonical_literal_name(sym_name, false).unwrap_or_else(|e| panic!("{}", e))
+    };
     // let sym_idx = SymbolIndex::new(scope_node, symbol_name);
 
     let literal_symidx = SymIdx::new(scope_node, sym_name);
@@ -852,3 +948,52 @@ fn process_literal(et_tree:&mut EtTree, ast_tree:&AstTree, _scope_tree:&ScopeTre
 //     // et_node
 
 // }
+
+/// 把 C 字面量引号内的内容转义为字节序列，支持 \n \t 等简单转义以及八进制 \ooo 与十六进制 \xhh
+pub fn unescape_c_literal(text:&str) -> Vec<u8>{
+    let bytes = text.as_bytes();
+    let mut ret = vec![];
+    let mut i = 0;
+    while i < bytes.len(){
+        if bytes[i] != b'\\' || i+1 == bytes.len(){
+            ret.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        i += 1;
+        match bytes[i]{
+            b'n' => ret.push(b'\n'),
+            b't' => ret.push(b'\t'),
+            b'r' => ret.push(b'\r'),
+            b'a' => ret.push(0x07),
+            b'b' => ret.push(0x08),
+            b'f' => ret.push(0x0c),
+            b'v' => ret.push(0x0b),
+            b'0'..=b'7' => {
+                let mut val:u32 = 0;
+                let mut len = 0;
+                while len < 3 && i < bytes.len() && (b'0'..=b'7').contains(&bytes[i]){
+                    val = val*8 + (bytes[i]-b'0') as u32;
+                    i += 1;
+                    len += 1;
+                }
+                ret.push(val as u8);
+                continue;
+            }
+            b'x' => {
+                let mut val:u32 = 0;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_hexdigit(){
+                    val = val*16 + (bytes[i] as char).to_digit(16).unwrap();
+                    i += 1;
+                }
+                ret.push(val as u8);
+                continue;
+            }
+            // \\ \' \" \? 以及未知转义都保留原字符
+            c => ret.push(c),
+        }
+        i += 1;
+    }
+    ret
+}
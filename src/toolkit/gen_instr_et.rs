@@ -210,6 +210,11 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                         let literal_et_struct:EtNode = EtNodeType::new_literal(0, rc_val_symidx.clone()).into();
                         let literal_et_node = add_node!({literal_et_struct} to instr_et);
                         literal_et_node
+                    }else if rc_val_symidx.as_ref_borrow().is_global_ptr(){
+                        // 例如 tab[0] = f 或者 *pp = &g ，函数和全局变量的地址不由任何指令定义
+                        let mut ptr_et_struct:EtNode = EtNodeType::new_symbol(0, rc_val_symidx.clone(), DeclOrDefOrUse::Use).into();
+                        ptr_et_struct.equivalent_symidx_vec.push(rc_val_symidx.clone());
+                        add_node!({ptr_et_struct} to instr_et)
                     }else {
                         panic!();
                     }
@@ -225,7 +230,7 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                 //获取索引
                 //构建数组名称节点
                 let rc_array_or_ptr = if *symtab.get(&rc_array_or_ptr.as_ref_borrow().to_src_symidx()).get_is_global() 
-                || symtab.get(&rc_array_or_ptr.as_ref_borrow().to_src_symidx()).get_type().is_array(){
+                || symtab.get(&rc_array_or_ptr.as_ref_borrow().to_src_symidx()).get_type().is_aggregate(){
                     // if it is global array
                     // if it is a local array
                     rc_array_or_ptr.as_ref_borrow().to_src_symidx().as_rc()
@@ -251,8 +256,12 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                     let rc_dim_literal = <Option<RcSymIdx> as Clone>::clone(&dim).unwrap();
                     let dim_literal = rc_dim_literal.as_ref_borrow();
                     let dim_ast = get_ast_from_symidx!(find dim_literal with scope_tree);
-                    //创建数组的[]节点
-                    let array_idx_et_node_struct:EtNode = EtNodeType::new_op_array_idx(dim_ast).into();
+                    //创建数组的[]节点 ，对 struct 的成员偏移则创建 . 节点以免与数组下标的 hash 相同
+                    let array_idx_et_node_struct:EtNode = if array_ty.is_struct() || array_ty.is_union(){
+                        EtNodeType::new_op_dot_member(dim_ast, String::new()).into()
+                    }else{
+                        EtNodeType::new_op_array_idx(dim_ast).into()
+                    };
                     let array_idx_et_node = add_node!({array_idx_et_node_struct} to instr_et);
                     add_edge!({EtEdgeType::Direct.into()} from array_idx_et_node to last_et_node in instr_et);
                     //创建具体dim内容节点
@@ -285,16 +294,16 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                         let arith_et_struct:EtNode = EtNodeType::new_op_mul(0).into();
                         process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                     },
-                    super::nhwc_instr::ArithOp::Div { a, b, vartype:_et_node } => {
-                        let arith_et_struct:EtNode = EtNodeType::new_op_div(0).into();
+                    super::nhwc_instr::ArithOp::Div { a, b, vartype } => {
+                        let arith_et_struct:EtNode = if vartype.is_u_32() { EtNodeType::new_op_unsigned_div(0).into() } else { EtNodeType::new_op_div(0).into() };
                         process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                     },
                     super::nhwc_instr::ArithOp::Sub { a, b, vartype:_ } =>{
                         let arith_et_struct:EtNode = EtNodeType::new_op_sub(0).into();
                         process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                     },
-                    super::nhwc_instr::ArithOp::Mod { a, b, vartype:_ } => {
-                        let arith_et_struct:EtNode = EtNodeType::new_op_mod(0).into();
+                    super::nhwc_instr::ArithOp::Mod { a, b, vartype } => {
+                        let arith_et_struct:EtNode = if vartype.is_u_32() { EtNodeType::new_op_unsigned_mod(0).into() } else { EtNodeType::new_op_mod(0).into() };
                         process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                     },
                     super::nhwc_instr::ArithOp::Icmp { plan, a, b, vartype } => {
@@ -308,19 +317,19 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                                 process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                             },
                             super::nhwc_instr::IcmpPlan::Ugt => {
-                                let arith_et_struct:EtNode = EtNodeType::new_op_greater_than(0).into();
+                                let arith_et_struct:EtNode = EtNodeType::new_op_unsigned_greater_than(0).into();
                                 process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                             },
                             super::nhwc_instr::IcmpPlan::Uge => {
-                                let arith_et_struct:EtNode = EtNodeType::new_op_greater_than_or_equal(0).into();
+                                let arith_et_struct:EtNode = EtNodeType::new_op_unsigned_greater_than_or_equal(0).into();
                                 process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                             },
                             super::nhwc_instr::IcmpPlan::Ult => {
-                                let arith_et_struct:EtNode = EtNodeType::new_op_less_than(0).into();
+                                let arith_et_struct:EtNode = EtNodeType::new_op_unsigned_less_than(0).into();
                                 process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                             },
                             super::nhwc_instr::IcmpPlan::Ule => {
-                                let arith_et_struct:EtNode = EtNodeType::new_op_less_than_or_equal(0).into();
+                                let arith_et_struct:EtNode = EtNodeType::new_op_unsigned_less_than_or_equal(0).into();
                                 process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
                             },
                             super::nhwc_instr::IcmpPlan::Sgt => {
@@ -377,6 +386,30 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                         let arith_et_struct:EtNode = EtNodeType::new_op_logical_or(0).into();
                         process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap)
                     },
+                    super::nhwc_instr::ArithOp::BitAnd { a, b, vartype:_ } => {
+                        let arith_et_struct:EtNode = EtNodeType::new_op_bitwise_and(0).into();
+                        process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
+                    },
+                    super::nhwc_instr::ArithOp::BitOr { a, b, vartype:_ } => {
+                        let arith_et_struct:EtNode = EtNodeType::new_op_bitwise_or(0).into();
+                        process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
+                    },
+                    super::nhwc_instr::ArithOp::BitXor { a, b, vartype:_ } => {
+                        let arith_et_struct:EtNode = EtNodeType::new_op_bitwise_xor(0).into();
+                        process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
+                    },
+                    super::nhwc_instr::ArithOp::Shl { a, b, vartype:_ } => {
+                        let arith_et_struct:EtNode = EtNodeType::new_op_left_shift(0).into();
+                        process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
+                    },
+                    super::nhwc_instr::ArithOp::Ashr { a, b, vartype:_ } => {
+                        let arith_et_struct:EtNode = EtNodeType::new_op_right_shift(0).into();
+                        process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
+                    },
+                    super::nhwc_instr::ArithOp::Lshr { a, b, vartype:_ } => {
+                        let arith_et_struct:EtNode = EtNodeType::new_op_logical_right_shift(0).into();
+                        process_arith_et(rc_lhs,a, b,instr,arith_et_struct,instr_et,rc_symidx_et_node_map,scope_tree,instr_et_node_bimap )
+                    },
                     super::nhwc_instr::ArithOp::LogicNot { a: rc_a, vartype } => {
                         let mut arith_et_struct:EtNode = EtNodeType::new_op_logical_not(0).into();
                         arith_et_struct.equivalent_symidx_vec.push(rc_lhs.clone());
@@ -395,6 +428,25 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                         instr_et_node_bimap.insert(instr,arith_et_node);
                         arith_et_node
                     },
+                    super::nhwc_instr::ArithOp::Select { cond, a, b, vartype:_ } => {
+                        let mut arith_et_struct:EtNode = EtNodeType::new_op_conditional(0).into();
+                        arith_et_struct.equivalent_symidx_vec.push(rc_lhs.clone());
+                        let arith_et_node = add_node!({arith_et_struct} to instr_et);
+                        //三个操作数依次连边，没有的话添加节点
+                        for rc_operand in [cond,a,b]{
+                            if let Some(&operand_node) = rc_symidx_et_node_map.get(rc_operand){
+                                add_edge!({EtEdgeType::Direct.into()} from arith_et_node to operand_node in instr_et);
+                            }else{
+                                let mut et_node_struct:EtNode = EtNodeType::new_symbol(0,rc_operand.clone(),super::et_node::DeclOrDefOrUse::Use).into();
+                                et_node_struct.equivalent_symidx_vec.push(rc_operand.clone());
+                                let operand_et = add_node_with_edge!({ et_node_struct } with_edge {EtEdgeType::Direct.into()} from arith_et_node in instr_et);
+                                rc_symidx_et_node_map.insert(rc_operand.clone(), operand_et);
+                            }
+                        }
+                        rc_symidx_et_node_map.insert(rc_lhs.clone(), arith_et_node);
+                        instr_et_node_bimap.insert(instr,arith_et_node);
+                        arith_et_node
+                    },
                 };
                 instr_mut!(at instr in instr_slab).add_cor_instr_et_node(instr_et_node);
             },
@@ -416,6 +468,16 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                         rc_symidx_et_node_map.insert(rc_lhs.clone(), literal_et_node);
                         instr_et_node_bimap.insert(instr,literal_et_node);
                         literal_et_node
+                    }else if rhs.is_global_ptr(){
+                        // 例如 p = &g ，全局变量的地址不由任何指令定义
+                        let mut ptr_et_struct:EtNode = EtNodeType::new_symbol(0, rc_rhs.clone(), DeclOrDefOrUse::Use).into();
+                        ptr_et_struct.equivalent_symidx_vec.push(rc_rhs.clone());
+                        ptr_et_struct.equivalent_symidx_vec.push(rc_lhs.clone());
+                        let ptr_et_node = add_node!({ptr_et_struct} to instr_et);
+                        rc_symidx_et_node_map.insert(rc_rhs.clone(), ptr_et_node);
+                        rc_symidx_et_node_map.insert(rc_lhs.clone(), ptr_et_node);
+                        instr_et_node_bimap.insert(instr,ptr_et_node);
+                        ptr_et_node
                     }else {
                         panic!();
                     }
@@ -484,7 +546,8 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                         if let Some(&et_node) = rc_symidx_et_node_map.get(&phi_pair.symidx){
                             if node!(at et_node in instr_et).et_node_type.is_literal(){
                                 if last_et_node.is_some(){
-                                    if last_et_node.unwrap() == et_node{
+                                    // 只有所有来源都是同一个字面量时才能合并，不同字面量之间不能合并
+                                    if last_et_node.unwrap() != et_node{
                                         flag = false;
                                         break;
                                     }
@@ -511,6 +574,7 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
                     }else {
                         let literal_et_node = last_et_node.unwrap();
                         node_mut!(at literal_et_node in instr_et).equivalent_symidx_vec.push(rc_lhs.clone());
+                        rc_symidx_et_node_map.insert(rc_lhs.clone(), literal_et_node);
                     }
                 }
             },
@@ -637,11 +701,14 @@ pub fn parse_instr_list_to_et(instrs:impl Iterator<Item = usize>, instr_et:&mut
             },
             super::nhwc_instr::NhwcInstrType::TranType { lhs: rc_lhs, op } => {
                 match op{
-                    super::nhwc_instr::Trans::Fptosi { float_symidx } => {
+                    super::nhwc_instr::Trans::Fptosi { float_symidx } | super::nhwc_instr::Trans::Fptoui { float_symidx } | super::nhwc_instr::Trans::Fptosi64 { float_symidx }
+                    | super::nhwc_instr::Trans::Fpext { float_symidx } | super::nhwc_instr::Trans::Fptrunc { double_symidx:float_symidx } | super::nhwc_instr::Trans::FptosiF64 { double_symidx:float_symidx } => {
                         let et_node =process_trans_et(rc_lhs,float_symidx,symtab, instr, rc_symidx_et_node_map,instr_et_node_bimap,scope_tree, 0,  instr_et)?;
                         instr_mut!(at instr in instr_slab).add_cor_instr_et_node(et_node);
                     },
-                    super::nhwc_instr::Trans::Sitofp { int_symidx } => {
+                    super::nhwc_instr::Trans::Sitofp { int_symidx } | super::nhwc_instr::Trans::Uitofp { int_symidx } | super::nhwc_instr::Trans::Sext { int_symidx }
+                    | super::nhwc_instr::Trans::ZextU32 { uint_symidx:int_symidx } | super::nhwc_instr::Trans::Trunc { long_symidx:int_symidx } | super::nhwc_instr::Trans::TruncI8 { int_symidx } | super::nhwc_instr::Trans::Sitofp64 { long_symidx:int_symidx }
+                    | super::nhwc_instr::Trans::SitofpF64 { int_symidx } => {
                         let et_node =process_trans_et(rc_lhs,int_symidx,symtab, instr, rc_symidx_et_node_map,instr_et_node_bimap,scope_tree, 0,  instr_et)?;
                         instr_mut!(at instr in instr_slab).add_cor_instr_et_node(et_node);
                     },
@@ -1106,4 +1173,30 @@ pub fn get_array_symbol_of_array_index_op_or_symbol_node(et_node:u32, instr_et:&
         }
         _ => panic!("find no array rc_symidx of this array index node:{}",et_node),
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::{passes::{ast2cfg_pass::Ast2CfgPass, ast2st_pass::Ast2StPass, call_graph_pass::CallGraphPass, cfg2ncfg_pass::Cfg2NcfgPass, chi_mu_insertion_pass::ChiMuInsertionPass, code2ast_pass::Code2AstPass, gvngcm_pass::GvnGcmPass, ncfg2djg_pass::Ncfg2DjgPass, preprocess_pass::PreprocessPass, semantic_check_pass::SemanticCheckPass, ssa_pass::SsaPass}, toolkit::pass_manager::{Pass, PassManager}, Args};
+
+    /// 用 goto 跳到 label 处时，label 所在的 bb 被 Branch 直接支配，gvn 访问它时 phi 的两个来源都已经折叠成了不同的字面量 3 与 4  
+    /// 此时 phi 不能合并到任何一个字面量上，否则 a*2 中的 a 找不到对应的 et node
+    #[test]
+    fn test_phi_of_different_literals() {
+        let path = std::env::temp_dir().join(format!("phi_of_different_literals_{}.c", std::process::id()));
+        fs::write(&path, "int main(){ int a=3; if (a==1) goto x; a=a+1; x: a=a*2; return a; }\n").unwrap();
+        let mut pass_manager = PassManager::new(Args { input:path.clone(), parser:"antlr".to_string(), ..Default::default() });
+        let passes:Vec<Box<dyn Pass>> = vec![
+            Box::new(PreprocessPass::new(false)), Box::new(Code2AstPass::new(false)), Box::new(Ast2StPass::new(false)), Box::new(SemanticCheckPass::new()),
+            Box::new(Ast2CfgPass::new(false)), Box::new(Cfg2NcfgPass::new(false, false)), Box::new(CallGraphPass::new(false)), Box::new(ChiMuInsertionPass::new(false)),
+            Box::new(Ncfg2DjgPass::new(false)), Box::new(SsaPass::new(false, false)), Box::new(GvnGcmPass::new(false, false)),
+        ];
+        for pass in passes {
+            pass_manager.add_pass(pass);
+        }
+        let rst = pass_manager.execute_passes();
+        fs::remove_file(&path).unwrap();
+        assert!(rst.is_ok());
+    }
+}
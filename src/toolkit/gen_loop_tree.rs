@@ -12,37 +12,63 @@ pub fn dfs_inner_while_struct(cfg_start_node:u32,cfg_stop_while_node:u32,cfg_sto
         CfgEdgeType::IfFalse {  } => 4,
         CfgEdgeType::Direct {  } => 7,
         CfgEdgeType::IfTrue {  } => 2,
-        CfgEdgeType::BodyTail {  } => -6,
+        // do while 的条件节点只能从循环体尾部到达
+        CfgEdgeType::BodyTail {  } => if is_do_while_node(e.target().index() as u32, cfg_graph) { 1 } else { -6 },
+        CfgEdgeType::Case {  } => 3,
     },
     |e| e == cfg_stop_out_node||e == cfg_stop_while_node);
     dfs_vec
 }
 
+fn is_do_while_node(cfg_node:u32,cfg_graph:&CfgGraph) -> bool{
+    node!(at cfg_node in cfg_graph).cfg_node_type.is_do_while_loop()
+}
+fn is_loop_node(cfg_type:&CfgNodeType) -> bool{
+    cfg_type.is_while_loop() || cfg_type.is_do_while_loop()
+}
+
+///do while 的循环体在 dfs 中先于 DoWhileLoop 节点被访问，把它们从当前层去掉，交给 process_loop_node 处理
+pub fn remove_do_while_body_nodes(dfs_vec:Vec<u32>,cfg_graph:&CfgGraph) -> Vec<u32>{
+    let mut body_nodes = vec![];
+    for &cfg_node in dfs_vec.iter().filter(|&&n| is_do_while_node(n, cfg_graph)){
+        let body_head = direct_child_node!(at cfg_node in cfg_graph with_predicate { |e:&EdgeReference<CfgEdge>| e.weight().cfg_edge_type.is_body_head()});
+        let out_node = get_out_node_of_do_while(cfg_node, cfg_graph);
+        body_nodes.extend(dfs_inner_while_struct(body_head, cfg_node, out_node, cfg_graph));
+    }
+    dfs_vec.into_iter().filter(|n| !body_nodes.contains(n)).collect()
+}
+///do while 节点不在循环体的 BodyHead 边就是跳出循环的边
+fn get_out_node_of_do_while(cfg_do_while_node:u32,cfg_graph:&CfgGraph) -> u32{
+    direct_child_node!(at cfg_do_while_node in cfg_graph with_predicate { |e:&EdgeReference<CfgEdge>| !e.weight().cfg_edge_type.is_body_head()})
+}
+
 ///递归处理嵌套的每层while节点，在loop_tree上一个父亲节点新增一个loop节点，while内的节点（不包括下一层while）都连在这个loop上
 pub fn process_loop_node(cfg_loop_node:u32,out_loop_node:u32,parent_loop_node:u32,cfg_graph:&CfgGraph,loop_tree:&mut LoopTree) -> Result<()>{
     //从while内的bodyhead的边的节点开始
     let inner_start = direct_child_node!(at cfg_loop_node in cfg_graph with_predicate { |e:&EdgeReference<CfgEdge>| e.weight().cfg_edge_type.is_body_head()});
-    let inner_cfgnode_vec = dfs_inner_while_struct(inner_start,cfg_loop_node,out_loop_node,cfg_graph);
+    let inner_cfgnode_vec = remove_do_while_body_nodes(dfs_inner_while_struct(inner_start,cfg_loop_node,out_loop_node,cfg_graph),cfg_graph);
     // println!("内层：{:?}",inner_cfgnode_vec);
     for window_nodes in inner_cfgnode_vec.windows(2){
         let current_inner_cfgnode = window_nodes[0];
         let next_inner_cfgnode = window_nodes[1];
         // println!("当前节点：{}",current_inner_cfgnode);
         let cfg_type = &node!(at current_inner_cfgnode in cfg_graph).cfg_node_type;
-        if let CfgNodeType::WhileLoop { ast_expr_node:_ } = cfg_type.clone(){
+        if is_loop_node(cfg_type){
             let loop_node = add_node_with_edge!({LoopNode::new_loop_node()} from parent_loop_node in loop_tree);
             add_node_with_edge!({LoopNode::new_terminal_node(current_inner_cfgnode,cfg_type.clone())} from loop_node in loop_tree);
-            process_loop_node(current_inner_cfgnode,next_inner_cfgnode,loop_node,cfg_graph,loop_tree)?;
+            let out_node = if cfg_type.is_do_while_loop() { get_out_node_of_do_while(current_inner_cfgnode, cfg_graph) } else { next_inner_cfgnode };
+            process_loop_node(current_inner_cfgnode,out_node,loop_node,cfg_graph,loop_tree)?;
         }else{
             add_node_with_edge!({LoopNode::new_terminal_node(current_inner_cfgnode, cfg_type.clone())} from parent_loop_node in loop_tree);
         }
     }
     if let Some(&last_node) = inner_cfgnode_vec.last(){
         let cfg_type = &node!(at last_node in cfg_graph).cfg_node_type;
-        if let CfgNodeType::WhileLoop { ast_expr_node:_ } = cfg_type.clone(){
+        if is_loop_node(cfg_type){
             let loop_node = add_node_with_edge!({LoopNode::new_loop_node()} from parent_loop_node in loop_tree);
             add_node_with_edge!({LoopNode::new_terminal_node(last_node,cfg_type.clone())} from loop_node in loop_tree);
-            process_loop_node(last_node,last_node,loop_node,cfg_graph,loop_tree)?;
+            let out_node = if cfg_type.is_do_while_loop() { get_out_node_of_do_while(last_node, cfg_graph) } else { last_node };
+            process_loop_node(last_node,out_node,loop_node,cfg_graph,loop_tree)?;
         }else{
             add_node_with_edge!({LoopNode::new_terminal_node(last_node, cfg_type.clone())} from parent_loop_node in loop_tree);
         }
@@ -64,19 +90,22 @@ pub fn parse_cfg2loop_tree(loop_tree:&mut LoopTree,cfg_graph:&CfgGraph) -> Resul
             CfgEdgeType::IfFalse {  } => 6,
             CfgEdgeType::Direct {  } => 1,
             CfgEdgeType::IfTrue {  } => 7,
-            CfgEdgeType::BodyTail {  } => -1,
+            CfgEdgeType::BodyTail {  } => if is_do_while_node(e.target().index() as u32, cfg_graph) { 1 } else { -1 },
+            CfgEdgeType::Case {  } => 3,
         });
+        let dfs_vec = remove_do_while_body_nodes(dfs_vec, cfg_graph);
         // println!("最外层：{:?}",dfs_vec);
         for windows_nodes in dfs_vec.windows(2) {
             let current_cfg_node = windows_nodes[0];
             let next_cfg_node = windows_nodes[1];
             let cfg_type   = &node!(at current_cfg_node in cfg_graph).cfg_node_type;
             //这里找到的是第一层循环
-            if let CfgNodeType::WhileLoop { ast_expr_node:_ } = cfg_type{
+            if is_loop_node(cfg_type){
                 let while_loop_node = add_node_with_edge!({LoopNode::new_loop_node()} from func_node in loop_tree);
                 add_node_with_edge!({LoopNode::new_terminal_node(current_cfg_node,cfg_type.clone())} from while_loop_node in loop_tree);
                 //处理while内部
-                process_loop_node(current_cfg_node,next_cfg_node,while_loop_node,cfg_graph,loop_tree)?;
+                let out_node = if cfg_type.is_do_while_loop() { get_out_node_of_do_while(current_cfg_node, cfg_graph) } else { next_cfg_node };
+                process_loop_node(current_cfg_node,out_node,while_loop_node,cfg_graph,loop_tree)?;
             }else{
                 add_node_with_edge!({LoopNode::new_terminal_node(current_cfg_node, cfg_type.clone())} from func_node in loop_tree);
             }
@@ -84,11 +113,12 @@ pub fn parse_cfg2loop_tree(loop_tree:&mut LoopTree,cfg_graph:&CfgGraph) -> Resul
         if let Some(&last_node) = dfs_vec.last(){
             let cfg_type   = &node!(at last_node in cfg_graph).cfg_node_type;
             //这里找到的是第一层循环
-            if let CfgNodeType::WhileLoop { ast_expr_node:_ } = cfg_type{
+            if is_loop_node(cfg_type){
                 let while_loop_node = add_node_with_edge!({LoopNode::new_loop_node()} from func_node in loop_tree);
                 add_node_with_edge!({LoopNode::new_terminal_node(last_node,cfg_type.clone())} from while_loop_node in loop_tree);
                 //处理while内部
-                process_loop_node(last_node,last_node,while_loop_node,cfg_graph,loop_tree)?;
+                let out_node = if cfg_type.is_do_while_loop() { get_out_node_of_do_while(last_node, cfg_graph) } else { last_node };
+                process_loop_node(last_node,out_node,while_loop_node,cfg_graph,loop_tree)?;
             }else{
                 add_node_with_edge!({LoopNode::new_terminal_node(last_node, cfg_type.clone())} from func_node in loop_tree);
             }
@@ -21,17 +21,19 @@ use super::etc::dfs_with_priority;
 use super::eval_et::{self, can_eliminate_despite_array_idx_and_call};
 use super::field::{ArrayEleMap, Value};
 use super::{gen_et, symbol, symtab};
-use super::gen_scope::get_while_scope_of_scope_node;
+use super::gen_cfg::{get_label_name_of_ast_node, is_goto_stmt};
+use super::gen_scope::{find_typedef_type, process_fn_type, get_break_scope_of_scope_node, get_while_scope_of_scope_node};
+use super::diagnostic::Diagnostic;
 use super::mem_layout::MemLayout;
-use super::nhwc_instr::{CmpPlan, JumpOp};
+use super::nhwc_instr::{CmpPlan, ComparedPair, JumpOp};
 use super::symtab::{NzU32Op, SymIdx, WithBorrow};
 use super::{cfg_edge::CfgEdge, nhwc_instr::NhwcInstr};
 use super::{
-    ast_node::AstTree, cfg_node::CfgGraph, et_node::{DeclOrDefOrUse, EtNodeType, EtTree}, gen_et::process_any_stmt, nhwc_instr::NhwcInstrType, scope_node::ScopeTree, symtab::{RcSymIdx, SymTab, SymTabGraph}
+    ast_node::{find_fn_ptr_direct_decl_of_ident, find_type_specifier_of_decl, get_ptr_depth_of_declarator, is_fn_ptr_direct_decl, get_storage_class_of_decl, get_storage_class_of_type_node, is_identifier_addr_taken, is_typedef_decl, AstTree, SrcLoc}, cfg_node::CfgGraph, et_node::{DeclOrDefOrUse, EtNodeType, EtTree}, gen_et::process_any_stmt, nhwc_instr::NhwcInstrType, scope_node::ScopeTree, symtab::{RcSymIdx, SymTab, SymTabGraph}
 };
 use super::{cfg_edge::CfgEdgeType, cfg_node::CfgNodeType, field::Field, nhwc_instr::InstrSlab};
-use crate::antlr_parser::clexer::{Identifier, LeftParen};
-use crate::antlr_parser::cparser::{RULE_breakStatement, RULE_breakpointArg, RULE_breakpointStatement, RULE_continueStatement, RULE_declarationSpecifier, RULE_initDeclarator, RULE_initDeclaratorList, RULE_returnStatement, RULE_typeSpecifier};
+use crate::antlr_parser::clexer::{Ellipsis, Extern, Identifier, LeftParen, Static};
+use crate::antlr_parser::cparser::{RULE_breakStatement, RULE_breakpointArg, RULE_breakpointStatement, RULE_constantExpression, RULE_continueStatement, RULE_declarationSpecifier, RULE_enumSpecifier, RULE_enumerationConstant, RULE_enumerator, RULE_enumeratorList, RULE_initDeclarator, RULE_initDeclaratorList, RULE_returnStatement, RULE_typeSpecifier};
 use crate::toolkit::nhwc_instr::BreakpointArg;
 use crate::toolkit::scope_node::ST_ROOT;
 use crate::{debug_info_blue, debug_info_green, debug_info_red, direct_parent_node};
@@ -58,7 +60,8 @@ make_field_trait_for_struct!(
     SlotIdx,
     MemLayout,
     HashSet<RcSymIdx>,
-    LoopInfo
+    LoopInfo,
+    SrcLoc
 );
 
 // reg_field_for_struct!(EtNode {
@@ -73,6 +76,7 @@ reg_field_for_struct!(Symbol {
         IS_FUNC_PARA:bool,
         IS_LITERAL:bool,
         IS_EXTERNAL:bool,
+        IS_STATIC:bool,
         POINTED_SYMIDX:RcSymIdx,
         LABEL_CFG_NODE:u32,
         TEMP_COUNTER:u32,
@@ -80,6 +84,8 @@ reg_field_for_struct!(Symbol {
         GLOBAL_CHI_SET:HashSet<RcSymIdx>,
         LOCAL_MU_SET:HashSet<RcSymIdx>,
         LOCAL_CHI_SET:HashSet<RcSymIdx>,
+        IS_ADDR_TAKEN:bool,
+        STR_LITERAL_VAL:Value,
     } with_fields fields);
 // global_mu_call_set stores all mu to global variable in 
 // for compilation unit symbol
@@ -87,12 +93,16 @@ reg_field_for_struct!(Symbol {
         ALL_CFG_FUNC_SYMIDX_ENTRY_TUPLES:Vec<(RcSymIdx,u32)>,
         GLOBAL_VARS:Vec<RcSymIdx>,
         EXTERNAL_FUNC_SYMIDX_VEC:Vec<RcSymIdx>,
+        STR_LITERALS:Vec<RcSymIdx>,
+        // 生成 nhwc ir 时发现的错误，由 Cfg2NcfgPass 统一输出
+        DIAGNOSTICS:Vec<Diagnostic>,
     } with_fields fields);
 // for func symbol
 // declared_vars field is not for mem_alloc, it's about simulator and ssa 
 reg_field_for_struct!(Symbol {
         DECLARED_VARS:Vec<RcSymIdx>,
         CFG_ENTRY_NODE:u32,
+        IS_VARIADIC:bool,
     } with_fields fields);
 reg_field_for_struct!(CfgNode {
     FUNC_COR_SYMIDX:RcSymIdx,
@@ -102,6 +112,7 @@ reg_field_for_struct!(CfgNode {
 // for Instruction
 reg_field_for_struct!(NhwcInstr {
         CFG_INSTR_IDX:SlotIdx,
+        SRC_LOC:SrcLoc,
     } with_fields info);
 
 fn check_child_nodes(child_nodes:&Vec<u32>,num:usize) {
@@ -127,13 +138,26 @@ fn parse_stmt_or_expr2nhwc(
     //如果该节点有子树
     let et_nodes = direct_child_nodes!(at sep_node in et_tree);
     let mut sep_symidx_vec= vec![];
+    let instr_count_before = node!(at cfg_node in cfg_graph).instrs.len();
     for et_node in et_nodes {
         let op_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, stmt_parent_scope, cfg_node, instr_slab,ast2scope,);
         sep_symidx_vec.push(op_symidx)
     }
+    attach_src_loc_to_new_instrs(ast_tree, cfg_graph, instr_slab, ast_stmt_node, cfg_node, instr_count_before);
     Ok(sep_symidx_vec)
 }
 
+/// 这条语句新生成的指令(cfg_node 中第 instr_count_before 条之后的)记录下语句的源代码位置，已经有更精确位置(如除法的运算符)的指令不变
+fn attach_src_loc_to_new_instrs(ast_tree:&AstTree, cfg_graph:&CfgGraph, instr_slab:&mut InstrSlab<NhwcInstr>, ast_stmt_node:u32, cfg_node:u32, instr_count_before:usize){
+    if let Some(src_loc) = &node!(at ast_stmt_node in ast_tree).src_loc{
+        for &instr in node!(at cfg_node in cfg_graph).instrs.iter().skip(instr_count_before){
+            if !instr!(at instr in instr_slab).has_src_loc(){
+                instr_mut!(at instr in instr_slab).add_src_loc(src_loc.clone());
+            }
+        }
+    }
+}
+
 fn parse_bb2nhwc(
     ast_tree:&AstTree, cfg_graph:&mut CfgGraph, scope_tree:&ScopeTree, et_tree:&mut EtTree, symtab:&mut SymTab, ast2scope:&HashMap<u32, u32>, ast_nodes:Vec<u32>, cfg_bb:u32,
     instr_slab:&mut InstrSlab<NhwcInstr>, 
@@ -143,7 +167,9 @@ fn parse_bb2nhwc(
             (RULE_declaration, declaration_node) => {
                 if let Some(&decl_scope) = ast2scope.get(&declaration_node) {
                     let decl_scope = direct_parent_node!(at decl_scope in scope_tree);
-                    parse_declvar2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, decl_scope, declaration_node, cfg_bb,  ast2scope,instr_slab, )?
+                    let instr_count_before = node!(at cfg_bb in cfg_graph).instrs.len();
+                    parse_declvar2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, decl_scope, declaration_node, cfg_bb,  ast2scope,instr_slab, )?;
+                    attach_src_loc_to_new_instrs(ast_tree, cfg_graph, instr_slab, declaration_node, cfg_bb, instr_count_before);
                 } else {
                     panic!("找不到astnode{}的scope", declaration_node)
                 }
@@ -198,6 +224,14 @@ fn parse_bb2nhwc(
                 // debug_info_blue!("visit jumpstmt at {jump_node}");
                 if let Some(&jump_scope) = ast2scope.get(&jump_node){
                     let jump_parent_scope = direct_parent_node!(at jump_scope in scope_tree);
+                    if is_goto_stmt(jump_node, ast_tree){
+                        // goto 的出边在生成 cfg 的时候已经连到了标签所在的 bb ，这里只需要跳过去
+                        let cfg_label_node = direct_child_node!(at cfg_bb in cfg_graph);
+                        let label_symidx = find_or_new_label_to_cfg_node(cfg_label_node, jump_scope, get_label_name_of_ast_node(jump_node, ast_tree).leak(), symtab, cfg_graph, instr_slab);
+                        let jump_instr = NhwcInstrType::new_jump(label_symidx).into();
+                        node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(jump_instr, instr_slab);
+                        continue;
+                    }
                     let jump_type_ast = direct_child_node!(at jump_node in ast_tree);
                     match (rule_id!(at jump_type_ast in ast_tree),jump_type_ast){
                         (RULE_returnStatement,ret_ast) => {
@@ -239,8 +273,8 @@ fn parse_bb2nhwc(
                             }
                         },
                         (RULE_breakStatement,break_ast) => {
-                            let while_or_for_node = match get_while_or_for_node_of_cfg_node(cfg_bb, cfg_graph, scope_tree, ast2scope){
-                                anyhow::Result::Ok(while_or_for_node ) => while_or_for_node ,
+                            let while_or_switch_node = match get_break_target_node_of_cfg_node(cfg_bb, cfg_graph, scope_tree, ast2scope){
+                                anyhow::Result::Ok(while_or_switch_node ) => while_or_switch_node ,
                                 Err(e) => {
                                     if direct_parent_node!(at cfg_bb in cfg_graph ret_option).is_some(){
                                         return Err(e);
@@ -250,8 +284,12 @@ fn parse_bb2nhwc(
                                 },
                             };
                             // 说明这个bb 已经被孤立了
-                            debug_info_green!("while_or_for_node_of_cfg_node {cfg_bb} is {while_or_for_node}");
-                            let body_exit_node = get_exit_node_of_while_or_for_node(while_or_for_node, cfg_graph);
+                            debug_info_green!("break target of cfg_node {cfg_bb} is {while_or_switch_node}");
+                            let body_exit_node = if node!(at while_or_switch_node in cfg_graph).cfg_node_type.is_switch(){
+                                *node!(at while_or_switch_node in cfg_graph).get_cfg_cor_gather()
+                            }else{
+                                get_exit_node_of_while_or_for_node(while_or_switch_node, cfg_graph)
+                            };
                             // we should remove the edge 
                             let cfg_child_node = direct_child_node!(at cfg_bb in cfg_graph);
                             let cfg_edge_to_removed = cfg_graph.find_edge(node_index(cfg_bb as usize), node_index(cfg_child_node as usize)).unwrap();
@@ -308,9 +346,15 @@ fn parse_whileloop2nhwc(
                     let cfg_body_head_node = direct_child_node!(at cfg_whileloop in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_body_head() });
                     let cfg_exit_node = get_exit_node_of_while_or_for_node(cfg_whileloop, cfg_graph);
                     debug_info_blue!("exit_node of cfg_while_node:{cfg_whileloop} is cfg_exit_node:{cfg_exit_node}");
-                    let while_head_symidx = find_or_new_label_to_cfg_node(cfg_whileloop,*ast2scope.get(&ast_expr_node).unwrap(), "while.head", symtab,cfg_graph,instr_slab);
-                    let while_body_symidx = find_or_new_label_to_cfg_node(cfg_body_head_node,*ast2scope.get(&ast_expr_node).unwrap(), "while.body", symtab,cfg_graph, instr_slab);
-                    let while_exit_symidx = find_or_new_label_to_cfg_node(cfg_exit_node,*ast2scope.get(&ast_expr_node).unwrap(), "while.exit", symtab,cfg_graph,instr_slab);
+                    // do while 的条件节点在循环体之后，标签名区分开方便阅读
+                    let (head_label,body_label,exit_label) = if node!(at cfg_whileloop in cfg_graph).cfg_node_type.is_do_while_loop(){
+                        ("dowhile.cond","dowhile.body","dowhile.exit")
+                    }else{
+                        ("while.head","while.body","while.exit")
+                    };
+                    let while_head_symidx = find_or_new_label_to_cfg_node(cfg_whileloop,*ast2scope.get(&ast_expr_node).unwrap(), head_label, symtab,cfg_graph,instr_slab);
+                    let while_body_symidx = find_or_new_label_to_cfg_node(cfg_body_head_node,*ast2scope.get(&ast_expr_node).unwrap(), body_label, symtab,cfg_graph, instr_slab);
+                    let while_exit_symidx = find_or_new_label_to_cfg_node(cfg_exit_node,*ast2scope.get(&ast_expr_node).unwrap(), exit_label, symtab,cfg_graph,instr_slab);
 
                     let br_whileloop_instr_struct = NhwcInstrType::new_br(r2bool_symidx.clone(),while_body_symidx,while_exit_symidx).into();
                     node_mut!(at cfg_whileloop in cfg_graph).push_nhwc_instr(br_whileloop_instr_struct, instr_slab);
@@ -385,28 +429,28 @@ fn parse_forloop2nhwc(
             // push before instr label
             let cfg_for_parent_node = etc::element_remained_after_exclusion_in_vec(direct_parent_nodes!(at cfg_forloop in cfg_graph), cfg_body_tail_node)?;
             // debug_info_yellow!("{:?}",direct_parent_nodes!(at cfg_forloop in cfg_graph));
+            // insert_bb_between 已经给新的 bb 加上了 label
             let cfg_new_bb_node = insert_bb_between(cfg_for_parent_node, cfg_forloop, cfg_graph, symtab,instr_slab);
-
-            let label_before_symidx = process_label_symbol(cfg_for_parent_node,*ast2scope.get(&ast_before_node).unwrap(), "for.before:", symtab);
-            let label_before_instr = NhwcInstrType::new_label(label_before_symidx).into();
-
-            node_mut!(at cfg_new_bb_node in cfg_graph).push_nhwc_instr(label_before_instr, instr_slab);
             if let Some(&before_scope) = ast2scope.get(&ast_before_node) {
                 let before_parent_scope = direct_parent_node!(at before_scope in scope_tree);
-                let before_ast = direct_child_node!(at ast_before_node in ast_tree);
-                parse_declvar2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, before_parent_scope, before_ast, cfg_new_bb_node,  ast2scope,instr_slab, )?
+                // 初始化部分可以是声明，也可以是表达式，也可以为空
+                for before_ast in node!(at ast_before_node in ast_tree).child_vec.clone(){
+                    if rule_id!(at before_ast in ast_tree) == RULE_expression {
+                        parse_stmt_or_expr2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, before_parent_scope, before_ast, cfg_new_bb_node, ast2scope, instr_slab, )?;
+                    }else {
+                        parse_declvar2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, before_parent_scope, before_ast, cfg_new_bb_node,  ast2scope,instr_slab, )?
+                    }
+                }
             } else {
                 panic!("找不到astnode的scope");
             }
         }
         _ => panic!("forloop中未知RULE,{}不是或forBeforeExpression", ast_before_node),
     }
+    // 条件中有副作用的条件运算符会把 for 节点的入边移走，所以先插入 after 所在的 bb
+    let cfg_after_bb_node = insert_bb_between(cfg_body_tail_node, cfg_forloop, cfg_graph, symtab, instr_slab);
     match rule_id!(at ast_mid_node in ast_tree) {
         RULE_forMidExpression => {
-            // push before instr label
-            let label_mid_symidx = process_label_symbol(cfg_forloop,*ast2scope.get(&ast_mid_node).unwrap(), "for.mid:", symtab);
-            let label_mid_instr = NhwcInstrType::new_label(label_mid_symidx).into();
-            node_mut!(at cfg_forloop in cfg_graph).push_nhwc_instr(label_mid_instr, instr_slab);
             if let Some(&mid_scope) = ast2scope.get(&ast_mid_node) {
                 let mid_parent_scope = direct_parent_node!(at mid_scope in scope_tree);
                 let ret_vec = parse_stmt_or_expr2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, mid_parent_scope, ast_mid_node, cfg_forloop,ast2scope,  instr_slab, )?;
@@ -415,6 +459,14 @@ fn parse_forloop2nhwc(
                 }
                 if let Some(rst_symidx) =&ret_vec[0]{
                     let r2bool_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I1,  &rst_symidx, ast_mid_node, cfg_forloop,  instr_slab, None, et_tree);
+                    // 条件中的短路运算或条件运算可能已经把条件节点分割，标签在分割之后再添加
+                    let cfg_body_head_node = direct_child_node!(at cfg_forloop in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_body_head() });
+                    let cfg_exit_node = get_exit_node_of_while_or_for_node(cfg_forloop, cfg_graph);
+                    find_or_new_label_to_cfg_node(cfg_forloop, mid_scope, "for.mid", symtab, cfg_graph, instr_slab);
+                    let for_body_symidx = find_or_new_label_to_cfg_node(cfg_body_head_node, mid_scope, "for.body", symtab, cfg_graph, instr_slab);
+                    let for_exit_symidx = find_or_new_label_to_cfg_node(cfg_exit_node, mid_scope, "for.exit", symtab, cfg_graph, instr_slab);
+                    let br_forloop_instr_struct = NhwcInstrType::new_br(r2bool_symidx.clone(), for_body_symidx, for_exit_symidx).into();
+                    node_mut!(at cfg_forloop in cfg_graph).push_nhwc_instr(br_forloop_instr_struct, instr_slab);
                     node_mut!(at cfg_forloop in cfg_graph).add_jump_det(r2bool_symidx);
                 } else{
                     panic!("for mid_expr can't be void type")
@@ -427,14 +479,7 @@ fn parse_forloop2nhwc(
     }
     match rule_id!(at ast_after_node in ast_tree) {
         RULE_forAfterExpression => {
-            // push before instr label
-
-            let cfg_new_bb_node = insert_bb_between(cfg_body_tail_node, cfg_forloop, cfg_graph, symtab, instr_slab);
-
-            let label_after_symidx = process_label_symbol(cfg_new_bb_node,*ast2scope.get(&ast_after_node).unwrap(),"for.after:", symtab);
-            let label_after_instr = NhwcInstrType::new_label(label_after_symidx).into();
-
-            node_mut!(at cfg_new_bb_node in cfg_graph).push_nhwc_instr(label_after_instr, instr_slab);
+            let cfg_new_bb_node = cfg_after_bb_node;
             if let Some(&after_scope) = ast2scope.get(&ast_after_node) {
                 let after_parent_scope = direct_parent_node!(at after_scope in scope_tree);
                 parse_stmt_or_expr2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, after_parent_scope, ast_after_node, cfg_new_bb_node,  ast2scope,instr_slab, )?;
@@ -490,6 +535,73 @@ fn parse_branch2nhwc(
 
     Ok(())
 }
+/// switch 的每一条 Case 边对应 switch instr 中的一个 ComparedPair ，指向没有 case 常量的 bb(即 default) 或者 gather 的边作为 default
+fn parse_switch2nhwc(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, scope_tree:&ScopeTree, et_tree:&mut EtTree, symtab:&mut SymTab, ast2scope:&HashMap<u32, u32>, ast_expr_node:u32, cfg_switch_node:u32, 
+    instr_slab:&mut InstrSlab<NhwcInstr>, 
+) -> Result<()> {
+    let &expr_scope = ast2scope.get(&ast_expr_node).unwrap_or_else(||panic!("找不到astnode {}的scope",ast_expr_node));
+    let expr_parent_scope = direct_parent_node!(at expr_scope in scope_tree);
+    let ret_vec = parse_stmt_or_expr2nhwc(ast_tree, cfg_graph, symtab, scope_tree, et_tree, expr_parent_scope, ast_expr_node, cfg_switch_node,ast2scope,  instr_slab, )?;
+    // 条件表达式先做整数提升，case 的值都转换成提升后的类型再比较
+    let cond_ty = match &ret_vec[..]{
+        [Some(rst_symidx)] => match symtab.get(&rst_symidx.as_ref_borrow()).get_type(){
+            Type::I32 | Type::U32 | Type::I64 => symtab.get(&rst_symidx.as_ref_borrow()).get_type().clone(),
+            Type::I8 | Type::I1 => Type::I32,
+            ty => {
+                report_error(symtab, node!(at ast_expr_node in ast_tree).src_loc.clone(), format!("switch 的条件表达式类型为 {:?} ，不是整型", ty));
+                Type::I32
+            },
+        },
+        _ => panic!("{:?} switch 表达式的返回值数量不为1",ret_vec),
+    };
+    let cond_symidx = force_trans_type_discriminant(cfg_graph, symtab, (&cond_ty).into(), ret_vec[0].as_ref().unwrap(), ast_expr_node, cfg_switch_node, instr_slab, None, et_tree);
+
+    let mut op_default_label = None;
+    let mut compared_pairs = vec![];
+    let cfg_case_nodes = direct_child_nodes!(at cfg_switch_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_case()});
+    for cfg_case_node in cfg_case_nodes{
+        match node!(at cfg_case_node in cfg_graph).get_op_case_const_ast_node().cloned(){
+            Some(Some(ast_case_const_node)) => {
+                let &case_scope = ast2scope.get(&ast_case_const_node).unwrap();
+                let case_parent_scope = direct_parent_node!(at case_scope in scope_tree);
+                let case_et_sep = process_any_stmt(et_tree, ast_tree, scope_tree, ast_case_const_node, case_parent_scope);
+                eval_et::compress_et_for_gen_nhwc(et_tree, case_et_sep, &mut can_eliminate_despite_array_idx_and_call, symtab, case_parent_scope, scope_tree, true)?;
+                let case_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, direct_child_node!(at case_et_sep in et_tree), case_parent_scope, cfg_switch_node, instr_slab, ast2scope).unwrap();
+                let case_src_loc = node!(at ast_case_const_node in ast_tree).src_loc.clone();
+                let case_ty_discriminant = TypeDiscriminants::new_from_const_str(case_symidx.as_ref_borrow().symbol_name);
+                if !case_symidx.as_ref_borrow().is_literal() || !matches!(case_ty_discriminant, TypeDiscriminants::I32 | TypeDiscriminants::U32 | TypeDiscriminants::I64 | TypeDiscriminants::I8){
+                    report_error(symtab, case_src_loc, "case 标签不是整型常量表达式".to_string());
+                    continue;
+                }
+                let case_val = Value::from_symidx(&case_symidx.as_ref_borrow()).trans_to_specific_type(&cond_ty);
+                let case_symidx = process_literal(symtab, case_val.to_symidx().symbol_name);
+                if compared_pairs.iter().any(|pair:&ComparedPair| pair.compared == case_symidx){
+                    report_error(symtab, case_src_loc, format!("switch 中出现了重复的 case 值 {}", case_symidx.as_ref_borrow().symbol_name.trim_end_matches(['u','l'])));
+                    continue;
+                }
+                let case_label = find_or_new_label_to_cfg_node(cfg_case_node, case_scope, "case", symtab, cfg_graph, instr_slab);
+                compared_pairs.push(ComparedPair::new(case_symidx, case_label));
+            },
+            Some(None) => {
+                op_default_label = Some(find_or_new_label_to_cfg_node(cfg_case_node, expr_scope, "default", symtab, cfg_graph, instr_slab));
+            },
+            None => {
+                // 没有 default 的 switch ，所有 case 都不匹配时直接跳到 gather
+                op_default_label = Some(find_or_new_label_to_cfg_node(cfg_case_node, expr_scope, "switch_exit", symtab, cfg_graph, instr_slab));
+            },
+        }
+    }
+    let default_label = op_default_label.unwrap_or_else(||panic!("switch cfg_node:{} 没有 default 分支",cfg_switch_node));
+    let switch_instr_struct = NhwcInstrType::new_switch(cond_symidx.clone(), default_label, compared_pairs).into();
+    node_mut!(at cfg_switch_node in cfg_graph).push_nhwc_instr(switch_instr_struct, instr_slab);
+    node_mut!(at cfg_switch_node in cfg_graph).add_jump_det(cond_symidx);
+    Ok(())
+}
+/// 记录一条错误，继续生成以便一次报告尽可能多的错误
+fn report_error(symtab:&mut SymTab, src_loc:Option<SrcLoc>, msg:String){
+    symtab.get_mut_global_info().get_mut_diagnostics().push(Diagnostic::new_error(src_loc, msg));
+}
 fn process_literal(symtab:&mut SymTab, const_literal:&'static str, ) -> RcSymIdx {
     // 我们认为 constant 的scope node 都是全局的
     // match find!(symbol mut {const_literal.clone()} of scope {0} in symtab debug symtab_graph ){
@@ -511,10 +623,67 @@ fn process_literal(symtab:&mut SymTab, const_literal:&'static str, ) -> RcSymIdx
     let rc_literal_symidx = SymIdx::new(0, const_literal).as_rc();
     rc_literal_symidx
 }
+/// 字符串字面量作为只读的全局 char 数组 str.{n} 放在 .rodata 中，内容相同的字面量只保留一份  
+/// 返回指向这个数组的 globl ptr
+fn process_str_literal(cfg_graph:&mut CfgGraph, symtab:&mut SymTab, instr_slab:&mut InstrSlab<NhwcInstr>, str_literal:&'static str) -> RcSymIdx {
+    let mut bytes = gen_et::unescape_c_literal(&str_literal[1..str_literal.len()-1]);
+    bytes.push(0);
+    let dims = vec![process_literal(symtab, bytes.len().to_string().leak())];
+    let mut value_map = ArrayEleMap::new();
+    for (idx,&b) in bytes.iter().enumerate(){
+        value_map.insert_ele(idx, Value::new_i8(b as i8));
+    }
+    let str_val = Value::new_array(value_map, dims.clone(), Type::I8);
+    for rc_str_symidx in symtab.get_global_info().get_str_literals().iter(){
+        let str_symidx = rc_str_symidx.as_ref_borrow();
+        if symtab.get(&str_symidx).get_str_literal_val() == &str_val{
+            return symtab.get(&str_symidx.to_globl_ptr()).rc_symidx.clone();
+        }
+    }
+    let str_type = Type::new_array_dims_known(Type::I8, dims);
+    let str_name:&'static str = format!("str.{}",symtab.get_global_info().get_str_literals().len()).leak();
+    debug_info_green!("try add str literal symbol {}",str_name);
+    let rc_str_symidx = add_symbol!({Symbol::new(ST_ROOT, str_name)}
+        with_field TYPE:{str_type.clone()}
+        with_field DEF_CFG_NODE_VEC:{vec![CFG_ROOT]}
+        with_field DEF_INSTRS_VEC:{Vec::<usize>::new()}
+        with_field IS_GLOBAL:{true}
+        with_field IS_TEMP:{false}
+        with_field IS_LITERAL:{false}
+        with_field IS_FUNC_PARA:{false}
+        with_field STR_LITERAL_VAL:{str_val}
+    to symtab);
+    let rc_str_ptr_symidx = add_symbol!({rc_str_symidx.as_ref_borrow().to_globl_ptr().into_symbol()}
+        with_field TYPE:{str_type.to_ref_ptr_type()}
+        with_field DEF_CFG_NODE_VEC:{vec![CFG_ROOT]}
+        with_field DEF_INSTRS_VEC:{Vec::<usize>::new()}
+        with_field IS_GLOBAL:{true}
+        with_field IS_TEMP:{false}
+        with_field IS_LITERAL:{false}
+        with_field POINTED_SYMIDX:{rc_str_symidx.clone()}
+    to symtab);
+    symtab.get_mut_global_info().get_mut_global_vars().push(rc_str_symidx.clone());
+    symtab.get_mut_global_info().get_mut_str_literals().push(rc_str_symidx.clone());
+    let global_instr = NhwcInstrType::new_globl(str_type, rc_str_symidx).into();
+    node_mut!(at CFG_ROOT in cfg_graph).insert_nhwc_instr(global_instr, 0, instr_slab);
+    rc_str_ptr_symidx
+}
 fn process_func_symbol(
-    symtab:&mut SymTab, func_name:&'static str, is_external:bool,
+    symtab:&mut SymTab, func_name:&'static str, is_external:bool, is_static:bool,
 )->RcSymIdx{
     debug_info_green!("try add func symbol {}",func_name);
+    // 函数可以先声明再定义，定义时沿用声明的符号，只要有一处写了 static 它就是 static 函数
+    let func_symidx = SymIdx::new(ST_ROOT, func_name);
+    if symtab.has_symbol(&func_symidx){
+        let rc_func_symidx = symtab.get_symidx_cor_rc(&func_symidx);
+        let is_static = is_static || *symtab.get(&func_symidx).get_is_static();
+        symtab.get_mut(&func_symidx).add_is_static(is_static);
+        if !is_external{
+            symtab.get_mut(&func_symidx).add_is_external(false);
+            symtab.get_mut_global_info().get_mut_external_func_symidx_vec().retain(|rc_symidx| rc_symidx != &rc_func_symidx);
+        }
+        return rc_func_symidx
+    }
     let func_symidx = add_symbol!({Symbol::new(0, func_name)} 
         with_field DECLARED_VARS:{vec![]}
         // with_field SSA_REACHING_DEF:{None}
@@ -522,6 +691,8 @@ fn process_func_symbol(
         with_field IS_TEMP:{false}
         with_field IS_LITERAL:{false}
         with_field IS_EXTERNAL:{is_external}
+        with_field IS_VARIADIC:{false}
+        with_field IS_STATIC:{is_static}
         with_field GLOBAL_MU_SET:{HashSet::new()} 
         with_field GLOBAL_CHI_SET:{HashSet::new()} 
         with_field LOCAL_MU_SET:{HashSet::new()} 
@@ -541,26 +712,58 @@ fn process_symbol(
         if node!(at et_node in et_tree).has_dims(){ Some(node!(at et_node in et_tree).get_dims()) }else{ None }
     }else { None };
     match decldef_def_or_use {
-        &DeclOrDefOrUse::DeclDef { type_ast_node, is_const } => {
-            let var_type: Type ;
+        &DeclOrDefOrUse::DeclDef { type_ast_node, is_const, ptr_depth } => {
+            let mut var_type: Type ;
+            // typedef 名从变量所在的 scope 开始向外查找
+            let mut base_type = Type::new(type_ast_node, ast_tree, &|name| find_typedef_type(symtab, scope_tree, scope_parent_node, name));
+            if let Some(fn_direct_decl_node) = op_et_node.and_then(|et_node| find_fn_ptr_direct_decl_of_ident(ast_tree, node!(at et_node in et_tree).et_node_type.get_ast_node())){
+                // 函数指针 (*fp)(int) 的 base_type 是函数类型，此时 ptr_depth 是括号里的指针层数，括号外的指针属于返回类型
+                let ret_ptr_depth = direct_parent_node!(at fn_direct_decl_node in ast_tree ret_option).map_or(0, |declarator_node| get_ptr_depth_of_declarator(ast_tree, declarator_node));
+                let ret_type = (0..ret_ptr_depth).fold(base_type, |ty, _| Type::Ptr64 { ty: Box::new(ty) });
+                base_type = process_fn_type(ast_tree, scope_tree, symtab, scope_parent_node, fn_direct_decl_node, symbol_name, ret_type).unwrap();
+            }
+            for _ in 0..ptr_depth{
+                base_type = Type::Ptr64 { ty: Box::new(base_type) };
+            }
             // 这里有两种可能，一种是数组，一种是普通变量，如果是数组，那么et_node 上一定有 dims 字段
             if let Some(op_dims) = op_dims{
-                var_type = Type::new_array_dims_may_unknown(Type::new(type_ast_node, ast_tree), op_dims.clone());
+                var_type = Type::new_array_dims_may_unknown(base_type, op_dims.clone());
             }else{
-                var_type = Type::new(type_ast_node, ast_tree);
+                var_type = base_type;
             }
             // let var_type = Type::new(*type_ast_node, ast_tree);
             // if scope parent node is root, then the variable must be global
-            let is_global = scope_parent_node == ST_ROOT;
-            // array
-            let mut has_unknown_dim = false; 
-            if var_type.is_array(){
-                for i in op_dims.unwrap(){
-                    if i.is_none(){
-                        has_unknown_dim = true
-                    }
+            // static 局部变量留在原来的 scope 中但和全局变量一样放在 .data/.bss ，块作用域中的 extern 声明引用的是全局变量，因此登记到 ST_ROOT
+            let storage_class = get_storage_class_of_type_node(ast_tree, type_ast_node);
+            let (is_static, is_extern) = (storage_class == Some(Static), storage_class == Some(Extern));
+            let scope_parent_node = if is_extern { ST_ROOT } else { scope_parent_node };
+            let is_global = scope_parent_node == ST_ROOT || is_static;
+            // 之前已经有 extern 声明时沿用同一个符号，遇到定义时它就不再是外部符号
+            let symidx_in_scope = SymIdx::new(scope_parent_node, symbol_name);
+            if is_global && symtab.has_symbol(&symidx_in_scope) {
+                let rc_symidx = symtab.get_symidx_cor_rc(&symidx_in_scope);
+                if !is_extern {
+                    symtab.get_mut(&symidx_in_scope).add_is_external(false);
+                }
+                if let Some(et_node) = op_et_node{
+                    node_mut!(at et_node in et_tree).add_type(symtab.get(&symidx_in_scope).get_type().to_ref_ptr_type());
                 }
+                return rc_symidx
             }
+            let is_func_para = node!(at scope_parent_node in scope_tree).scope_type.is_func();
+            // typedef 出来的数组类型作为参数时同样退化为指针，即第一维未知
+            if let (true, None, Type::Array { dims, ele_ty: _ }) = (is_func_para, op_dims, &mut var_type){
+                dims[0] = None;
+            }
+            // 被取地址的局部标量需要放在内存中，这里把它当作只有一个元素的数组
+            let is_addr_taken = !is_global && !is_func_para && !var_type.is_array() && !var_type.is_aggregate()
+                && is_identifier_addr_taken(ast_tree, node!(at scope_parent_node in scope_tree).ast_node, symbol_name);
+            let var_type = if is_addr_taken { Type::new_array_dims_known(var_type, vec![process_literal(symtab, "1")]) } else { var_type };
+            // array
+            let has_unknown_dim = match &var_type{
+                Type::Array { dims, ele_ty: _ } => dims.iter().any(|dim| dim.is_none()),
+                _ => false
+            };
             debug_info_green!("try add symbol {}",symbol_name);
             let rc_symidx = add_symbol!({Symbol::new(scope_parent_node,symbol_name)}
                 with_field TYPE:{
@@ -570,8 +773,13 @@ fn process_symbol(
                 with_field IS_GLOBAL:{is_global}
                 with_field IS_TEMP:{false} 
                 with_field IS_LITERAL:{false}
-                with_field IS_FUNC_PARA:{node!(at scope_parent_node in scope_tree).scope_type.is_func()}
+                with_field IS_FUNC_PARA:{is_func_para}
+                with_field IS_EXTERNAL:{is_extern}
+                with_field IS_STATIC:{is_static}
             to symtab );
+            if is_addr_taken{
+                symtab.get_mut(&rc_symidx.as_ref_borrow()).add_is_addr_taken(true);
+            }
             // if it is global then add global ptr to this global variable and add global variables 
             if is_global{
                 debug_info_green!("try add global symbol of {:?}",symbol_name);
@@ -587,7 +795,7 @@ fn process_symbol(
                 to symtab);
                 symtab.get_mut_global_info().get_mut_global_vars().push(rc_symidx.clone());
             }
-            if node!(at cfg_node in cfg_graph).has_func_cor_symidx(){
+            if !is_global && node!(at cfg_node in cfg_graph).has_func_cor_symidx(){
                 let func_symidx = CfgNode::get_func_cor_symidx(node_mut!(at cfg_node in cfg_graph));
                 symtab.get_mut(&func_symidx.as_ref_borrow()).get_mut_declared_vars().push(rc_symidx.clone());
             }
@@ -603,10 +811,19 @@ fn process_symbol(
                 if symbol_scope!=ST_ROOT{
                     symbol_scope = direct_parent_node!(at symbol_scope in scope_tree)
                 }else{
-                    panic!("scope为{}符号表中未找到{:?}", symbol_scope, symbol_name.clone());
+                    panic!("{}: scope为{}符号表中未找到{:?}", SrcLoc::fmt_op(&op_et_node.and_then(|et_node| node!(at et_node in et_tree).src_loc.clone())), symbol_scope, symbol_name.clone());
                 }
             }
             let rc_symidx = symtab.get_symidx_cor_rc(&SymIdx{scope_node:symbol_scope, symbol_name, ssa_idx:None, temp_idx:None});
+            if symtab.get(&rc_symidx.as_ref_borrow()).get_type().is_fn(){
+                // 函数名作为值使用时就是函数的地址
+                let rc_fn_ptr_symidx = process_fn_designator(symtab, &rc_symidx);
+                if let Some(et_node) = op_et_node{
+                    let fn_ptr_type = symtab.get(&rc_fn_ptr_symidx.as_ref_borrow()).get_type().clone();
+                    node_mut!(at et_node in et_tree).add_type(fn_ptr_type);
+                }
+                return rc_fn_ptr_symidx
+            }
             if let Some(et_node) = op_et_node.clone(){
                 let symidx = &rc_symidx.as_ref_borrow();
                 node_mut!(at et_node in et_tree).add_type(symtab.get(&symidx).get_type().clone());
@@ -616,7 +833,7 @@ fn process_symbol(
             if *symtab.get(&rc_symidx.as_ref_borrow()).get_is_global() {
                 let symidx = &rc_symidx.as_ref_borrow();
                 match symtab.get(&symidx).get_type(){
-                    Type::Array { dims, ele_ty } => {
+                    Type::Array { .. } | Type::Struct { .. } | Type::Union { .. } => {
                         // let temp_type = symtab.get(&symidx)?.get_type()?.clone();
                         // let ptr_type = symtab.get(&symidx.to_globl_ptr()?)?.get_type()?.clone();
                         // let temp_symidx = process_temp_symbol(cfg_graph, symtab, &temp_type, scope_parent_node, cfg_node, instr_slab, &mut None, op_et_node, et_tree, "ptr2globl")?;
@@ -633,6 +850,14 @@ fn process_symbol(
                         temp_symidx
                     }
                 }
+            }else if is_addr_taken_local(symtab, &rc_symidx.as_ref_borrow()){
+                // 被取地址的局部标量与全局变量一样需要从内存中 load 出来
+                let ele_ty = symtab.get(&rc_symidx.as_ref_borrow()).get_type().get_ele_ty();
+                let rc_ptr_symidx = process_ptr_of_addr_taken_local(cfg_graph, symtab, &rc_symidx, scope_parent_node, cfg_node, instr_slab, et_tree);
+                let temp_symidx = process_temp_symbol(cfg_graph, symtab, &ele_ty, scope_parent_node, cfg_node, instr_slab, op_et_node, et_tree, "value_from_ptr");
+                let load_instr = node_mut!(at cfg_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_load(temp_symidx.clone(), rc_ptr_symidx, ele_ty.to_ref_ptr_type()).into(), instr_slab);
+                let _mu_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(NhwcInstrType::new_mu(rc_symidx.clone(),load_instr).into(),instr_slab);
+                temp_symidx
             }else{
                 rc_symidx
             }
@@ -642,17 +867,26 @@ fn process_symbol(
                 if symbol_scope!=ST_ROOT{
                     symbol_scope = direct_parent_node!(at symbol_scope in scope_tree);
                 }else{
-                    panic!("scope为{}符号表中未找到{:?}", symbol_scope, symbol_name.clone());
+                    panic!("{}: scope为{}符号表中未找到{:?}", SrcLoc::fmt_op(&op_et_node.and_then(|et_node| node!(at et_node in et_tree).src_loc.clone())), symbol_scope, symbol_name.clone());
                 }
             }
             let rc_symidx = &symtab.get_symidx_cor_rc(&SymIdx { scope_node: symbol_scope,symbol_name, ssa_idx: None,temp_idx: None });
+            if symtab.get(&rc_symidx.as_ref_borrow()).get_type().is_fn(){
+                // &func 取的是函数的地址
+                let rc_fn_ptr_symidx = process_fn_designator(symtab, rc_symidx);
+                if let Some(et_node) = op_et_node{
+                    let fn_ptr_type = symtab.get(&rc_fn_ptr_symidx.as_ref_borrow()).get_type().clone();
+                    node_mut!(at et_node in et_tree).add_type(fn_ptr_type);
+                }
+                return rc_fn_ptr_symidx
+            }
             let symidx = rc_symidx.as_ref_borrow();
             if let Some(et_node) = op_et_node.clone(){
                 node_mut!(at et_node in et_tree).add_type(symtab.get(&symidx).get_type().clone());
             }
             if *symtab.get(&symidx).get_is_global() {
                 match symtab.get(&symidx).get_type(){
-                    Type::Array { dims, ele_ty } => {
+                    Type::Array { .. } | Type::Struct { .. } | Type::Union { .. } => {
                         // return the global_ptr if is global variable 
                         symtab.get(&symidx.to_globl_ptr()).rc_symidx.clone()
                     },
@@ -666,6 +900,20 @@ fn process_symbol(
         },
     }
 }
+/// 函数名作为值时对应的全局指针符号 *func ，在汇编中通过 la 取得函数地址，第一次用到时才加入符号表
+fn process_fn_designator(symtab:&mut SymTab, rc_func_symidx:&RcSymIdx) -> RcSymIdx{
+    let fn_ptr_symidx = rc_func_symidx.as_ref_borrow().to_globl_ptr();
+    if symtab.has_symbol(&fn_ptr_symidx){
+        return symtab.get(&fn_ptr_symidx).rc_symidx.clone()
+    }
+    let fn_type = symtab.get(&rc_func_symidx.as_ref_borrow()).get_type().clone();
+    add_symbol!({fn_ptr_symidx.into_symbol()}
+        with_field TYPE:{Type::Ptr64 { ty: Box::new(fn_type) }}
+        with_field IS_GLOBAL:{true}
+        with_field IS_TEMP:{false}
+        with_field IS_LITERAL:{false}
+    to symtab)
+}
 /// if op_et_node is Some it will inject type info to that et_node 
 pub fn process_temp_symbol(
     cfg_graph:&mut CfgGraph, symtab:&mut SymTab, temp_type:&Type,  scope_node:u32, cfg_node:u32,  instr_slab:&mut InstrSlab<NhwcInstr>,
@@ -703,6 +951,198 @@ pub fn process_temp_symbol(
         }
         rc_temp_symidx.clone()
 }
+/// 处理与 u32 有关的类型转换，i32 与 u32 之间只需重新解释比特位(字面量则换成转换后的字面量)，  
+/// 与 f32 之间使用 uitofp/fptoui ，其余情况返回 None ，交给 i32 的转换逻辑处理
+fn force_trans_u32(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, ty_be_transed:TypeDiscriminants, ty_trans_to:TypeDiscriminants, symidx_be_transed:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
+    op_et_node:Option<u32>,et_tree:&mut EtTree,
+) -> Option<RcSymIdx> {
+    match (ty_be_transed, ty_trans_to) {
+        (TypeDiscriminants::I32, TypeDiscriminants::U32) | (TypeDiscriminants::U32, TypeDiscriminants::I32) => {
+            let ty_trans_to:Type = ty_trans_to.into();
+            if symidx_be_transed.as_ref_borrow().is_literal(){
+                let value = Value::from_symidx(&symidx_be_transed.as_ref_borrow()).trans_to_specific_type(&ty_trans_to);
+                return Some(process_literal(symtab, value.to_symidx().symbol_name));
+            }
+            let tmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &ty_trans_to, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let assign_instr = NhwcInstrType::new_assign(tmp_type_symidx.clone(), symidx_be_transed.clone(), ty_trans_to).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(assign_instr, instr_slab);
+            Some(tmp_type_symidx)
+        }
+        (TypeDiscriminants::U32, TypeDiscriminants::F32) => {
+            let ftmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let u2f_instr = NhwcInstrType::new_uint2float(symidx_be_transed.clone(), ftmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(u2f_instr, instr_slab);
+            Some(ftmp_type_symidx)
+        }
+        (TypeDiscriminants::F32, TypeDiscriminants::U32) => {
+            let utmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::U32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let f2u_instr = NhwcInstrType::new_float2uint(symidx_be_transed.clone(), utmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(f2u_instr, instr_slab);
+            Some(utmp_type_symidx)
+        }
+        (TypeDiscriminants::I1, TypeDiscriminants::U32) => {
+            let utmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::U32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let b2u_instr = NhwcInstrType::new_bool2int(symidx_be_transed.clone(), utmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(b2u_instr, instr_slab);
+            Some(utmp_type_symidx)
+        }
+        _ => None,
+    }
+}
+/// 处理与 i64 有关的类型转换，i32 使用 sext ，u32 使用 zext ，i64 转回 32 位使用 trunc ，
+/// 字面量直接换成转换后的字面量，其余情况返回 None
+fn force_trans_i64(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, ty_be_transed:TypeDiscriminants, ty_trans_to:TypeDiscriminants, symidx_be_transed:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
+    op_et_node:Option<u32>,et_tree:&mut EtTree,
+) -> Option<RcSymIdx> {
+    let is_int = |ty:TypeDiscriminants| matches!(ty, TypeDiscriminants::I32 | TypeDiscriminants::U32 | TypeDiscriminants::I64);
+    if is_int(ty_be_transed) && is_int(ty_trans_to) && symidx_be_transed.as_ref_borrow().is_literal(){
+        let value = Value::from_symidx(&symidx_be_transed.as_ref_borrow()).trans_to_specific_type(&ty_trans_to.into());
+        return Some(process_literal(symtab, value.to_symidx().symbol_name));
+    }
+    match (ty_be_transed, ty_trans_to) {
+        (TypeDiscriminants::I32, TypeDiscriminants::I64) => {
+            let ltmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let i2l_instr = NhwcInstrType::new_int2long(symidx_be_transed.clone(), ltmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(i2l_instr, instr_slab);
+            Some(ltmp_type_symidx)
+        }
+        (TypeDiscriminants::U32, TypeDiscriminants::I64) => {
+            let ltmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let u2l_instr = NhwcInstrType::new_uint2long(symidx_be_transed.clone(), ltmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(u2l_instr, instr_slab);
+            Some(ltmp_type_symidx)
+        }
+        (TypeDiscriminants::I64, TypeDiscriminants::I32) | (TypeDiscriminants::I64, TypeDiscriminants::U32) => {
+            let tmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &ty_trans_to.into(), scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let l2i_instr = NhwcInstrType::new_long2int(symidx_be_transed.clone(), tmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(l2i_instr, instr_slab);
+            Some(tmp_type_symidx)
+        }
+        (TypeDiscriminants::I64, TypeDiscriminants::F32) => {
+            let ftmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let l2f_instr = NhwcInstrType::new_long2float(symidx_be_transed.clone(), ftmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(l2f_instr, instr_slab);
+            Some(ftmp_type_symidx)
+        }
+        (TypeDiscriminants::F32, TypeDiscriminants::I64) => {
+            let ltmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let f2l_instr = NhwcInstrType::new_float2long(symidx_be_transed.clone(), ltmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(f2l_instr, instr_slab);
+            Some(ltmp_type_symidx)
+        }
+        (TypeDiscriminants::I1, TypeDiscriminants::I64) => {
+            let ltmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let b2l_instr = NhwcInstrType::new_bool2int(symidx_be_transed.clone(), ltmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(b2l_instr, instr_slab);
+            Some(ltmp_type_symidx)
+        }
+        (TypeDiscriminants::I64, TypeDiscriminants::I1) => {
+            let btmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I1, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let lzero_symidx = process_literal(symtab, "0l");
+            let l2b_instr = NhwcInstrType::new_icmp(btmp_type_symidx.clone(), IcmpPlan::Ne, symidx_be_transed.clone(), lzero_symidx, Type::I64).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(l2b_instr, instr_slab);
+            Some(btmp_type_symidx)
+        }
+        _ => None,
+    }
+}
+/// 处理与 f64 有关的类型转换，f32 与 f64 之间使用 fpext/fptrunc ，整数与 f64 之间使用 sitofp/fptosi ，
+/// 字面量直接换成转换后的字面量，其余情况返回 None
+fn force_trans_f64(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, ty_be_transed:TypeDiscriminants, ty_trans_to:TypeDiscriminants, symidx_be_transed:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
+    op_et_node:Option<u32>,et_tree:&mut EtTree,
+) -> Option<RcSymIdx> {
+    let is_num = |ty:TypeDiscriminants| matches!(ty, TypeDiscriminants::I32 | TypeDiscriminants::U32 | TypeDiscriminants::I64 | TypeDiscriminants::F32 | TypeDiscriminants::F64);
+    if (ty_be_transed.is_f_64() || ty_trans_to.is_f_64()) && is_num(ty_be_transed) && is_num(ty_trans_to) && symidx_be_transed.as_ref_borrow().is_literal(){
+        let value = if ty_be_transed.is_f_32(){
+            // 浮点字面量按原文重新解析，避免先舍入到 f32 再扩展带来的误差
+            Value::from_string_with_specific_type(symidx_be_transed.as_ref_borrow().symbol_name, &Type::F64)
+        }else {
+            Value::from_symidx(&symidx_be_transed.as_ref_borrow()).trans_to_specific_type(&ty_trans_to.into())
+        };
+        return Some(process_literal(symtab, value.to_symidx().symbol_name));
+    }
+    match (ty_be_transed, ty_trans_to) {
+        (TypeDiscriminants::F32, TypeDiscriminants::F64) => {
+            let dtmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let f2d_instr = NhwcInstrType::new_float2double(symidx_be_transed.clone(), dtmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(f2d_instr, instr_slab);
+            Some(dtmp_type_symidx)
+        }
+        (TypeDiscriminants::F64, TypeDiscriminants::F32) => {
+            let ftmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let d2f_instr = NhwcInstrType::new_double2float(symidx_be_transed.clone(), ftmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(d2f_instr, instr_slab);
+            Some(ftmp_type_symidx)
+        }
+        (TypeDiscriminants::I32, TypeDiscriminants::F64) | (TypeDiscriminants::U32, TypeDiscriminants::F64) | (TypeDiscriminants::I64, TypeDiscriminants::F64) => {
+            let dtmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let i2d_instr = NhwcInstrType::new_int2double(symidx_be_transed.clone(), dtmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(i2d_instr, instr_slab);
+            Some(dtmp_type_symidx)
+        }
+        (TypeDiscriminants::F64, TypeDiscriminants::I32) | (TypeDiscriminants::F64, TypeDiscriminants::U32) | (TypeDiscriminants::F64, TypeDiscriminants::I64) => {
+            let tmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &ty_trans_to.into(), scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let d2i_instr = NhwcInstrType::new_double2int(symidx_be_transed.clone(), tmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(d2i_instr, instr_slab);
+            Some(tmp_type_symidx)
+        }
+        (TypeDiscriminants::I1, TypeDiscriminants::F64) => {
+            //i1转f64分两步，先转i32，后转f64
+            let itmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let b2i_instr = NhwcInstrType::new_bool2int(symidx_be_transed.clone(), itmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(b2i_instr, instr_slab);
+            let dtmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F64, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let i2d_instr = NhwcInstrType::new_int2double(itmp_type_symidx, dtmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(i2d_instr, instr_slab);
+            Some(dtmp_type_symidx)
+        }
+        (TypeDiscriminants::F64, TypeDiscriminants::I1) => {
+            let btmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I1, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let dzero_symidx = process_literal(symtab, "0.0d");
+            let d2b_instr = NhwcInstrType::new_fcmp(btmp_type_symidx.clone(), FcmpPlan::One, symidx_be_transed.clone(), dzero_symidx, Type::F64).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(d2b_instr, instr_slab);
+            Some(btmp_type_symidx)
+        }
+        _ => None,
+    }
+}
+/// 处理与 i8 有关的类型转换，i8 转 i32 只需赋值(寄存器中本来就是符号扩展的)，i32 转 i8 使用 trunc ，
+/// 其他类型都先经过 i32 中转，字面量直接截断成 i32 字面量，其余情况返回 None
+fn force_trans_i8(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, ty_be_transed:TypeDiscriminants, ty_trans_to:TypeDiscriminants, symidx_be_transed:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
+    op_et_node:Option<u32>,et_tree:&mut EtTree,
+) -> Option<RcSymIdx> {
+    if ty_trans_to.is_i_8() && symidx_be_transed.as_ref_borrow().is_literal(){
+        let value = Value::from_symidx(&symidx_be_transed.as_ref_borrow()).force_to_ty(&Type::I8).force_to_ty(&Type::I32);
+        return Some(process_literal(symtab, value.to_symidx().symbol_name));
+    }
+    match (ty_be_transed, ty_trans_to) {
+        (TypeDiscriminants::I8, TypeDiscriminants::I32) => {
+            let itmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let assign_instr = NhwcInstrType::new_assign(itmp_type_symidx.clone(), symidx_be_transed.clone(), Type::I32).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(assign_instr, instr_slab);
+            Some(itmp_type_symidx)
+        }
+        (TypeDiscriminants::I8, _) => {
+            let itmp_type_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+            Some(force_trans_type_discriminant(cfg_graph, symtab, ty_trans_to, &itmp_type_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree))
+        }
+        (TypeDiscriminants::I32, TypeDiscriminants::I8) => {
+            let ctmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I8, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let i2c_instr = NhwcInstrType::new_int2char(symidx_be_transed.clone(), ctmp_type_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(i2c_instr, instr_slab);
+            Some(ctmp_type_symidx)
+        }
+        (_, TypeDiscriminants::I8) => {
+            let itmp_type_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+            Some(force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I8, &itmp_type_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree))
+        }
+        _ => None,
+    }
+}
 ///具有赋值性质的会将value的类型强制转换为var的类型，返回转换后的symidx
 fn force_trans_type(
     cfg_graph:&mut CfgGraph, symtab:&mut SymTab, symidx_trans_to:&RcSymIdx,  symidx_be_transed:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
@@ -718,7 +1158,28 @@ fn force_trans_type(
     // if type_be_transed == type_to_trans_to {
     //     return Ok(symidx.clone());
     // }
-    match (ty_be_transed.into(), ty_trans_to.into()) {
+    let (ty_be_transed, ty_trans_to) = (ty_be_transed.clone(), ty_trans_to.clone());
+    if let Some(rc_transed_symidx) = force_trans_i8(cfg_graph, symtab, (&ty_be_transed).into(), (&ty_trans_to).into(), symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    if let Some(rc_transed_symidx) = force_trans_u32(cfg_graph, symtab, (&ty_be_transed).into(), (&ty_trans_to).into(), symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    if let Some(rc_transed_symidx) = force_trans_i64(cfg_graph, symtab, (&ty_be_transed).into(), (&ty_trans_to).into(), symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    if let Some(rc_transed_symidx) = force_trans_f64(cfg_graph, symtab, (&ty_be_transed).into(), (&ty_trans_to).into(), symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    // u32 转 i1 与 i32 转 i1 相同
+    match ((&ty_be_transed).into(), (&ty_trans_to).into()) {
+        (TypeDiscriminants::U32, TypeDiscriminants::I1) => {
+            let btmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I1, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let izero_symidx = process_literal(symtab, "0u");
+            let u2b_instr = NhwcInstrType::new_icmp(btmp_type_symidx.clone(), IcmpPlan::Ne, symidx_be_transed.clone(), izero_symidx, Type::U32).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(u2b_instr, instr_slab);
+            return btmp_type_symidx;
+        }
         (TypeDiscriminants::I32, TypeDiscriminants::F32) => {
             //创建f32类型的临时变量
             let ftmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
@@ -785,7 +1246,26 @@ fn force_trans_type_discriminant(
     if ty_be_transed == ty_trans_to {
         return symidx_be_transed.clone()
     }
+    if let Some(rc_transed_symidx) = force_trans_i8(cfg_graph, symtab, ty_be_transed, ty_trans_to, symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    if let Some(rc_transed_symidx) = force_trans_u32(cfg_graph, symtab, ty_be_transed, ty_trans_to, symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    if let Some(rc_transed_symidx) = force_trans_i64(cfg_graph, symtab, ty_be_transed, ty_trans_to, symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
+    if let Some(rc_transed_symidx) = force_trans_f64(cfg_graph, symtab, ty_be_transed, ty_trans_to, symidx_be_transed, scope_node, cfg_bb, instr_slab, op_et_node, et_tree){
+        return rc_transed_symidx;
+    }
     match (ty_be_transed, ty_trans_to) {
+        (TypeDiscriminants::U32, TypeDiscriminants::I1) => {
+            let btmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I1, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
+            let izero_symidx = process_literal(symtab, "0u");
+            let u2b_instr = NhwcInstrType::new_icmp(btmp_type_symidx.clone(), IcmpPlan::Ne, symidx_be_transed.clone(), izero_symidx, Type::U32).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(u2b_instr, instr_slab);
+            btmp_type_symidx
+        }
         (TypeDiscriminants::I32, TypeDiscriminants::F32) => {
             //创建f32类型的临时变量
             let ftmp_type_symidx = process_temp_symbol(cfg_graph, symtab, &Type::F32, scope_node, cfg_bb,  instr_slab, op_et_node,et_tree, "");
@@ -841,6 +1321,10 @@ fn force_trans_type_discriminant(
         (TypeDiscriminants::Array ,TypeDiscriminants::I32) => {
             symidx_be_transed.clone()
         }
+        (TypeDiscriminants::I32 ,TypeDiscriminants::Ptr64) => {
+            // 例如 int *p = 0;
+            symidx_be_transed.clone()
+        }
         _ => panic!("{}", format!("{:?}=>{:?}该类型不支持强制转化",ty_be_transed,ty_trans_to)),
     }
 }
@@ -849,6 +1333,20 @@ fn autotrans_arith_type(
     cfg_graph:&mut CfgGraph, symtab:&mut SymTab, l_type:&Type, l_symidx:&RcSymIdx, r_type:&Type, r_symidx:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
     op_et_node:Option<u32>,et_tree:&mut EtTree
 ) -> (RcSymIdx, RcSymIdx) {
+    //char 先提升为 i32
+    if l_type.is_i_8() || r_type.is_i_8(){
+        let rc_l_symidx = force_trans_type_discriminant(cfg_graph, symtab, if l_type.is_i_8() {TypeDiscriminants::I32} else {l_type.into()}, l_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+        let rc_r_symidx = force_trans_type_discriminant(cfg_graph, symtab, if r_type.is_i_8() {TypeDiscriminants::I32} else {r_type.into()}, r_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+        let (l_type, r_type) = (if l_type.is_i_8() {Type::I32} else {l_type.clone()}, if r_type.is_i_8() {Type::I32} else {r_type.clone()});
+        return autotrans_arith_type(cfg_graph, symtab, &l_type, &rc_l_symidx, &r_type, &rc_r_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+    }
+    //有一边是 u32 i64 或 f64 时两边都转换为提升后的类型
+    if l_type.is_u_32() || r_type.is_u_32() || l_type.is_i_64() || r_type.is_i_64() || l_type.is_f_64() || r_type.is_f_64(){
+        let adapted_ty:TypeDiscriminants = (&Type::arith_adapt(l_type, r_type)).into();
+        let rc_transed_l_symidx = force_trans_type_discriminant(cfg_graph, symtab, adapted_ty, l_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+        let rc_transed_r_symidx = force_trans_type_discriminant(cfg_graph, symtab, adapted_ty, r_symidx, scope_node, cfg_bb, instr_slab, op_et_node, et_tree);
+        return (rc_transed_l_symidx, rc_transed_r_symidx);
+    }
     //adapt函数会去除掉不能进行运算的类型情况
     match (l_type.into(), r_type.into()) {
         (TypeDiscriminants::I32, TypeDiscriminants::F32) => {
@@ -913,6 +1411,12 @@ fn autotrans_logic_type(
     cfg_graph:&mut CfgGraph, symtab:&mut SymTab, l_type:&Type, l_symidx:&RcSymIdx, r_type:&Type, r_symidx:&RcSymIdx, scope_node:u32, cfg_node1:u32,cfg_node2:u32, instr_slab:&mut InstrSlab<NhwcInstr>,
     op_et_node:Option<u32>,et_tree:&mut EtTree
 ) -> (RcSymIdx, RcSymIdx) {
+    //u32 i8 i64 和 f64 转 i1 直接和 0 比较，之后两边都是 i1
+    if l_type.is_u_32() || r_type.is_u_32() || l_type.is_i_8() || r_type.is_i_8() || l_type.is_i_64() || r_type.is_i_64() || l_type.is_f_64() || r_type.is_f_64(){
+        let rc_transed_l_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I1, l_symidx, scope_node, cfg_node1, instr_slab, op_et_node, et_tree);
+        let rc_transed_r_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I1, r_symidx, scope_node, cfg_node2, instr_slab, op_et_node, et_tree);
+        return (rc_transed_l_symidx, rc_transed_r_symidx);
+    }
     //adapt函数会去除掉不能进行运算的类型情况
     match (l_type.into(), r_type.into()) {
         (TypeDiscriminants::I32, TypeDiscriminants::F32) => {
@@ -1030,6 +1534,31 @@ fn process_self_increment(
             let add_instr = NhwcInstrType::new_add(tmp_addvar_symidx.clone(), rc_var_symidx.clone(), ione_symidx, var_type.clone()).into();
             node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
         }
+        Type::U32 => {
+            let uone_symidx = process_literal(symtab, "1u");
+            let add_instr = NhwcInstrType::new_add(tmp_addvar_symidx.clone(), rc_var_symidx.clone(), uone_symidx, var_type.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
+        }
+        Type::I64 => {
+            let lone_symidx = process_literal(symtab, "1l");
+            let add_instr = NhwcInstrType::new_add(tmp_addvar_symidx.clone(), rc_var_symidx.clone(), lone_symidx, var_type.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
+        }
+        Type::F64 => {
+            let done_symidx = process_literal(symtab, "1.0d");
+            let add_instr = NhwcInstrType::new_add(tmp_addvar_symidx.clone(), rc_var_symidx.clone(), done_symidx, var_type.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
+        }
+        Type::I8 => {
+            //char 先提升为 i32 ，加一后再截断回 i8
+            let rc_int_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, &rc_var_symidx, scope_node, cfg_bb, instr_slab, Some(et_node), et_tree);
+            let ione_symidx = process_literal(symtab, "1");
+            let tmp_int_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I32, scope_node, cfg_bb,  instr_slab, Some(et_node),et_tree, "");
+            let add_instr = NhwcInstrType::new_add(tmp_int_symidx.clone(), rc_int_symidx, ione_symidx, Type::I32).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
+            let i2c_instr = NhwcInstrType::new_int2char(tmp_int_symidx, tmp_addvar_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(i2c_instr, instr_slab);
+        }
         _ => panic!("自增自减操作数不是数字类型的"),
     }
 
@@ -1039,6 +1568,341 @@ fn process_self_increment(
     (tmp_addvar_symidx, tmp_loadvar_symidx)
 }
 
+/// 处理 . 与 -> ，先用 getelementptr 得到成员的地址  
+/// 若成员是标量且不在赋值号左边则 load 出成员的值，否则返回成员的指针
+fn process_member_access(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
+) -> RcSymIdx {
+    let (is_arrow, member_name) = match &node!(at et_node in et_tree).et_node_type{
+        EtNodeType::Operator { op, ast_node: _, text, op_rc_symidx: _ } => (op.is_arrow_member(), text.clone()),
+        _ => panic!("et_node {} 不是成员访问",et_node)
+    };
+    let et_base_node = direct_child_node!(at et_node in et_tree);
+    // base 可能是 struct 变量本身，也可能是指向 struct 的指针(全局 struct ，数组元素，-> 左边的指针)
+    let rc_base_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_base_node, scope_node, cfg_bb, instr_slab, ast2scope).unwrap();
+    let base_ty = symtab.get(&rc_base_symidx.as_ref_borrow()).get_type().clone();
+    let struct_ty = match &base_ty{
+        Type::Struct { .. } | Type::Union { .. } if !is_arrow => base_ty.clone(),
+        Type::Ptr64 { ty } if ty.is_struct() || ty.is_union() => ty.to_complete_type(&|name| find_typedef_type(symtab, scope_tree, scope_node, name)),
+        _ => panic!("{:?} 无法通过 {} 访问成员 {} at et_node {}",base_ty,if is_arrow {"->"} else {"."},member_name,et_node)
+    };
+    let (offset, member_ty) = struct_ty.get_member(&member_name);
+    let rc_offset_symidx = process_literal(symtab, offset.to_string().leak());
+    let rc_member_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &member_ty.to_ref_ptr_type(), scope_node, cfg_bb, instr_slab, Some(et_node), et_tree, "ptr_of_member");
+    let get_member_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_member_ptr_symidx.clone(), rc_base_symidx.clone(), struct_ty, vec![Some(rc_offset_symidx)]).into();
+    node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(get_member_ptr_instr, instr_slab);
+    add_may_pointed_symidx_to(symtab, &rc_member_ptr_symidx, &rc_base_symidx);
+
+    // 外层的 [] 会把下标放到这个 et_node 的 dims 上 , 例如 p.a[1]
+    let (rc_ptr_symidx, infered_ty) = if node!(at et_node in et_tree).has_dims(){
+        let dims = node!(at et_node in et_tree).get_dims().clone();
+        let mut infered_ty = member_ty.clone();
+        for _ in 0..dims.len(){
+            infered_ty.pop_dim();
+        }
+        let rc_ele_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &infered_ty.to_ref_ptr_type(), scope_node, cfg_bb, instr_slab, Some(et_node), et_tree, "ptr_of_member_ele");
+        let get_ele_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_ele_ptr_symidx.clone(), rc_member_ptr_symidx.clone(), member_ty, dims).into();
+        node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(get_ele_ptr_instr, instr_slab);
+        add_may_pointed_symidx_to(symtab, &rc_ele_ptr_symidx, &rc_member_ptr_symidx);
+        (rc_ele_ptr_symidx, infered_ty)
+    }else{
+        (rc_member_ptr_symidx, member_ty)
+    };
+
+    if infered_ty.is_aggregate() || is_et_node_assigned(et_tree, et_node){
+        node_mut!(at et_node in et_tree).add_type(infered_ty.to_ref_ptr_type());
+        rc_ptr_symidx
+    }else{
+        let rc_member_symidx = process_temp_symbol(cfg_graph, symtab, &infered_ty, scope_node, cfg_bb, instr_slab, Some(et_node), et_tree, "member");
+        let load_instr = node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_load(rc_member_symidx.clone(), rc_ptr_symidx.clone(), infered_ty.to_ref_ptr_type()).into(), instr_slab);
+        push_mu_of_ptr(cfg_graph, symtab, cfg_bb, &rc_ptr_symidx, load_instr, instr_slab);
+        rc_member_symidx
+    }
+}
+/// 被取地址的局部标量被赋值时，需要通过指针 store 到内存中
+fn process_store_to_addr_taken_local(
+    cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, symtab:&mut SymTab, et_node:u32, left_child_et_node:u32, scope_node:u32, cfg_node:u32, instr_slab:&mut InstrSlab<NhwcInstr>, rc_l_symidx:&RcSymIdx, rc_r_symidx:&RcSymIdx,
+) {
+    let l_type = symtab.get(&rc_l_symidx.as_ref_borrow()).get_type().clone();
+    let ele_ty = l_type.get_ele_ty();
+    if let EtNodeType::Symbol { decldef_def_or_use:DeclOrDefOrUse::DeclDef { .. }, .. } = &node!(at left_child_et_node in et_tree).et_node_type {
+        let defvar_instr_struct = NhwcInstrType::new_def_var(l_type.clone(), rc_l_symidx.clone(), None).into();
+        node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(defvar_instr_struct, instr_slab);
+    }
+    let rc_l_ptr_symidx = process_ptr_of_addr_taken_local(cfg_graph, symtab, rc_l_symidx, scope_node, cfg_node, instr_slab, et_tree);
+    let rc_r_symidx = if ele_ty.is_ptr_64() { process_ptr_decay_or_bitcast(cfg_graph, symtab, &ele_ty, rc_r_symidx, scope_node, cfg_node, instr_slab, et_tree) } else { rc_r_symidx.clone() };
+    let transed_value_symidx = force_trans_type_discriminant(cfg_graph, symtab, (&ele_ty).into(),  &rc_r_symidx, scope_node, cfg_node, instr_slab, Some(et_node),et_tree);
+    let store_instr_struct = NhwcInstrType::new_store(rc_l_ptr_symidx, ele_ty.to_ref_ptr_type(), transed_value_symidx, ele_ty.clone()).into();
+    node_mut!(at et_node in et_tree).add_type(ele_ty);
+    let store_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(store_instr_struct, instr_slab);
+    let _chi_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(NhwcInstrType::new_chi(rc_l_symidx.clone(), rc_l_symidx.clone(), store_instr).into(), instr_slab);
+}
+/// 处理 & ，操作数是按 Def 处理的，因此 [] . -> * 返回的已经是地址
+fn process_addr_of(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_node:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
+) -> RcSymIdx {
+    let et_child = direct_child_node!(at et_node in et_tree);
+    let rc_child_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_child, scope_node, cfg_node, instr_slab, ast2scope,).unwrap();
+    let child_ty = symtab.get(&rc_child_symidx.as_ref_borrow()).get_type().clone();
+    let rc_ptr_symidx = if !node!(at et_child in et_tree).et_node_type.is_symbol() || rc_child_symidx.as_ref_borrow().is_global_ptr(){
+        rc_child_symidx.clone()
+    }else if is_addr_taken_local(symtab, &rc_child_symidx.as_ref_borrow()){
+        process_ptr_of_addr_taken_local(cfg_graph, symtab, &rc_child_symidx, scope_node, cfg_node, instr_slab, et_tree)
+    }else if *symtab.get(&rc_child_symidx.as_ref_borrow()).get_is_global(){
+        symtab.get(&rc_child_symidx.as_ref_borrow().to_globl_ptr()).rc_symidx.clone()
+    }else if child_ty.is_aggregate(){
+        let rc_temp_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &child_ty.to_ref_ptr_type(), scope_node, cfg_node, instr_slab, None, et_tree, "ptr_of_{:?}");
+        let get_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_temp_ptr_symidx.clone(), rc_child_symidx.clone(), child_ty.clone(), vec![]).into();
+        node_mut!(at cfg_node in cfg_graph).push_nhwc_instr(get_ptr_instr, instr_slab);
+        add_may_pointed_symidx_to(symtab, &rc_temp_ptr_symidx, &rc_child_symidx);
+        rc_temp_ptr_symidx
+    }else{
+        panic!("无法对 {:?} 取地址，暂不支持对函数参数取地址 at et_node {}",rc_child_symidx,et_node)
+    };
+    let ptr_ty = symtab.get(&rc_ptr_symidx.as_ref_borrow()).get_type().clone();
+    node_mut!(at et_node in et_tree).add_type(ptr_ty);
+    rc_ptr_symidx
+}
+/// 处理 * ，先用 getelementptr 得到一个新的指针  
+/// 若指向的是标量且不在赋值号左边则 load 出它的值，否则返回这个指针
+fn process_deref(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_node:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
+) -> RcSymIdx {
+    let et_child = direct_child_node!(at et_node in et_tree);
+    let rc_child_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_child, scope_node, cfg_node, instr_slab, ast2scope,).unwrap();
+    let child_ty = symtab.get(&rc_child_symidx.as_ref_borrow()).get_type().clone();
+    if child_ty.is_fn_ptr(){
+        // *fp 得到的函数作为值使用时又会变回函数指针，因此什么也不用做
+        node_mut!(at et_node in et_tree).add_type(child_ty);
+        return rc_child_symidx
+    }
+    let ptr_ty = match &child_ty{
+        Type::Ptr64 { .. } => child_ty.clone(),
+        Type::Array { .. } => child_ty.arr2ptr(),
+        _ => panic!("无法对 {:?} 类型的 {:?} 解引用 at et_node {}",child_ty,rc_child_symidx,et_node)
+    };
+    let rc_child_ptr_symidx = process_ptr_decay_or_bitcast(cfg_graph, symtab, &ptr_ty, &rc_child_symidx, scope_node, cfg_node, instr_slab, et_tree);
+    // 通过 getelementptr 得到一个新的指针，这样 load 与 store 的指针总是由当前基本块中的指令定义
+    let rc_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &ptr_ty, scope_node, cfg_node, instr_slab, None, et_tree, "ptr_of_{:?}");
+    let rc_zero_symidx = process_literal(symtab, "0");
+    let get_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_ptr_symidx.clone(), rc_child_ptr_symidx.clone(), ptr_ty.clone(), vec![Some(rc_zero_symidx)]).into();
+    node_mut!(at cfg_node in cfg_graph).push_nhwc_instr(get_ptr_instr, instr_slab);
+    add_may_pointed_symidx_to(symtab, &rc_ptr_symidx, &rc_child_ptr_symidx);
+    let pointee_ty = match &ptr_ty { Type::Ptr64 { ty } => ty.to_complete_type(&|name| find_typedef_type(symtab, scope_tree, scope_node, name)), _ => unreachable!() };
+    if pointee_ty.is_aggregate() || is_et_node_assigned(et_tree, et_node){
+        node_mut!(at et_node in et_tree).add_type(ptr_ty);
+        rc_ptr_symidx
+    }else{
+        let rc_value_symidx = process_temp_symbol(cfg_graph, symtab, &pointee_ty, scope_node, cfg_node, instr_slab, Some(et_node), et_tree, "value_from_ptr");
+        let load_instr = node_mut!(at cfg_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_load(rc_value_symidx.clone(), rc_ptr_symidx.clone(), ptr_ty).into(), instr_slab);
+        push_mu_of_ptr(cfg_graph, symtab, cfg_node, &rc_ptr_symidx, load_instr, instr_slab);
+        rc_value_symidx
+    }
+}
+/// 沿着 [] 与 . 向上寻找，判断 et_node 是否处在赋值号的左边
+fn is_et_node_assigned(et_tree:&EtTree, et_node:u32) -> bool {
+    let mut cur_et_node = et_node;
+    while let Some(parent_et_node) = direct_parent_node!(at cur_et_node in et_tree ret_option){
+        let is_first_child = direct_child_nodes!(at parent_et_node in et_tree)[0] == cur_et_node;
+        match &node!(at parent_et_node in et_tree).et_node_type{
+            EtNodeType::Operator { op: ExprOp::ArrayIndex, .. } | EtNodeType::Operator { op: ExprOp::DotMember, .. } if is_first_child => { cur_et_node = parent_et_node; },
+            EtNodeType::Operator { op: ExprOp::Assign, .. } => { return is_first_child },
+            // & 需要的是地址
+            EtNodeType::Operator { op: ExprOp::AddrOf, .. } => { return true },
+            _ => { return false },
+        }
+    }
+    false
+}
+
+/// 在通过 ptr 读内存的 load 之后插入 mu
+fn push_mu_of_ptr(cfg_graph:&mut CfgGraph, symtab:&SymTab, cfg_bb:u32, rc_ptr_symidx:&RcSymIdx, load_instr:usize, instr_slab:&mut InstrSlab<NhwcInstr>){
+    let rc_func_symidx = node!(at cfg_bb in cfg_graph).get_func_cor_symidx().clone();
+    for rc_may_pointed_symidx in get_may_pointed_symidx_vec(symtab, &rc_func_symidx, rc_ptr_symidx){
+        node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_mu(rc_may_pointed_symidx, load_instr).into(), instr_slab);
+    }
+}
+/// 在通过 ptr 写内存的 store 之后插入 chi
+fn push_chi_of_ptr(cfg_graph:&mut CfgGraph, symtab:&SymTab, cfg_bb:u32, rc_ptr_symidx:&RcSymIdx, store_instr:usize, instr_slab:&mut InstrSlab<NhwcInstr>){
+    let rc_func_symidx = node!(at cfg_bb in cfg_graph).get_func_cor_symidx().clone();
+    for rc_may_pointed_symidx in get_may_pointed_symidx_vec(symtab, &rc_func_symidx, rc_ptr_symidx){
+        node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_chi(rc_may_pointed_symidx.clone(), rc_may_pointed_symidx, store_instr).into(), instr_slab);
+    }
+}
+/// 得到指向被取地址的局部标量的指针
+fn process_ptr_of_addr_taken_local(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, rc_symidx:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>, et_tree:&mut EtTree,
+) -> RcSymIdx {
+    let ty = symtab.get(&rc_symidx.as_ref_borrow()).get_type().clone();
+    let rc_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &ty.to_ref_ptr_type(), scope_node, cfg_bb, instr_slab, None, et_tree, "ptr_of_{:?}");
+    let rc_zero_symidx = process_literal(symtab, "0");
+    let get_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_ptr_symidx.clone(), rc_symidx.clone(), ty, vec![Some(rc_zero_symidx)]).into();
+    node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(get_ptr_instr, instr_slab);
+    add_may_pointed_symidx_to(symtab, &rc_ptr_symidx, rc_symidx);
+    rc_ptr_symidx
+}
+/// 左边是局部的 struct/union 变量本身，或者是指向它的指针(全局变量以及 [] . -> * 得到的地址)，且右边也是 struct/union 时整体复制，返回被复制的类型
+fn get_aggregate_copy_ty(symtab:&SymTab, l_symidx:&SymIdx, l_type:&Type, r_type:&Type, is_l_var:bool) -> Option<Type>{
+    let is_r_aggregate = match r_type { Type::Ptr64 { ty } => ty.is_struct() || ty.is_union(), _ => r_type.is_struct() || r_type.is_union() };
+    match l_type{
+        Type::Struct { .. } | Type::Union { .. } if is_l_var && is_r_aggregate => Some(l_type.clone()),
+        Type::Ptr64 { ty } if (ty.is_struct() || ty.is_union()) && (!is_l_var || *symtab.get(l_symidx).get_is_global()) && is_r_aggregate => Some(*ty.clone()),
+        _ => None,
+    }
+}
+/// 处理 struct/union 的整体赋值，单独成函数以免增大递归的 process_et 的栈帧
+fn process_aggregate_assign(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, et_node:u32, left_child_et_node:u32, l_symidx:&SymIdx, l_type:&Type, r_type:&Type, is_l_var:bool, rc_l_symidx:&RcSymIdx, rc_r_symidx:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>, et_tree:&mut EtTree,
+){
+    let aggregate_ty = get_aggregate_copy_ty(symtab, l_symidx, l_type, r_type, is_l_var).unwrap();
+    // 声明时的初始化只由复制之后的 chi 定义，在它之前还需要一个定义
+    if let EtNodeType::Symbol { decldef_def_or_use:DeclOrDefOrUse::DeclDef { .. }, .. } = &node!(at left_child_et_node in et_tree).et_node_type {
+        let def_instr = node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_def_var(l_type.clone(), rc_l_symidx.clone(), None).into(), instr_slab);
+        node_mut!(at cfg_bb in cfg_graph).get_mut_def_symidx_instr_tuple_vec().push((rc_l_symidx.clone(),def_instr));
+    }
+    process_aggregate_copy(cfg_graph, symtab, &aggregate_ty, rc_l_symidx, rc_r_symidx, scope_node, cfg_bb, instr_slab, et_tree);
+    node_mut!(at et_node in et_tree).add_type(aggregate_ty);
+}
+/// struct/union 之间整体赋值，展开成其中每个标量的 load 与 store ，这样 mu 与 chi 和普通的成员访问一样在这里插入  
+/// 两边可能是局部的 struct/union 变量本身，也可能是指向它的指针(全局变量，成员，数组元素，解引用)
+fn process_aggregate_copy(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, aggregate_ty:&Type, rc_l_symidx:&RcSymIdx, rc_r_symidx:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>, et_tree:&mut EtTree,
+){
+    for (offset, leaf_ty) in aggregate_ty.get_scalar_leaves(){
+        let rc_offset_symidx = process_literal(symtab, offset.to_string().leak());
+        let mut leaf_ptr_of = |rc_base_symidx:&RcSymIdx, symtab:&mut SymTab, cfg_graph:&mut CfgGraph| {
+            let rc_leaf_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &leaf_ty.to_ref_ptr_type(), scope_node, cfg_bb, instr_slab, None, et_tree, "ptr_of_copied");
+            let get_leaf_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_leaf_ptr_symidx.clone(), rc_base_symidx.clone(), aggregate_ty.clone(), vec![Some(rc_offset_symidx.clone())]).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(get_leaf_ptr_instr, instr_slab);
+            add_may_pointed_symidx_to(symtab, &rc_leaf_ptr_symidx, rc_base_symidx);
+            rc_leaf_ptr_symidx
+        };
+        let rc_src_ptr_symidx = leaf_ptr_of(rc_r_symidx, symtab, cfg_graph);
+        let rc_dest_ptr_symidx = leaf_ptr_of(rc_l_symidx, symtab, cfg_graph);
+        let rc_value_symidx = process_temp_symbol(cfg_graph, symtab, &leaf_ty, scope_node, cfg_bb, instr_slab, None, et_tree, "copied");
+        let load_instr = node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_load(rc_value_symidx.clone(), rc_src_ptr_symidx.clone(), leaf_ty.to_ref_ptr_type()).into(), instr_slab);
+        push_mu_of_ptr(cfg_graph, symtab, cfg_bb, &rc_src_ptr_symidx, load_instr, instr_slab);
+        let store_instr = node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_store(rc_dest_ptr_symidx.clone(), leaf_ty.to_ref_ptr_type(), rc_value_symidx, leaf_ty.clone()).into(), instr_slab);
+        push_chi_of_ptr(cfg_graph, symtab, cfg_bb, &rc_dest_ptr_symidx, store_instr, instr_slab);
+    }
+}
+/// 把数组退化为指向首元素的指针，若与 ptr_ty 指向的类型不同则再进行 bitcast  
+/// 整数(例如 0)原样返回
+fn process_ptr_decay_or_bitcast(
+    cfg_graph:&mut CfgGraph, symtab:&mut SymTab, ptr_ty:&Type, rc_symidx:&RcSymIdx, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>, et_tree:&mut EtTree,
+) -> RcSymIdx {
+    let ty = symtab.get(&rc_symidx.as_ref_borrow()).get_type().clone();
+    let (rc_ptr_symidx, ty) = match &ty{
+        Type::Array { .. } => {
+            let rc_ele_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &ty.arr2ptr(), scope_node, cfg_bb, instr_slab, None, et_tree, "ele_ptr_of_{:?}");
+            let get_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_ele_ptr_symidx.clone(), rc_symidx.clone(), ty.clone(), vec![]).into();
+            node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(get_ptr_instr, instr_slab);
+            add_may_pointed_symidx_to(symtab, &rc_ele_ptr_symidx, rc_symidx);
+            (rc_ele_ptr_symidx, ty.arr2ptr())
+        },
+        Type::Ptr64 { .. } => (rc_symidx.clone(), ty),
+        Type::I32 | Type::U32 | Type::I64 => return rc_symidx.clone(),
+        _ => panic!("{:?} 无法转化为指针 {:?}",ty,ptr_ty),
+    };
+    if &ty == ptr_ty || !ptr_ty.is_ptr_64(){
+        rc_ptr_symidx
+    }else{
+        let rc_casted_ptr_symidx = process_temp_symbol(cfg_graph, symtab, ptr_ty, scope_node, cfg_bb, instr_slab, None, et_tree, "bitcast_ptr");
+        let bitcast_instr = NhwcInstrType::new_ptr2ptr(rc_casted_ptr_symidx.clone(), ptr_ty.clone(), rc_ptr_symidx.clone(), ty).into();
+        node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(bitcast_instr, instr_slab);
+        add_may_pointed_symidx_to(symtab, &rc_casted_ptr_symidx, &rc_ptr_symidx);
+        rc_casted_ptr_symidx
+    }
+}
+/// 处理指针与整数之间的 + - ，两边都不是指针时返回 None 交给普通的算术运算处理  
+/// p + i 被翻译为以 i 为下标的 getelementptr ，因此偏移会自动乘上元素的大小
+fn process_ptr_arith(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>, is_sub:bool,
+) -> Option<RcSymIdx> {
+    let next_nodes = direct_child_nodes!(at et_node in et_tree with_predicate {|e|!e.weight().et_edge_type.is_deleted()});
+    check_child_nodes(&next_nodes, 2);
+    let rc_l_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[0], scope_node, cfg_bb, instr_slab, ast2scope,).unwrap();
+    let rc_r_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[1], scope_node, cfg_bb, instr_slab, ast2scope,).unwrap();
+    let is_ptr = |rc_symidx:&RcSymIdx| { let ty = symtab.get(&rc_symidx.as_ref_borrow()).get_type(); ty.is_ptr_64() || ty.is_array() };
+    // 这个函数处在 process_et 的递归路径上，因此真正生成指令的部分放到另一个函数中以减小栈帧
+    match (is_ptr(&rc_l_symidx), is_ptr(&rc_r_symidx)){
+        (false, false) => None,
+        (true, false) => Some(process_ptr_offset(cfg_graph, et_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, &rc_l_symidx, &rc_r_symidx, is_sub)),
+        (false, true) if !is_sub => Some(process_ptr_offset(cfg_graph, et_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, &rc_r_symidx, &rc_l_symidx, is_sub)),
+        _ => panic!("暂不支持两个指针之间的运算 at et_node {}",et_node),
+    }
+}
+/// 生成 ptr + offset 或 ptr - offset 对应的 getelementptr
+fn process_ptr_offset(
+    cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>, rc_ptr_symidx:&RcSymIdx, rc_offset_symidx:&RcSymIdx, is_sub:bool,
+) -> RcSymIdx {
+    let ptr_ty = {
+        let ty = symtab.get(&rc_ptr_symidx.as_ref_borrow()).get_type().clone();
+        if ty.is_array() { ty.arr2ptr() } else { ty }
+    };
+    let rc_ptr_symidx = process_ptr_decay_or_bitcast(cfg_graph, symtab, &ptr_ty, rc_ptr_symidx, scope_node, cfg_bb, instr_slab, et_tree);
+    let rc_offset_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, rc_offset_symidx, scope_node, cfg_bb, instr_slab, None, et_tree);
+    let rc_offset_symidx = if is_sub {
+        let rc_neg_offset_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I32, scope_node, cfg_bb, instr_slab, None, et_tree, "neg_offset");
+        let rc_zero_symidx = process_literal(symtab, "0");
+        node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(NhwcInstrType::new_sub(rc_neg_offset_symidx.clone(), rc_zero_symidx, rc_offset_symidx, Type::I32).into(), instr_slab);
+        rc_neg_offset_symidx
+    }else{
+        rc_offset_symidx
+    };
+    let rc_rst_ptr_symidx = process_temp_symbol(cfg_graph, symtab, &ptr_ty, scope_node, cfg_bb, instr_slab, Some(et_node), et_tree, "ptr_arith");
+    let get_ptr_instr = NhwcInstrType::new_get_element_ptr(rc_rst_ptr_symidx.clone(), rc_ptr_symidx.clone(), ptr_ty.clone(), vec![Some(rc_offset_symidx)]).into();
+    node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(get_ptr_instr, instr_slab);
+    add_may_pointed_symidx_to(symtab, &rc_rst_ptr_symidx, &rc_ptr_symidx);
+    node_mut!(at et_node in et_tree).add_type(ptr_ty);
+    rc_rst_ptr_symidx
+}
+
+///处理位运算和移位运算符，操作数按整型提升转换为 i32 或 u32 ，按位取反生成 xor -1  
+///移位运算的结果类型只取决于左操作数，u32 的右移生成逻辑右移 lshr
+fn process_bitwise_op(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>, op:&ExprOp,
+) -> RcSymIdx {
+    let next_nodes = direct_child_nodes!(at et_node in et_tree with_predicate {|e|!e.weight().et_edge_type.is_deleted()});
+    check_child_nodes(&next_nodes, if op.is_bitwise_not() { 1 } else { 2 });
+    let mut rc_operand_vec = vec![];
+    for &next_node in next_nodes.iter(){
+        let rc_operand_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_node, scope_node, cfg_bb, instr_slab, ast2scope,).unwrap();
+        match symtab.get(&rc_operand_symidx.as_ref_borrow()).get_type(){
+            Type::I32 | Type::U32 | Type::I8 | Type::I64 | Type::I1 => {},
+            ty => panic!("{:?} 类型的操作数不能进行位运算 {:?} at et_node {}",ty,op,et_node),
+        }
+        rc_operand_vec.push(rc_operand_symidx);
+    }
+    let is_u32 = |rc_symidx:&RcSymIdx| symtab.get(&rc_symidx.as_ref_borrow()).get_type().is_u_32();
+    let is_i64 = |rc_symidx:&RcSymIdx| symtab.get(&rc_symidx.as_ref_borrow()).get_type().is_i_64();
+    let var_type = match op{
+        ExprOp::LShift | ExprOp::RShift | ExprOp::BitwiseNot if is_i64(&rc_operand_vec[0]) => Type::I64,
+        ExprOp::LShift | ExprOp::RShift | ExprOp::BitwiseNot if is_u32(&rc_operand_vec[0]) => Type::U32,
+        ExprOp::BitwiseOr | ExprOp::BitwiseAnd | ExprOp::BitwiseXor if rc_operand_vec.iter().any(is_i64) => Type::I64,
+        ExprOp::BitwiseOr | ExprOp::BitwiseAnd | ExprOp::BitwiseXor if rc_operand_vec.iter().any(is_u32) => Type::U32,
+        _ => Type::I32,
+    };
+    let rc_operand_vec = rc_operand_vec.iter().map(|rc_operand_symidx| force_trans_type_discriminant(cfg_graph, symtab, (&var_type).into(), rc_operand_symidx, scope_node, cfg_bb, instr_slab, None, et_tree)).collect_vec();
+    let rc_tmp_var_symidx = process_temp_symbol(cfg_graph, symtab, &var_type, scope_node, cfg_bb, instr_slab, Some(et_node), et_tree, "bitop");
+    let (rc_a, rc_b) = match op{
+        ExprOp::BitwiseNot if var_type.is_u_32() => (rc_operand_vec[0].clone(), process_literal(symtab, "4294967295u")),
+        ExprOp::BitwiseNot if var_type.is_i_64() => (rc_operand_vec[0].clone(), process_literal(symtab, "-1l")),
+        ExprOp::BitwiseNot => (rc_operand_vec[0].clone(), process_literal(symtab, "-1")),
+        _ => (rc_operand_vec[0].clone(), rc_operand_vec[1].clone()),
+    };
+    let bitwise_instr = match op{
+        ExprOp::BitwiseOr => NhwcInstrType::new_bit_or(rc_tmp_var_symidx.clone(), rc_a, rc_b, var_type),
+        ExprOp::BitwiseAnd => NhwcInstrType::new_bit_and(rc_tmp_var_symidx.clone(), rc_a, rc_b, var_type),
+        ExprOp::BitwiseXor | ExprOp::BitwiseNot => NhwcInstrType::new_bit_xor(rc_tmp_var_symidx.clone(), rc_a, rc_b, var_type),
+        ExprOp::LShift => NhwcInstrType::new_shl(rc_tmp_var_symidx.clone(), rc_a, rc_b, var_type),
+        ExprOp::RShift if var_type.is_u_32() => NhwcInstrType::new_lshr(rc_tmp_var_symidx.clone(), rc_a, rc_b, var_type),
+        ExprOp::RShift => NhwcInstrType::new_ashr(rc_tmp_var_symidx.clone(), rc_a, rc_b, var_type),
+        _ => panic!("{:?} 不是位运算符",op),
+    }.into();
+    node_mut!(at cfg_bb in cfg_graph).push_nhwc_instr(bitwise_instr, instr_slab);
+    rc_tmp_var_symidx
+}
 ///处理自减运算符，不分左右
 fn process_self_attennuation(
     ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32,  instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
@@ -1065,6 +1929,31 @@ fn process_self_attennuation(
             let sub_instr = NhwcInstrType::new_sub(tmp_subvar_symidx.clone(), rc_var_symidx.clone(), ione_symidx, var_type.clone()).into();
             node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
         }
+        Type::U32 => {
+            let uone_symidx = process_literal(symtab, "1u");
+            let sub_instr = NhwcInstrType::new_sub(tmp_subvar_symidx.clone(), rc_var_symidx.clone(), uone_symidx, var_type.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
+        }
+        Type::I64 => {
+            let lone_symidx = process_literal(symtab, "1l");
+            let sub_instr = NhwcInstrType::new_sub(tmp_subvar_symidx.clone(), rc_var_symidx.clone(), lone_symidx, var_type.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
+        }
+        Type::F64 => {
+            let done_symidx = process_literal(symtab, "1.0d");
+            let sub_instr = NhwcInstrType::new_sub(tmp_subvar_symidx.clone(), rc_var_symidx.clone(), done_symidx, var_type.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
+        }
+        Type::I8 => {
+            //char 先提升为 i32 ，减一后再截断回 i8
+            let rc_int_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, &rc_var_symidx, scope_node, cfg_bb, instr_slab, Some(et_node), et_tree);
+            let ione_symidx = process_literal(symtab, "1");
+            let tmp_int_symidx = process_temp_symbol(cfg_graph, symtab, &Type::I32, scope_node, cfg_bb,  instr_slab, Some(et_node),et_tree, "");
+            let sub_instr = NhwcInstrType::new_sub(tmp_int_symidx.clone(), rc_int_symidx, ione_symidx, Type::I32).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
+            let i2c_instr = NhwcInstrType::new_int2char(tmp_int_symidx, tmp_subvar_symidx.clone()).into();
+            node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(i2c_instr, instr_slab);
+        }
         _ => {
             panic!("自增自减操作数不是数字类型的");
         }
@@ -1128,6 +2017,192 @@ fn process_logicop(
 
     (l_symidx.clone(), l_symidx, r_symidx)
 }
+///处理出现在bb中(即作为值而不是作为条件)的 && 与 || ，按短路求值展开为控制流
+/// 把 cfg_node 分割为 pre -> rhs -> cfg_node ，左操作数在 pre 中求值并跳转，右操作数只在 rhs 中求值，
+/// 结果变量在 pre 中先赋为短路值(&& 为 0，|| 为 1)，在 rhs 中赋为右操作数的值，由 ssa 在 cfg_node 处插入 phi
+fn process_logic_value(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_node:u32, logic_op:&ExprOp, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
+) -> RcSymIdx {
+    let next_nodes = direct_child_nodes!(at et_node in et_tree with_predicate {|e|!e.weight().et_edge_type.is_deleted()});
+    check_child_nodes(&next_nodes, 2);
+    let rc_l_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[0], scope_node, cfg_node, instr_slab, ast2scope,).unwrap();
+
+    // 左操作数的指令全部移动到 pre 中，cfg_node 成为汇合点
+    let cfg_pre_node = split_pre_node_of_join(cfg_node, cfg_graph, symtab, instr_slab);
+    let loop_level = node!(at cfg_node in cfg_graph).loop_level;
+    let mut rhs_struct = CfgNode::new_bb(vec![],loop_level);
+    rhs_struct.add_func_cor_symidx(node!(at cfg_node in cfg_graph).get_func_cor_symidx().clone());
+    let (cfg_rhs_node,short_value) = match logic_op{
+        ExprOp::LogicalAnd => {
+            let cfg_rhs_node = add_node_with_edge!({rhs_struct} with_edge {CfgEdge::new_if_true()} from cfg_pre_node in cfg_graph);
+            add_edge!({CfgEdge::new_if_false()} from cfg_pre_node to cfg_node in cfg_graph);
+            (cfg_rhs_node,"0")
+        },
+        ExprOp::LogicalOr => {
+            let cfg_rhs_node = add_node_with_edge!({rhs_struct} with_edge {CfgEdge::new_if_false()} from cfg_pre_node in cfg_graph);
+            add_edge!({CfgEdge::new_if_true()} from cfg_pre_node to cfg_node in cfg_graph);
+            (cfg_rhs_node,"1")
+        },
+        _=>{
+            panic!("操作符{:?}不是逻辑运算符",*logic_op)
+        }
+    };
+    add_edge!({CfgEdge::new_direct()} from cfg_rhs_node to cfg_node in cfg_graph);
+
+    // 结果变量会被定义两次，因此不能是 temp ，需要作为声明过的变量交给 ssa 处理
+    let rc_rst_symidx = add_symbol!({Symbol::new(scope_node,format!("logic.{}",et_node).leak())}
+        with_field TYPE:{Type::I32}
+        with_field DEF_CFG_NODE_VEC:{vec![cfg_pre_node,cfg_rhs_node]}
+        with_field DEF_INSTRS_VEC:{Vec::<usize>::new()}
+        with_field IS_GLOBAL:{false}
+        with_field IS_TEMP:{false}
+        with_field IS_LITERAL:{false}
+        with_field IS_FUNC_PARA:{false}
+    to symtab);
+    let func_symidx = node!(at cfg_node in cfg_graph).get_func_cor_symidx().clone();
+    symtab.get_mut(&func_symidx.as_ref_borrow()).get_mut_declared_vars().push(rc_rst_symidx.clone());
+    let cfg_entry = get_cfg_entry_by_cfg_node(cfg_graph, symtab, cfg_node);
+    node_mut!(at cfg_entry in cfg_graph).push_nhwc_instr(NhwcInstrType::new_alloc(Type::I32, rc_rst_symidx.clone()).into(), instr_slab);
+
+    let rc_r_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[1], scope_node, cfg_rhs_node, instr_slab, ast2scope,).unwrap();
+    let (l_type,r_type) = (symtab.get(&rc_l_symidx.as_ref_borrow()).get_type().clone(),symtab.get(&rc_r_symidx.as_ref_borrow()).get_type().clone());
+    let (rc_l_symidx, rc_r_symidx) = autotrans_logic_type(cfg_graph, symtab, &l_type, &rc_l_symidx, &r_type, &rc_r_symidx, scope_node, cfg_pre_node, cfg_rhs_node, instr_slab, Some(et_node),et_tree);
+
+    let short_symidx = process_literal(symtab, short_value);
+    let def_instr = node_mut!(at cfg_pre_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_def_var(Type::I32, rc_rst_symidx.clone(), None).into(), instr_slab);
+    node_mut!(at cfg_pre_node in cfg_graph).get_mut_def_symidx_instr_tuple_vec().push((rc_rst_symidx.clone(),def_instr));
+    node_mut!(at cfg_pre_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_assign(rc_rst_symidx.clone(), short_symidx, Type::I32).into(), instr_slab);
+    let rc_r_i32_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, &rc_r_symidx, scope_node, cfg_rhs_node, instr_slab, Some(et_node), et_tree);
+    let assign_instr = node_mut!(at cfg_rhs_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_assign(rc_rst_symidx.clone(), rc_r_i32_symidx, Type::I32).into(), instr_slab);
+    node_mut!(at cfg_rhs_node in cfg_graph).get_mut_def_symidx_instr_tuple_vec().push((rc_rst_symidx.clone(),assign_instr));
+
+    // 右操作数中可能还有短路运算，rhs 可能已被分割，因此跳转目标需要通过 pre 的出边重新查找
+    let cfg_true_node = direct_child_node!(at cfg_pre_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_true()});
+    let cfg_false_node = direct_child_node!(at cfg_pre_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_false()});
+    let label_true_symidx = find_or_new_label_to_cfg_node(cfg_true_node,cfg_pre_node, "logic_short_circuit_true", symtab,cfg_graph,instr_slab);
+    let label_false_symidx = find_or_new_label_to_cfg_node(cfg_false_node,cfg_pre_node, "logic_short_circuit_false", symtab,cfg_graph,instr_slab);
+    let br_instr = NhwcInstrType::new_br(rc_l_symidx,label_true_symidx,label_false_symidx).into();
+    node_mut!(at cfg_pre_node in cfg_graph).push_nhwc_instr(br_instr, instr_slab);
+
+    node_mut!(at et_node in et_tree).add_type(Type::I32);
+    rc_rst_symidx
+}
+/// 在表达式中间展开控制流时使用，把 cfg_node 分割为 pre 与 cfg_node ，cfg_node 中已有的指令、标签与入边都移动到 pre 中，
+/// cfg_node 留作之后新建分支的汇合点，之后的指令继续加入 cfg_node 。pre 与 cfg_node 之间没有边，由调用者连接  
+/// cfg_node 可以是 bb ，也可以是 Branch 、 WhileLoop 这类条件节点
+fn split_pre_node_of_join(cfg_node:u32, cfg_graph:&mut CfgGraph, symtab:&mut SymTab, instr_slab:&mut InstrSlab<NhwcInstr>) -> u32{
+    let (cfg_pre_node,pre_edge) = split_bb_node(cfg_node, cfg_graph, true).unwrap();
+    cfg_graph.remove_edge(EdgeIndex::new(pre_edge as usize));
+    // BodyTail 边约定只连到循环节点，循环节点的条件被分割后，循环体尾部改为以 Direct 边连到 pre
+    for cfg_edge in cfg_graph.edges_directed(node_index(cfg_pre_node as usize), Incoming).filter(|e| e.weight().cfg_edge_type.is_body_tail()).map(|e| e.id()).collect_vec(){
+        cfg_graph[cfg_edge] = CfgEdge::new_direct();
+    }
+    if let Some(def_symidx_instr_tuple_vec) = node_mut!(at cfg_node in cfg_graph).get_op_mut_def_symidx_instr_tuple_vec().map(mem::take){
+        node_mut!(at cfg_pre_node in cfg_graph).add_def_symidx_instr_tuple_vec(def_symidx_instr_tuple_vec);
+    }
+    if node!(at cfg_pre_node in cfg_graph).op_label_instr.is_some(){
+        let rc_label_symidx = find_label_of_cfg_node(cfg_pre_node, cfg_graph, instr_slab);
+        *symtab.get_mut(&rc_label_symidx.as_ref_borrow()).get_mut_label_cfg_node() = cfg_pre_node;
+    }
+    cfg_pre_node
+}
+/// 判断 et_node 为根的表达式是否没有副作用且不会出错，这样的表达式可以无条件求值
+fn is_pure_et(et_node:u32, et_tree:&EtTree) -> bool{
+    match &node!(at et_node in et_tree).et_node_type{
+        EtNodeType::Literal { .. } | EtNodeType::Symbol { .. } => true,
+        EtNodeType::Operator { op, .. } => {
+            matches!(op, ExprOp::Add | ExprOp::Sub | ExprOp::Mul | ExprOp::BitwiseOr | ExprOp::BitwiseAnd | ExprOp::BitwiseXor | ExprOp::BitwiseNot
+                | ExprOp::Eq | ExprOp::NEq | ExprOp::Less | ExprOp::Greater | ExprOp::LEq | ExprOp::GEq | ExprOp::LShift | ExprOp::RShift
+                | ExprOp::LogicalNot | ExprOp::Negative | ExprOp::Positive)
+            && direct_child_nodes!(at et_node in et_tree with_predicate {|e|!e.weight().et_edge_type.is_deleted()}).into_iter().all(|et_child_node| is_pure_et(et_child_node, et_tree))
+        },
+        EtNodeType::Separator { .. } => false,
+    }
+}
+///处理条件运算符 c ? a : b
+/// 两侧都没有副作用时在 cfg_node 中求值两侧并生成 select ，
+/// 否则把 cfg_node 分割为 pre -> then/else -> cfg_node 的菱形，两侧分别只在 then 与 else 中求值，
+/// 结果变量在 then 与 else 中分别赋值，由 ssa 在 cfg_node 处插入 phi
+fn process_conditional(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_node:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
+) -> RcSymIdx {
+    let next_nodes = direct_child_nodes!(at et_node in et_tree with_predicate {|e|!e.weight().et_edge_type.is_deleted()});
+    check_child_nodes(&next_nodes, 3);
+    let rc_cond_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[0], scope_node, cfg_node, instr_slab, ast2scope,).unwrap();
+    let rc_cond_symidx = force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I1, &rc_cond_symidx, scope_node, cfg_node, instr_slab, Some(et_node), et_tree);
+    //两侧类型相同时不做转换，有一侧是指针时取指针类型，否则按算术转换的规则统一类型
+    let common_type = |a_type:&Type,b_type:&Type| -> Type {
+        if a_type == b_type || a_type.is_ptr_64() { a_type.clone() }
+        else if b_type.is_ptr_64() { b_type.clone() }
+        else { Type::arith_adapt(a_type, b_type) }
+    };
+    if is_pure_et(next_nodes[1], et_tree) && is_pure_et(next_nodes[2], et_tree){
+        let rc_a_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[1], scope_node, cfg_node, instr_slab, ast2scope,).unwrap();
+        let rc_b_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[2], scope_node, cfg_node, instr_slab, ast2scope,).unwrap();
+        let (a_type,b_type) = (symtab.get(&rc_a_symidx.as_ref_borrow()).get_type().clone(),symtab.get(&rc_b_symidx.as_ref_borrow()).get_type().clone());
+        let rst_type = common_type(&a_type,&b_type);
+        let (rc_a_symidx,rc_b_symidx) = if a_type == b_type || rst_type.is_ptr_64() { (rc_a_symidx,rc_b_symidx) } else {
+            (force_trans_type_discriminant(cfg_graph, symtab, (&rst_type).into(), &rc_a_symidx, scope_node, cfg_node, instr_slab, Some(et_node), et_tree),
+            force_trans_type_discriminant(cfg_graph, symtab, (&rst_type).into(), &rc_b_symidx, scope_node, cfg_node, instr_slab, Some(et_node), et_tree))
+        };
+        let rc_rst_symidx = process_temp_symbol(cfg_graph, symtab, &rst_type, scope_node, cfg_node, instr_slab, Some(et_node), et_tree, "select");
+        let select_instr = NhwcInstrType::new_select(rc_rst_symidx.clone(), rc_cond_symidx, rc_a_symidx, rc_b_symidx, rst_type).into();
+        node_mut!(at cfg_node in cfg_graph).push_nhwc_instr(select_instr, instr_slab);
+        return rc_rst_symidx;
+    }
+    // 条件的指令全部移动到 pre 中，cfg_node 成为汇合点，cfg_node 是 if 、 while 或 for 的条件节点时它自己的出边与跳转保持不变
+    let cfg_pre_node = split_pre_node_of_join(cfg_node, cfg_graph, symtab, instr_slab);
+    let loop_level = node!(at cfg_node in cfg_graph).loop_level;
+    let func_symidx = node!(at cfg_node in cfg_graph).get_func_cor_symidx().clone();
+    let mut then_struct = CfgNode::new_bb(vec![],loop_level);
+    then_struct.add_func_cor_symidx(func_symidx.clone());
+    let mut else_struct = CfgNode::new_bb(vec![],loop_level);
+    else_struct.add_func_cor_symidx(func_symidx.clone());
+    let cfg_then_node = add_node_with_edge!({then_struct} with_edge {CfgEdge::new_if_true()} from cfg_pre_node in cfg_graph);
+    let cfg_else_node = add_node_with_edge!({else_struct} with_edge {CfgEdge::new_if_false()} from cfg_pre_node in cfg_graph);
+    add_edge!({CfgEdge::new_direct()} from cfg_then_node to cfg_node in cfg_graph);
+    add_edge!({CfgEdge::new_direct()} from cfg_else_node to cfg_node in cfg_graph);
+
+    let rc_a_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[1], scope_node, cfg_then_node, instr_slab, ast2scope,).unwrap();
+    let rc_b_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, next_nodes[2], scope_node, cfg_else_node, instr_slab, ast2scope,).unwrap();
+    let (a_type,b_type) = (symtab.get(&rc_a_symidx.as_ref_borrow()).get_type().clone(),symtab.get(&rc_b_symidx.as_ref_borrow()).get_type().clone());
+    let rst_type = common_type(&a_type,&b_type);
+    let (rc_a_symidx,rc_b_symidx) = if a_type == b_type || rst_type.is_ptr_64() { (rc_a_symidx,rc_b_symidx) } else {
+        (force_trans_type_discriminant(cfg_graph, symtab, (&rst_type).into(), &rc_a_symidx, scope_node, cfg_then_node, instr_slab, Some(et_node), et_tree),
+        force_trans_type_discriminant(cfg_graph, symtab, (&rst_type).into(), &rc_b_symidx, scope_node, cfg_else_node, instr_slab, Some(et_node), et_tree))
+    };
+
+    // 结果变量会被定义两次，因此不能是 temp ，需要作为声明过的变量交给 ssa 处理
+    let rc_rst_symidx = add_symbol!({Symbol::new(scope_node,format!("cond.{}",et_node).leak())}
+        with_field TYPE:{rst_type.clone()}
+        with_field DEF_CFG_NODE_VEC:{vec![cfg_pre_node,cfg_then_node,cfg_else_node]}
+        with_field DEF_INSTRS_VEC:{Vec::<usize>::new()}
+        with_field IS_GLOBAL:{false}
+        with_field IS_TEMP:{false}
+        with_field IS_LITERAL:{false}
+        with_field IS_FUNC_PARA:{false}
+    to symtab);
+    symtab.get_mut(&func_symidx.as_ref_borrow()).get_mut_declared_vars().push(rc_rst_symidx.clone());
+    let cfg_entry = get_cfg_entry_by_cfg_node(cfg_graph, symtab, cfg_node);
+    node_mut!(at cfg_entry in cfg_graph).push_nhwc_instr(NhwcInstrType::new_alloc(rst_type.clone(), rc_rst_symidx.clone()).into(), instr_slab);
+    let def_instr = node_mut!(at cfg_pre_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_def_var(rst_type.clone(), rc_rst_symidx.clone(), None).into(), instr_slab);
+    node_mut!(at cfg_pre_node in cfg_graph).get_mut_def_symidx_instr_tuple_vec().push((rc_rst_symidx.clone(),def_instr));
+    for (cfg_arm_node,rc_arm_symidx) in [(cfg_then_node,rc_a_symidx),(cfg_else_node,rc_b_symidx)]{
+        let assign_instr = node_mut!(at cfg_arm_node in cfg_graph).push_nhwc_instr(NhwcInstrType::new_assign(rc_rst_symidx.clone(), rc_arm_symidx, rst_type.clone()).into(), instr_slab);
+        node_mut!(at cfg_arm_node in cfg_graph).get_mut_def_symidx_instr_tuple_vec().push((rc_rst_symidx.clone(),assign_instr));
+    }
+
+    // 两侧中可能还有短路运算或条件运算，then 与 else 可能已被分割，因此跳转目标需要通过 pre 的出边重新查找
+    let cfg_true_node = direct_child_node!(at cfg_pre_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_true()});
+    let cfg_false_node = direct_child_node!(at cfg_pre_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_false()});
+    let label_true_symidx = find_or_new_label_to_cfg_node(cfg_true_node,cfg_pre_node, "cond_true", symtab,cfg_graph,instr_slab);
+    let label_false_symidx = find_or_new_label_to_cfg_node(cfg_false_node,cfg_pre_node, "cond_false", symtab,cfg_graph,instr_slab);
+    let br_instr = NhwcInstrType::new_br(rc_cond_symidx,label_true_symidx,label_false_symidx).into();
+    node_mut!(at cfg_pre_node in cfg_graph).push_nhwc_instr(br_instr, instr_slab);
+
+    node_mut!(at et_node in et_tree).add_type(rst_type);
+    rc_rst_symidx
+}
 ///处理比较运算符，先将操作数根据运算符自动转换，根据操作符生成对应instr并返回和临时变量symidx
 fn process_cmp_op(
     ast_tree:&AstTree, cfg_graph:&mut CfgGraph, et_tree:&mut EtTree, scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,cmp_plan:CmpPlan,ast2scope:&HashMap<u32, u32>,
@@ -1156,9 +2231,23 @@ fn process_cmp_op(
     //     return Err(anyhow!("{:?} and {:?} type {:?} neq {:?}",transed_l_symidx,transed_r_symidx,symtab.get(&transed_l_symidx)?.get_type()?,symtab.get(&transed_l_symidx)?.get_type()?))
     // }
     let ty = symtab.get(&transed_l_symidx).get_type().clone();
-    if ty.is_f_32(){
+    // i64 与 u32 比较时 u32 已经零扩展为 i64 ，仍然按有符号比较
+    let adapted_ty = symtab.get(&rc_transed_l_symidx.as_ref_borrow()).get_type().clone();
+    // char 已经提升为 i32
+    let ty = if ty.is_i_8() { Type::I32 } else { ty };
+    if adapted_ty.is_f_64(){
+        let fcmp_instr = NhwcInstrType::new_fcmp(tmp_var_symidx.clone(), cmp_plan.to_fcmp_plan(), rc_transed_l_symidx, rc_transed_r_symidx, Type::F64).into();
+        (fcmp_instr,tmp_var_symidx)
+    }else if ty.is_f_32(){
         let fcmp_instr = NhwcInstrType::new_fcmp(tmp_var_symidx.clone(), cmp_plan.to_fcmp_plan(), rc_transed_l_symidx, rc_transed_r_symidx, ty).into();
         (fcmp_instr,tmp_var_symidx)
+    }else if adapted_ty.is_i_64(){
+        let icmp_instr = NhwcInstrType::new_icmp(tmp_var_symidx.clone(), cmp_plan.to_icmp_plan(), rc_transed_l_symidx, rc_transed_r_symidx, Type::I64).into();
+        (icmp_instr,tmp_var_symidx)
+    }else if adapted_ty.is_u_32(){
+        // 有一边是 u32 时按无符号比较
+        let icmp_instr = NhwcInstrType::new_icmp(tmp_var_symidx.clone(), cmp_plan.to_unsigned_icmp_plan(), rc_transed_l_symidx, rc_transed_r_symidx, Type::U32).into();
+        (icmp_instr,tmp_var_symidx)
     }else {
         let icmp_instr = NhwcInstrType::new_icmp(tmp_var_symidx.clone(), cmp_plan.to_icmp_plan(), rc_transed_l_symidx, rc_transed_r_symidx, ty).into();
         (icmp_instr,tmp_var_symidx)
@@ -1172,19 +2261,29 @@ fn process_call(
     //取函数名和实参
     let func_name_and_args = direct_child_nodes!(at et_node in et_tree with_predicate {|e| !e.weight().et_edge_type.is_deleted()});
     let func_name_et_node = func_name_and_args[0];
-    let et_type = &node!(at func_name_et_node in et_tree).et_node_type;
-    let func_name_str ;
-    match et_type {
-        EtNodeType::Symbol { rc_symidx:_, ast_node, text:_, decldef_def_or_use } => {
-            let ast_node = *ast_node;
-            func_name_str = node!(at ast_node in ast_tree).op_text.clone().unwrap().leak();
-        }
-        _ => {
-            panic!("et生成错误，call节点下第一个不是函数名")
+    // 被调用的名字在 scope 中解析为函数时是直接调用，否则被调用的是一个函数指针的值
+    let op_direct_func_symidx = match &node!(at func_name_et_node in et_tree).et_node_type {
+        &EtNodeType::Symbol { rc_symidx:_, ast_node, text:_, decldef_def_or_use:_ } => {
+            let func_name_str:&'static str = node!(at ast_node in ast_tree).op_text.clone().unwrap().leak();
+            let mut symbol_scope = scope_node;
+            while symtab.try_get(&SymIdx::new(symbol_scope, func_name_str)).is_err() && symbol_scope != ST_ROOT {
+                symbol_scope = direct_parent_node!(at symbol_scope in scope_tree);
+            }
+            symtab.try_get(&SymIdx::new(symbol_scope, func_name_str)).ok().filter(|symbol| symbol.get_type().is_fn()).map(|symbol| symbol.rc_symidx.clone())
         }
-    }
-    let rc_callee_func_symidx = &symtab.get(&SymIdx::new(0, func_name_str)).rc_symidx.clone();
+        _ => None
+    };
+    let (rc_callee_func_symidx, is_indirect) = match op_direct_func_symidx {
+        Some(rc_func_symidx) => (rc_func_symidx, false),
+        None => (process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, func_name_et_node, scope_node, cfg_bb, instr_slab, ast2scope,).unwrap(), true),
+    };
+    let rc_callee_func_symidx = &rc_callee_func_symidx;
     let callee_func_symidx = rc_callee_func_symidx.as_ref_borrow();
+    // 间接调用时通过函数指针指向的函数类型检查实参
+    let callee_func_type = match symtab.get(&callee_func_symidx).get_type(){
+        Type::Ptr64 { ty } if is_indirect && ty.is_fn() => ty.as_ref().clone(),
+        ty => ty.clone(),
+    };
     // 给func call节点的field中添加cor symidx信息
     let rc_caller_func_symidx = node_mut!(at cfg_bb in cfg_graph).get_func_cor_symidx().clone();
     let caller_func_symidx = rc_caller_func_symidx.as_ref_borrow();
@@ -1211,13 +2310,25 @@ fn process_call(
         };
         rc_para_symidx_vec.push(para_symidx);
     }
-    let ret_type = if let Type::Fn { arg_syms, ret_sym } = symtab.get(&callee_func_symidx).get_type().clone(){
+    let is_variadic = !is_indirect && *symtab.get(&callee_func_symidx).get_is_variadic();
+    let ret_type = if let Type::Fn { arg_syms, ret_sym } = callee_func_type{
         //检查形参和实参是否一致
-        if rc_para_symidx_vec.len() == arg_syms.len(){
+        // `f()` 声明的函数参数未指定，多出来的实参和 ... 一样只做默认实参提升，`f(void)` 的多余实参在语义检查中已经报错
+        if rc_para_symidx_vec.len() == arg_syms.len() || ((is_variadic || arg_syms.is_empty()) && rc_para_symidx_vec.len() > arg_syms.len()){
             for arg_idx in 0..arg_syms.len(){
                 let transed_symidx = force_trans_type(cfg_graph, symtab, &arg_syms[arg_idx], &rc_para_symidx_vec[arg_idx], scope_node, cfg_bb, instr_slab,  Some(et_node), et_tree);
                 *rc_para_symidx_vec.get_mut(arg_idx).unwrap() = transed_symidx; 
             }
+            // ... 对应的实参做默认实参提升，float 提升为 double ，char 与 bool 提升为 int
+            for arg_idx in arg_syms.len()..rc_para_symidx_vec.len(){
+                let promoted_ty = match TypeDiscriminants::from(symtab.get(&rc_para_symidx_vec[arg_idx].as_ref_borrow()).get_type()){
+                    TypeDiscriminants::F32 => TypeDiscriminants::F64,
+                    TypeDiscriminants::I8 | TypeDiscriminants::I1 => TypeDiscriminants::I32,
+                    ty => ty,
+                };
+                let transed_symidx = force_trans_type_discriminant(cfg_graph, symtab, promoted_ty, &rc_para_symidx_vec[arg_idx], scope_node, cfg_bb, instr_slab, Some(et_node), et_tree);
+                *rc_para_symidx_vec.get_mut(arg_idx).unwrap() = transed_symidx;
+            }
         }else{
             panic!("传入实参与函数形参数量不符")
         }
@@ -1225,8 +2336,13 @@ fn process_call(
     }else{
         panic!("调用对象不是函数类型")
     };
+    let new_call = |assigned, ret_type| if is_indirect {
+        NhwcInstrType::new_indirect_func_call(assigned, rc_callee_func_symidx.clone(), rc_para_symidx_vec.clone(), ret_type)
+    }else{
+        NhwcInstrType::new_func_call(assigned, rc_callee_func_symidx.clone(), rc_para_symidx_vec.clone(), ret_type)
+    };
     if let Type::Void = ret_type{
-        let call_instr_struct = NhwcInstrType::new_func_call(None, rc_callee_func_symidx.clone(), rc_para_symidx_vec.clone(), ret_type).into();
+        let call_instr_struct = new_call(None, ret_type).into();
         let call_instr = node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(call_instr_struct, instr_slab);
         for rc_para_symidx in rc_para_symidx_vec{
             let para_symidx = rc_para_symidx.as_ref_borrow();
@@ -1234,7 +2350,7 @@ fn process_call(
         (None,call_instr)
     }else{
         let tmp_symidx = process_temp_symbol(cfg_graph, symtab, &ret_type, scope_node, cfg_bb,  instr_slab, Some(et_node),et_tree, "ret_val");
-        let call_instr_struct = NhwcInstrType::new_func_call(Some(tmp_symidx.clone()), rc_callee_func_symidx.clone(), rc_para_symidx_vec.clone(), ret_type).into();
+        let call_instr_struct = new_call(Some(tmp_symidx.clone()), ret_type).into();
         let call_instr = node_mut!(at cfg_bb in cfg_graph ).push_nhwc_instr(call_instr_struct, instr_slab);
         for rc_para_symidx in rc_para_symidx_vec.iter().dedup(){
             let para_symidx = rc_para_symidx.as_ref_borrow();
@@ -1276,26 +2392,34 @@ fn process_et(
                     }
                     super::et_node::ExprOp::Add => {
                         if let Some(_) = direct_child_node!(at et_node in et_tree ret_option) {
-                            let (tmp_var_symidx, l_symidx, r_symidx, var_type, _) =
-                                process_arithop(ast_tree, cfg_graph, et_tree, et_node,scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
+                            if let Some(rc_ptr_symidx) = process_ptr_arith(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope, false){
+                                Some(rc_ptr_symidx)
+                            }else{
+                                let (tmp_var_symidx, l_symidx, r_symidx, var_type, _) =
+                                    process_arithop(ast_tree, cfg_graph, et_tree, et_node,scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
 
-                            let add_instr = NhwcInstrType::new_add(tmp_var_symidx.clone(), l_symidx, r_symidx, var_type).into();
-                            node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
+                                let add_instr = NhwcInstrType::new_add(tmp_var_symidx.clone(), l_symidx, r_symidx, var_type).into();
+                                node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(add_instr, instr_slab);
 
-                            Some(tmp_var_symidx)
+                                Some(tmp_var_symidx)
+                            }
                         } else {
                             panic!("操作符{}下缺少符号", et_node);
                         }
                     }
                     super::et_node::ExprOp::Sub => {
                         if let Some(_) = direct_child_node!(at et_node in et_tree ret_option) {
-                            let (tmp_var_symidx, l_symidx, r_symidx, var_type, _) =
-                                process_arithop(ast_tree, cfg_graph, et_tree,et_node, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
-                            // println!("lsymidx is {:?} while r_symidx is {:?}",l_symidx,r_symidx);
-                            let sub_instr = NhwcInstrType::new_sub(tmp_var_symidx.clone(), l_symidx, r_symidx, var_type).into();
-                            node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
+                            if let Some(rc_ptr_symidx) = process_ptr_arith(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope, true){
+                                Some(rc_ptr_symidx)
+                            }else{
+                                let (tmp_var_symidx, l_symidx, r_symidx, var_type, _) =
+                                    process_arithop(ast_tree, cfg_graph, et_tree,et_node, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
+                                // println!("lsymidx is {:?} while r_symidx is {:?}",l_symidx,r_symidx);
+                                let sub_instr = NhwcInstrType::new_sub(tmp_var_symidx.clone(), l_symidx, r_symidx, var_type).into();
+                                node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(sub_instr, instr_slab);
 
-                            Some(tmp_var_symidx)
+                                Some(tmp_var_symidx)
+                            }
                         } else {
                             panic!("操作符{}下缺少符号", et_node);
                         }
@@ -1305,7 +2429,10 @@ fn process_et(
                             let (tmp_var_symidx, l_symidx, r_symidx, var_type, _) =
                                 process_arithop(ast_tree, cfg_graph, et_tree,et_node, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
                             let div_instr = NhwcInstrType::new_div(tmp_var_symidx.clone(), l_symidx, r_symidx, var_type).into();
-                            node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(div_instr, instr_slab);
+                            let div_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(div_instr, instr_slab);
+                            if let Some(src_loc) = node!(at et_node in et_tree).src_loc.clone(){
+                                instr_mut!(at div_instr in instr_slab).add_src_loc(src_loc);
+                            }
 
                             Some(tmp_var_symidx)
                         } else {
@@ -1318,7 +2445,10 @@ fn process_et(
                                 process_arithop(ast_tree, cfg_graph, et_tree,et_node, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab,ast2scope, );
 
                             let mod_instr = NhwcInstrType::new_mod(tmp_var_symidx.clone(), l_symidx, r_symidx, var_type).into();
-                            node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(mod_instr, instr_slab);
+                            let mod_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(mod_instr, instr_slab);
+                            if let Some(src_loc) = node!(at et_node in et_tree).src_loc.clone(){
+                                instr_mut!(at mod_instr in instr_slab).add_src_loc(src_loc);
+                            }
 
                             Some(tmp_var_symidx)
                         } else {
@@ -1327,20 +2457,23 @@ fn process_et(
                     }
                     //逻辑运算符
                     super::et_node::ExprOp::LogicalOr | super::et_node::ExprOp::LogicalAnd => {
-                        if let Some(_) = direct_child_node!(at et_node in et_tree ret_option) {
+                        if direct_child_node!(at et_node in et_tree ret_option).is_some() && node!(at cfg_node in cfg_graph).cfg_node_type.is_basic_block() {
+                            // 作为值使用时在 bb 中展开短路求值
+                            Some(process_logic_value(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, op, instr_slab, ast2scope))
+                        } else if let Some(_) = direct_child_node!(at et_node in et_tree ret_option) {
                             let (br_node,new_br_node) = process_short_logic(cfg_node,cfg_graph,op,instr_slab);
                             let (tmp_var_symidx, l_symidx, r_symidx) = process_logicop(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node,new_br_node, instr_slab,ast2scope, );
 
                             let c_cfg_true_node = direct_child_node!(at new_br_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_true()|| e.weight().cfg_edge_type.is_body_head()});
                             let c_cfg_false_node = direct_child_node!(at new_br_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_false()|| e.weight().cfg_edge_type.is_direct() });
-                            let c_label_true_symidx = find_or_new_label_to_cfg_node(c_cfg_true_node,scope_node+et_node+new_br_node, "branch_short_circuit_c_true", symtab,cfg_graph,instr_slab);
-                            let c_label_false_symidx = find_or_new_label_to_cfg_node(c_cfg_false_node,scope_node+et_node+new_br_node, "branch_short_circuit_c_false", symtab,cfg_graph,instr_slab);
+                            let c_label_true_symidx = find_or_new_label_to_cfg_node(c_cfg_true_node,new_br_node, "branch_short_circuit_c_true", symtab,cfg_graph,instr_slab);
+                            let c_label_false_symidx = find_or_new_label_to_cfg_node(c_cfg_false_node,new_br_node, "branch_short_circuit_c_false", symtab,cfg_graph,instr_slab);
 
                             //  = NhwcInstrType::new_logic_and(tmp_var_symidx.clone(), l_symidx, r_symidx, Type::I2).into();
                             let p_cfg_true_node = direct_child_node!(at br_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_true() || e.weight().cfg_edge_type.is_body_head()});
                             let p_cfg_false_node = direct_child_node!(at br_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_if_false()  || e.weight().cfg_edge_type.is_direct() });
-                            let p_label_true_symidx = find_or_new_label_to_cfg_node(p_cfg_true_node,scope_node+et_node+new_br_node, "branch_short_circuit_p_true", symtab,cfg_graph,instr_slab);
-                            let p_label_false_symidx = find_or_new_label_to_cfg_node(p_cfg_false_node,scope_node+et_node+new_br_node, "branch_short_circuit_p_false", symtab,cfg_graph,instr_slab);
+                            let p_label_true_symidx = find_or_new_label_to_cfg_node(p_cfg_true_node,new_br_node, "branch_short_circuit_p_true", symtab,cfg_graph,instr_slab);
+                            let p_label_false_symidx = find_or_new_label_to_cfg_node(p_cfg_false_node,new_br_node, "branch_short_circuit_p_false", symtab,cfg_graph,instr_slab);
 
                             let logic_a_br_instr = NhwcInstrType::new_br(l_symidx,p_label_true_symidx,p_label_false_symidx).into();
                             node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(logic_a_br_instr, instr_slab);
@@ -1352,6 +2485,9 @@ fn process_et(
                             panic!("操作符{}下缺少符号", et_node);
                         }
                     }
+                    super::et_node::ExprOp::Conditional => {
+                        Some(process_conditional(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope))
+                    }
                     super::et_node::ExprOp::LogicalNot => {
                         if let Some(next_node) = direct_child_node!(at et_node in et_tree ret_option) {
                             //取操作数的symidx和type
@@ -1367,11 +2503,26 @@ fn process_et(
                                     let f2b_instr = NhwcInstrType::new_fcmp(num2bool_tmp_symidx.clone(), FcmpPlan::One, rc_symbol_symidx, fzero_symidx, Type::F32).into();
                                     node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(f2b_instr, instr_slab);
                                 }
-                                Type::I32 => {
+                                Type::I32 | Type::I8 => {
                                     let izero_symidx = process_literal(symtab, &"0", );
                                     let i2b_instr = NhwcInstrType::new_icmp(num2bool_tmp_symidx.clone(), IcmpPlan::Ne, rc_symbol_symidx, izero_symidx, Type::I32).into();
                                     node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(i2b_instr, instr_slab);
                                 }
+                                Type::U32 => {
+                                    let uzero_symidx = process_literal(symtab, "0u");
+                                    let u2b_instr = NhwcInstrType::new_icmp(num2bool_tmp_symidx.clone(), IcmpPlan::Ne, rc_symbol_symidx, uzero_symidx, Type::U32).into();
+                                    node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(u2b_instr, instr_slab);
+                                }
+                                Type::I64 => {
+                                    let lzero_symidx = process_literal(symtab, "0l");
+                                    let l2b_instr = NhwcInstrType::new_icmp(num2bool_tmp_symidx.clone(), IcmpPlan::Ne, rc_symbol_symidx, lzero_symidx, Type::I64).into();
+                                    node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(l2b_instr, instr_slab);
+                                }
+                                Type::F64 => {
+                                    let dzero_symidx = process_literal(symtab, "0.0d");
+                                    let d2b_instr = NhwcInstrType::new_fcmp(num2bool_tmp_symidx.clone(), FcmpPlan::One, rc_symbol_symidx, dzero_symidx, Type::F64).into();
+                                    node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(d2b_instr, instr_slab);
+                                }
                                 Type::I1 => {
                                     let izero_symidx = process_literal(symtab, &"0", );
                                     let i2b_instr = NhwcInstrType::new_icmp(num2bool_tmp_symidx.clone(), IcmpPlan::Ne, rc_symbol_symidx, izero_symidx, Type::I1).into();
@@ -1389,12 +2540,10 @@ fn process_et(
                         }
                     }
                     //位运算符
-                    super::et_node::ExprOp::BitwiseOr => todo!(),
-                    super::et_node::ExprOp::BitwiseAnd => todo!(),
-                    super::et_node::ExprOp::BitwiseXor => todo!(),
-                    super::et_node::ExprOp::BitwiseNot => todo!(),
-                    super::et_node::ExprOp::LShift => todo!(),
-                    super::et_node::ExprOp::RShift => todo!(),
+                    super::et_node::ExprOp::BitwiseOr | super::et_node::ExprOp::BitwiseAnd | super::et_node::ExprOp::BitwiseXor | super::et_node::ExprOp::BitwiseNot
+                    | super::et_node::ExprOp::LShift | super::et_node::ExprOp::RShift => {
+                        Some(process_bitwise_op(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope, op))
+                    }
                     super::et_node::ExprOp::DivAssign => todo!(),
                     super::et_node::ExprOp::MulAssign => todo!(),
                     super::et_node::ExprOp::PlusAssign => todo!(),
@@ -1445,6 +2594,10 @@ fn process_et(
                     super::et_node::ExprOp::Negative => {
                         if let Some(abs_symbol) = direct_child_node!(at et_node in et_tree ret_option){
                             if let Some(rc_symbol_symidx) = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, abs_symbol, scope_node, cfg_node, instr_slab, ast2scope,){
+                                //char 先提升为 i32
+                                let rc_symbol_symidx = if symtab.get(&rc_symbol_symidx.as_ref_borrow()).get_type().is_i_8() {
+                                    force_trans_type_discriminant(cfg_graph, symtab, TypeDiscriminants::I32, &rc_symbol_symidx, scope_node, cfg_node, instr_slab, Some(et_node), et_tree)
+                                } else { rc_symbol_symidx };
                                 let symbol_type = symtab.get(&rc_symbol_symidx.as_ref_borrow())
                                     .get_type().clone();
                                 let neg_tmp_symidx = process_temp_symbol(cfg_graph, symtab, &symbol_type, scope_node, cfg_node, instr_slab,  Some(et_node), et_tree, "");
@@ -1453,6 +2606,15 @@ fn process_et(
                                     Type::I32 => {
                                         process_literal(symtab, &"0", )
                                     },
+                                    Type::U32 => {
+                                        process_literal(symtab, "0u")
+                                    },
+                                    Type::I64 => {
+                                        process_literal(symtab, "0l")
+                                    },
+                                    Type::F64 => {
+                                        process_literal(symtab, "0.0d")
+                                    },
                                     Type::F32 => {
                                         process_literal(symtab, &"0.0", )
                                     },
@@ -1485,10 +2647,15 @@ fn process_et(
                         }
                     },
                     //引用与解引用
-                    super::et_node::ExprOp::AddrOf => todo!(),
-                    super::et_node::ExprOp::Deref => panic!("find deref at {:?}",et_node),
-                    super::et_node::ExprOp::DotMember => todo!(),
-                    super::et_node::ExprOp::ArrowMember => todo!(),
+                    super::et_node::ExprOp::AddrOf => {
+                        Some(process_addr_of(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope))
+                    },
+                    super::et_node::ExprOp::Deref => {
+                        Some(process_deref(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope))
+                    },
+                    super::et_node::ExprOp::DotMember | super::et_node::ExprOp::ArrowMember => {
+                        Some(process_member_access(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope))
+                    },
                     //单目运算符
                     super::et_node::ExprOp::LPlusPlus => {
                         if let Some(symbol_node) = direct_child_node!(at et_node in et_tree ret_option) {
@@ -1634,19 +2801,19 @@ fn process_et(
                                 }
 
                                 // 这里要分情况，如果不是寻求变量而是寻求它的某个维度的指针，就不能用这个，只有索引维度恰好等于数组维度的时候才需要用load 
-                                if !infered_ty.is_array() && !infered_ty.is_ptr_64(){
+                                // 函数指针数组的元素本身就是要 load 出来的值
+                                if (!infered_ty.is_aggregate() && !infered_ty.is_ptr_64()) || infered_ty.is_fn_ptr(){
                                     let temp_symidx = process_temp_symbol(cfg_graph, symtab, &infered_ty, scope_node, cfg_node,  instr_slab,  Some(et_node), et_tree,format!("ele_of_{:?}",rc_array_symidx).leak());
                                     let load_ele_instr_struct = NhwcInstrType::new_load(temp_symidx.clone(), rc_temp_ptr_symidx.clone(), infered_ty.to_ref_ptr_type()).into();
                                     let load_ele_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(load_ele_instr_struct, instr_slab);
-                                    let pointed_symidx = symtab.get(&rc_temp_ptr_symidx.as_ref_borrow()).get_pointed_symidx();
-                                    let _mu_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(NhwcInstrType::new_mu(pointed_symidx.clone(), load_ele_instr).into(), instr_slab);
+                                    push_mu_of_ptr(cfg_graph, symtab, cfg_node, &rc_temp_ptr_symidx, load_ele_instr, instr_slab);
                                     Some(temp_symidx)
                                 }else {
                                     Some(rc_temp_ptr_symidx)
                                 }
                                 
                             },
-                            EtNodeType::Symbol { rc_symidx: _, ast_node: _, text: _, decldef_def_or_use:DeclOrDefOrUse::DeclDef { type_ast_node: _, is_const } } => {
+                            EtNodeType::Symbol { rc_symidx: _, ast_node: _, text: _, decldef_def_or_use:DeclOrDefOrUse::DeclDef { type_ast_node: _, is_const, ptr_depth: _ } } => {
                                 // 可删此段
                                 let l_child_type = node!(at et_l_child in et_tree).get_type().clone();
                                 // debug_info_blue!("left child type {:?}",l_child_type);
@@ -1676,7 +2843,7 @@ fn process_et(
                                 // debug_info_red!("{:?}",dims);
                                 
                                 // because symidx maybe const symbol so you should match the parsing error 
-                                let mut reversed_remained_dims = vec![];
+                                let mut dims_usize = vec![];
                                 let mut has_uninitialized_dims = false;
                                 for op_symidx in dims{
                                     let rc_symidx = match op_symidx.as_ref(){
@@ -1692,13 +2859,11 @@ fn process_et(
                                             symtab.get(&symidx).get_const_cor_literal_symidx().symbol_name.parse().unwrap()
                                         },
                                     };
-                                    reversed_remained_dims.push(idx);
+                                    dims_usize.push(idx);
                                 }
                                 if !has_uninitialized_dims{
-                                    reversed_remained_dims.reverse();
-
                                     // add values to value_map according to initializer
-                                    array_initialize( et_tree, &mut array_ele_map, &ele_ty, &mut reversed_remained_dims, &mut 0, ast_tree, cfg_graph, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
+                                    array_initialize( et_tree, &mut array_ele_map, &ele_ty, &dims_usize, 0, 0, ast_tree, cfg_graph, scope_tree, symtab, et_node, scope_node, cfg_node, instr_slab, ast2scope,);
                                     debug_info_red!("{:?}",array_ele_map);
                                     let rc_initializer_symidx = process_literal(symtab, format!("{{{:?}}} ", array_ele_map).leak(), );
                                     let initializer_symidx = rc_initializer_symidx.as_ref_borrow();
@@ -1736,7 +2901,7 @@ fn process_et(
                         // let value_type = find!(field TYPE:Type at value_symidx in symtab debug symtab_graph ).unwrap().clone();
                         let r_type = symtab.get(&r_symidx).get_type().clone();
 
-                        if let EtNodeType::Symbol { rc_symidx, ast_node, text, decldef_def_or_use:DeclOrDefOrUse::DeclDef { type_ast_node, is_const }} = &node!(at left_child_et_node in et_tree).et_node_type {
+                        if let EtNodeType::Symbol { rc_symidx, ast_node, text, decldef_def_or_use:DeclOrDefOrUse::DeclDef { type_ast_node, is_const, ptr_depth: _ }} = &node!(at left_child_et_node in et_tree).et_node_type {
                             if *is_const {
                                 // here we should transform value of const symidx r_symidx into the target type
                                 let val = Value::from_symidx(&r_symidx);
@@ -1744,19 +2909,48 @@ fn process_et(
                                 symtab.get_mut(&rc_symidx.as_ref_borrow()).add_const_cor_literal_symidx(r_symidx);
                             }
                         };
+                        // const 数组记录它的初值 {..} ，下标都是常量的元素会在 compress_et 时被替换成字面量
+                        let mut decl_et_node = left_child_et_node;
+                        while matches!(node!(at decl_et_node in et_tree).et_node_type, EtNodeType::Operator { op:ExprOp::ArrayIndex, .. }) {
+                            decl_et_node = direct_child_nodes!(at decl_et_node in et_tree with_predicate {|e| !e.weight().et_edge_type.is_deleted()})[0];
+                        }
+                        if let EtNodeType::Symbol { decldef_def_or_use:DeclOrDefOrUse::DeclDef { is_const:true, .. }, .. } = &node!(at decl_et_node in et_tree).et_node_type {
+                            if l_type.is_array() && r_type.is_array() && symtab.get(&r_symidx).has_value() {
+                                symtab.get_mut(&l_symidx).add_const_cor_literal_symidx(r_symidx.clone());
+                            }
+                        }
 
                         node_mut!(at et_node in et_tree).add_type(l_type.clone());
+                        // 左边是 symbol 时赋值给变量本身，否则左边是 [] . -> * 得到的地址，需要 store 进去
+                        let is_l_var = node!(at left_child_et_node in et_tree).et_node_type.is_symbol() && !*symtab.get(&l_symidx).get_is_temp();
+                        let is_aggregate_copy = get_aggregate_copy_ty(symtab, &l_symidx, &l_type, &r_type, is_l_var).is_some();
+                        // 指针变量被赋值时，右边的数组需要退化为指针，不同类型的指针之间需要 bitcast
+                        let (rc_r_symidx, r_type) = if is_l_var && l_type.is_ptr_64() && !is_aggregate_copy {
+                            let rc_r_symidx = process_ptr_decay_or_bitcast(cfg_graph, symtab, &l_type, &rc_r_symidx, scope_node, cfg_node, instr_slab, et_tree);
+                            let r_type = symtab.get(&rc_r_symidx.as_ref_borrow()).get_type().clone();
+                            (rc_r_symidx, r_type)
+                        }else{
+                            (rc_r_symidx.clone(), r_type)
+                        };
 
                         //如果结果和变量类型不同，添加自动转化instr
                         match (&l_type,&r_type){
-                            (Type::Ptr64 { ty:l_deref_var_type },_)=> {
+                            _ if is_aggregate_copy => {
+                                process_aggregate_assign(cfg_graph, symtab, et_node, left_child_et_node, &l_symidx, &l_type, &r_type, is_l_var, &rc_l_symidx, &rc_r_symidx, scope_node, cfg_node, instr_slab, et_tree);
+                                Some(rc_l_symidx.clone())
+                            },
+                            _ if is_l_var && is_addr_taken_local(symtab, &l_symidx) => {
+                                process_store_to_addr_taken_local(cfg_graph, et_tree, symtab, et_node, left_child_et_node, scope_node, cfg_node, instr_slab, &rc_l_symidx, &rc_r_symidx);
+                                Some(rc_l_symidx.clone())
+                            },
+                            (Type::Ptr64 { ty:l_deref_var_type },_) if !is_l_var => {
+                                let rc_r_symidx = if l_deref_var_type.is_ptr_64() { process_ptr_decay_or_bitcast(cfg_graph, symtab, l_deref_var_type, &rc_r_symidx, scope_node, cfg_node, instr_slab, et_tree) } else { rc_r_symidx };
                                 let transed_value_symidx = force_trans_type_discriminant(cfg_graph, symtab, l_deref_var_type.as_ref().into(),  &rc_r_symidx, scope_node, cfg_node, instr_slab, Some(et_node),et_tree);
                                 let store_instr_struct = NhwcInstrType::new_store(rc_l_symidx.clone(), l_type.clone(),  transed_value_symidx, *l_deref_var_type.clone()).into();
                                 node_mut!(at et_node in et_tree).add_type(*l_deref_var_type.clone());
-                                let pointed_symidx = symtab.get(&l_symidx).get_pointed_symidx();
 
                                 let store_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(store_instr_struct, instr_slab);
-                                let _chi_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(NhwcInstrType::new_chi(pointed_symidx.clone(), pointed_symidx.clone(), store_instr).into(), instr_slab);
+                                push_chi_of_ptr(cfg_graph, symtab, cfg_node, &rc_l_symidx, store_instr, instr_slab);
                                 
                                 Some(rc_l_symidx.clone())
                             },
@@ -1791,7 +2985,8 @@ fn process_et(
                                 //      func_call_instr).into();
                                 // node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(chi_instr_struct, instr_slab)?;
 
-                                for (&offset,value) in value_map.iter(){
+                                // memset 之后值为 0 的元素不需要再 store
+                                for (&offset,value) in value_map.iter().filter(|(_,value)| !value.is_zero_bits()).sorted_by_key(|x| x.0){
                                     let value_symidx = value.to_symidx();
                                     process_literal(symtab, &value_symidx.symbol_name, );
                                     let array_idx_vec = deduce_linear_offset_by_weights(offset, r_type.get_array_dim_stride_symidx_vec().into_iter().map(|s| s.borrow().symbol_name.parse().unwrap()).collect_vec());
@@ -1804,9 +2999,9 @@ fn process_et(
                                     let get_ele_ptr_instr_struct = NhwcInstrType::new_get_element_ptr(rc_temp_ptr_symidx.clone(), rc_l_symidx.clone(), l_type.clone(), array_idx_vec.iter().map(|idx| Some(SymIdx::from_str(idx.to_string().leak()).as_rc())).collect_vec()).into();
                                     node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(get_ele_ptr_instr_struct, instr_slab);
 
-                                    let pointed_symidx = add_may_pointed_symidx_to(symtab, &rc_temp_ptr_symidx, &rc_l_symidx);
+                                    let pointed_symidx = add_may_pointed_symidx_to(symtab, &rc_temp_ptr_symidx, &rc_l_symidx).unwrap();
                                     // let transed_value_symidx = force_trans_type(cfg_graph, symtab, &l_deref_var_type, &r_type, &r_symidx, scope_node, cfg_bb, instr_slab, Some(et_node),et_tree)?;
-                                    let store_instr_struct = NhwcInstrType::new_store(rc_temp_ptr_symidx.clone(), l_type.to_ref_ptr_type(),  value_symidx.as_rc(), *l_ele_ty.clone()).into();
+                                    let store_instr_struct = NhwcInstrType::new_store(rc_temp_ptr_symidx.clone(), l_ele_ty.to_ref_ptr_type(),  value_symidx.as_rc(), *l_ele_ty.clone()).into();
 
                                     let store_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(store_instr_struct, instr_slab);
                                     let _chi_instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(NhwcInstrType::new_chi(pointed_symidx.clone(), pointed_symidx.clone(), store_instr).into(), instr_slab);
@@ -1843,7 +3038,11 @@ fn process_et(
                 let ast_node = *ast_node;
                 let literal = &rc_literal_symidx.as_ref_borrow().symbol_name;
                 // debug_info_blue!("add constant {}",constant_literal);
-                Some(process_literal(symtab, literal, ))
+                if literal.starts_with('"'){
+                    Some(process_str_literal(cfg_graph, symtab, instr_slab, literal))
+                }else{
+                    Some(process_literal(symtab, literal, ))
+                }
             }
             EtNodeType::Symbol { rc_symidx: _, ast_node, text: _, decldef_def_or_use }=> {
                 let symbol_name_text = &node!(at et_node in et_tree).name_text();
@@ -1870,14 +3069,16 @@ fn parse_extern_declfunc2nhwc(
         let func_name = node!(at func_name_ast_node in ast_tree).op_text.clone().unwrap().leak();
         // let name_symidx = SymIdx::new(0, func_name);
         //获取返回类型
-        let ast_retype = find!(rule RULE_declarationSpecifiers then RULE_declarationSpecifier finally RULE_typeSpecifier at decl_func_ast_node in ast_tree).unwrap();
+        let ast_retype = find_type_specifier_of_decl(ast_tree, decl_func_ast_node).unwrap();
         let ast_retype = direct_child_node!(at ast_retype in ast_tree);
         let _func_rettype = &node!(at ast_retype in ast_tree).op_text;
         //添加到符号表中，
-        let rc_func_symidx = process_func_symbol(symtab, func_name,true);
+        let rc_func_symidx = process_func_symbol(symtab, func_name, true, get_storage_class_of_decl(ast_tree, decl_func_ast_node) == Some(Static));
         let func_symidx = rc_func_symidx.as_ref_borrow();
         // 添加返回值到符号表
-        let rc_func_ret_symidx = process_symbol(ast_tree, scope_tree, symtab,instr_slab, &DeclOrDefOrUse::DeclDef { type_ast_node: ast_retype, is_const: false }, ST_ROOT, format!("{}_{}",func_name,"ret").leak(), cfg_root, cfg_graph, None,et_tree);
+        // 返回类型的指针层数在函数的 declarator 上
+        let ret_ptr_depth = find!(rule RULE_initDeclaratorList then RULE_initDeclarator finally RULE_declarator at decl_func_ast_node in ast_tree).map_or(0, |declarator_node| get_ptr_depth_of_declarator(ast_tree, declarator_node));
+        let rc_func_ret_symidx = process_symbol(ast_tree, scope_tree, symtab,instr_slab, &DeclOrDefOrUse::DeclDef { type_ast_node: ast_retype, is_const: false, ptr_depth: ret_ptr_depth }, ST_ROOT, format!("{}_{}",func_name,"ret").leak(), cfg_root, cfg_graph, None,et_tree);
         let func_ret_symidx = rc_func_ret_symidx.as_ref_borrow();
         //获取参数列表
         let mut arg_syms = vec![];
@@ -1891,8 +3092,9 @@ fn parse_extern_declfunc2nhwc(
             let ast_func_args = find_nodes!(rule RULE_parameterList finally RULE_parameterDeclaration at para in ast_tree);
             // debug_info_red!("func_args: {:?}",ast_func_args);
             for ast_parameter_decl_node in ast_func_args {
-                let ast_arg_type = find!(rule RULE_declarationSpecifiers then RULE_declarationSpecifier finally RULE_typeSpecifier at ast_parameter_decl_node in ast_tree).unwrap();
+                let ast_arg_type = find_type_specifier_of_decl(ast_tree, ast_parameter_decl_node).unwrap();
                 let ast_arg_type = direct_child_node!(at ast_arg_type in ast_tree);
+                let ptr_depth = find!(rule RULE_declarator at ast_parameter_decl_node in ast_tree).map_or(0, |declarator_node| get_ptr_depth_of_declarator(ast_tree, declarator_node));
 
                 let (et_sym_node,ast_sym_name_node) ={
                     let sep_node = gen_et::process_any_stmt(et_tree, ast_tree, scope_tree, ast_parameter_decl_node, func_scope);
@@ -1933,11 +3135,13 @@ fn parse_extern_declfunc2nhwc(
                 };
                 let sym_name = node!(at ast_sym_name_node in ast_tree).op_text.clone().unwrap().leak();
 
-                let arg_symidx = process_symbol(ast_tree, scope_tree, symtab, instr_slab, &DeclOrDefOrUse::DeclDef { type_ast_node:ast_arg_type , is_const:false}, func_scope, sym_name, cfg_root, cfg_graph,Some(et_sym_node),et_tree);
+                let arg_symidx = process_symbol(ast_tree, scope_tree, symtab, instr_slab, &DeclOrDefOrUse::DeclDef { type_ast_node:ast_arg_type , is_const:false, ptr_depth}, func_scope, sym_name, cfg_root, cfg_graph,Some(et_sym_node),et_tree);
                 arg_syms.push(arg_symidx);
             }
             let func_type = Type::Fn { arg_syms: arg_syms.clone(), ret_sym:rc_func_ret_symidx.clone()};
             symtab.get_mut(&func_symidx).add_type(func_type.clone());
+            // 参数列表以 ... 结尾的是变长参数函数
+            symtab.get_mut(&func_symidx).add_is_variadic(find!(term Ellipsis at para in ast_tree).is_some());
             symtab.get_mut_global_info().get_mut_external_func_symidx_vec().push(rc_func_symidx.clone());
             // label:function
             func_type
@@ -1959,11 +3163,61 @@ fn parse_extern_declfunc2nhwc(
     Ok(())
 }
 
+/// 把 declaration 中 enum E { A, B = 5, C } 的每个枚举常量作为 I32 常量加入 decl_parent_scope  
+/// 和 const int 一样记录 CONST_COR_LITERAL_SYMIDX ，使用它们的地方会在 recursive_replace_const_symbol 中被替换为字面量
+fn process_enum_decl(
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph, scope_tree:&ScopeTree, et_tree:&mut EtTree, symtab:&mut SymTab, ast2scope:&HashMap<u32, u32>, instr_slab:&mut InstrSlab<NhwcInstr>, ast_decl_node:u32, decl_parent_scope:u32, cfg_node:u32,
+) -> Result<()> {
+    for decl_spec_node in find_nodes!(rule RULE_declarationSpecifiers finally RULE_declarationSpecifier at ast_decl_node in ast_tree){
+        let Some(type_spec_node) = find!(rule RULE_typeSpecifier at decl_spec_node in ast_tree) else { continue };
+        let Some(enum_spec_node) = find!(rule RULE_enumSpecifier at type_spec_node in ast_tree) else { continue };
+        let Some(enumerator_list_node) = find!(rule RULE_enumeratorList at enum_spec_node in ast_tree) else { continue };
+        // 没有给出值的枚举常量等于前一个加 1 ，第一个为 0
+        let mut next_val:i32 = 0;
+        for enumerator_node in find_nodes!(rule RULE_enumerator at enumerator_list_node in ast_tree){
+            let ident_node = find!(rule RULE_enumerationConstant finally term Identifier at enumerator_node in ast_tree).unwrap();
+            let enum_const_name:&'static str = node!(at ident_node in ast_tree).op_text.clone().unwrap().leak();
+            if let Some(const_expr_node) = find!(rule RULE_constantExpression at enumerator_node in ast_tree){
+                let et_sep = process_any_stmt(et_tree, ast_tree, scope_tree, const_expr_node, decl_parent_scope);
+                eval_et::compress_et_for_gen_nhwc(et_tree, et_sep, &mut can_eliminate_despite_array_idx_and_call, symtab, decl_parent_scope, scope_tree, true)?;
+                let rc_val_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, direct_child_node!(at et_sep in et_tree), decl_parent_scope, cfg_node, instr_slab, ast2scope).unwrap();
+                let val_symidx = rc_val_symidx.as_ref_borrow();
+                next_val = match (val_symidx.is_literal(), Value::from_symidx(&val_symidx)) {
+                    (true, Value::I32(Some(val))) => val,
+                    _ => return Err(anyhow!("{}: 枚举常量 {} 的值不是整型常量表达式", SrcLoc::fmt_op(&node!(at const_expr_node in ast_tree).src_loc), enum_const_name)),
+                };
+            }
+            let rc_literal_symidx = process_literal(symtab, next_val.to_string().leak());
+            let rc_enum_const_symidx = add_symbol!({Symbol::new(decl_parent_scope, enum_const_name)}
+                with_field TYPE:{Type::I32}
+                with_field IS_GLOBAL:{false}
+                with_field IS_TEMP:{false}
+                with_field IS_LITERAL:{false}
+                with_field IS_FUNC_PARA:{false}
+            to symtab);
+            symtab.get_mut(&rc_enum_const_symidx.as_ref_borrow()).add_const_cor_literal_symidx(rc_literal_symidx.as_ref_borrow().clone());
+            next_val = next_val.wrapping_add(1);
+        }
+    }
+    Ok(())
+}
+
 ///定义变量的decl转为ir，并通过et查找元素是否合法
 fn parse_declvar2nhwc(
     ast_tree:&AstTree, cfg_graph:&mut CfgGraph, symtab:&mut SymTab, scope_tree:&ScopeTree, et_tree:&mut EtTree, decl_parent_scope:u32, ast_decl_node:u32, cfg_node:u32, ast2scope:&HashMap<u32, u32>,
     instr_slab:&mut InstrSlab<NhwcInstr>, 
 ) -> Result<()> {
+    process_enum_decl(ast_tree, cfg_graph, scope_tree, et_tree, symtab, ast2scope, instr_slab, ast_decl_node, decl_parent_scope, cfg_node)?;
+    if find!(rule RULE_initDeclaratorList at ast_decl_node in ast_tree).is_none() || is_typedef_decl(ast_tree, ast_decl_node){
+        // 形如 enum E {...}; 的局部声明和 typedef 都不产生任何变量，typedef 已经在 Ast2StPass 中登记过了
+        return Ok(());
+    }
+    // static 局部变量只在程序开始前初始化一次，extern 声明引用别处的定义，它们的指令和全局变量一样放到 CFG_ROOT 中
+    let storage_class = get_storage_class_of_decl(ast_tree, ast_decl_node);
+    let cfg_node = match storage_class {
+        Some(Static) | Some(Extern) => CFG_ROOT,
+        _ => cfg_node,
+    };
     //将declaration生成et
     let et_sep_node = process_any_stmt(et_tree, ast_tree, scope_tree, ast_decl_node, decl_parent_scope);
     //如果该节点有子树
@@ -1978,9 +3232,11 @@ fn parse_declvar2nhwc(
                 let rc_var_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_item_node, decl_parent_scope, cfg_node,  instr_slab, ast2scope,).unwrap();
                 let var_symidx = rc_var_symidx.as_ref_borrow();
                 let var_type = symtab.get(&var_symidx).get_type().clone();
+                // 带初值的 extern 声明就是定义
+                symtab.get_mut(&var_symidx).add_is_external(false);
 
                 // 加入 alloc 指令 分配内存
-                if decl_parent_scope != ST_ROOT{
+                if !*symtab.get(&var_symidx).get_is_global(){
                     // 是 局部变量(在函数内定义的变量)
                     let cfg_entry = get_cfg_entry_by_cfg_node(cfg_graph, symtab, cfg_node); 
                     let alloc_instr = NhwcInstrType::new_alloc(var_type.clone(), rc_var_symidx.clone()).into();
@@ -2008,12 +3264,12 @@ fn parse_declvar2nhwc(
                 // let value_type = find!(field TYPE:Type at value_symidx in symtab debug symtab_graph ).unwrap().clone();
 
                 // 加入 alloc 指令 分配内存
-                if decl_parent_scope != ST_ROOT{
+                if !*symtab.get(&var_symidx).get_is_global(){
                     // local variable
                     let alloc_instr = NhwcInstrType::new_alloc(var_type, rc_var_symidx.clone()).into();
                     let cfg_entry = get_cfg_entry_by_cfg_node(cfg_graph, symtab, cfg_node); 
                     node_mut!(at cfg_entry in cfg_graph ).push_nhwc_instr(alloc_instr, instr_slab);
-                }else{
+                }else if storage_class != Some(Extern){
                     // global variable
                     let global_instr = NhwcInstrType::new_globl(var_type.clone(), rc_var_symidx.clone()).into();
                     node_mut!(at cfg_node in cfg_graph ).insert_nhwc_instr(global_instr, 0,instr_slab);
@@ -2027,22 +3283,20 @@ fn parse_declvar2nhwc(
             EtNodeType::Literal { rc_literal_symidx: _, ast_node: _, text: _ } => todo!(),
             EtNodeType::Symbol { rc_symidx, ast_node, text:_, decldef_def_or_use } => {
                 //获得变量类型，做成symidx
-                let type_ast_node = find!(rule RULE_declarationSpecifiers then RULE_declarationSpecifier finally RULE_typeSpecifier at ast_decl_node in ast_tree).unwrap();
-                let type_ast_node = direct_child_node!(at type_ast_node in ast_tree);
-                let var_type = Type::new(type_ast_node, ast_tree);
                 let var_str = rc_symidx.as_ref_borrow().symbol_name;
                 let decl_def_or_use_cloned = &decldef_def_or_use.clone();
                 let symbol_symidx = process_symbol(ast_tree, scope_tree, symtab,instr_slab,&decl_def_or_use_cloned ,  decl_parent_scope,var_str ,cfg_node,cfg_graph,Some(et_item_node),et_tree);
+                let var_type = symtab.get(&symbol_symidx.as_ref_borrow()).get_type().clone();
                 let def_instr = NhwcInstrType::new_def_var(var_type.clone(), symbol_symidx.clone(), None).into();
                 let alloc_instr = NhwcInstrType::new_alloc(var_type.clone(), symbol_symidx.clone()).into();
-                if decl_parent_scope != ST_ROOT{
+                if !*symtab.get(&symbol_symidx.as_ref_borrow()).get_is_global(){
                     // function local variables 
                     let cfg_entry =get_cfg_entry_by_cfg_node(cfg_graph, symtab, cfg_node);
                     // 注意，这里def_instr 需要放到 cfg_entry 中，不能放到这个basic block，它可能是在一个循环中
                     node_mut!(at cfg_entry in cfg_graph ).push_nhwc_instr(alloc_instr, instr_slab);
                     let instr = node_mut!(at cfg_node in cfg_graph ).push_nhwc_instr(def_instr, instr_slab);
                     node_mut!(at cfg_node in cfg_graph).get_mut_def_symidx_instr_tuple_vec().push((symbol_symidx.clone(),instr));
-                }else{
+                }else if storage_class != Some(Extern){
                     // 说明这个 cfg_node 就是root ，那么直接把 global 指令加入 root 就行了
                     let global_instr = NhwcInstrType::new_globl(var_type.clone(), symbol_symidx.clone()).into();
                     node_mut!(at cfg_node in cfg_graph ).insert_nhwc_instr(global_instr, 0,instr_slab);
@@ -2065,14 +3319,15 @@ fn parse_func2nhwc(
         let func_name = node!(at func_name_ast_node in ast_tree).op_text.clone().unwrap().leak();
         // let name_symidx = SymIdx::new(0, func_name);
         //获取返回类型
-        let ast_retype = find!(rule RULE_declarationSpecifiers then RULE_declarationSpecifier finally RULE_typeSpecifier at func_def_ast_node in ast_tree).unwrap();
+        let ast_retype = find_type_specifier_of_decl(ast_tree, func_def_ast_node).unwrap();
         let ast_retype = direct_child_node!(at ast_retype in ast_tree);
         //添加到符号表中，
-        let rc_func_symidx = process_func_symbol(symtab,  func_name, false);
+        let rc_func_symidx = process_func_symbol(symtab, func_name, false, get_storage_class_of_decl(ast_tree, func_def_ast_node) == Some(Static));
         let func_symidx = rc_func_symidx.as_ref_borrow();
         let _:Vec<_> = etc::dfs(cfg_graph, cfg_entry).iter().map(|&cfg_node|{node_mut!(at cfg_node in cfg_graph).add_func_cor_symidx(rc_func_symidx.clone())}).collect();
         // 添加返回值到符号表
-        let func_ret_symidx = process_symbol(ast_tree, scope_tree, symtab,instr_slab, &DeclOrDefOrUse::DeclDef { type_ast_node: ast_retype, is_const: false }, ST_ROOT,format!("{}_{}",func_name,"ret").leak(), cfg_entry, cfg_graph, None,et_tree);
+        let ret_ptr_depth = find!(rule RULE_declarator at func_def_ast_node in ast_tree).map_or(0, |declarator_node| get_ptr_depth_of_declarator(ast_tree, declarator_node));
+        let func_ret_symidx = process_symbol(ast_tree, scope_tree, symtab,instr_slab, &DeclOrDefOrUse::DeclDef { type_ast_node: ast_retype, is_const: false, ptr_depth: ret_ptr_depth }, ST_ROOT,format!("{}_{}",func_name,"ret").leak(), cfg_entry, cfg_graph, None,et_tree);
         //获取参数列表
         let mut arg_syms:Vec<RcSymIdx> = vec![];
         //函数有参数
@@ -2090,6 +3345,7 @@ fn parse_func2nhwc(
             }
             let func_type = Type::Fn { arg_syms: arg_syms.clone(), ret_sym:func_ret_symidx.clone()};
             symtab.get_mut(&func_symidx).add_type(func_type);
+            symtab.get_mut(&func_symidx).add_is_variadic(find!(term Ellipsis at para in ast_tree).is_some());
             // label:function
         }
         //函数无参数，则不需要处理参数部分
@@ -2121,13 +3377,19 @@ pub fn parse_cfg_into_nhwc_cfg(
     // let (cfg_graph,scope_tree,ast_tree,symtab,et_tree,ast2scope)= (&mut context.cfg_graph , &mut context.scope_tree,&mut context.ast_tree,&mut context.symtab,&mut context.et_tree,&context.ast2scope);
     if let CfgNodeType::Root { static_ast_nodes } = &node!(at CFG_ROOT in cfg_graph).cfg_node_type{
         for &static_ast_node in static_ast_nodes.clone().iter(){
+            if find!(rule RULE_initDeclaratorList at static_ast_node in ast_tree).is_none() || is_typedef_decl(ast_tree, static_ast_node){
+                // 形如 struct P {...}; 的声明和 typedef 只定义了类型，不产生任何变量，enum E {...}; 还会定义枚举常量
+                process_enum_decl(ast_tree, cfg_graph, scope_tree, et_tree, symtab, ast2scope, instr_slab, static_ast_node, ST_ROOT, CFG_ROOT)?;
+                continue;
+            }
+            // 形如 int (*fp)(int); 的是函数指针变量而不是函数声明
             let op_arg_parenthesis = find!(rule RULE_initDeclaratorList
                 then RULE_initDeclarator
                 then RULE_declarator
                 then RULE_directDeclarator
                 finally term LeftParen
                 at static_ast_node in ast_tree
-            );
+            ).filter(|&left_paren_node| !is_fn_ptr_direct_decl(ast_tree, direct_parent_node!(at left_paren_node in ast_tree)));
             match op_arg_parenthesis{
                 Some(_) => {
                     let func_name_ast_node = find!(rule RULE_initDeclaratorList then RULE_initDeclarator then RULE_declarator then RULE_directDeclarator then RULE_directDeclarator finally term Identifier at static_ast_node in ast_tree).unwrap();
@@ -2164,13 +3426,14 @@ pub fn parse_cfg_into_nhwc_cfg(
             CfgEdgeType::Direct {  } => 1,
             CfgEdgeType::IfTrue {  } => 7,
             CfgEdgeType::BodyTail {  } => 5,
+            CfgEdgeType::Case {  } => 3,
         });
         // 遍历一遍这个函数体，确保 将 whileloop cfg_node 存到  scope_node 中 
         for &cfg_node in dfs_vec.iter(){
-            if node!(at cfg_node in cfg_graph).cfg_node_type.is_while_loop(){
+            if node!(at cfg_node in cfg_graph).cfg_node_type.is_while_loop() || node!(at cfg_node in cfg_graph).cfg_node_type.is_do_while_loop(){
                 // if it is while loop then add current cfg_while_loop 
                 match &node!(at cfg_node in cfg_graph).cfg_node_type{
-                    CfgNodeType::WhileLoop { ast_expr_node } => {
+                    CfgNodeType::WhileLoop { ast_expr_node } | CfgNodeType::DoWhileLoop { ast_expr_node } => {
                         let &scope_expr_node = ast2scope.get(ast_expr_node).unwrap();
                         let while_scope_node = get_while_scope_of_scope_node(scope_expr_node, scope_tree);
                         match &mut node_mut!(at while_scope_node in scope_tree).scope_type{
@@ -2182,6 +3445,16 @@ pub fn parse_cfg_into_nhwc_cfg(
                     },
                     _ => {panic!()}
                 };
+            }else if let CfgNodeType::Switch { ast_expr_node } = &node!(at cfg_node in cfg_graph).cfg_node_type{
+                // break 的时候需要通过 scope 找到对应的 switch
+                let &scope_expr_node = ast2scope.get(ast_expr_node).unwrap();
+                let switch_scope_node = direct_parent_node!(at scope_expr_node in scope_tree);
+                match &mut node_mut!(at switch_scope_node in scope_tree).scope_type{
+                    super::scope_node::ScopeType::Switch { op_cfg_switch_node } => {
+                        *op_cfg_switch_node = Some(cfg_node);
+                    },
+                    _ => {panic!()}
+                }
             }
         }
         // firstly parse all branch node 
@@ -2202,8 +3475,8 @@ pub fn parse_cfg_into_nhwc_cfg(
                 CfgNodeType::Branch { ast_expr_node } => {
                     parse_branch2nhwc(ast_tree, cfg_graph, scope_tree, et_tree, symtab, ast2scope, *ast_expr_node, cfg_node,  instr_slab)?
                 }
-                CfgNodeType::Switch { ast_expr_node:_ } => {
-                    // parse_branch2nhwc(ast_tree, cfg_graph, scope_tree, et_tree, symtab, ast2scope, *ast_expr_node, cfg_node, )
+                CfgNodeType::Switch { ast_expr_node } => {
+                    parse_switch2nhwc(ast_tree, cfg_graph, scope_tree, et_tree, symtab, ast2scope, *ast_expr_node, cfg_node,  instr_slab)?
                 }
                 CfgNodeType::ForLoop { ast_before_node, ast_mid_node, ast_after_node } => {
                     parse_forloop2nhwc(
@@ -2220,7 +3493,7 @@ pub fn parse_cfg_into_nhwc_cfg(
                         instr_slab,
                     )?;
                 }
-                CfgNodeType::WhileLoop { ast_expr_node } => {
+                CfgNodeType::WhileLoop { ast_expr_node } | CfgNodeType::DoWhileLoop { ast_expr_node } => {
                     parse_whileloop2nhwc(ast_tree, cfg_graph, scope_tree, et_tree, symtab, ast2scope, *ast_expr_node, cfg_node, instr_slab, )?
                 }
                 CfgNodeType::BasicBlock { ast_nodes } => {
@@ -2273,6 +3546,16 @@ pub fn parse_cfg_into_nhwc_cfg(
     // insert_additional_mu_chi_for_call_instr(cfg_graph, instr_slab, symtab)?;
     // debug_info_yellow!("success end");
 
+    // 没有对应到具体语句的指令(比如 label 、跳转和 phi)使用所在 cfg 节点的位置
+    for cfg_node in cfg_graph.node_indices().map(|n| n.index() as u32).collect::<Vec<_>>(){
+        if let Some(src_loc) = node!(at cfg_node in cfg_graph).get_op_src_loc().cloned(){
+            for instr in node!(at cfg_node in cfg_graph).iter_all_instrs().cloned().collect::<Vec<_>>(){
+                if !instr!(at instr in instr_slab).has_src_loc(){
+                    instr_mut!(at instr in instr_slab).add_src_loc(src_loc.clone());
+                }
+            }
+        }
+    }
     Ok(())
 }
 
@@ -2280,8 +3563,10 @@ pub fn parse_cfg_into_nhwc_cfg(
 // this function will insert a new basic block before specified cfg_node and inherit all edges of 
 // specified cfg_node, and take all normal instrs(except for label instr & jump instr)
 /// return the index u32 of cfg_new_bb
+/// cfg_node 也可以是 Branch 、 WhileLoop 这类条件节点，此时新 bb 放在条件节点之前
 pub fn split_bb_node(cfg_node:u32,cfg_graph:&mut CfgGraph,is_move_instrs:bool) -> Result<(u32,u32)>{
-    assert!(node!(at cfg_node in cfg_graph).cfg_node_type.is_basic_block(), "bb分割操作不能用于bb外的节点{}",cfg_node);
+    let cfg_node_type = &node!(at cfg_node in cfg_graph).cfg_node_type;
+    assert!(!(cfg_node_type.is_entry() || cfg_node_type.is_exit() || cfg_node_type.is_root()), "bb分割操作不能用于 entry exit root 节点{}",cfg_node);
     // add new bb cfg node 
     let loop_level = node!(at cfg_node in cfg_graph).loop_level;
     let mut bb_struct = CfgNode::new_bb(vec![],loop_level);
@@ -2314,7 +3599,7 @@ pub fn update_jump_instr_of_parents_of_cfg_node(cfg_node:u32, cfg_graph:&mut Cfg
                             update_br_instr_of_branch(parent_cfg_node, cfg_graph, instr_slab);
                         },
                         JumpOp::Switch { cond, default, compared } => {
-                            todo!()
+                            update_switch_instr_of_switch(parent_cfg_node, cfg_node, cfg_graph, instr_slab);
                         },
                         JumpOp::DirectJump { label_symidx } => {
                             panic!("you can't insert direct jump before the nhwc dump pass")
@@ -2349,7 +3634,8 @@ pub fn insert_bb_between(cfg_node_from:u32, cfg_node_to:u32, cfg_graph:&mut CfgG
         Ok(new_bb)
     } else if cfg_former_edge_removed.cfg_edge_type.is_body_head() 
     || cfg_former_edge_removed.cfg_edge_type.is_if_true() 
-    || cfg_former_edge_removed.cfg_edge_type.is_if_false() {
+    || cfg_former_edge_removed.cfg_edge_type.is_if_false() 
+    || cfg_former_edge_removed.cfg_edge_type.is_case() {
         let mut bb_struct = CfgNode::new_bb(vec![],loop_level);
         bb_struct.add_func_cor_symidx(node!(at cfg_node_from in cfg_graph).get_func_cor_symidx().clone());
         let new_bb = add_node_with_edge!({bb_struct} with_edge {cfg_former_edge_removed} from cfg_node_from in cfg_graph);
@@ -2442,7 +3728,7 @@ pub fn process_short_logic(cfg_node:u32,cfg_graph:&mut CfgGraph,logic_op:&ExprOp
             add_edge!({false_edge.clone()} from new_br to outside_node in cfg_graph);
             (br_node,new_br)
         },
-        (CfgNodeType::WhileLoop { ast_expr_node },while_node) => {
+        (CfgNodeType::WhileLoop { ast_expr_node } | CfgNodeType::DoWhileLoop { ast_expr_node },while_node) => {
             let ast_expr_node = *ast_expr_node;
             let br_outedges = cfg_graph.edges_directed(node_index(while_node as usize),petgraph::Direction::Outgoing);
             let edge_weights: Vec<(EdgeIndex, CfgEdge)> = br_outedges.map(|edge| (edge.id(), edge.weight().clone())).collect();
@@ -2521,8 +3807,26 @@ pub fn update_br_instr_of_branch(cfg_branch_node:u32,cfg_graph:&mut CfgGraph, in
     };
 }
 
+/// `cfg_new_node` 是刚刚插入在 switch 和某个 case 之间的 bb，把 switch instr 中指向原来 case 的 label 换成 `cfg_new_node` 的 label
+pub fn update_switch_instr_of_switch(cfg_switch_node:u32, cfg_new_node:u32, cfg_graph:&mut CfgGraph, instr_slab:&mut InstrSlab<NhwcInstr>) {
+    let cfg_former_case_node = direct_child_node!(at cfg_new_node in cfg_graph);
+    let former_label = find_label_of_cfg_node(cfg_former_case_node, cfg_graph, instr_slab);
+    let new_label = find_label_of_cfg_node(cfg_new_node, cfg_graph, instr_slab);
+    let jump_instr = node!(at cfg_switch_node in cfg_graph).op_jump_instr.unwrap();
+    match &mut instr_mut!(at jump_instr in instr_slab).instr_type{
+        NhwcInstrType::Jump { jump_op:JumpOp::Switch { cond:_, default, compared } } => {
+            if *default == former_label{
+                *default = new_label.clone();
+            }
+            for pair in compared.iter_mut().filter(|pair| pair.label == former_label){
+                pair.label = new_label.clone();
+            }
+        },
+        _ => {panic!("jump instr should be a switch ")}
+    };
+}
 pub fn get_exit_node_of_while_or_for_node(cfg_while_or_for_node:u32, cfg_graph:&mut CfgGraph) -> u32{
-    assert!(node!(at cfg_while_or_for_node in cfg_graph).cfg_node_type.is_while_loop() || node!(at cfg_while_or_for_node in cfg_graph).cfg_node_type.is_for_loop());
+    assert!(node!(at cfg_while_or_for_node in cfg_graph).cfg_node_type.is_while_loop() || node!(at cfg_while_or_for_node in cfg_graph).cfg_node_type.is_do_while_loop() || node!(at cfg_while_or_for_node in cfg_graph).cfg_node_type.is_for_loop());
     direct_child_node!(at cfg_while_or_for_node in cfg_graph with_predicate {|e| !e.weight().cfg_edge_type.is_body_head()})
 }
 /// return head and tail of `cfg_while_node` or `cfg_for_node` 
@@ -2558,7 +3862,7 @@ pub fn get_while_or_for_node_of_cfg_node(cfg_node:u32, cfg_graph:&mut CfgGraph,
     debug_info_green!("try find while node of cfg_node {}",cfg_node);
     // get any ast_node of the cfg _node 
     let ast_node = match &node!(at cfg_node in cfg_graph).cfg_node_type{
-        CfgNodeType::WhileLoop { ast_expr_node } => {ast_expr_node},
+        CfgNodeType::WhileLoop { ast_expr_node } | CfgNodeType::DoWhileLoop { ast_expr_node } => {ast_expr_node},
         CfgNodeType::BasicBlock { ast_nodes } => {
             ast_nodes.get(0).unwrap()
         },
@@ -2575,6 +3879,28 @@ pub fn get_while_or_for_node_of_cfg_node(cfg_node:u32, cfg_graph:&mut CfgGraph,
     Ok(while_cfg_node)
 }
 
+/// return the while node or switch node that a `break` in the cfg_node jumps out of
+pub fn get_break_target_node_of_cfg_node(cfg_node:u32, cfg_graph:&mut CfgGraph, scope_tree:&ScopeTree, ast2scope:&HashMap<u32, u32>) -> Result<u32>{
+    let ast_node = match &node!(at cfg_node in cfg_graph).cfg_node_type{
+        CfgNodeType::BasicBlock { ast_nodes } => {
+            ast_nodes.get(0).unwrap()
+        },
+        _ => { panic!() }
+    };
+    let &scope_node = ast2scope.get(ast_node).unwrap();
+    let break_scope_node = get_break_scope_of_scope_node(scope_node, scope_tree);
+    let cfg_target_node = match &node!(at break_scope_node in scope_tree).scope_type {
+        crate::toolkit::scope_node::ScopeType::While { op_cfg_while_node } => {
+            op_cfg_while_node.unwrap()
+        },
+        crate::toolkit::scope_node::ScopeType::Switch { op_cfg_switch_node } => {
+            op_cfg_switch_node.unwrap()
+        },
+        _ => panic!()
+    };
+    Ok(cfg_target_node)
+}
+
 // pub fn find_gather_of_branch_downward(cfg_branch_node:u32,cfg_graph:&CfgGraph)-> Result<u32>{
 //     let cur_branch_layer_count = 0;
 //     recursive_find_gather(cfg_branch_node, cfg_graph, cur_branch_layer_count)
@@ -2629,52 +3955,59 @@ pub fn get_cfg_entry_by_cfg_node(cfg_graph:&CfgGraph,symtab:&SymTab,cfg_node:u32
     cfg_entry
 }
 
-/// array init
-/// require input `reversed_remained_dims` dimensions to be reversed 
-pub fn array_initialize( et_tree:&mut EtTree, array_ele_map:&mut ArrayEleMap, ele_type:&Type,reversed_remained_dims:&mut Vec<usize>, array_offset:&mut usize,
+/// 按照 C 的花括号省略规则把 et_node 这个 ArrayWrapper 中的初值放到 array_ele_map 中  
+/// et_node 初始化的是维度为 dims[depth..] 的子数组，它的第一个元素位于线性偏移 base_offset  
+/// 遇到内层的 {} 时，它初始化的是当前位置开始的、对齐的最大的更低维子数组，位置不对齐到最后一维时就只初始化一个标量  
+/// 多出来的初值是错误，报告之后忽略
+pub fn array_initialize( et_tree:&mut EtTree, array_ele_map:&mut ArrayEleMap, ele_type:&Type, dims:&[usize], depth:usize, base_offset:usize,
     ast_tree:&AstTree, cfg_graph:&mut CfgGraph,  scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
 ) {
-    let ele_count_to_read:usize = if reversed_remained_dims.len()!=0 { reversed_remained_dims.iter().product()}else {panic!()};
-        // array_ele_map.add_ele_from_usize(*array_offset, Value::from_string_with_specific_type(&"0", ele_type)?)?;
-        // debug_info_blue!("add array ele with offset {},{:?}",*array_offset, Value::from_string_with_specific_type(&"0", ele_type)?);
-    let last_array_offset = *array_offset;
-    *array_offset+= (ele_count_to_read - *array_offset % ele_count_to_read)%ele_count_to_read;
-    debug_info_blue!("array_init {:?} at et_node {}",reversed_remained_dims,et_node);
-    let et_node_vec = direct_child_nodes!(at et_node in et_tree with_predicate {|e| !e.weight().et_edge_type.is_deleted()});
-    let mut i = 0;
-    while i < et_node_vec.len(){
-        let et_node = et_node_vec[i];
-        debug_info_blue!("ele_count_to_read =  {},{:?}  cur_et_node:{et_node}",ele_count_to_read , reversed_remained_dims);
-        match &node!(at et_node in et_tree).et_node_type{
-            EtNodeType::Operator { op:ExprOp::ArrayWrapper, ast_node: _, text: _, op_rc_symidx: op_symidx }=>{
-                let cur_dim = reversed_remained_dims.pop().unwrap();
-                array_initialize( et_tree, array_ele_map, ele_type,reversed_remained_dims, array_offset,ast_tree, cfg_graph, scope_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, ast2scope,);
-                reversed_remained_dims.push(cur_dim);
-            },
-            EtNodeType::Literal { rc_literal_symidx, ast_node: _, text: _ } => {
-                array_ele_map.insert_ele(*array_offset, Value::from_string_with_specific_type(&rc_literal_symidx.as_ref_borrow().symbol_name, ele_type));
-                // debug_info_blue!("add array ele with offset {},{:?}",i, Value::from_string_with_specific_type(&const_sym_idx.symbol_name, ele_type)?);
-                *array_offset +=1;
-            },
-            _ => {
-                let l_type = ele_type;
-                let rc_r_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, ast2scope,).unwrap_or_else(||panic!("no value of this expr et node{}",et_node));
-                let r_symidx = rc_r_symidx.as_ref_borrow();
-                // only 2 types should be considered, f32 & i32
-                let r_type = symtab.get(&r_symidx).get_type().clone();
-                let new_value_symidx = force_trans_type_discriminant(cfg_graph, symtab, l_type.into(),  &rc_r_symidx, scope_node, cfg_bb, instr_slab, Some(et_node),et_tree);
-                array_ele_map.insert_ele(*array_offset, Value::new_ref(new_value_symidx, r_type));
-                *array_offset +=1;
-            },
-            _ => {
-                panic!("expect a constant or arraywrapper but meet nor of them at {}",et_node)
+    let sub_array_len = |d:usize| -> usize { dims[d..].iter().product() };
+    let end_offset = base_offset + sub_array_len(depth);
+    let mut array_offset = base_offset;
+    debug_info_blue!("array_init {:?} from depth {} at et_node {}",dims,depth,et_node);
+    for et_node in direct_child_nodes!(at et_node in et_tree with_predicate {|e| !e.weight().et_edge_type.is_deleted()}){
+        if array_offset >= end_offset {
+            report_error(symtab, node!(at et_node in et_tree).src_loc.clone(), "数组的初值过多".to_string());
+            break;
+        }
+        if is_array_wrapper(et_tree, et_node) {
+            let sub_depth = (depth+1..dims.len()).find(|&d| array_offset.is_multiple_of(sub_array_len(d))).unwrap_or(dims.len());
+            if sub_depth < dims.len() {
+                array_initialize( et_tree, array_ele_map, ele_type, dims, sub_depth, array_offset, ast_tree, cfg_graph, scope_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, ast2scope,);
+                array_offset += sub_array_len(sub_depth);
+                continue;
             }
-        };
-        i+=1;
+            // 用花括号包起来的标量 {x} 只取第一个值
+            let mut scalar_et_node = Some(et_node);
+            while let Some(wrapper_et_node) = scalar_et_node.filter(|&n| is_array_wrapper(et_tree, n)) {
+                scalar_et_node = direct_child_nodes!(at wrapper_et_node in et_tree with_predicate {|e| !e.weight().et_edge_type.is_deleted()}).first().copied();
+            }
+            if let Some(scalar_et_node) = scalar_et_node {
+                array_initialize_scalar(et_tree, array_ele_map, ele_type, array_offset, ast_tree, cfg_graph, scope_tree, symtab, scalar_et_node, scope_node, cfg_bb, instr_slab, ast2scope);
+            }
+        }else {
+            array_initialize_scalar(et_tree, array_ele_map, ele_type, array_offset, ast_tree, cfg_graph, scope_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, ast2scope);
+        }
+        array_offset += 1;
     }
-    // if has ele in this dimension and is not full
-    if last_array_offset+ele_count_to_read!=*array_offset{
-        *array_offset+= ele_count_to_read - *array_offset % ele_count_to_read;
+}
+fn is_array_wrapper(et_tree:&EtTree, et_node:u32) -> bool{
+    matches!(node!(at et_node in et_tree).et_node_type, EtNodeType::Operator { op:ExprOp::ArrayWrapper, .. })
+}
+fn array_initialize_scalar( et_tree:&mut EtTree, array_ele_map:&mut ArrayEleMap, ele_type:&Type, array_offset:usize,
+    ast_tree:&AstTree, cfg_graph:&mut CfgGraph,  scope_tree:&ScopeTree, symtab:&mut SymTab, et_node:u32, scope_node:u32, cfg_bb:u32, instr_slab:&mut InstrSlab<NhwcInstr>,ast2scope:&HashMap<u32, u32>,
+) {
+    match &node!(at et_node in et_tree).et_node_type{
+        EtNodeType::Literal { rc_literal_symidx, ast_node: _, text: _ } => {
+            array_ele_map.insert_ele(array_offset, Value::from_string_with_specific_type(&rc_literal_symidx.as_ref_borrow().symbol_name, ele_type));
+        },
+        _ => {
+            let rc_r_symidx = process_et(ast_tree, cfg_graph, et_tree, scope_tree, symtab, et_node, scope_node, cfg_bb, instr_slab, ast2scope,).unwrap_or_else(||panic!("no value of this expr et node{}",et_node));
+            let r_type = symtab.get(&rc_r_symidx.as_ref_borrow()).get_type().clone();
+            let new_value_symidx = force_trans_type_discriminant(cfg_graph, symtab, ele_type.into(),  &rc_r_symidx, scope_node, cfg_bb, instr_slab, Some(et_node),et_tree);
+            array_ele_map.insert_ele(array_offset, Value::new_ref(new_value_symidx, r_type));
+        },
     }
 }
 /// transform the linear offset into vec<usize> which represent the offsets of all dimensions 
@@ -2688,22 +4021,54 @@ pub fn deduce_linear_offset_by_weights(offset:usize, weights:Vec<usize>) -> Vec<
     decoded_vec
 }
 
-pub fn add_may_pointed_symidx_to(symtab:&mut SymTab, rc_ptr_symidx:&RcSymIdx, rc_may_pointed_symidx:&RcSymIdx) -> RcSymIdx{
+/// 返回 ptr 可能指向的变量，指针指向的变量未知时返回 None
+pub fn add_may_pointed_symidx_to(symtab:&mut SymTab, rc_ptr_symidx:&RcSymIdx, rc_may_pointed_symidx:&RcSymIdx) -> Option<RcSymIdx>{
     // add_pointed_symidx for temp_ptr 
     let may_pointed_symidx = rc_may_pointed_symidx.as_ref_borrow();
     let ptr_symidx = rc_ptr_symidx.as_ref_borrow();
     let rc_pointed_symidx = if may_pointed_symidx.is_global_ptr() {
         let pointed_symidx = may_pointed_symidx.to_deglobal_ptr();
-        let rc_pointed_symidx = symtab.get(&pointed_symidx).rc_symidx.clone();
-        symtab.get_mut(&ptr_symidx).add_pointed_symidx(rc_pointed_symidx.clone());
-        rc_pointed_symidx
+        symtab.get(&pointed_symidx).rc_symidx.clone()
+    } else if symtab.get(&may_pointed_symidx).has_pointed_symidx(){
+        // 由另一个指针得到的指针，指向的变量与之相同
+        symtab.get(&may_pointed_symidx).get_pointed_symidx().clone()
+    } else if symtab.get(&may_pointed_symidx).get_type().is_ptr_64() && !is_func_para(symtab, &may_pointed_symidx){
+        // 指针变量的值在运行时才能确定
+        return None
     } else{
-        symtab.get_mut(&ptr_symidx).add_pointed_symidx(rc_may_pointed_symidx.clone());
         rc_may_pointed_symidx.clone()
     };
-    rc_pointed_symidx
+    symtab.get_mut(&ptr_symidx).add_pointed_symidx(rc_pointed_symidx.clone());
+    Some(rc_pointed_symidx)
+}
+fn is_func_para(symtab:&SymTab, symidx:&SymIdx) -> bool{
+    let sym = symtab.get(symidx);
+    sym.has_is_func_para() && *sym.get_is_func_para()
+}
+/// 局部标量被取地址后会作为只有一个元素的数组放在内存中
+pub fn is_addr_taken_local(symtab:&SymTab, symidx:&SymIdx) -> bool{
+    let sym = symtab.get(symidx);
+    sym.has_is_addr_taken() && *sym.get_is_addr_taken()
+}
+/// 返回通过 ptr 读写内存时可能涉及的变量，用于插入 mu 与 chi  
+/// 不知道 ptr 指向哪个变量时，保守地认为它可能指向任何放在内存中的变量
+pub fn get_may_pointed_symidx_vec(symtab:&SymTab, rc_func_symidx:&RcSymIdx, rc_ptr_symidx:&RcSymIdx) -> Vec<RcSymIdx>{
+    let ptr_symidx = rc_ptr_symidx.as_ref_borrow();
+    if symtab.get(&ptr_symidx).has_pointed_symidx(){
+        vec![symtab.get(&ptr_symidx).get_pointed_symidx().clone()]
+    }else if is_func_para(symtab, &ptr_symidx){
+        // 与数组参数一样，指针参数指向的内存用参数本身表示
+        vec![rc_ptr_symidx.clone()]
+    }else{
+        let mut may_pointed_symidx_vec = symtab.get_global_info().get_global_vars().clone();
+        may_pointed_symidx_vec.extend(symtab.get(&rc_func_symidx.as_ref_borrow()).get_declared_vars().iter().filter(|rc_symidx| {
+            let symidx = rc_symidx.as_ref_borrow();
+            let sym = symtab.get(&symidx);
+            !*sym.get_is_temp() && (sym.get_type().is_aggregate() || sym.get_type().is_ptr_64() && is_func_para(symtab, &symidx))
+        }).cloned());
+        may_pointed_symidx_vec
+    }
 }
-
 
 
 // pub fn remove_redundant_assign(cfg_graph:&mut CfgGraph, instr_slab:&mut InstrSlab<NhwcInstr>){
@@ -2,10 +2,11 @@
 //|symidx,temp_reg,symtab,asm_sect,regtab|{
 use crate::{antlr_parser::clexer::{Register, Void}, debug_info_blue, debug_info_red, direct_child_nodes, instr, node, node_mut, passes::simulator_debug_pass::debug_simu_run, reg_field_for_struct, toolkit::{field::{TypeDiscriminants, Value}, rv64_instr::{Arithmetic, REG_A_RANGE, REG_FA_RANGE}, symtab::WithBorrow}};
 use anyhow::*;
+use petgraph::visit::EdgeRef;
 use anyhow::Ok;
 use itertools::Itertools;
 
-use super::{asm_struct::{AsmSection, AsmStructure}, cfg_edge::CfgEdgeType, cfg_node::{CfgGraph, CfgNode, CFG_ROOT}, dot::Config, etc::{dfs_with_priority, generate_png_by_graph}, field::Type, gen_nhwc_cfg::IS_LITERAL, nhwc_instr::{FuncOp, InstrSlab, NhwcInstr, NhwcInstrType}, regtab::{self, RegTab}, rv64_instr::{Compare, Imm, Loads, Logical, PseudoInstr, RV64Instr, Register, RiscvOffsetLimit, Shifts, Stores, Trans, REG_FS_RANGE, REG_S_RANGE}, simulator::Simulator, symtab::{self, RcSymIdx, SymIdx, SymTab}};
+use super::{asm_struct::{AsmSection, AsmStructure}, cfg_edge::CfgEdgeType, cfg_node::{CfgGraph, CfgNode, CFG_ROOT}, dot::Config, etc::{dfs_with_priority, generate_png_by_graph}, field::Type, gen_nhwc_cfg::IS_LITERAL, nhwc_instr::{ArithOp, ComparedPair, FuncOp, InstrSlab, NhwcInstr, NhwcInstrType}, regtab::{self, RegTab}, rv64_instr::{Branch, Compare, Imm, Loads, Logical, PseudoInstr, RV64Instr, Register, RiscvOffsetLimit, Shifts, Stores, Trans, REG_FS_RANGE, REG_S_RANGE}, simulator::Simulator, symtab::{self, RcSymIdx, SymIdx, SymTab}};
 
 reg_field_for_struct!(CfgNode {
     REGTAB:RegTab,
@@ -46,13 +47,24 @@ fn parse_root2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhwc
                     super::field::Type::Fn { arg_syms: _, ret_sym: _ } => {
                         // do nothing when it is a function
                     },
+                    _ if src_symtab.get_global_info().get_str_literals().contains(var_symidx) => {
+                        // 字符串字面量统一放到后面的 .rodata 中
+                    },
                     _ => {
                         asm_sect.align(vartype.get_align());
-                        asm_sect.global(Imm::new_global_label(var_symidx.clone()));
+                        // static 变量只在本文件内可见
+                        if !*src_symtab.get(&var_symidx.as_ref_borrow()).get_is_static(){
+                            asm_sect.global(Imm::new_global_label(var_symidx.clone()));
+                        }
                         asm_sect.annotate(format!("{:?}",&instr!(at instr in nhwc_instr_slab)));
                         asm_sect.obj_type(Imm::new_global_label(var_symidx.clone()));
                         asm_sect.label(Imm::new_global_label(var_symidx.clone()));
-                        asm_sect.apply_value(simulator.simu_symtab.get(&var_symidx.as_ref_borrow().to_src_symidx()).get_simu_val());
+                        if vartype.is_struct() || vartype.is_union(){
+                            // simulator 不追踪 struct 的内容，全局 struct 只能零初始化
+                            asm_sect.zero(vartype.get_mem_len());
+                        }else{
+                            asm_sect.apply_value(simulator.simu_symtab.get(&var_symidx.as_ref_borrow().to_src_symidx()).get_simu_val());
+                        }
                     }
                 }
             },
@@ -61,6 +73,18 @@ fn parse_root2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhwc
             }
         }
     }
+    let str_literals = src_symtab.get_global_info().get_str_literals();
+    if !str_literals.is_empty(){
+        asm_sect.rodata();
+        for rc_str_symidx in str_literals.iter(){
+            let str_val = src_symtab.get(&rc_str_symidx.as_ref_borrow()).get_str_literal_val();
+            let Value::Array { value_map, dims: _, ele_ty: _ } = str_val else { panic!("字符串字面量 {:?} 的值不是数组",rc_str_symidx) };
+            let bytes = (0..str_val.get_mem_len()-1).map(|idx| value_map.get_ele_at(idx).unwrap().as_i32() as u8).collect_vec();
+            asm_sect.align(1);
+            asm_sect.label(Imm::new_global_label(rc_str_symidx.clone()));
+            asm_sect.asciz(bytes);
+        }
+    }
     if asm_sect.stmts.len() != 1{
         Ok(Some(asm_sect))
     }else{
@@ -74,6 +98,31 @@ fn parse_root2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhwc
 //     }
 // }
 
+/// 按照 LP64D 约定，变长参数函数中 ... 对应的实参全部通过整数寄存器传递，用完 a0-a7 后再通过栈传递，浮点数传递的是它的比特位  
+/// 返回固定参数的个数，不是变长参数函数的调用返回 usize::MAX
+pub fn get_fixed_arg_count_of_call(func_op:&FuncOp, symtab:&SymTab) -> usize{
+    let func_symidx = &func_op.rc_func_symidx.as_ref_borrow();
+    match symtab.get(func_symidx).get_type(){
+        Type::Fn { arg_syms, ret_sym:_ } if !func_op.is_indirect && *symtab.get(func_symidx).get_is_variadic() => arg_syms.len(),
+        _ => usize::MAX,
+    }
+}
+/// 实参的类型，字面量按它的文本判断
+fn get_arg_ty(arg:&SymIdx, symtab:&SymTab) -> TypeDiscriminants{
+    if arg.is_literal(){
+        TypeDiscriminants::new_from_const_str(&arg.symbol_name)
+    }else {
+        symtab.get(&arg.to_src_symidx()).get_type().into()
+    }
+}
+/// 变长参数函数调用中通过栈传递的实参占用的空间，按 16 对齐，由调用者在自己栈帧的底部预留
+pub fn get_vararg_stack_size_of_call(func_op:&FuncOp, symtab:&SymTab) -> usize{
+    let fixed_arg_count = get_fixed_arg_count_of_call(func_op, symtab);
+    if fixed_arg_count == usize::MAX { return 0 }
+    let gpr_arg_count = func_op.actual_arg_symidx_vec.iter().enumerate()
+        .filter(|(idx, arg)| *idx >= fixed_arg_count || !matches!(get_arg_ty(&arg.as_ref_borrow(), symtab), TypeDiscriminants::F32 | TypeDiscriminants::F64)).count();
+    (gpr_arg_count.saturating_sub(8) * 8).next_multiple_of(16)
+}
 pub fn add_literal_to_reg(asm_sect:&mut AsmSection,rd:Register,rs:Register, regtab:&mut RegTab,symtab:&mut SymTab, offset:isize) -> Result<()>{
     if offset>-2000 && offset<2000 {
         asm_sect.asm(Arithmetic::new_addi(rd, rs, Imm::from_offset(offset)).into());
@@ -144,7 +193,9 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
             CfgEdgeType::IfFalse {  } => 2,
             CfgEdgeType::Direct {  } => 2,
             CfgEdgeType::IfTrue {  } => 1,
-            CfgEdgeType::BodyTail {  } => -1,
+            // do while 节点只能从循环体尾部到达
+            CfgEdgeType::BodyTail {  } => if node!(at {e.target().index() as u32} in cfg_graph).cfg_node_type.is_do_while_loop() { 2 } else { -1 },
+            CfgEdgeType::Case {  } => 2,
         });
         let mut _regtab = RegTab::new();
         let regtab =&mut _regtab;
@@ -178,7 +229,9 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                         asm_sect.label(Imm::new_local_label(label_symidx.clone()));
                     },
                     NhwcInstrType::DefineFunc { func_symidx, ret_symidx: _, args } => {
-                        asm_sect.global(Imm::new_global_label(func_symidx.clone()));
+                        if !*symtab.get(&func_symidx.as_ref_borrow().to_src_symidx()).get_is_static(){
+                            asm_sect.global(Imm::new_global_label(func_symidx.clone()));
+                        }
                         asm_sect.func_type(Imm::new_global_label(func_symidx.clone()));
                         asm_sect.label(Imm::new_global_label(func_symidx.clone()));
                         let func_symidx = func_symidx.as_ref_borrow();
@@ -189,7 +242,7 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                         let mut gpr_args = vec![];
                         for (_idx,arg) in args.iter().enumerate(){
                             match symtab.get(&arg.as_ref_borrow().to_src_symidx()).get_type().into(){
-                                TypeDiscriminants::F32 => {
+                                TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                                     fpr_args.push(arg)
                                 },
                                 _ => {
@@ -244,12 +297,12 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                     NhwcInstrType::Globl { var_symidx: _, vartype: _ } => {
                         // do nothing
                     },
-                    NhwcInstrType::Load { lhs, ptr_symidx, ptr_ty: _ } => {
+                    NhwcInstrType::Load { lhs, ptr_symidx, ptr_ty } => {
                         debug_info_red!("{:?} {:?}",instr_struct, ptr_symidx);
-                        load_from_ptr(asm_sect, &ptr_symidx.as_ref_borrow(), &lhs.as_ref_borrow(),  regtab, symtab)?;
+                        load_from_ptr(asm_sect, &ptr_symidx.as_ref_borrow(), ptr_ty, &lhs.as_ref_borrow(),  regtab, symtab)?;
                     },
-                    NhwcInstrType::Store { val_symidx: value_symidx, value_ty: _, ptr_symidx, ptr_ty: _ } => {
-                        store_from_ptr(asm_sect, &ptr_symidx.as_ref_borrow(), &value_symidx.as_ref_borrow(),regtab, symtab)?;
+                    NhwcInstrType::Store { val_symidx: value_symidx, value_ty: _, ptr_symidx, ptr_ty } => {
+                        store_from_ptr(asm_sect, &ptr_symidx.as_ref_borrow(), ptr_ty, &value_symidx.as_ref_borrow(),regtab, symtab)?;
                     },
                     NhwcInstrType::GetElementPtr { lhs, ptr_symidx: array_or_ptr_symidx, array_ty, idx_vec } => {
                         let lhs = lhs.as_ref_borrow();
@@ -277,7 +330,14 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                         // finally plus array offset 
                         let ptr_reg = regtab.find_and_occupy_reg(&lhs,&TypeDiscriminants::I32, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                         _load_sym_or_imm(asm_sect, &SymIdx::from_str("0"), ptr_reg.clone(), regtab, symtab)?;
-                        for (idx,weight) in idx_vec.iter().zip(array_ty.get_array_dim_stride_symidx_vec().iter()){
+                        // struct 的偏移已经以字节为单位 ，而元素大小不是 2 的幂时(例如 struct 数组)只能把元素大小乘进 weight 中
+                        let ele_size = if array_ty.is_struct() || array_ty.is_union() { 1 } else { array_ty.get_ele_size() };
+                        let (weights,ele_size) = if ele_size.is_power_of_two(){
+                            (array_ty.get_array_dim_stride_symidx_vec(),ele_size)
+                        }else {
+                            (array_ty.get_array_dim_stride_symidx_vec().iter().map(|weight| (Value::from_symidx(weight)*Value::new_i32(ele_size as i32)).to_symidx()).collect_vec(),1)
+                        };
+                        for (idx,weight) in idx_vec.iter().zip(weights.iter()){
                             // println!("{:?}, {:?}",idx , weight);
                             let idx = idx.as_ref().unwrap().as_ref_borrow();
                             let temp_idx_mul_weight_reg = regtab.find_and_anonymous_occupy(&SymIdx::from_str("temp_idx_mul_weight_reg"),&TypeDiscriminants::I32, symtab, asm_sect, &mut default_store, &mut no_load)?;
@@ -285,7 +345,6 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                             asm_sect.asm(Arithmetic::new_add(ptr_reg.clone(), ptr_reg.clone(),temp_idx_mul_weight_reg.clone() ).into());
                             regtab.unoccupied_reg(temp_idx_mul_weight_reg,symtab,asm_sect,&mut default_store)?;
                         }
-                        let ele_size = array_ty.get_ele_size();
                         asm_sect.asm(Shifts::new_slli_from_multiple(ptr_reg.clone(), ptr_reg.clone(), ele_size)?.into());
                         // 2 situations : 1. array is global  2. array is local to stack
                         match symtab.get(&array_or_ptr_symidx).has_is_global() &&*symtab.get(&array_or_ptr_symidx).get_is_global(){
@@ -304,7 +363,7 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                         asm_sect.asm(Arithmetic::new_add(ptr_reg.clone(), ptr_reg.clone(),addr_reg.clone()).into());
                                         regtab.unoccupied_reg(addr_reg,symtab, asm_sect,&mut default_store)?;
                                     },
-                                    Type::Array { dims, ele_ty } => {
+                                    Type::Array { .. } | Type::Struct { .. } | Type::Union { .. } => {
                                         asm_sect.asm(Arithmetic::new_add(ptr_reg.clone(), ptr_reg.clone(),Register::SP).into());
                                         // assert!(array_or_ptr_symidx.index_ssa.is_none());
                                         let &offset2sp = symtab.get(&array_or_ptr_symidx.to_src_symidx()).get_mem_offset2sp();
@@ -329,12 +388,18 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 let val_reg2= regtab.find_and_occupy_reg(&b, &ty_discriminants, symtab, asm_sect,&mut default_store,&mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                 let rst_reg= regtab.find_and_occupy_reg(&lhs, &ty_discriminants, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                 match vartype.into(){
-                                    TypeDiscriminants::I32 => {
+                                    TypeDiscriminants::I32 | TypeDiscriminants::U32 => {
                                         asm_sect.asm(Arithmetic::new_addw(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
+                                    TypeDiscriminants::I64 => {
+                                        asm_sect.asm(Arithmetic::new_add(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
                                     TypeDiscriminants::F32 => {
                                         asm_sect.asm(Arithmetic::new_fadds(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
+                                    TypeDiscriminants::F64 => {
+                                        asm_sect.asm(Arithmetic::new_faddd(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
                                     _ => {panic!("not support arithmetic operation on types except i32 or f32")}
                                 };
                                 regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
@@ -347,16 +412,29 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
 
                                 let ty_discriminants = vartype.into();
                                 match ty_discriminants{
-                                    TypeDiscriminants::I32 => {
+                                    TypeDiscriminants::I32 | TypeDiscriminants::U32 => {
                                         let rst_reg= regtab.find_and_occupy_reg(&lhs, &ty_discriminants, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                         magic_i32_mul(asm_sect, regtab,rst_reg.clone(), &a, &b, true,symtab)?;
                                         regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
                                     },
-                                    TypeDiscriminants::F32 => {
+                                    TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
+                                        let val_reg1= regtab.find_and_occupy_reg(&a, &ty_discriminants,symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                        let val_reg2= regtab.find_and_occupy_reg(&b, &ty_discriminants, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                        let rst_reg= regtab.find_and_occupy_reg(&lhs, &ty_discriminants, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                        if ty_discriminants.is_f_64(){
+                                            asm_sect.asm(Arithmetic::new_fmuld(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                        }else{
+                                            asm_sect.asm(Arithmetic::new_fmuls(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).clone().into());
+                                        }
+                                        regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
+                                        regtab.unoccupied_reg(val_reg2,symtab,asm_sect,&mut default_store)?;
+                                        regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                                    },
+                                    TypeDiscriminants::I64 => {
                                         let val_reg1= regtab.find_and_occupy_reg(&a, &ty_discriminants,symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                         let val_reg2= regtab.find_and_occupy_reg(&b, &ty_discriminants, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                         let rst_reg= regtab.find_and_occupy_reg(&lhs, &ty_discriminants, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
-                                        asm_sect.asm(Arithmetic::new_fmuls(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).clone().into());
+                                        asm_sect.asm(Arithmetic::new_mul(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                         regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
                                         regtab.unoccupied_reg(val_reg2,symtab,asm_sect,&mut default_store)?;
                                         regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
@@ -375,9 +453,18 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                     TypeDiscriminants::I32 => {
                                         asm_sect.asm(Arithmetic::new_divw(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
+                                    TypeDiscriminants::U32 => {
+                                        asm_sect.asm(Arithmetic::new_divuw(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
+                                    TypeDiscriminants::I64 => {
+                                        asm_sect.asm(Arithmetic::new_div(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
                                     TypeDiscriminants::F32 => {
                                         asm_sect.asm(Arithmetic::new_fdivs(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
+                                    TypeDiscriminants::F64 => {
+                                        asm_sect.asm(Arithmetic::new_fdivd(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
                                     _ => {panic!("not support arithmetic operation on types except i32 or f32")}
                                 };
                                 regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
@@ -392,14 +479,17 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 let val_reg2= regtab.find_and_occupy_reg(&b, &vartype.into(), symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                 let rst_reg= regtab.find_and_occupy_reg(&lhs,& vartype.into(), symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                 match vartype.into(){
-                                    TypeDiscriminants::I32 => {
+                                    TypeDiscriminants::I32 | TypeDiscriminants::U32 => {
                                         asm_sect.annotate(format!("regtab:{:?}\n",regtab));
                                         asm_sect.asm(Arithmetic::new_subw(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
                                     TypeDiscriminants::F32 => {
                                         asm_sect.asm(Arithmetic::new_fsubs(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
-                                    TypeDiscriminants::I1 => {
+                                    TypeDiscriminants::F64 => {
+                                        asm_sect.asm(Arithmetic::new_fsubd(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
+                                    TypeDiscriminants::I1 | TypeDiscriminants::I64 => {
                                         asm_sect.asm(Arithmetic::new_sub(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     }
                                     _ => {panic!("not support arithmetic operation on types except i32 or f32")}
@@ -419,6 +509,13 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                     TypeDiscriminants::I32 => {
                                         asm_sect.asm(Arithmetic::new_rem(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).clone().clone().into());
                                     },
+                                    TypeDiscriminants::I64 => {
+                                        asm_sect.asm(Arithmetic::new_rem(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
+                                    TypeDiscriminants::U32 => {
+                                        // u32 在寄存器中是符号扩展的，不能直接用 64 位的 remu
+                                        asm_sect.asm(Arithmetic::new_remuw(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                    },
                                     TypeDiscriminants::F32 => {
                                         panic!("not support arithmetic operation on type f32 ")
                                     },
@@ -445,17 +542,20 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                         asm_sect.asm(Logical::new_xor(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                         asm_sect.asm(PseudoInstr::new_snez(rst_reg.clone(), rst_reg.clone()).into());
                                     },
+                                    // 两个 u32 都是符号扩展的，64 位 sltu 的结果与 32 位无符号比较一致
                                     super::nhwc_instr::IcmpPlan::Ugt => {
-                                        todo!()
+                                        asm_sect.asm(Compare::new_sltu(rst_reg.clone(), val_reg2.clone(), val_reg1.clone()).into());
                                     },
                                     super::nhwc_instr::IcmpPlan::Uge => {
-                                        todo!()
+                                        asm_sect.asm(Compare::new_sltu(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into());
+                                        asm_sect.asm(Logical::new_xori(rst_reg.clone(), rst_reg.clone(), Imm::from_offset(1)).into());
                                     },
                                     super::nhwc_instr::IcmpPlan::Ult => {
-                                        todo!()
+                                        asm_sect.asm(Compare::new_sltu(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into());
                                     },
                                     super::nhwc_instr::IcmpPlan::Ule => {
-                                        todo!()
+                                        asm_sect.asm(Compare::new_sltu(rst_reg.clone(), val_reg2.clone(), val_reg1.clone()).into());
+                                        asm_sect.asm(Logical::new_xori(rst_reg.clone(), rst_reg.clone(), Imm::from_offset(1)).into());
                                     },
                                     super::nhwc_instr::IcmpPlan::Sgt => {
                                         asm_sect.asm(Compare::new_slt(rst_reg.clone(), val_reg2.clone(), val_reg1.clone()).into());
@@ -483,25 +583,31 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 let val_reg1= regtab.find_and_occupy_reg(&a, &vartype.into(), symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                 let val_reg2= regtab.find_and_occupy_reg(&b, &vartype.into(), symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
                                 let rst_reg= regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::I1, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                type FcmpCtor = fn(Register,Register,Register) -> Compare;
+                                let (feq,flt,fle):(FcmpCtor,FcmpCtor,FcmpCtor) = if vartype.is_f_64(){
+                                    (Compare::new_feq_d,Compare::new_flt_d,Compare::new_fle_d)
+                                }else {
+                                    (Compare::new_feq_s,Compare::new_flt_s,Compare::new_fle_s)
+                                };
                                 match plan{
                                     super::nhwc_instr::FcmpPlan::Oeq => {
-                                        asm_sect.asm({Compare::new_feq_s} (rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                        asm_sect.asm(feq(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
                                     super::nhwc_instr::FcmpPlan::One => {
-                                        asm_sect.asm(Compare::new_feq_s(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                        asm_sect.asm(feq(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                         asm_sect.asm(PseudoInstr::new_seqz(rst_reg.clone(),rst_reg.clone()).into());
                                     },
                                     super::nhwc_instr::FcmpPlan::Ogt => {
-                                        asm_sect.asm(Compare::new_flt_s(rst_reg.clone(),val_reg2.clone(),val_reg1.clone()).into());
+                                        asm_sect.asm(flt(rst_reg.clone(),val_reg2.clone(),val_reg1.clone()).into());
                                     },
                                     super::nhwc_instr::FcmpPlan::Oge => {
-                                        asm_sect.asm(Compare::new_fle_s(rst_reg.clone(),val_reg2.clone(),val_reg1.clone()).into());
+                                        asm_sect.asm(fle(rst_reg.clone(),val_reg2.clone(),val_reg1.clone()).into());
                                     },
                                     super::nhwc_instr::FcmpPlan::Olt => {
-                                        asm_sect.asm(Compare::new_flt_s(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                        asm_sect.asm(flt(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
                                     super::nhwc_instr::FcmpPlan::Ole => {
-                                        asm_sect.asm(Compare::new_fle_s(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
+                                        asm_sect.asm(fle(rst_reg.clone(),val_reg1.clone(),val_reg2.clone()).into());
                                     },
                                 }
                                 regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
@@ -532,6 +638,10 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 regtab.unoccupied_reg(val_reg2,symtab,asm_sect,&mut default_store)?;
                                 regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
                             },
+                            super::nhwc_instr::ArithOp::BitAnd { a, b, vartype:_ } | super::nhwc_instr::ArithOp::BitOr { a, b, vartype:_ } | super::nhwc_instr::ArithOp::BitXor { a, b, vartype:_ } 
+                            | super::nhwc_instr::ArithOp::Shl { a, b, vartype:_ } | super::nhwc_instr::ArithOp::Ashr { a, b, vartype:_ } | super::nhwc_instr::ArithOp::Lshr { a, b, vartype:_ } => {
+                                bitwise_int_op(asm_sect, regtab, &lhs, &a.as_ref_borrow(), &b.as_ref_borrow(), rhs, symtab).with_context(||format!("err when gen bitwise op {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                            },
                             super::nhwc_instr::ArithOp::LogicNot { a, vartype} => {
                                 let a = a.as_ref_borrow();
 
@@ -541,6 +651,38 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
                                 regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
                             },
+                            super::nhwc_instr::ArithOp::Select { cond, a, b, vartype } => {
+                                // 不产生跳转，用掩码选择 : rst = b ^ ((a ^ b) & -cond)
+                                let (cond,a,b) = (cond.as_ref_borrow(),a.as_ref_borrow(),b.as_ref_borrow());
+                                let cond_reg= regtab.find_and_occupy_reg(&cond, &TypeDiscriminants::I1, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let val_reg1= regtab.find_and_occupy_reg(&a, &vartype.into(), symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let val_reg2= regtab.find_and_occupy_reg(&b, &vartype.into(), symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg= regtab.find_and_occupy_reg(&lhs, &vartype.into(), symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let mask_reg = regtab.find_and_anonymous_occupy(&SymIdx::from_str("select_mask_reg"),&TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut no_load)?;
+                                asm_sect.asm(PseudoInstr::new_neg(mask_reg.clone(), cond_reg.clone()).into());
+                                if vartype.is_f_32() || vartype.is_f_64(){
+                                    // 浮点数先按位搬到整数寄存器中再选择，fmv.x.d / fmv.d.x 会保留 f32 的 NaN-boxing
+                                    let int_reg1 = regtab.find_and_anonymous_occupy(&SymIdx::from_str("select_int_reg1"),&TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut no_load)?;
+                                    let int_reg2 = regtab.find_and_anonymous_occupy(&SymIdx::from_str("select_int_reg2"),&TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut no_load)?;
+                                    asm_sect.asm(PseudoInstr::new_fmv_x_d(int_reg1.clone(), val_reg1.clone()).into());
+                                    asm_sect.asm(PseudoInstr::new_fmv_x_d(int_reg2.clone(), val_reg2.clone()).into());
+                                    asm_sect.asm(Logical::new_xor(int_reg1.clone(), int_reg1.clone(), int_reg2.clone()).into());
+                                    asm_sect.asm(Logical::new_and(int_reg1.clone(), int_reg1.clone(), mask_reg.clone()).into());
+                                    asm_sect.asm(Logical::new_xor(int_reg1.clone(), int_reg1.clone(), int_reg2.clone()).into());
+                                    asm_sect.asm(PseudoInstr::new_fmv_d_x(rst_reg.clone(), int_reg1.clone()).into());
+                                    regtab.unoccupied_reg(int_reg1,symtab,asm_sect,&mut default_store)?;
+                                    regtab.unoccupied_reg(int_reg2,symtab,asm_sect,&mut default_store)?;
+                                }else{
+                                    asm_sect.asm(Logical::new_xor(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into());
+                                    asm_sect.asm(Logical::new_and(rst_reg.clone(), rst_reg.clone(), mask_reg.clone()).into());
+                                    asm_sect.asm(Logical::new_xor(rst_reg.clone(), rst_reg.clone(), val_reg2.clone()).into());
+                                }
+                                regtab.unoccupied_reg(mask_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(cond_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(val_reg2,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
                         }
                         // store reg to lhs
                     },
@@ -565,7 +707,8 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                         let mut gpr_args = vec![];
                         let func_symidx = &func_op.rc_func_symidx.as_ref_borrow();
                         asm_sect.annotate("saved register dumping to mem".to_string());
-                        if symtab.get(func_symidx).has_stack_pass_args(){
+                        // 间接调用时 func_symidx 是函数指针，不知道被调用函数的栈帧，也就没有栈传参
+                        if !func_op.is_indirect && symtab.get(func_symidx).has_stack_pass_args(){
                             let stack_pass_args = symtab.get(func_symidx).get_stack_pass_args().clone();
                             // if we need to pass args by stack (may because arg reg is not enough)
                             if stack_pass_args.len()!=0 {
@@ -600,15 +743,16 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                         }
                         asm_sect.annotate("caller-saved register dumped to mem".to_string());
                         asm_sect.annotate("arg load start".to_string());
-                        for (_idx,arg) in func_op.actual_arg_symidx_vec.iter().enumerate(){
+                        let fixed_arg_count = get_fixed_arg_count_of_call(func_op, symtab);
+                        let arg_ty_of = get_arg_ty;
+                        for (idx,arg) in func_op.actual_arg_symidx_vec.iter().enumerate(){
                             let arg = arg.as_ref_borrow();
-                            let ty = if arg.is_literal(){
-                                TypeDiscriminants::new_from_const_str(&arg.symbol_name)
-                            }else {
-                                symtab.get(&arg.to_src_symidx()).get_type().into()
-                            };
+                            let ty = arg_ty_of(&arg, symtab);
                             match ty {
-                                TypeDiscriminants::F32 => {
+                                _ if idx >= fixed_arg_count => {
+                                    gpr_args.push(arg)
+                                },
+                                TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                                     fpr_args.push(arg)
                                 },
                                 _ => {
@@ -616,6 +760,16 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 }
                             }
                         }   
+                        if func_op.is_indirect && (gpr_args.len() > 8 || fpr_args.len() > 8){
+                            return Err(anyhow!("间接调用不支持通过栈传递的参数: {:?}",instr_struct))
+                        }
+                        // 变长参数中放不进 a0-a7 的部分存到调用者栈帧底部预留的区域，即被调函数入口处的 0(sp) 开始，sp 不需要移动
+                        for (idx,arg) in gpr_args.iter().skip(8).enumerate(){
+                            let ty = arg_ty_of(arg, symtab);
+                            let value_reg = regtab.find_and_anonymous_occupy(arg, &ty, symtab, asm_sect, &mut default_store, &mut default_load)?;
+                            asm_sect.asm(Stores::new(8, value_reg.clone(), Register::SP, idx as isize * 8, value_reg.is_fpr()).into());
+                            regtab.unoccupied_reg(value_reg, symtab, asm_sect, &mut default_store)?;
+                        }
                         // for fpu arg 
                         {
                             // first make all args occupyied 
@@ -630,7 +784,17 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                             for (idx,arg) in gpr_args.iter().take(8).enumerate(){
                                 if REG_A_RANGE.contains(&(idx as u8)){
                                     let reg =Register::new_a(idx as u8);
-                                    regtab.anonymous_load_into(reg, arg, &TypeDiscriminants::I32, symtab, asm_sect, &mut default_store, &mut default_load)?;
+                                    if matches!(arg_ty_of(arg, symtab), TypeDiscriminants::F32 | TypeDiscriminants::F64){
+                                        // 变长参数中的 double 先装入 fpr ，再把比特位搬到整数寄存器
+                                        let fpr_reg = regtab.find_and_anonymous_occupy(arg, &TypeDiscriminants::F64, symtab, asm_sect, &mut default_store, &mut default_load)?;
+                                        regtab.anonymous_load_into(reg, arg, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut |_symidx,reg,_symtab,asm_sect,_regtab|{
+                                            asm_sect.asm(PseudoInstr::new_fmv_x_d(reg, fpr_reg.clone()).into());
+                                            Ok(())
+                                        })?;
+                                        regtab.unoccupied_reg(fpr_reg, symtab, asm_sect, &mut default_store)?;
+                                    }else{
+                                        regtab.anonymous_load_into(reg, arg, &TypeDiscriminants::I32, symtab, asm_sect, &mut default_store, &mut default_load)?;
+                                    }
                                 }
                             }   
                         }  
@@ -650,17 +814,26 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                             }
                         }
                         asm_sect.annotate("arg load ended\n".to_string());
-                        asm_sect.asm(PseudoInstr::new_call(Imm::new_global_label(func_op.rc_func_symidx.clone())).into());
+                        if func_op.is_indirect{
+                            // 参数寄存器都已装好，通过 t0 中的函数地址跳转
+                            let fn_ptr_ty = symtab.get(&func_symidx.to_src_symidx()).get_type().into();
+                            let fn_ptr_reg = regtab.anonymous_load_into(Register::new_t(0), func_symidx, &fn_ptr_ty, symtab, asm_sect, &mut default_store, &mut default_load)?;
+                            // t0 会被被调函数改写，不能让 regtab 记住它
+                            regtab.forget(fn_ptr_reg.clone(), symtab)?;
+                            asm_sect.asm(PseudoInstr::new_jalr(fn_ptr_reg).into());
+                        }else{
+                            asm_sect.asm(PseudoInstr::new_call(Imm::new_global_label(func_op.rc_func_symidx.clone())).into());
+                        }
                         match op_assigned_symidx{
                             Some(assigned_symidx) => {
                                 let assigned_symidx = assigned_symidx.as_ref_borrow();
                                 match symtab.get(&assigned_symidx.to_src_symidx()).get_type().into(){
-                                    TypeDiscriminants::I32 => {
+                                    TypeDiscriminants::I32 | TypeDiscriminants::U32 | TypeDiscriminants::I8 | TypeDiscriminants::I64 | TypeDiscriminants::Ptr64 => {
                                         let reg = Register::new_a(0);
                                         regtab.try_release_reg(reg.clone(), symtab, asm_sect, &mut default_store)?;
                                         regtab.set_freed_reg(reg, &assigned_symidx, symtab)?;
                                     },
-                                    TypeDiscriminants::F32 => {
+                                    TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                                         let reg =Register::new_fa(0);
                                         regtab.try_release_reg(reg.clone(), symtab, asm_sect, &mut default_store)?;
                                         regtab.set_freed_reg(reg, &assigned_symidx, symtab)?;
@@ -693,7 +866,7 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                         }else {
                                             symtab.get(&ret_sym.to_src_symidx()).get_type().into()
                                         }}{
-                                            TypeDiscriminants::F32 => {
+                                            TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                                                 Some(regtab.load_into(Register::new_fa(0), &ret_sym, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store,  &mut default_load)?)
                                             },
                                             _ => {
@@ -751,7 +924,11 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 }
                                 asm_sect.asm(PseudoInstr::new_j(Imm::new_local_label(rc_t2.clone())).into());
                             },
-                            super::nhwc_instr::JumpOp::Switch { cond: _, default: _, compared: _ } => todo!(),
+                            super::nhwc_instr::JumpOp::Switch { cond, default, compared } => {
+                                let cond = cond.as_ref_borrow().clone();
+                                let (default,compared) = (default.clone(),compared.clone());
+                                switch_dispatch(asm_sect, regtab, &cond, &default, &compared, cfg_node, cfg_graph, symtab)?;
+                            },
                             super::nhwc_instr::JumpOp::DirectJump { label_symidx: rc_label_symidx } => {
                                 let label_symidx = rc_label_symidx.as_ref_borrow();
                                 let &label_cfg_node = symtab.get(&label_symidx).get_label_cfg_node();
@@ -794,6 +971,123 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
                                 regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
                             },
+                            super::nhwc_instr::Trans::Fptoui { float_symidx } => {
+                                let float_symidx = float_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&float_symidx, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::U32, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Trans::new_fcvt_wu_s(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Uitofp { int_symidx } => {
+                                let int_symidx = int_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&int_symidx, &TypeDiscriminants::U32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Trans::new_fcvt_s_wu(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Sext { int_symidx } => {
+                                // i32 在寄存器中本来就是符号扩展的，sext.w 保证高位一致
+                                let int_symidx = int_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&int_symidx, &TypeDiscriminants::I32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(PseudoInstr::new_sext_w(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::ZextU32 { uint_symidx } => {
+                                // u32 在寄存器中是符号扩展的，需要先左移再逻辑右移清掉高 32 位
+                                let uint_symidx = uint_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&uint_symidx, &TypeDiscriminants::U32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Shifts::new_slli(rst_reg.clone(),val_reg.clone(),Imm::from_offset(32)).into());
+                                asm_sect.asm(Shifts::new_srli(rst_reg.clone(),rst_reg.clone(),Imm::from_offset(32)).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Trunc { long_symidx } => {
+                                // 截断到 32 位后同样保持符号扩展
+                                let long_symidx = long_symidx.as_ref_borrow();
+                                let lhs_type:TypeDiscriminants = symtab.get(&lhs.to_src_symidx()).get_type().into();
+                                let val_reg =regtab.find_and_occupy_reg(&long_symidx, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &lhs_type, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(PseudoInstr::new_sext_w(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::TruncI8 { int_symidx } => {
+                                // 保留低 8 位并做符号扩展
+                                let int_symidx = int_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&int_symidx, &TypeDiscriminants::I32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::I8, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Shifts::new_slli(rst_reg.clone(),val_reg.clone(),Imm::from_offset(56)).into());
+                                asm_sect.asm(Shifts::new_srai(rst_reg.clone(),rst_reg.clone(),Imm::from_offset(56)).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Fptosi64 { float_symidx } => {
+                                let float_symidx = float_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&float_symidx, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Trans::new_fcvt_l_s(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Sitofp64 { long_symidx } => {
+                                let long_symidx = long_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&long_symidx, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Trans::new_fcvt_s_l(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Fpext { float_symidx } => {
+                                let float_symidx = float_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&float_symidx, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::F64, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Trans::new_fcvt_d_s(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::Fptrunc { double_symidx } => {
+                                let double_symidx = double_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&double_symidx, &TypeDiscriminants::F64, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::F32, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(Trans::new_fcvt_s_d(rst_reg.clone(),val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::FptosiF64 { double_symidx } => {
+                                // 目标可能是 i32 u32 或 i64
+                                let double_symidx = double_symidx.as_ref_borrow();
+                                let lhs_type:TypeDiscriminants = symtab.get(&lhs.to_src_symidx()).get_type().into();
+                                let val_reg =regtab.find_and_occupy_reg(&double_symidx, &TypeDiscriminants::F64, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &lhs_type, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                match lhs_type{
+                                    TypeDiscriminants::I32 => asm_sect.asm(Trans::new_fcvt_w_d(rst_reg.clone(),val_reg.clone()).into()),
+                                    TypeDiscriminants::U32 => asm_sect.asm(Trans::new_fcvt_wu_d(rst_reg.clone(),val_reg.clone()).into()),
+                                    TypeDiscriminants::I64 => asm_sect.asm(Trans::new_fcvt_l_d(rst_reg.clone(),val_reg.clone()).into()),
+                                    _ => panic!("can't convert f64 to {:?}",lhs_type)
+                                }
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
+                            super::nhwc_instr::Trans::SitofpF64 { int_symidx } => {
+                                // 源可能是 i32 u32 或 i64
+                                let int_symidx = int_symidx.as_ref_borrow();
+                                let int_type:TypeDiscriminants = symtab.get(&int_symidx.to_src_symidx()).get_type().into();
+                                let val_reg =regtab.find_and_occupy_reg(&int_symidx, &int_type, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::F64, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                match int_type{
+                                    TypeDiscriminants::I32 => asm_sect.asm(Trans::new_fcvt_d_w(rst_reg.clone(),val_reg.clone()).into()),
+                                    TypeDiscriminants::U32 => asm_sect.asm(Trans::new_fcvt_d_wu(rst_reg.clone(),val_reg.clone()).into()),
+                                    TypeDiscriminants::I64 => asm_sect.asm(Trans::new_fcvt_d_l(rst_reg.clone(),val_reg.clone()).into()),
+                                    _ => panic!("can't convert {:?} to f64",int_type)
+                                }
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+                            },
                             super::nhwc_instr::Trans::Zext { bool_symidx } => {
                                 let bool_symidx = bool_symidx.as_ref_borrow();
                                 //b->i
@@ -805,9 +1099,14 @@ fn parse_funcs2riscv(cfg_graph:&mut CfgGraph, nhwc_instr_slab:&mut InstrSlab<Nhw
                                 regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
 
                             },
-                            super::nhwc_instr::Trans::Bitcast { rptr_symidx: _, rptr_type: _, lptr_type: _ } => {
-                                //p->p
-                                todo!();
+                            super::nhwc_instr::Trans::Bitcast { rptr_symidx, rptr_type: _, lptr_type: _ } => {
+                                //p->p 指针的位模式不变，只需要搬运
+                                let rptr_symidx = rptr_symidx.as_ref_borrow();
+                                let val_reg =regtab.find_and_occupy_reg(&rptr_symidx, &TypeDiscriminants::Ptr64, symtab, asm_sect, &mut default_store, &mut default_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                let rst_reg = regtab.find_and_occupy_reg(&lhs, &TypeDiscriminants::Ptr64, symtab, asm_sect, &mut default_store, &mut no_load).with_context(||format!("err when occupy reg {:?}",instr!(at instr in nhwc_instr_slab)))?;
+                                asm_sect.asm(PseudoInstr::new_reg_mv(rst_reg.clone(), val_reg.clone()).into());
+                                regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+                                regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
                             },
                         }
                     },
@@ -874,14 +1173,15 @@ pub fn load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,regtab:&mut RegTa
 /// reg value -> M[ptr_sym]
 /// require ptr_symidx to be symbol 
 /// reg alloc is finished in this scope 
-pub fn store_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx,val_symidx:&SymIdx, regtab:&mut RegTab,symtab:&mut SymTab)-> Result<()>{
+/// 存入的宽度和寄存器类别由 store 指令自己的 ptr_ty 决定，union 的不同成员可能共用同一个指针(例如被 gvn 合并)
+pub fn store_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx,ptr_ty:&Type,val_symidx:&SymIdx, regtab:&mut RegTab,symtab:&mut SymTab)-> Result<()>{
 
-    let ptr_ty = symtab.get(&ptr_symidx.to_src_symidx()).get_type();
-    let ele_ty = ptr_ty.to_deref_ptr_type().into();
+    let deref_ty = ptr_ty.to_deref_ptr_type();
+    let ele_ty = (&deref_ty).into();
 
-    let ptr_reg = regtab.find_and_occupy_reg(ptr_symidx, &ptr_ty.into(), symtab,asm_sect, &mut default_store, &mut default_load)?;
+    let ptr_reg = regtab.find_and_occupy_reg(ptr_symidx, &symtab.get(&ptr_symidx.to_src_symidx()).get_type().into(), symtab,asm_sect, &mut default_store, &mut default_load)?;
     let val_reg = regtab.find_and_occupy_reg( &val_symidx, &ele_ty, symtab,asm_sect, &mut default_store, &mut default_load)?;
-    _store_from_ptr(asm_sect, ptr_symidx, ptr_reg.clone(), val_reg.clone(), symtab)?;
+    _store_from_ptr(asm_sect, ptr_symidx, &deref_ty, ptr_reg.clone(), val_reg.clone())?;
     regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
     regtab.unoccupied_reg(ptr_reg,symtab,asm_sect,&mut default_store)?;
     Ok(())
@@ -889,14 +1189,15 @@ pub fn store_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx,val_symidx:&Sy
 
 // load the value from ptr symidx to value reg
 /// reg alloc is finished in this scope 
-pub fn load_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx,val_symidx:&SymIdx, regtab:&mut RegTab, symtab:&mut SymTab) ->Result<()>{
+/// 与 store_from_ptr 一样按照 load 指令自己的 ptr_ty 决定读取的宽度和寄存器类别
+pub fn load_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx,ptr_ty:&Type,val_symidx:&SymIdx, regtab:&mut RegTab, symtab:&mut SymTab) ->Result<()>{
 
-    let ptr_ty = symtab.get(&ptr_symidx.to_src_symidx()).get_type();
-    let ele_ty = ptr_ty.to_deref_ptr_type().into();
+    let deref_ty = ptr_ty.to_deref_ptr_type();
+    let ele_ty = (&deref_ty).into();
 
-    let ptr_reg = regtab.find_and_occupy_reg(ptr_symidx, &ptr_ty.into(), symtab,asm_sect,&mut default_store, &mut default_load)?;
-    let val_reg = regtab.find_and_occupy_reg( &val_symidx, &ele_ty, symtab,asm_sect,&mut default_store,&mut |symidx,val_reg,symtab,asm_sect,regtab|{
-        _load_from_ptr(asm_sect, ptr_symidx, ptr_reg.clone(), val_reg.clone(), symtab)?;
+    let ptr_reg = regtab.find_and_occupy_reg(ptr_symidx, &symtab.get(&ptr_symidx.to_src_symidx()).get_type().into(), symtab,asm_sect,&mut default_store, &mut default_load)?;
+    let val_reg = regtab.find_and_occupy_reg( &val_symidx, &ele_ty, symtab,asm_sect,&mut default_store,&mut |_symidx,val_reg,_symtab,asm_sect,_regtab|{
+        _load_from_ptr(asm_sect, &deref_ty, ptr_reg.clone(), val_reg.clone())?;
         Ok(())
     })?;
     regtab.unoccupied_reg(val_reg.clone(),symtab,asm_sect,&mut default_store)?;
@@ -927,6 +1228,17 @@ pub fn _load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,reg:Register,reg
                         regtab.unoccupied_reg(temp_reg,symtab,asm_sect,&mut default_store)?;
                         Ok(())
                     },
+                    TypeDiscriminants::F64 => {
+                        // f64 同样先把比特位 li 到 gpr ，再用 fmv.d.x 搬到 fpr
+                        assert!(reg.is_fpr());
+                        let temp_reg = regtab.find_and_anonymous_occupy(symidx, &TypeDiscriminants::I64, symtab, asm_sect, &mut default_store, &mut |symidx,temp_reg,_symtab,asm_sect,_regtab|{
+                            asm_sect.asm(PseudoInstr::new_li(temp_reg.clone(), Imm::new_literal(symidx.clone().as_rc())).into());
+                            Ok(())
+                        })?;
+                        asm_sect.asm(PseudoInstr::new_fmv_d_x(reg, temp_reg.clone()).into());
+                        regtab.unoccupied_reg(temp_reg,symtab,asm_sect,&mut default_store)?;
+                        Ok(())
+                    },
                     TypeDiscriminants::I32 => {
                         // i32 ... since riscv li instr has ensured the bitwidth will be ext from 32 to 64 
                         // no need sext.w
@@ -947,7 +1259,7 @@ pub fn _load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,reg:Register,reg
                 let size = symtab.get(&symidx.to_src_symidx()).get_type().get_size();
                 let ty = symtab.get(&symidx.to_src_symidx()).get_type().into();
                 let &symidx_offset2sp = match &ty{
-                    TypeDiscriminants::Array => {
+                    TypeDiscriminants::Array | TypeDiscriminants::Struct | TypeDiscriminants::Union => {
                         symtab.get(&symidx.to_src_symidx()).get_mem_offset2sp()
                     },
                     _ => {
@@ -955,7 +1267,7 @@ pub fn _load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,reg:Register,reg
                     }
                 };
                 match ty{
-                    TypeDiscriminants::F32 => {
+                    TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                         assert!(reg.is_fpr());
                         // because load is used after occupy, so you can always find reg of symidx, it should be deleted 
                         if let Some(reg_in_regtab) = symtab.reg_of_symidx(symidx)?{
@@ -979,7 +1291,7 @@ pub fn _load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,reg:Register,reg
                         }
                         Ok(())
                     },
-                    TypeDiscriminants::I32 | TypeDiscriminants::I1 => {
+                    TypeDiscriminants::I32 | TypeDiscriminants::U32 | TypeDiscriminants::I8 | TypeDiscriminants::I1 => {
                         // you should ext it by sext.w
                         assert!(!reg.is_fpr());
                         if let Some(reg_in_regtab) = symtab.reg_of_symidx(symidx)?{
@@ -1002,7 +1314,7 @@ pub fn _load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,reg:Register,reg
                         }
                         Ok(())
                     }
-                    TypeDiscriminants::Ptr64 => {
+                    TypeDiscriminants::Ptr64 | TypeDiscriminants::I64 => {
                         assert!(!reg.is_fpr());
                         if let Some(reg_in_regtab) = symtab.reg_of_symidx(symidx)?{
                             if reg_in_regtab != reg{
@@ -1023,7 +1335,7 @@ pub fn _load_sym_or_imm(asm_sect:&mut AsmSection,symidx:&SymIdx,reg:Register,reg
                         }
                         Ok(())
                     }
-                    TypeDiscriminants::Array => {
+                    TypeDiscriminants::Array | TypeDiscriminants::Struct | TypeDiscriminants::Union => {
                         // load for array is just caculate the head ptr of the array
                         assert!(!reg.is_fpr());
                         if symidx_offset2sp.is_legal_offset(){
@@ -1056,7 +1368,8 @@ pub fn _store_sym(asm_sect:&mut AsmSection, symidx:&SymIdx,value_reg:Register,re
     // assert!(symidx.index_ssa.is_none());
     let symidx_offset2sp = *symtab.get(&symidx).get_mem_offset2sp() + additional_mem_offset;
     let size = symtab.get(&symidx.to_src_symidx()).get_type().get_size();
-    if value_reg.is_fpr() ^ symtab.get(&symidx.to_src_symidx()).get_type().is_f_32(){
+    let is_float = symtab.get(&symidx.to_src_symidx()).get_type().is_f_32() || symtab.get(&symidx.to_src_symidx()).get_type().is_f_64();
+    if value_reg.is_fpr() ^ is_float{
         return Err(anyhow!("can't store symidx {:?}:{:?} from register {:?}",symidx,symtab.get(&symidx.to_src_symidx()).get_type(),value_reg))
     }
     if symidx_offset2sp.is_legal_offset(){
@@ -1075,10 +1388,11 @@ pub fn _store_sym(asm_sect:&mut AsmSection, symidx:&SymIdx,value_reg:Register,re
 }
 /// reg value -> M[ptr_sym]
 /// require value and pointer is ready in ptr_reg and val_reg
-pub fn _store_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx, ptr_reg:Register, val_reg:Register ,symtab:&mut SymTab)-> Result<()>{
-    let ele_size = symtab.get(&ptr_symidx.to_src_symidx()).get_type().to_deref_ptr_type().get_ele_size();
-    if val_reg.is_fpr() ^ symtab.get(&ptr_symidx.to_src_symidx()).get_type().to_deref_ptr_type().is_f_32(){
-        return Err(anyhow!("can't store symidx {:?}:{:?}'s pointed symidx to register {:?}",ptr_symidx,symtab.get(&ptr_symidx.to_src_symidx()).get_type(),val_reg))
+pub fn _store_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx, deref_ty:&Type, ptr_reg:Register, val_reg:Register)-> Result<()>{
+    // 指针本身作为值被存入内存时占 8 字节
+    let ele_size = if deref_ty.is_ptr_64() { deref_ty.get_mem_len() } else { deref_ty.get_ele_size() };
+    if val_reg.is_fpr() ^ (deref_ty.is_f_32() || deref_ty.is_f_64()){
+        return Err(anyhow!("can't store {:?} to {:?}'s pointed symidx from register {:?}",deref_ty,ptr_symidx,val_reg))
     }
     asm_sect.asm(Stores::new( ele_size,val_reg.clone(),ptr_reg, 0, val_reg.is_fpr()).into());
     Ok(())
@@ -1086,17 +1400,164 @@ pub fn _store_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx, ptr_reg:Regi
 
 // load the value from ptr symidx to value reg
 // require ptr_reg and val_reg is ready 
-pub fn _load_from_ptr(asm_sect:&mut AsmSection,ptr_symidx:&SymIdx,ptr_reg:Register, val_reg:Register ,symtab:&mut SymTab) ->Result<Register>{
+pub fn _load_from_ptr(asm_sect:&mut AsmSection,deref_ty:&Type,ptr_reg:Register, val_reg:Register) ->Result<Register>{
     // deref the ptr then get its ele size
-    let size = symtab.get(&ptr_symidx.to_src_symidx()).get_type().to_deref_ptr_type().get_ele_size();
-    let is_f32 = symtab.get(&ptr_symidx.to_src_symidx()).get_type().to_deref_ptr_type().is_f_32();
+    let size = if deref_ty.is_ptr_64() { deref_ty.get_mem_len() } else { deref_ty.get_ele_size() };
+    let is_float = deref_ty.is_f_32() || deref_ty.is_f_64();
 
-    asm_sect.asm(Loads::new(size, val_reg.clone(), ptr_reg.clone(), 0, is_f32)?.into());
+    asm_sect.asm(Loads::new(size, val_reg.clone(), ptr_reg.clone(), 0, is_float)?.into());
     Ok(val_reg)
 }
 
 
+/// switch 的各个目标都被 switch 所支配，所以进入 switch 时它们都还没有 regtab
+/// 这里统一 forget_all_temp 之后把同一份 regtab 交给所有目标，之后只使用 t0 t1 t2 做分派
+pub fn switch_dispatch(asm_sect:&mut AsmSection,regtab:&mut RegTab,cond:&SymIdx,default:&RcSymIdx,compared:&[ComparedPair],cfg_node:u32,cfg_graph:&mut CfgGraph,symtab:&mut SymTab) -> Result<()>{
+    let cond_ty:TypeDiscriminants = symtab.get(&cond.to_src_symidx()).get_type().into();
+    // 同 Br 一样先 occupy 再释放，保证 clone 出去的 regtab 里有 cond 这一项
+    let val_reg = regtab.find_and_occupy_reg(cond, &cond_ty, symtab, asm_sect, &mut default_store, &mut default_load)?;
+    regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+    regtab.forget_all_temp(asm_sect, symtab, &mut default_store)?;
+    let target_labels = compared.iter().map(|pair| pair.label.clone()).chain([default.clone()]).unique_by(|label| label.as_ref_borrow().clone()).collect_vec();
+    for rc_label in target_labels.iter(){
+        let &label_cfg_node = symtab.get(&rc_label.as_ref_borrow()).get_label_cfg_node();
+        if node!(at label_cfg_node in cfg_graph).has_regtab(){
+            panic!("target {:?} of switch in cfg_node {} has been visited before the switch",rc_label,cfg_node);
+        }
+        node_mut!(at label_cfg_node in cfg_graph).add_regtab(regtab.clone());
+    }
+    let val_reg = regtab.find_and_occupy_reg(cond, &cond_ty, symtab, asm_sect, &mut default_store, &mut default_load)?;
+    let (t0,t1,t2) = (Register::new_t(0),Register::new_t(1),Register::new_t(2));
+    asm_sect.asm(PseudoInstr::new_reg_mv(t0.clone(), val_reg.clone()).into());
+    regtab.unoccupied_reg(val_reg,symtab,asm_sect,&mut default_store)?;
+
+    // 按 case 值在寄存器中的比特位比较，U32 在寄存器中保持符号扩展
+    let mut pairs = compared.iter().map(|pair| match Value::from_symidx(&pair.compared.as_ref_borrow()){
+        Value::U32(Some(v)) => (v as i32 as isize, pair.label.clone()),
+        v => (v.as_i64() as isize, pair.label.clone()),
+    }).collect_vec();
+    pairs.sort_by_key(|(v,_)| *v);
+    if pairs.len() <= 3 {
+        switch_compare_chain(asm_sect, &t0, &t1, &pairs, default);
+        return Ok(());
+    }
+    let (min,max) = (pairs.first().unwrap().0, pairs.last().unwrap().0);
+    let range = max - min + 1;
+    if range <= 3*pairs.len() as isize && range <= 1024 {
+        // 跳转表 : t0 = cond - min ，无符号比较一次同时排除了 cond < min 和 cond > max
+        let table_label = SymIdx::new(cfg_node, "switch_table").as_rc();
+        if min != 0 {
+            asm_sect.asm(PseudoInstr::new_li(t1.clone(), Imm::new_literal_isize(min)).into());
+            if cond_ty == TypeDiscriminants::I64 {
+                asm_sect.asm(Arithmetic::new_sub(t0.clone(), t0.clone(), t1.clone()).into());
+            }else {
+                asm_sect.asm(Arithmetic::new_subw(t0.clone(), t0.clone(), t1.clone()).into());
+            }
+        }
+        asm_sect.asm(PseudoInstr::new_li(t1.clone(), Imm::new_literal_isize(range)).into());
+        asm_sect.asm(Branch::new_bgeu(t0.clone(), t1.clone(), Imm::new_local_label(default.clone())).into());
+        asm_sect.asm(PseudoInstr::new_lla(t1.clone(), Imm::new_local_label(table_label.clone())).into());
+        asm_sect.asm(Shifts::new_slli(t0.clone(), t0.clone(), Imm::from_offset(3)).into());
+        asm_sect.asm(Arithmetic::new_add(t1.clone(), t1.clone(), t0.clone()).into());
+        asm_sect.asm(Loads::new_ld(t2.clone(), t1.clone(), Imm::from_offset(0)).into());
+        asm_sect.asm(PseudoInstr::new_jr(t2.clone()).into());
+        asm_sect.rodata();
+        asm_sect.align(3);
+        asm_sect.label(Imm::new_local_label(table_label));
+        let mut iter = pairs.iter().peekable();
+        for v in min..=max{
+            match iter.next_if(|(compared,_)| *compared == v){
+                Some((_,label)) => asm_sect.dword(Imm::new_local_label(label.clone())),
+                None => asm_sect.dword(Imm::new_local_label(default.clone())),
+            }
+        }
+        asm_sect.text();
+    }else {
+        let mut label_count = 0;
+        switch_binary_search(asm_sect, &t0, &t1, &pairs, default, cfg_node, &mut label_count);
+    }
+    Ok(())
+}
+/// 依次比较，都不相等则跳到 default
+fn switch_compare_chain(asm_sect:&mut AsmSection,t0:&Register,t1:&Register,pairs:&[(isize,RcSymIdx)],default:&RcSymIdx){
+    for (v,label) in pairs{
+        asm_sect.asm(PseudoInstr::new_li(t1.clone(), Imm::new_literal_isize(*v)).into());
+        asm_sect.asm(Branch::new_beq(t0.clone(), t1.clone(), Imm::new_local_label(label.clone())).into());
+    }
+    asm_sect.asm(PseudoInstr::new_j(Imm::new_local_label(default.clone())).into());
+}
+/// pairs 已经按照 case 的值升序排列，对半查找
+fn switch_binary_search(asm_sect:&mut AsmSection,t0:&Register,t1:&Register,pairs:&[(isize,RcSymIdx)],default:&RcSymIdx,cfg_node:u32,label_count:&mut usize){
+    if pairs.len() <= 3 {
+        switch_compare_chain(asm_sect, t0, t1, pairs, default);
+        return;
+    }
+    let mid = pairs.len()/2;
+    let left_label = SymIdx::new(cfg_node, format!("switch_bs{}",label_count).leak()).as_rc();
+    *label_count += 1;
+    asm_sect.asm(PseudoInstr::new_li(t1.clone(), Imm::new_literal_isize(pairs[mid].0)).into());
+    asm_sect.asm(Branch::new_beq(t0.clone(), t1.clone(), Imm::new_local_label(pairs[mid].1.clone())).into());
+    asm_sect.asm(Branch::new_blt(t0.clone(), t1.clone(), Imm::new_local_label(left_label.clone())).into());
+    switch_binary_search(asm_sect, t0, t1, &pairs[mid+1..], default, cfg_node, label_count);
+    asm_sect.label(Imm::new_local_label(left_label));
+    switch_binary_search(asm_sect, t0, t1, &pairs[..mid], default, cfg_node, label_count);
+}
 /// you should unoccupy the register by yourself
+/// 位运算和移位，右操作数是字面量时使用 andi/ori/xori/slliw/sraiw 这类立即数指令
+pub fn bitwise_int_op(asm_sect:&mut AsmSection,regtab:& mut RegTab,lhs:&SymIdx, a:&SymIdx,b:&SymIdx,op:&ArithOp,symtab:&mut SymTab) ->Result<()>{
+    // i64 使用不带 w 后缀的移位指令，其余整数类型都按 i32 处理
+    let is_64 = symtab.get(&lhs.to_src_symidx()).get_type().is_i_64();
+    let vartype = if is_64 { TypeDiscriminants::I64 } else { TypeDiscriminants::I32 };
+    let is_shift = matches!(op, ArithOp::Shl { .. } | ArithOp::Ashr { .. } | ArithOp::Lshr { .. });
+    // and or xor 满足交换律，把字面量换到右边
+    let (a,b) = if !is_shift && a.is_literal() && !b.is_literal() { (b,a) } else { (a,b) };
+    let op_imm = if b.is_literal(){
+        let imm = if is_64 { Value::from_symidx(b).as_i64() as isize } else { Value::from_symidx(b).as_i32() as isize };
+        // 移位量只取低 5 位(i64 取低 6 位)，和 sllw/sraw 以及 sll/sra 保持一致
+        if is_shift { Some(imm & if is_64 { 63 } else { 31 }) } else if imm.is_legal_offset() { Some(imm) } else { None }
+    }else { None };
+    let val_reg1= regtab.find_and_occupy_reg(a, &vartype, symtab, asm_sect, &mut default_store, &mut default_load)?;
+    match op_imm{
+        Some(imm) => {
+            let rst_reg= regtab.find_and_occupy_reg(lhs, &vartype, symtab, asm_sect, &mut default_store, &mut no_load)?;
+            let imm = Imm::from_offset(imm);
+            asm_sect.asm(match op{
+                ArithOp::BitAnd { .. } => Logical::new_andi(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::BitOr { .. } => Logical::new_ori(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::BitXor { .. } => Logical::new_xori(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::Shl { .. } if is_64 => Shifts::new_slli(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::Ashr { .. } if is_64 => Shifts::new_srai(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::Lshr { .. } if is_64 => Shifts::new_srli(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::Shl { .. } => Shifts::new_slliw(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::Ashr { .. } => Shifts::new_sraiw(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                ArithOp::Lshr { .. } => Shifts::new_srliw(rst_reg.clone(), val_reg1.clone(), imm).into(),
+                _ => panic!("{:?} is not a bitwise op",op),
+            });
+            regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
+            regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+        },
+        None => {
+            let val_reg2= regtab.find_and_occupy_reg(b, &vartype, symtab, asm_sect, &mut default_store, &mut default_load)?;
+            let rst_reg= regtab.find_and_occupy_reg(lhs, &vartype, symtab, asm_sect, &mut default_store, &mut no_load)?;
+            asm_sect.asm(match op{
+                ArithOp::BitAnd { .. } => Logical::new_and(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::BitOr { .. } => Logical::new_or(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::BitXor { .. } => Logical::new_xor(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::Shl { .. } if is_64 => Shifts::new_sll(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::Ashr { .. } if is_64 => Shifts::new_sra(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::Lshr { .. } if is_64 => Shifts::new_srl(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::Shl { .. } => Shifts::new_sllw(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::Ashr { .. } => Shifts::new_sraw(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                ArithOp::Lshr { .. } => Shifts::new_srlw(rst_reg.clone(), val_reg1.clone(), val_reg2.clone()).into(),
+                _ => panic!("{:?} is not a bitwise op",op),
+            });
+            regtab.unoccupied_reg(val_reg1,symtab,asm_sect,&mut default_store)?;
+            regtab.unoccupied_reg(val_reg2,symtab,asm_sect,&mut default_store)?;
+            regtab.unoccupied_reg(rst_reg,symtab,asm_sect,&mut default_store)?;
+        },
+    }
+    Ok(())
+}
 pub fn magic_i32_mul(asm_sect:&mut AsmSection,regtab:& mut RegTab,rst_reg:Register, a:&SymIdx,b:&SymIdx,w:bool ,symtab:&mut SymTab) ->Result<()>{
     let vartype = TypeDiscriminants::I32;
     if a.is_literal() && b.is_literal(){
@@ -1149,4 +1610,352 @@ pub fn magic_i32_mul(asm_sect:&mut AsmSection,regtab:& mut RegTab,rst_reg:Regist
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::{build_pipeline, toolkit::pass_manager::PassManager, Args, OptLevel};
+
+    /// 以 -O0 的 pass 顺序把 code 编译到汇编，返回汇编文本
+    fn compile_to_asm(name:&str, code:&str) -> anyhow::Result<String> {
+        compile_to_asm_at(name, code, OptLevel::O0)
+    }
+
+    /// 按 main 中给定优化等级的 pass 列表把 code 编译到汇编，返回汇编文本
+    fn compile_to_asm_at(name:&str, code:&str, optimize:OptLevel) -> anyhow::Result<String> {
+        compile_to_asm_with_args(name, code, Args { optimize, ..Default::default() })
+    }
+
+    /// 输入输出文件由这里指定，其余选项取自 args
+    fn compile_to_asm_with_args(name:&str, code:&str, args:Args) -> anyhow::Result<String> {
+        let (pass_manager, rst) = run_passes(name, code, args);
+        rst.map(|_| pass_manager.ctx.asm_structure.dump(false))
+    }
+
+    /// 执行 args 对应的 pass 列表，返回 PassManager 以便检查各个阶段的中间结果
+    fn run_passes(name:&str, code:&str, args:Args) -> (PassManager, anyhow::Result<()>) {
+        let path = std::env::temp_dir().join(format!("{}_{:?}_{}.c", name, args.optimize, std::process::id()));
+        let output = path.with_extension("s");
+        fs::write(&path, code).unwrap();
+        let args = Args { input:path.clone(), output:output.clone(), parser:"antlr".to_string(), ..args };
+        let passes = build_pipeline(&args);
+        let mut pass_manager = PassManager::new(args);
+        for pass in passes {
+            pass_manager.add_pass(pass);
+        }
+        let rst = pass_manager.execute_passes();
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&output);
+        (pass_manager, rst)
+    }
+
+    /// 内层作用域的 struct S 遮蔽外层的 struct S 时，成员按各自作用域中的定义查找
+    #[test]
+    fn test_shadowed_struct_tag() {
+        let asm = compile_to_asm("shadowed_struct_tag", "struct S { int x; int y; };\nint main(){ struct S a; a.x = 1; { struct S { int z; }; struct S b; b.z = 2; putint(b.z); } putint(a.x); return 0; }\n");
+        assert!(asm.is_ok());
+    }
+
+    /// union 的成员按成员自己的类型 load/store ，float 成员写入后可以作为 int 成员读出
+    #[test]
+    fn test_union_type_punning() {
+        let asm = compile_to_asm("union_type_punning", "union U { float f; int i; };\nint main(){ union U u; u.f = 1.0; putint(u.i); u.i = 3; putfloat(u.f); return 0; }\n").unwrap();
+        assert!(asm.contains("fsw") && asm.contains("flw"));
+    }
+
+    /// struct 之间整体赋值，包括局部变量，全局变量以及声明时的初始化
+    #[test]
+    fn test_struct_assignment() {
+        let asm = compile_to_asm("struct_assignment", "struct P { int x; float y; int a[3]; };\nstruct P g;\nint main(){ struct P x; struct P y; x.x = 1; x.y = 2.0; x.a[2] = 5; y = x; g = y; struct P z = g; putint(z.x); putfloat(y.y); putint(g.a[2]); return 0; }\n");
+        assert!(asm.is_ok());
+    }
+
+    /// 局部 float 数组用整数初值初始化时，每个元素按 f32 store
+    #[test]
+    fn test_float_array_initializer() {
+        let asm = compile_to_asm("float_array_initializer", "int main(){ float a[10][5]={{1,2,3,4,5}}; putfarray(5, a[0]); return 0; }\n").unwrap();
+        assert!(asm.contains("fsw"));
+    }
+
+    /// -O2 起 while 循环旋转为被 if 保护的 do while ，条件在循环体之后求值，-O1 不旋转
+    #[test]
+    fn test_while_loop_rotation() {
+        let code = "int main(){ int i = 0; int s = 0; while (i < 10) { if (i == 5) { i = i + 1; continue; } if (s > 100) break; s = s + i; i = i + 1; } putint(s); return 0; }\n";
+        let o1_asm = compile_to_asm_at("while_loop_rotation", code, OptLevel::O1).unwrap();
+        assert!(o1_asm.contains(".while.head") && !o1_asm.contains(".dowhile.cond"));
+        let o2_asm = compile_to_asm_at("while_loop_rotation", code, OptLevel::O2).unwrap();
+        assert!(o2_asm.contains(".dowhile.cond") && !o2_asm.contains(".while.head"));
+        // 循环体以 break 结尾时旋转后的条件节点不可达，嵌套的循环也要能编译
+        let nested_code = "int main(){ int a = 1; while (a) { int i = 0; while (i < 3) { a = a + 1; i = i + 1; } break; } return a; }\n";
+        assert!(compile_to_asm_at("while_loop_rotation_nested", nested_code, OptLevel::O3).is_ok());
+    }
+
+    /// switch 的条件先做整数提升，case 的值转换成条件的类型，unsigned 的 case 按符号扩展后的比特位比较
+    #[test]
+    fn test_switch_on_long_and_unsigned() {
+        let asm = compile_to_asm("switch_on_long", "int f(long x){ switch(x){ case 5000000000: return 1; case 2: return 2; default: return 3; } }\nint main(){ return f(5000000000); }\n").unwrap();
+        assert!(asm.contains("li      t1, 5000000000"));
+        let asm = compile_to_asm("switch_on_unsigned", "int g(unsigned u){ switch(u){ case 7u: return 1; case 4294967295u: return 2; default: return 3; } }\nint main(){ return g(7u); }\n").unwrap();
+        assert!(asm.contains("li      t1, 7") && asm.contains("li      t1, -1"));
+        let asm = compile_to_asm("switch_on_char", "int h(char c){ switch(c){ case 'a': return 1; case 98: return 2; } return 0; }\nint main(){ return h(97); }\n").unwrap();
+        assert!(asm.contains("li      t1, 97"));
+    }
+
+    /// 重复的 case 值报告为错误，不再在生成 nhwc ir 时 panic
+    #[test]
+    fn test_duplicate_case_label() {
+        assert!(compile_to_asm("duplicate_case_label", "int main(){ long x = getint(); switch(x){ case 1: x = 2; break; case 2-1: x = 3; break; default: break; } return x; }\n").is_err());
+    }
+
+
+    /// `f()` 声明的函数参数未指定，调用时传入任意个数的参数都不报错；参数个数不符的带原型的调用返回错误而不是 panic
+    #[test]
+    fn test_call_without_prototype() {
+        assert!(compile_to_asm("call_without_prototype", "int none(){ return 1; }\nint main(){ none(); return none(3); }\n").is_ok());
+        assert!(compile_to_asm("call_with_wrong_arg_count", "int one(void){ return 1; }\nint main(){ return one(3); }\n").is_err());
+    }
+
+
+    /// -Werror 时警告作为错误返回，不再 panic
+    #[test]
+    fn test_werror_fails_without_panic() {
+        let code = "int main(){ return 0; putint(1); }\n";
+        let warnings = |flags:&[&str]| Args { warnings:flags.iter().map(|flag| flag.to_string()).collect(), ..Default::default() };
+        assert!(compile_to_asm_with_args("wall", code, warnings(&["all"])).is_ok());
+        assert!(compile_to_asm_with_args("wall_werror", code, warnings(&["all", "error"])).is_err());
+    }
+
+
+    /// 两侧都没有副作用的条件运算符生成 select ，不产生分支；有副作用时展开成菱形，只求值被选中的一侧
+    #[test]
+    fn test_conditional_select_and_diamond() {
+        let header = "int g;\nint inc(){ g = g + 1; return g; }\n";
+        let asm = compile_to_asm("conditional_select", &format!("{}int main(){{ int a = getint(); int b = getint(); putint(a > b ? a : b); return 0; }}\n", header)).unwrap();
+        assert!(!asm.contains(".cond_true"));
+        let asm = compile_to_asm("conditional_diamond", &format!("{}int main(){{ int a = getint(); int b = getint(); putint(a > b ? inc() : b); return 0; }}\n", header)).unwrap();
+        assert!(asm.contains(".cond_true") && asm.contains(".cond_false"));
+    }
+
+    /// for 的初始化、条件和步进中都可以出现有副作用的条件运算符
+    #[test]
+    fn test_conditional_in_for_header() {
+        let code = "int g;\nint inc(){ g = g + 1; return g; }\nint main(){ int s = 0; int i; for (i = g > 0 ? inc() : 0; i < (g < 3 ? inc() : 5); i = i + (i > 2 ? inc() : 1)) { s = s + i; } putint(s); return 0; }\n";
+        for optimize in [OptLevel::O0, OptLevel::O1, OptLevel::O2] {
+            let asm = compile_to_asm_at("conditional_in_for_header", code, optimize).unwrap();
+            assert_eq!(asm.matches("call    inc").count(), 3);
+        }
+    }
+
+    /// 常量子表达式在 eval_et 中折叠，-O0 下也不会生成对应的运算指令
+    #[test]
+    fn test_constant_folding_in_eval_et() {
+        let asm = compile_to_asm("constant_folding", "int main(){ putint((1 << 4) * 3 + (7 & 5) - 10 / 2 ? 2 * 3 + 4 : 0); return 0; }\n").unwrap();
+        assert!(asm.contains("li      a0, 10"));
+        assert!(!asm.contains("mul") && !asm.contains("div") && !asm.contains("sll") && !asm.contains(".cond_true"));
+    }
+
+
+    /// do while 循环体的头不与之前的 bb 合并，continue 跳到条件，break 跳出循环
+    #[test]
+    fn test_do_while_with_break_and_continue() {
+        let code = "int main(){ int i = 0; int s = 0; do { i = i + 1; if (i == 2) continue; if (i > 5) break; s = s + i; } while (i < 10); putint(s); return 0; }\n";
+        for optimize in [OptLevel::O0, OptLevel::O2] {
+            let asm = compile_to_asm_at("do_while_with_break_and_continue", code, optimize).unwrap();
+            assert!(asm.contains(".dowhile.cond") && asm.contains(".dowhile.exit"));
+            // 进入循环和回边各有一次跳到循环体的头
+            assert!(asm.matches("j       .dowhile.body").count() >= 2);
+        }
+    }
+
+
+    /// 括号省略：不对齐的内层大括号只初始化一个标量，全局数组的初值压缩成 .word/.zero
+    #[test]
+    fn test_array_brace_elision_and_zero_runs() {
+        let asm = compile_to_asm("array_brace_elision", "int a[2][3][4] = {1, {2,3}, {{4},5}};\nint b[4][2] = {{1}, 2, 3, {4}, 5};\nint z[300] = {0, 0, 7};\nint main(){ int l[2][3][4] = {1, {2,3}, {{4},5}}; return a[0][0][2] + l[0][0][2] + b[1][1]; }\n").unwrap();
+        assert!(asm.contains("a:\n    .word 1\n    .word 2\n    .word 4\n    .zero 84\n"));
+        assert!(asm.contains("b:\n    .word 1\n    .zero 4\n    .word 2\n    .word 3\n    .word 4\n    .zero 4\n    .word 5\n    .zero 4\n"));
+        assert!(asm.contains("z:\n    .zero 8\n    .word 7\n    .zero 1188\n"));
+    }
+
+    /// 多出来的初值报告为错误
+    #[test]
+    fn test_excess_array_initializer() {
+        assert!(compile_to_asm("excess_global_initializer", "int g[2] = {1, 2, 3};\nint main(){ return g[0]; }\n").is_err());
+        assert!(compile_to_asm("excess_local_initializer", "int main(){ int a[2][2] = {1, 2, 3, 4, 5}; return a[0][0]; }\n").is_err());
+    }
+
+
+    /// 取出 asm 中某个函数从标签到下一个符号之前的那一段，ret 之后还可能有这个函数的 bb
+    fn func_body<'a>(asm:&'a str, func_name:&str) -> &'a str {
+        let body = asm.split(&format!("\n{}:\n", func_name)).nth(1).unwrap();
+        &body[..["\n    .type ", "\n.section"].iter().filter_map(|end| body.find(end)).min().unwrap_or(body.len())]
+    }
+
+    /// 指针加整数按所指类型的大小缩放，函数可以返回指针
+    #[test]
+    fn test_pointer_arithmetic_scaling() {
+        let code = "long *lp(long *r, int n);\nlong *lp(long *r, int n){ return r + n; }\nint *ip(int *q, int n){ return q + n; }\nlong arr[4];\nint main(){ int x = 1; int *p = &x; *p = 3; int a[4]; *ip(a, 1) = 2; *lp(arr, 2) = 7; return x + a[1] + *(p + 0); }\n";
+        for optimize in [OptLevel::O0, OptLevel::O2] {
+            let asm = compile_to_asm_at("pointer_arithmetic_scaling", code, optimize).unwrap();
+            let (lp, ip) = (func_body(&asm, "lp"), func_body(&asm, "ip"));
+            assert!(lp.lines().any(|l| l.starts_with("    slli") && l.ends_with(",3")) && !lp.contains(",2\n"));
+            assert!(ip.lines().any(|l| l.starts_with("    slli") && l.ends_with(",2")));
+        }
+    }
+
+
+    /// 位运算和移位在常量时折叠，有立即数时选 andi/slliw/sraiw
+    #[test]
+    fn test_bitwise_and_shift_ops() {
+        let asm = compile_to_asm("bitwise_and_shift_ops", "int f(int a){ return (a & 15) | (a << 3) ^ (a >> 2); }\nint g(int a, int b){ return (a & b) | (a << b) ^ (a >> b) ^ ~a; }\nint main(){ putint(f(getint())); putint(g(3, 4)); putint((12 & 10) | (1 << 5) ^ (64 >> 2)); return 0; }\n").unwrap();
+        let f = func_body(&asm, "f");
+        assert!(f.contains("andi    a1,a0,15") && f.contains("slliw a2,a0,3") && f.contains("sraiw a3,a0,2"));
+        let g = func_body(&asm, "g");
+        assert!(g.contains("and     ") && g.contains("sllw ") && g.contains("sraw ") && g.contains("xori    a1,a0,-1"));
+        assert!(func_body(&asm, "main").contains("li      a0, 56"));
+    }
+
+
+    /// unsigned 的参数和返回值也是 U32 ，除法、取余、右移和比较都选无符号的指令
+    #[test]
+    fn test_unsigned_div_shift_and_compare() {
+        let code = "unsigned int ud(unsigned int a, unsigned int b){ return a / b + a % b + (a >> 3); }\nint ult(unsigned int a, unsigned int b){ if (a < b) return 1; return a >= b; }\nint main(){ putint(ud(getint(), 3)); putint(ult(getint(), 3)); return 0; }\n";
+        for optimize in [OptLevel::O0, OptLevel::O2] {
+            let asm = compile_to_asm_at("unsigned_div_shift_and_compare", code, optimize).unwrap();
+            let ud = func_body(&asm, "ud");
+            assert!(ud.contains("divuw") && ud.contains("remuw") && ud.contains("srliw") && !ud.contains("divw") && !ud.contains("sraiw"));
+            let ult = func_body(&asm, "ult");
+            assert!(ult.contains("sltu") && !ult.contains("slt "));
+        }
+    }
+
+
+    /// long 用 64 位的指令和 ld/sd ，大立即数用一条 li 装入，窄化为 int 时 sext.w
+    #[test]
+    fn test_long_arith_and_large_literal() {
+        let code = "long g = 5000000000;\nlong mul3(long a){ return a * 3000000000 + g; }\nlong idx(long *p, long i){ return p[i] / i; }\nint main(){ long a = getint(); long arr[3]; arr[1] = mul3(a); int n = arr[1]; putint(n); putint(idx(arr, 1)); return 0; }\n";
+        let asm = compile_to_asm("long_arith_and_large_literal", code).unwrap();
+        assert!(asm.contains("g:\n    .dword 5000000000\n"));
+        let mul3 = func_body(&asm, "mul3");
+        assert!(mul3.contains("li      a1, 3000000000") && mul3.contains("    mul     ") && mul3.contains("    add     ") && !mul3.contains("mulw") && !mul3.contains("ADDW"));
+        let idx = func_body(&asm, "idx");
+        assert!(idx.lines().any(|l| l.starts_with("    ld      ") && !l.contains("(sp)")) && idx.contains("    div     ") && !idx.contains("divw"));
+        assert!(func_body(&asm, "main").contains("sext.w"));
+    }
+
+
+    /// double 选 .d 指令，float 参数先 fcvt.d.s 提升，返回值在 fa0 中
+    #[test]
+    fn test_double_instruction_selection() {
+        let code = "double gd = 2.5l;\ndouble h(double x, float y){ return x * y + 1.5l; }\nint main(){ double d = getfloat(); float f = 2.0; double arr[2]; arr[1] = h(d, f); putfloat(arr[1]); return d > 1.0l; }\n";
+        let asm = compile_to_asm("double_instruction_selection", code).unwrap();
+        assert!(asm.contains("gd:\n    .dword 4612811918334230528\n"));
+        let h = func_body(&asm, "h");
+        assert!(h.contains("fcvt.d.s") && h.contains("fmul.d") && h.contains("fadd.d") && h.contains("fmv.d.x") && h.contains("fld     fa0,"));
+        let main = func_body(&asm, "main");
+        assert!(main.contains("fsd     ") && main.contains("fcvt.s.d") && main.contains("flt.d"));
+    }
+
+
+    /// 字符串字面量转义后放进 .rodata ，相同的只放一份，char 用 lb/sb 按字节读写
+    #[test]
+    fn test_string_literal_rodata() {
+        let code = "char s[6] = \"hi\\n\";\nint main(){ char c = s[1]; s[0] = c; putf(\"a=%d\\n\", 1); putf(\"a=%d\\n\", 2); putf(\"b\\t\\\"%d\\\"\\n\", 3); return c; }\n";
+        let asm = compile_to_asm("string_literal_rodata", code).unwrap();
+        assert!(asm.contains("s:\n    .byte 104\n    .byte 105\n    .byte 10\n    .zero 3\n"));
+        assert!(asm.contains(".section .rodata"));
+        assert_eq!(asm.matches(".asciz \"a=%d\\012\"").count(), 1);
+        assert!(asm.contains(".asciz \"b\\011\\042%d\\042\\012\""));
+        let main = func_body(&asm, "main");
+        assert_eq!(main.matches("la      a0, str.0").count(), 2);
+        assert!(main.contains("    lb      ") && main.contains("    sb      "));
+    }
+
+
+    /// && 和 || 按短路求值生成分支，右边的数组访问和函数调用只在需要时执行
+    #[test]
+    fn test_short_circuit_as_control_flow() {
+        let code = "int g;\nint inc(){ g = g + 1; return g; }\nint main(){ int n = getint(); int a[4]; int i = getint(); if (i < 4 && a[i]) putint(1); if (n > 0 || inc()) putint(2); int b = n && inc(); putint(b); return 0; }\n";
+        let asm = compile_to_asm("short_circuit_as_control_flow", code).unwrap();
+        let main = func_body(&asm, "main");
+        // i < 4 为假时不读 a[i]
+        let (before_rhs, _) = main.split_once("\n.branch_short_circuit_p_true_").unwrap();
+        assert!(before_rhs.contains("slt ") && !before_rhs.contains("lw      "));
+        // n > 0 为假时才调用 inc
+        assert!(main.split_once("\n.branch_short_circuit_p_false_").unwrap().1.contains("call    inc"));
+        // 作为值的 n && inc() 在汇合处取得结果
+        assert!(main.split_once("\n.logic_short_circuit_true_").unwrap().1.contains("call    inc"));
+        assert!(!main.contains("    and     ") && !main.contains("    or      "));
+    }
+
+
+    /// nhwc 指令带着生成它的表达式或语句在源代码中的位置
+    #[test]
+    fn test_src_loc_on_nhwc_instrs() {
+        let (pass_manager, rst) = run_passes("src_loc_on_nhwc_instrs", "int main(){\n  int a = getint();\n  putint(a / 2);\n  return 0;\n}\n", Args::default());
+        rst.unwrap();
+        let instr_slab = &pass_manager.ctx.nhwc_instr_slab;
+        let loc_of = |pat:&str| instr_slab.iter().find(|(_, instr)| format!("{:?}", instr).contains(pat)).and_then(|(_, instr)| instr.get_op_src_loc()).map(|src_loc| (src_loc.file.ends_with(".c"), src_loc.line, src_loc.col)).unwrap();
+        assert_eq!(loc_of("Call i32 getint"), (true, 2, 3));
+        assert_eq!(loc_of("Call void putint"), (true, 3, 3));
+        // 除法指令记录的是运算符的位置
+        assert_eq!(loc_of("Div i32"), (true, 3, 12));
+    }
+
+
+    /// 枚举常量折叠为 I32 字面量，可以用作数组的维度和 case 标签，enum 类型的变量当作 int
+    #[test]
+    fn test_enum_in_case_labels_and_array_dims() {
+        let code = "enum Color { RED, GREEN = 5, BLUE };\nint tab[BLUE];\nint f(enum Color c){ switch (c) { case RED: return 10; case GREEN: return 20; case BLUE: return 30; default: return -1; } }\nint main(){ enum Color c = BLUE; putint(f(c)); return BLUE; }\n";
+        let asm = compile_to_asm("enum_in_case_labels_and_array_dims", code).unwrap();
+        assert!(asm.contains("tab:\n    .zero 24\n"));
+        let f = func_body(&asm, "f");
+        assert!(f.contains("li      t1, 0\n") && f.contains("li      t1, 5\n") && f.contains("li      t1, 6\n"));
+        assert_eq!(func_body(&asm, "main").matches("li      a0, 6\n").count(), 2);
+    }
+
+
+    /// 通过函数指针和函数指针数组的调用生成 jalr ，被调函数可能改写全局变量，之后要重新读取
+    #[test]
+    fn test_indirect_call_through_fn_ptr() {
+        let code = "int g;\nint add1(int x){ g = g + 1; return x + 1; }\nint dbl(int x){ return x * 2; }\nint apply(int (*fp)(int), int v){ return fp(v); }\nint main(){ int (*tab[2])(int) = {add1, dbl}; int (*p)(int) = dbl; int i = getint(); putint(tab[i](3)); putint(p(4)); putint(apply(add1, 5)); return g; }\n";
+        for optimize in [OptLevel::O0, OptLevel::O1, OptLevel::O2] {
+            let asm = compile_to_asm_at("indirect_call_through_fn_ptr", code, optimize).unwrap();
+            assert!(func_body(&asm, "apply").contains("jalr    x1, t0, 0"));
+            let main = func_body(&asm, "main");
+            assert_eq!(main.matches("jalr    x1, t0, 0").count(), 2);
+            assert!(main.contains("la      a0, add1\n    li      a1, 5\n    call    apply"));
+            assert!(main.rsplit_once("jalr").unwrap().1.contains("la      a0, g"));
+        }
+    }
+
+
+    /// 可变参数按 LP64D 传递：float 提升为 double 后放进整数寄存器，a7 之后的参数按 8 字节放在栈顶
+    #[test]
+    fn test_variadic_call_lp64d() {
+        let code = "int sum(int n, ...);\nint main(){ float f = 1.5; putf(\"%d %f %d %d %d %d %d %d %d\\n\", 1, f, 3, 4, 5, 6, 7, 8, 9); return sum(2, 10, 20); }\n";
+        let asm = compile_to_asm("variadic_call_lp64d", code).unwrap();
+        let main = func_body(&asm, "main");
+        assert!(main.contains("fcvt.d.s") && main.contains("fmv.x.d a2, fa0"));
+        assert!(main.contains("li      a0, 8\n    sd      a0,0(sp)\n    li      a1, 9\n    sd      a1,8(sp)\n"));
+        assert!(main.contains("li      a7, 7\n    call    putf"));
+        assert!(main.contains("li      a2, 20\n    call    sum"));
+    }
+
+
+    /// static 局部变量各自有一个不导出的标签并只初始化一次，static 的函数和全局变量不加 .globl ，extern 声明不分配空间
+    #[test]
+    fn test_static_and_extern_storage() {
+        let code = "extern int ext;\nstatic int hidden = 3;\nstatic int helper(int x){ return x + hidden; }\nint counter(){ static int n = 10; n = n + 1; return n; }\nint other(){ static int n; n = n + 2; return n; }\nint main(){ putint(counter()); putint(counter()); putint(other()); return helper(ext); }\n";
+        let asm = compile_to_asm("static_and_extern_storage", code).unwrap();
+        let static_local_labels:Vec<_> = asm.lines().filter(|l| l.starts_with("n.") && l.ends_with(':')).collect();
+        assert_eq!(static_local_labels.len(), 2);
+        assert!(static_local_labels.iter().all(|label| asm.contains(&format!("{}\n    .word ", label)) && !asm.contains(&format!(".globl {}", label.trim_end_matches(':')))));
+        assert!(asm.contains("hidden:\n    .word 3\n") && !asm.contains(".globl hidden") && !asm.contains(".globl helper"));
+        assert!(asm.contains(".globl counter") && asm.contains("la      a0, ext") && !asm.contains("ext:"));
+        // 初值放在数据段中，counter 里没有每次调用都执行的初始化
+        assert!(!func_body(&asm, "counter").contains(", 10\n"));
+    }
+
+}
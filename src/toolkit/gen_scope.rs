@@ -1,17 +1,28 @@
 
 use ahash::HashMap;
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
 
-use crate::toolkit::ast_node::AstTree;
+use crate::toolkit::ast_node::{find_dfs_rule_ast, find_single_constant_text, get_ptr_depth_of_declarator, is_typedef_decl, AstTree};
 
+use crate::antlr_parser::clexer::{Identifier, LeftParen};
 use crate::antlr_parser::cparser::{
-    RULE_blockItem, RULE_blockItemList, RULE_breakpointStatement, RULE_constantExpression, RULE_declaration, RULE_declarator, RULE_directDeclarator, RULE_expression, RULE_expressionStatement, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forCondition, RULE_forIterationStatement, RULE_forMidExpression, RULE_ifSelection, RULE_iterationStatement, RULE_jumpStatement, RULE_labeledStatement, RULE_parameterTypeList, RULE_selectionStatement, RULE_statement, RULE_switchSelection, RULE_whileIterationStatement
+    RULE_abstractDeclarator, RULE_assignmentExpression, RULE_blockItem, RULE_blockItemList, RULE_breakpointStatement, RULE_constantExpression, RULE_declaration, RULE_declarationSpecifier, RULE_declarationSpecifiers, RULE_declarationSpecifiers2, RULE_declarator, RULE_directDeclarator, RULE_expression, RULE_expressionStatement, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forCondition, RULE_forIterationStatement, RULE_forMidExpression, RULE_ifSelection, RULE_initDeclarator, RULE_initDeclaratorList, RULE_iterationStatement, RULE_jumpStatement, RULE_labeledStatement, RULE_parameterDeclaration, RULE_parameterList, RULE_parameterTypeList, RULE_selectionStatement, RULE_statement, RULE_structDeclarationList, RULE_structOrUnionSpecifier, RULE_switchSelection, RULE_typeSpecifier, RULE_typedefName, RULE_whileIterationStatement, RULE_doWhileIterationStatement
 };
-use crate::{add_node, add_node_with_edge, debug_info_red, debug_info_yellow, direct_child_node, direct_child_nodes, direct_parent_node, rule_id, RULE_compoundStatement, RULE_functionDefinition};
+use crate::{add_node, add_node_with_edge, add_symbol, debug_info_red, debug_info_yellow, direct_child_node, direct_child_nodes, direct_parent_node, reg_field_for_struct, rule_id, RULE_compoundStatement, RULE_functionDefinition};
 use crate::{find, find_nodes, node};
 
 use super::cfg_node::{CfgGraph};
+use super::field::{get_struct_or_union_tag, tag_symbol_name, Type};
+use super::literal::canonical_literal_name;
+use super::scope_node::{ScopeNode, ScopeTree, ScopeType, ST_ROOT};
+use super::symbol::Symbol;
+use super::symtab::{RcSymIdx, SymIdx, SymTab, WithBorrow};
 
-use super::scope_node::{ScopeNode, ScopeTree, ScopeType};
+// for typedef symbol
+reg_field_for_struct!(Symbol {
+        TYPEDEF_TYPE:Type,
+    } with_fields fields);
 
 ///将函数名添加进scopetree，返回下一部分衔接的u32
 pub fn process_function(scope_tree:&mut ScopeTree, ast_tree:&AstTree, scope_parent:u32, current_function_ast_node:u32, ast2scope:&mut HashMap<u32, u32>) -> u32 {
@@ -60,10 +71,9 @@ pub fn process_statement(scope_tree:&mut ScopeTree, ast_tree:&AstTree, scope_par
                 }
                 None => {}
             }
+            // label 后面的 statement 可能是任意语句，所以要递归处理
             let label_statment = find!(rule RULE_statement at label_node in ast_tree).unwrap();
-            let scope_label_stmt =
-                add_node_with_edge!({ScopeNode{ast_node:label_statment,op_text:None,scope_type:ScopeType::Terminal,loop_level}} from scope_label_node in scope_tree);
-            ast2scope.insert(label_statment, scope_label_stmt);
+            process_statement(scope_tree, ast_tree, scope_label_node, label_statment, ast2scope);
         }
         (RULE_jumpStatement, jump_node) => {
             let scope_jump = add_node_with_edge!({ScopeNode{ast_node:jump_node,op_text:None,scope_type:ScopeType::Terminal,loop_level}} from scope_parent in scope_tree);
@@ -110,7 +120,7 @@ pub fn process_if(scope_tree:&mut ScopeTree, ast_tree:&AstTree, scope_parent:u32
 ///处理switch情况
 pub fn process_switch(scope_tree:&mut ScopeTree, ast_tree:&AstTree, scope_parent:u32, current_switch_node:u32, ast2scope:&mut HashMap<u32, u32>) {
     let loop_level = node!(at scope_parent in scope_tree).loop_level;
-    let scope_switch_node = add_node_with_edge!({ScopeNode{ast_node:current_switch_node,op_text:None,scope_type:ScopeType::Switch,loop_level}} from scope_parent in scope_tree);
+    let scope_switch_node = add_node_with_edge!({ScopeNode{ast_node:current_switch_node,op_text:None,scope_type:ScopeType::Switch { op_cfg_switch_node: None },loop_level}} from scope_parent in scope_tree);
     ast2scope.insert(current_switch_node, scope_switch_node);
 
     //处理表达式节点
@@ -130,6 +140,8 @@ pub fn process_iteration(scope_tree:&mut ScopeTree, ast_tree:&AstTree, scope_par
     match (rule_id!(at iteration_nextnode in ast_tree), iteration_nextnode) {
         (RULE_forIterationStatement, ast_for_node) => process_for(scope_tree, ast_tree, scope_parent, ast_for_node, ast2scope),
         (RULE_whileIterationStatement, ast_while_node) => process_while(scope_tree, ast_tree, scope_parent, ast_while_node, ast2scope),
+        // do while 与 while 的作用域结构相同，break 与 continue 同样通过 While 作用域找到对应的循环节点
+        (RULE_doWhileIterationStatement, ast_do_while_node) => process_while(scope_tree, ast_tree, scope_parent, ast_do_while_node, ast2scope),
         (_, _) => {
             panic!("不属于for或while的循环结构")
         }
@@ -258,4 +270,194 @@ pub fn get_while_scope_of_scope_node(scope_node:u32, scope_tree:&ScopeTree) -> u
         scope_node = direct_parent_node!(at scope_node in scope_tree);
     }
     scope_node
-}
\ No newline at end of file
+}
+/// break 跳出的是最近的一层 while 或者 switch
+pub fn get_break_scope_of_scope_node(scope_node:u32, scope_tree:&ScopeTree) -> u32{
+    let mut scope_node = scope_node;
+    while !(node!(at scope_node in scope_tree).scope_type.is_while() || node!(at scope_node in scope_tree).scope_type.is_switch()) {
+        scope_node = direct_parent_node!(at scope_node in scope_tree);
+    }
+    scope_node
+}
+
+/// 从 scope_node 开始逐层往外查找名为 name 的 typedef  
+/// 如果先找到的是同名的变量，说明 typedef 被它遮盖了，返回 None
+pub fn find_typedef_type(symtab:&SymTab, scope_tree:&ScopeTree, scope_node:u32, name:&str) -> Option<Type>{
+    let name:&'static str = name.to_string().leak();
+    let mut scope = scope_node;
+    loop{
+        if let Ok(symbol) = symtab.try_get(&SymIdx::new(scope, name)){
+            return symbol.get_op_typedef_type().cloned();
+        }
+        if scope == ST_ROOT{
+            return None
+        }
+        scope = direct_parent_node!(at scope in scope_tree);
+    }
+}
+/// 把所有 typedef 声明以及带成员列表的 struct/union 定义作为类型符号登记到其所在 scope 的符号表中，内层 scope 的会遮盖外层的同名类型  
+/// 按照源码顺序处理，这样 typedef 和 struct 中用到的其他 typedef 名与 tag 总是已经登记过的
+pub fn parse_typedefs_to_symtab(ast_tree:&AstTree, scope_tree:&ScopeTree, ast2scope:&HashMap<u32, u32>, symtab:&mut SymTab) -> Result<()>{
+    let typedef_decl_nodes = find_dfs_rule_ast(ast_tree, 1, RULE_declaration).filter(|&decl_node| is_typedef_decl(ast_tree, decl_node));
+    let struct_def_nodes = find_dfs_rule_ast(ast_tree, 1, RULE_structOrUnionSpecifier).filter(|&struct_spec_node| find!(rule RULE_structDeclarationList at struct_spec_node in ast_tree).is_some());
+    for decl_node in typedef_decl_nodes.chain(struct_def_nodes).sorted().collect_vec(){
+        if rule_id!(at decl_node in ast_tree) == RULE_structOrUnionSpecifier{
+            process_struct_or_union_def(ast_tree, scope_tree, ast2scope, symtab, decl_node)?;
+            continue;
+        }
+        // declaration 在 scope_tree 中对应一个 Terminal 节点，它的父节点才是 typedef 所在的 scope
+        let scope_node = match ast2scope.get(&decl_node){
+            Some(&st_node) => direct_parent_node!(at st_node in scope_tree),
+            None => ST_ROOT,
+        };
+        let type_spec_nodes = find_nodes!(rule RULE_declarationSpecifiers finally RULE_declarationSpecifier at decl_node in ast_tree).into_iter()
+            .filter_map(|specifier_node| find!(rule RULE_typeSpecifier at specifier_node in ast_tree)).collect_vec();
+        if type_spec_nodes.is_empty(){
+            return Err(anyhow!("typedef 缺少类型说明符 at ast_node {}",decl_node));
+        }
+        let base_ty = process_typedef_base(ast_tree, scope_tree, symtab, scope_node, type_spec_nodes[0])?;
+        let mut typedefs = vec![];
+        match find!(rule RULE_initDeclaratorList at decl_node in ast_tree){
+            Some(init_decl_list_node) => {
+                for init_decl_node in find_nodes!(rule RULE_initDeclarator at init_decl_list_node in ast_tree){
+                    let declarator_node = find!(rule RULE_declarator at init_decl_node in ast_tree).unwrap();
+                    typedefs.push(process_typedef_declarator(ast_tree, scope_tree, symtab, scope_node, declarator_node, base_ty.clone())?);
+                }
+            },
+            None => {
+                // 形如 typedef int T; 的声明可能被解析成 typeSpecifier(int) typeSpecifier(typedefName T)，类型名总是最后一个
+                let last_type_spec_node = *type_spec_nodes.last().unwrap();
+                if type_spec_nodes.len() >= 2 {
+                    if let Some(typedef_name_node) = find!(rule RULE_typedefName at last_type_spec_node in ast_tree){
+                        let ident_node = find!(term Identifier at typedef_name_node in ast_tree).unwrap();
+                        typedefs.push((node!(at ident_node in ast_tree).op_text.clone().unwrap().leak() as &'static str, base_ty.clone()));
+                    }
+                }
+            }
+        }
+        for (name, ty) in typedefs{
+            match symtab.try_get(&SymIdx::new(scope_node, name)){
+                // C11 允许在同一个 scope 中重复 typedef 同一个类型
+                Ok(symbol) if symbol.get_op_typedef_type() == Some(&ty) => {},
+                Ok(_) => return Err(anyhow!("typedef {} 在同一个作用域中被重复定义为不同的类型 at ast_node {}",name,decl_node)),
+                Err(_) => {
+                    add_symbol!({Symbol::new(scope_node, name)}
+                        with_field TYPEDEF_TYPE:{ty}
+                    to symtab);
+                },
+            }
+        }
+    }
+    Ok(())
+}
+/// 把 struct/union 的定义以 "struct S" 这样的名字登记到包含它的声明所在的 scope 中，成员中的 tag 与 typedef 名从这个 scope 向外查找
+fn process_struct_or_union_def(ast_tree:&AstTree, scope_tree:&ScopeTree, ast2scope:&HashMap<u32, u32>, symtab:&mut SymTab, struct_spec_node:u32) -> Result<()>{
+    let mut ast_node = struct_spec_node;
+    let scope_node = loop{
+        if let Some(&st_node) = ast2scope.get(&ast_node){
+            // declaration 等语句在 scope_tree 中对应 Terminal 节点，它的父节点才是定义所在的 scope
+            break if node!(at st_node in scope_tree).scope_type.is_terminal() { direct_parent_node!(at st_node in scope_tree) } else { st_node };
+        }
+        match direct_parent_node!(at ast_node in ast_tree ret_option){
+            Some(parent_node) => ast_node = parent_node,
+            None => break ST_ROOT,
+        }
+    };
+    let (is_union, tag) = get_struct_or_union_tag(struct_spec_node, ast_tree);
+    let name:&'static str = tag_symbol_name(is_union, tag).leak();
+    if symtab.has_symbol(&SymIdx::new(scope_node, name)){
+        return Err(anyhow!("{} 在同一个作用域中被重复定义 at ast_node {}",name,struct_spec_node));
+    }
+    let ty = Type::new_struct_or_union(struct_spec_node, ast_tree, &mut vec![], &|name| find_typedef_type(symtab, scope_tree, scope_node, name));
+    add_symbol!({Symbol::new(scope_node, name)}
+        with_field TYPEDEF_TYPE:{ty}
+    to symtab);
+    Ok(())
+}
+/// typedef 中的基本类型，用到的 typedef 名和尚未补全的 struct/union 都保持原样，等到使用时再补全
+fn process_typedef_base(ast_tree:&AstTree, scope_tree:&ScopeTree, symtab:&SymTab, scope_node:u32, type_spec_node:u32) -> Result<Type>{
+    if let Some(typedef_name_node) = find!(rule RULE_typedefName at type_spec_node in ast_tree){
+        let ident_node = find!(term Identifier at typedef_name_node in ast_tree).unwrap();
+        let name = node!(at ident_node in ast_tree).op_text.as_ref().unwrap();
+        return find_typedef_type(symtab, scope_tree, scope_node, name).ok_or(anyhow!("{} 不是一个类型名 at ast_node {}",name,type_spec_node))
+    }
+    if let Some(struct_spec_node) = find!(rule RULE_structOrUnionSpecifier at type_spec_node in ast_tree){
+        if let Some(ty) = Type::new_incomplete_struct_or_union(struct_spec_node, ast_tree){
+            return Ok(ty)
+        }
+    }
+    Ok(Type::new_from_type_specifier(type_spec_node, ast_tree, &mut vec![], &|name| find_typedef_type(symtab, scope_tree, scope_node, name)))
+}
+/// 按照 C 的声明规则由外向内展开 declarator ，在 base_ty 上构建 typedef 所代表的类型，返回 typedef 名和类型
+fn process_typedef_declarator(ast_tree:&AstTree, scope_tree:&ScopeTree, symtab:&mut SymTab, scope_node:u32, declarator_node:u32, base_ty:Type) -> Result<(&'static str, Type)>{
+    let mut ty = base_ty;
+    for _ in 0..get_ptr_depth_of_declarator(ast_tree, declarator_node){
+        ty = Type::Ptr64 { ty: Box::new(ty) };
+    }
+    let direct_decl_node = find!(rule RULE_directDeclarator at declarator_node in ast_tree).unwrap();
+    process_typedef_direct_decl(ast_tree, scope_tree, symtab, scope_node, direct_decl_node, ty)
+}
+fn process_typedef_direct_decl(ast_tree:&AstTree, scope_tree:&ScopeTree, symtab:&mut SymTab, scope_node:u32, direct_decl_node:u32, ty:Type) -> Result<(&'static str, Type)>{
+    if let Some(sub_direct_decl_node) = find!(rule RULE_directDeclarator at direct_decl_node in ast_tree){
+        let ty = if find!(term LeftParen at direct_decl_node in ast_tree).is_some(){
+            // 函数类型，ty 是它的返回类型
+            let name = get_ident_of_direct_decl(ast_tree, sub_direct_decl_node);
+            process_fn_type(ast_tree, scope_tree, symtab, scope_node, direct_decl_node, name, ty)?
+        }else {
+            let assign_expr_node = find!(rule RULE_assignmentExpression at direct_decl_node in ast_tree).ok_or(anyhow!("typedef 中的数组维度必须给出 at ast_node {}",direct_decl_node))?;
+            let dim_text = find_single_constant_text(ast_tree, assign_expr_node).ok_or(anyhow!("typedef 中的数组维度只支持整数字面量 at ast_node {}",assign_expr_node))?;
+            Type::new_array_dims_known(ty, vec![SymIdx::from_str(canonical_literal_name(&dim_text, false)?).as_rc()])
+        };
+        process_typedef_direct_decl(ast_tree, scope_tree, symtab, scope_node, sub_direct_decl_node, ty)
+    }else if let Some(declarator_node) = find!(rule RULE_declarator at direct_decl_node in ast_tree){
+        // 形如 (*fp) 的括号内的 declarator
+        process_typedef_declarator(ast_tree, scope_tree, symtab, scope_node, declarator_node, ty)
+    }else {
+        Ok((get_ident_of_direct_decl(ast_tree, direct_decl_node), ty))
+    }
+}
+/// 由带参数列表的 directDeclarator 构建返回 ret_ty 的函数类型，返回值与参数作为 {name}_ret 与 {name}_arg{i} 登记在 scope_node 中  
+/// typedef 的函数类型和函数指针变量的类型都由这里构建
+pub fn process_fn_type(ast_tree:&AstTree, scope_tree:&ScopeTree, symtab:&mut SymTab, scope_node:u32, fn_direct_decl_node:u32, name:&str, ret_ty:Type) -> Result<Type>{
+    let rc_ret_symidx = add_symbol!({Symbol::new(scope_node, format!("{}_ret",name).leak())}
+        with_field TYPE:{ret_ty}
+    to symtab);
+    let mut arg_syms:Vec<RcSymIdx> = vec![];
+    if let Some(para_type_list_node) = find!(rule RULE_parameterTypeList at fn_direct_decl_node in ast_tree){
+        for (idx, para_decl_node) in find_nodes!(rule RULE_parameterList finally RULE_parameterDeclaration at para_type_list_node in ast_tree).into_iter().enumerate(){
+            let arg_ty = process_typedef_para(ast_tree, scope_tree, symtab, scope_node, para_decl_node)?;
+            if arg_ty == Type::Void { continue }
+            arg_syms.push(add_symbol!({Symbol::new(scope_node, format!("{}_arg{}",name,idx).leak())}
+                with_field TYPE:{arg_ty}
+            to symtab));
+        }
+    }
+    Ok(Type::Fn { arg_syms, ret_sym: rc_ret_symidx })
+}
+/// 函数类型 typedef 中参数的类型，数组参数会退化为指针
+fn process_typedef_para(ast_tree:&AstTree, scope_tree:&ScopeTree, symtab:&mut SymTab, scope_node:u32, para_decl_node:u32) -> Result<Type>{
+    // 没有名字的参数用的是 declarationSpecifiers2
+    let decl_specs_node = find!(rule RULE_declarationSpecifiers at para_decl_node in ast_tree).or(find!(rule RULE_declarationSpecifiers2 at para_decl_node in ast_tree)).unwrap();
+    let type_spec_node = find_nodes!(rule RULE_declarationSpecifier at decl_specs_node in ast_tree).into_iter()
+        .find_map(|specifier_node| find!(rule RULE_typeSpecifier at specifier_node in ast_tree)).ok_or(anyhow!("参数缺少类型说明符 at ast_node {}",para_decl_node))?;
+    let base_ty = process_typedef_base(ast_tree, scope_tree, symtab, scope_node, type_spec_node)?;
+    let ty = if let Some(declarator_node) = find!(rule RULE_declarator at para_decl_node in ast_tree){
+        process_typedef_declarator(ast_tree, scope_tree, symtab, scope_node, declarator_node, base_ty)?.1
+    }else if let Some(abstract_decl_node) = find!(rule RULE_abstractDeclarator at para_decl_node in ast_tree){
+        (0..get_ptr_depth_of_declarator(ast_tree, abstract_decl_node)).fold(base_ty, |ty, _| Type::Ptr64 { ty: Box::new(ty) })
+    }else {
+        base_ty
+    };
+    Ok(if ty.is_array() { ty.arr2ptr() } else { ty })
+}
+fn get_ident_of_direct_decl(ast_tree:&AstTree, direct_decl_node:u32) -> &'static str{
+    let mut cur_node = direct_decl_node;
+    loop{
+        if let Some(ident_node) = find!(term Identifier at cur_node in ast_tree){
+            return node!(at ident_node in ast_tree).op_text.clone().unwrap().leak();
+        }
+        cur_node = find!(rule RULE_directDeclarator at cur_node in ast_tree)
+            .or_else(|| find!(rule RULE_declarator at cur_node in ast_tree).and_then(|declarator_node| find!(rule RULE_directDeclarator at declarator_node in ast_tree)))
+            .unwrap_or_else(|| panic!("declarator 中找不到标识符 at ast_node {}",direct_decl_node));
+    }
+}
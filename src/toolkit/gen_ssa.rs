@@ -265,8 +265,9 @@ pub fn update_reaching_def(instr:usize,src_symidx:&SymIdx,symtab:&mut SymTab,cfg
             false
         }else {
             count  = count +1 ;
-            if count> 20 {
-                return Err(anyhow!("count >20"));
+            // 链上每个定义都是不同的指令，长度超过指令总数说明链上有环
+            if count > instr_slab.iter().len() {
+                return Err(anyhow!("{:?} 的 reaching def 链上有环",src_symidx));
             }
             debug_info_blue!("{:?} is_not_dominated_by {:?}",instr!(at instr in instr_slab), instr!(at instr2 in instr_slab));
             true
@@ -475,7 +476,7 @@ pub fn ssa_deconstruction(cfg_graph:&mut CfgGraph, dj_graph:&DjGraph,symtab:&mut
                     NhwcInstrType::Phi { lhs, rhs } => {
                         for phi_pair in &rhs.phi_pairs{
                             if phi_pair.symidx.as_ref_borrow().is_literal() 
-                            || !symtab.get(&phi_pair.symidx.as_ref_borrow().to_src_symidx()).get_type().is_array() 
+                            || !symtab.get(&phi_pair.symidx.as_ref_borrow().to_src_symidx()).get_type().is_aggregate() 
                             && !symtab.get(&phi_pair.symidx.as_ref_borrow().to_src_symidx()).get_type().is_ptr_64()
                             && !*symtab.get(&phi_pair.symidx.as_ref_borrow().to_src_symidx()).get_is_global() && phi_pair.symidx != *lhs{
                                 let target_cfg_node = if is_critical_edge(phi_pair.comming_cfg_node, cfg_node, cfg_graph){
@@ -890,7 +891,7 @@ pub fn last_use_and_first_def_analysis(symtab:&mut SymTab, cfg_graph:&mut CfgGra
             assert!(node!(at cfg_node in cfg_graph).phi_instrs.len() == 0 );
             for &instr in node!(at cfg_node in cfg_graph).iter_all_instrs_rev(){
                 for use_symidx in instr!(at instr in instr_slab).get_ssa_direct_use_symidx_vec(){
-                    if !use_symidx.as_ref_borrow().is_literal() && !symtab.get(&use_symidx.as_ref_borrow().to_src_symidx()).get_type().is_array()
+                    if !use_symidx.as_ref_borrow().is_literal() && !symtab.get(&use_symidx.as_ref_borrow().to_src_symidx()).get_type().is_aggregate()
                         && !*symtab.get(&use_symidx.as_ref_borrow().to_src_symidx()).get_is_global(){
                         if hash_map.get(use_symidx).is_none(){
                             hash_map.insert(use_symidx.clone(), Some(instr));
@@ -908,7 +909,7 @@ pub fn last_use_and_first_def_analysis(symtab:&mut SymTab, cfg_graph:&mut CfgGra
             let cfg_node = node!(at dj_node in dj_graph).cor_cfg_node;
             for &instr in node!(at cfg_node in cfg_graph).instrs.iter(){
                 for def_symidx in instr!(at instr in instr_slab).get_ssa_direct_def_symidx_vec(){
-                    if !def_symidx.as_ref_borrow().is_literal() && !symtab.get(&def_symidx.as_ref_borrow().to_src_symidx()).get_type().is_array()
+                    if !def_symidx.as_ref_borrow().is_literal() && !symtab.get(&def_symidx.as_ref_borrow().to_src_symidx()).get_type().is_aggregate()
                         && !*symtab.get(&def_symidx.as_ref_borrow().to_src_symidx()).get_is_global(){
                         let def_symbol = symtab.get_mut(&def_symidx.as_ref_borrow());
                         if !def_symbol.has_non_ssa_first_def(){
@@ -0,0 +1,171 @@
+use std::num::IntErrorKind;
+
+use anyhow::{anyhow, Result};
+
+use super::field::{Type, Value};
+
+/// 解析 C/SysY 的数值字面量，支持十进制、八进制、十六进制与二进制整数，十进制与十六进制浮点数，以及 u l ll f 等后缀
+/// is_negated 表示这个字面量是一元负号的操作数，此时返回取负之后的值  
+/// 类型按照字面量本身的值选择，只有十进制字面量按照取负之后的值选择，因此 -2147483648 是 int ，而 -0x80000000 是 unsigned int
+pub fn parse_num_literal(text:&str, is_negated:bool) -> Result<Value> {
+    let is_hex = text.starts_with("0x") || text.starts_with("0X");
+    let is_bin = text.starts_with("0b") || text.starts_with("0B");
+    let is_float = if is_hex { text.contains(['p', 'P']) } else { !is_bin && text.contains(['.', 'e', 'E']) };
+    if is_float { parse_float_literal(text, is_hex, is_negated) } else { parse_int_literal(text, is_negated) }
+}
+/// 数值字面量对应的规范字面量名，即 Value::to_symidx 的名字，这样 0x10 与 16 共用同一个字面量 SymIdx
+pub fn canonical_literal_name(text:&str, is_negated:bool) -> Result<&'static str> {
+    Ok(parse_num_literal(text, is_negated)?.to_symidx().symbol_name)
+}
+
+fn parse_int_literal(text:&str, is_negated:bool) -> Result<Value> {
+    let (body, suffix) = text.split_at(text.find(['u', 'U', 'l', 'L']).unwrap_or(text.len()));
+    let (radix, digits) = if let Some(digits) = body.strip_prefix("0x").or(body.strip_prefix("0X")) {
+        (16, digits)
+    }else if let Some(digits) = body.strip_prefix("0b").or(body.strip_prefix("0B")) {
+        (2, digits)
+    }else if body.len() > 1 && body.starts_with('0') {
+        (8, &body[1..])
+    }else {
+        (10, body)
+    };
+    let magnitude = u64::from_str_radix(digits, radix).map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow => anyhow!("整数字面量 {} 超出了可以表示的范围", text),
+        _ => anyhow!("整数字面量 {} 的格式不正确", text),
+    })?;
+    let suffix = suffix.to_ascii_lowercase();
+    let (is_unsigned, is_long) = match suffix.as_str() {
+        "" => (false, false),
+        "u" => (true, false),
+        "l" | "ll" => (false, true),
+        "ul" | "lu" | "ull" | "llu" => return Err(anyhow!("整数字面量 {} 的类型是 unsigned long ，暂不支持", text)),
+        _ => return Err(anyhow!("整数字面量 {} 的后缀 {} 不正确", text, suffix)),
+    };
+    // 按照 C 的规则依次尝试能放下这个值的类型，十进制不带后缀时不会选择无符号类型
+    let candidate_tys:&[Type] = match (is_unsigned, is_long, radix == 10) {
+        (false, false, true) => &[Type::I32, Type::I64],
+        (false, false, false) => &[Type::I32, Type::U32, Type::I64],
+        (true, _, _) => &[Type::U32],
+        (false, true, _) => &[Type::I64],
+    };
+    let is_negated_decimal = is_negated && radix == 10;
+    let fits = |ty:&Type| match ty {
+        Type::I32 => magnitude <= i32::MAX as u64 || (is_negated_decimal && magnitude == 1 << 31),
+        Type::U32 => magnitude <= u32::MAX as u64,
+        _ => magnitude <= i64::MAX as u64 || (is_negated_decimal && magnitude == 1 << 63),
+    };
+    let signed_val = if is_negated { (magnitude as i64).wrapping_neg() } else { magnitude as i64 };
+    match candidate_tys.iter().find(|ty| fits(ty)) {
+        Some(Type::I32) => Ok(Value::new_i32(signed_val as i32)),
+        Some(Type::U32) => Ok(Value::new_u32(if is_negated { (magnitude as u32).wrapping_neg() } else { magnitude as u32 })),
+        Some(_) => Ok(Value::new_i64(signed_val)),
+        None => Err(anyhow!("整数字面量 {} 超出了可以表示的范围", text)),
+    }
+}
+
+/// 与 SysY 保持一致，没有后缀的浮点字面量是 float 而不是 C 中的 double ，SysY 的测试用例按 float 的精度计算结果  
+/// 需要 double 的字面量使用 l 后缀
+fn parse_float_literal(text:&str, is_hex:bool, is_negated:bool) -> Result<Value> {
+    // 没有后缀与 f 后缀都是 float ，l 后缀作为 double
+    let (body, is_double) = match text.chars().last() {
+        Some('f' | 'F') => (&text[..text.len() - 1], false),
+        Some('l' | 'L') => (&text[..text.len() - 1], true),
+        _ => (text, false),
+    };
+    let val = match (is_hex, is_double) {
+        (true, _) => parse_hex_float(body).ok_or(anyhow!("浮点字面量 {} 的格式不正确", text))?,
+        (false, true) => body.parse::<f64>().map_err(|_| anyhow!("浮点字面量 {} 的格式不正确", text))?,
+        // 直接解析为 f32 ，避免先舍入到 f64 再舍入到 f32 的两次舍入
+        (false, false) => body.parse::<f32>().map_err(|_| anyhow!("浮点字面量 {} 的格式不正确", text))? as f64,
+    };
+    let val = if is_negated { -val } else { val };
+    match is_double {
+        true if val.is_finite() => Ok(Value::new_f64(val)),
+        false if (val as f32).is_finite() => Ok(Value::new_f32(val as f32)),
+        _ => Err(anyhow!("浮点字面量 {} 超出了可以表示的范围", text)),
+    }
+}
+/// 十六进制浮点数 0x<尾数>p<以 2 为底的指数>，尾数可以带小数点
+fn parse_hex_float(body:&str) -> Option<f64> {
+    let (mantissa, exp) = body[2..].split_once(['p', 'P'])?;
+    let exp:i32 = exp.parse().ok()?;
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None
+    }
+    let mut val = 0f64;
+    for c in int_part.chars().chain(frac_part.chars()) {
+        val = val * 16.0 + c.to_digit(16)? as f64;
+    }
+    Some(val * 2f64.powi(exp - 4 * frac_part.len() as i32))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{canonical_literal_name, parse_num_literal};
+    use crate::toolkit::field::Value;
+
+    #[test]
+    fn test_int_literals() {
+        let cases:&[(&str, bool, Value)] = &[
+            ("0", false, Value::new_i32(0)),
+            ("16", false, Value::new_i32(16)),
+            ("0x10", false, Value::new_i32(16)),
+            ("0X1F", false, Value::new_i32(31)),
+            ("017", false, Value::new_i32(15)),
+            ("0b101", false, Value::new_i32(5)),
+            ("2147483647", false, Value::new_i32(i32::MAX)),
+            ("2147483648", false, Value::new_i64(2147483648)),
+            ("2147483648", true, Value::new_i32(i32::MIN)),
+            ("0x80000000", false, Value::new_u32(0x80000000)),
+            ("0xffffffff", false, Value::new_u32(u32::MAX)),
+            ("0x100000000", false, Value::new_i64(0x100000000)),
+            ("10u", false, Value::new_u32(10)),
+            ("10L", false, Value::new_i64(10)),
+            ("9223372036854775808", true, Value::new_i64(i64::MIN)),
+            // 只有十进制字面量按取负之后的值选择类型
+            ("0x80000000", true, Value::new_u32(0x80000000)),
+            ("020000000000", true, Value::new_u32(0x80000000)),
+            ("0x7fffffff", true, Value::new_i32(-i32::MAX)),
+        ];
+        for (text, is_negated, expected) in cases {
+            assert_eq!(&parse_num_literal(text, *is_negated).unwrap(), expected, "literal {} negated {}", text, is_negated);
+        }
+    }
+
+    #[test]
+    fn test_float_literals() {
+        let cases:&[(&str, bool, Value)] = &[
+            ("1.5", false, Value::new_f32(1.5)),
+            (".5", false, Value::new_f32(0.5)),
+            ("5.", false, Value::new_f32(5.0)),
+            ("1e-5f", false, Value::new_f32(1e-5)),
+            ("1E3", false, Value::new_f32(1000.0)),
+            ("0x1.8p3", false, Value::new_f32(12.0)),
+            ("0x.8p1", false, Value::new_f32(1.0)),
+            ("0x1p-2", false, Value::new_f32(0.25)),
+            ("0x1P+4F", false, Value::new_f32(16.0)),
+            ("2.5", true, Value::new_f32(-2.5)),
+            ("0.1L", false, Value::new_f64(0.1)),
+        ];
+        for (text, is_negated, expected) in cases {
+            assert_eq!(&parse_num_literal(text, *is_negated).unwrap(), expected, "literal {} negated {}", text, is_negated);
+        }
+    }
+
+    #[test]
+    fn test_invalid_literals() {
+        for text in ["18446744073709551616", "9223372036854775808", "09", "0x", "10ul", "1e40f", "0x1.8"] {
+            assert!(parse_num_literal(text, false).is_err(), "literal {} should be rejected", text);
+        }
+        assert!(parse_num_literal("0x8000000000000000", true).is_err());
+    }
+
+    #[test]
+    fn test_canonical_names() {
+        let cases = [("0x10", "16"), ("020", "16"), ("16", "16"), ("0b10000", "16"), ("0x1p4", "16."), ("16.0f", "16."), ("1e-5", "0.00001"), ("0xffffffff", "4294967295u"), ("2147483648", "2147483648l")];
+        for (text, expected) in cases {
+            assert_eq!(canonical_literal_name(text, false).unwrap(), expected, "literal {}", text);
+        }
+    }
+}
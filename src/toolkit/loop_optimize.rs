@@ -1,17 +1,32 @@
-use crate::{direct_child_nodes, instr, node, node_mut};
+use crate::{direct_child_node, direct_child_nodes, instr, node, node_mut};
 use anyhow::{anyhow, Context, Ok, Result};
 use petgraph::{graph::{node_index, EdgeIndex, NodeIndex}, visit::EdgeRef};
-use super::{cfg_edge::{CfgEdge, CfgEdgeType}, cfg_node::{CfgGraph, CfgNodeType, LoopInfo}, etc::dfs_with_priority, field::Value, nhwc_instr::{ArithOp, InstrSlab, JumpOp, NhwcInstr, NhwcInstrType}, symtab::{self, RcSymIdx, SymTab, WithBorrow}};
+use super::{cfg_edge::{CfgEdge, CfgEdgeType}, cfg_node::{CfgGraph, CfgNode, CfgNodeType, LoopInfo}, etc::dfs_with_priority, field::Value, nhwc_instr::{ArithOp, InstrSlab, JumpOp, NhwcInstr, NhwcInstrType}, symtab::{self, RcSymIdx, SymTab, WithBorrow}};
 use crate::toolkit::cfg_node::CFG_ROOT;
 
 ///判断是否为可优化的循环，如果是的可优化的，则返回两个循环变量(一个用于比较的版本，一个用于自增)的option
 ///1.while条件表达式左边的是单一变量，不是表达式，右边是常量或者常数
 ///2.while循环体中的bb中只有一个对于循环变量的改变instr
+///do while 的比较在循环体之后，用的是改变之后的循环变量，循环变量的 phi 在循环体头部
 pub fn can_get_loop_info(cfg_node:u32,cfg_graph:&mut CfgGraph,instr_slab:&mut InstrSlab<NhwcInstr>,symtab:&SymTab) -> Result<Option<(RcSymIdx,RcSymIdx)>>{
     let while_loop_node = node!(at cfg_node in cfg_graph);
     let while_instr_vec = &while_loop_node.instrs.instr_vec;
     let comp_instr = while_instr_vec[while_instr_vec.len()-1];
     let comp_use_rcsymidx = instr!(at comp_instr in instr_slab).get_ssa_direct_use_symidx_vec();
+    if while_loop_node.cfg_node_type.is_do_while_loop() {
+        if comp_use_rcsymidx[0].as_ref_borrow().is_temp() || !comp_use_rcsymidx[1].as_ref_borrow().is_literal() {
+            return Ok(None)
+        }
+        let body_head = direct_child_node!(at cfg_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_body_head()});
+        for &phi_instr in node!(at body_head in cfg_graph).phi_instrs.instr_vec.iter(){
+            let phi_def_rcsymidx = instr!(at phi_instr in instr_slab).get_ssa_direct_def_symidx_vec()[0];
+            let phi_use_rcsymidx_vec = instr!(at phi_instr in instr_slab).get_ssa_direct_use_symidx_vec();
+            if phi_use_rcsymidx_vec.len() == 2 && phi_use_rcsymidx_vec.contains(&comp_use_rcsymidx[0]){
+                return Ok(Some((phi_def_rcsymidx.clone(),comp_use_rcsymidx[0].clone())))
+            }
+        }
+        return Ok(None)
+    }
     if !comp_use_rcsymidx[0].as_ref_borrow().is_temp() && comp_use_rcsymidx[1].as_ref_borrow().is_literal() {
         let phi_instr_vec = &while_loop_node.clone().phi_instrs.instr_vec;
         for phi_instr in phi_instr_vec{
@@ -140,10 +155,12 @@ pub fn parse_loop_optimize(cfg_graph:&mut CfgGraph){
             CfgEdgeType::IfFalse {  } => 6,
             CfgEdgeType::Direct {  } => 1,
             CfgEdgeType::IfTrue {  } => 7,
-            CfgEdgeType::BodyTail {  } => -1,
+            // do while 节点只能从循环体尾部到达
+            CfgEdgeType::BodyTail {  } => if node!(at {e.target().index() as u32} in cfg_graph).cfg_node_type.is_do_while_loop() { 1 } else { -1 },
+            CfgEdgeType::Case {  } => 3,
         });
         for cfg_node in dfs_vec{
-            if let CfgNodeType::WhileLoop { ast_expr_node } = node!(at cfg_node in cfg_graph).cfg_node_type{
+            if let CfgNodeType::WhileLoop { ast_expr_node } | CfgNodeType::DoWhileLoop { ast_expr_node } = node!(at cfg_node in cfg_graph).cfg_node_type{
                 // if node!(at cfg_node in cfg_graph)
                 let mut nest_while_node_vec = vec![cfg_node];
 
@@ -151,3 +168,51 @@ pub fn parse_loop_optimize(cfg_graph:&mut CfgGraph){
         }
     }
 }
+
+/// 循环旋转：把 while 循环改写为被 if 保护的 do while 循环  
+/// 原来 `while(c){body}` 每次迭代都要先跳回头部求值条件，再跳进循环体；旋转之后条件在循环体之后求值，条件为真时直接跳回循环体头部  
+/// 旋转之后的结构为 Branch(c) -IfTrue-> body -BodyTail-> DoWhileLoop(c) -BodyHead-> body ，Branch 的 IfFalse 与 DoWhileLoop 的出边汇合到新的 Gather  
+/// 出口本身是 bb 或 Gather 时直接作为汇合点，不再新建 Gather ，这样出口不会变成没有指令的空节点  
+/// 原 while 节点原地改为 DoWhileLoop ，因此 continue 仍然跳到条件处，break 跳到汇合点，for 循环带有 before/after 表达式，不做旋转  
+/// 需要在 Cfg2Ncfg 之前运行，返回旋转的循环个数
+pub fn rotate_while_loops(cfg_graph:&mut CfgGraph) -> Result<usize>{
+    let cfg_while_nodes = cfg_graph.node_indices().map(|n| n.index() as u32).filter(|&cfg_node| node!(at cfg_node in cfg_graph).cfg_node_type.is_while_loop()).collect::<Vec<_>>();
+    for &cfg_while_node in cfg_while_nodes.iter(){
+        let ast_expr_node = match &node!(at cfg_while_node in cfg_graph).cfg_node_type{
+            CfgNodeType::WhileLoop { ast_expr_node } => *ast_expr_node,
+            _ => unreachable!(),
+        };
+        // 循环节点自己的 loop_level 已经是循环内的层数，保护分支与汇合点在循环外
+        let outer_loop_level = node!(at cfg_while_node in cfg_graph).loop_level - 1;
+        let cfg_body_head = direct_child_node!(at cfg_while_node in cfg_graph with_predicate {|e| e.weight().cfg_edge_type.is_body_head()});
+        let (exit_edge,cfg_exit_node) = cfg_graph.edges_directed(node_index(cfg_while_node as usize), petgraph::Direction::Outgoing)
+            .find(|e| !e.weight().cfg_edge_type.is_body_head()).map(|e| (e.id(), e.target().index() as u32))
+            .ok_or(anyhow!("while 节点 {} 没有出口", cfg_while_node))?;
+        let in_edges = cfg_graph.edges_directed(node_index(cfg_while_node as usize), petgraph::Direction::Incoming)
+            .filter(|e| !e.weight().cfg_edge_type.is_body_tail()).map(|e| (e.id(), e.source().index() as u32, e.weight().clone())).collect::<Vec<_>>();
+
+        let mut branch_struct = CfgNode::new_branch(ast_expr_node, outer_loop_level);
+        if let Some(src_loc) = node!(at cfg_while_node in cfg_graph).get_op_src_loc().cloned(){
+            branch_struct.add_src_loc(src_loc);
+        }
+        let cfg_branch_node = cfg_graph.add_node(branch_struct).index() as u32;
+        let cfg_gather_node = match &node!(at cfg_exit_node in cfg_graph).cfg_node_type{
+            CfgNodeType::BasicBlock { .. } | CfgNodeType::Gather {} => cfg_exit_node,
+            _ => cfg_graph.add_node(CfgNode::new_gather(outer_loop_level)).index() as u32,
+        };
+        // 原来进入 while 的边都改为进入保护分支
+        for (in_edge, cfg_src_node, cfg_edge) in in_edges{
+            cfg_graph.remove_edge(in_edge);
+            cfg_graph.add_edge(node_index(cfg_src_node as usize), node_index(cfg_branch_node as usize), cfg_edge);
+        }
+        let exit_edge_weight = cfg_graph.remove_edge(exit_edge).unwrap();
+        if cfg_gather_node != cfg_exit_node{
+            cfg_graph.add_edge(node_index(cfg_gather_node as usize), node_index(cfg_exit_node as usize), exit_edge_weight);
+        }
+        cfg_graph.add_edge(node_index(cfg_branch_node as usize), node_index(cfg_body_head as usize), CfgEdge::new_if_true());
+        cfg_graph.add_edge(node_index(cfg_branch_node as usize), node_index(cfg_gather_node as usize), CfgEdge::new_if_false());
+        cfg_graph.add_edge(node_index(cfg_while_node as usize), node_index(cfg_gather_node as usize), CfgEdge::new_direct());
+        node_mut!(at cfg_while_node in cfg_graph).cfg_node_type = CfgNodeType::DoWhileLoop { ast_expr_node };
+    }
+    Ok(cfg_while_nodes.len())
+}
@@ -5,7 +5,7 @@ use std::fmt::Debug;
 use anyhow::*;
 use crate::{reg_field_for_struct};
 
-use super::{cfg_node::{CfgNode}, symbol::Symbol, symtab::{RcSymIdx, SymTab}};
+use super::{cfg_node::{CfgNode}, field::Type, symbol::Symbol, symtab::{RcSymIdx, SymTab}};
 use itertools::{self, Itertools};
 
 //  mem offset is the offset to s0 (so you should divide stack_size by mem_offset to get offset to sp)
@@ -118,6 +118,12 @@ impl MemLayout{
         }
         cur_mem_pos
     }
+    /// 在末尾(即 sp 处)预留 len 字节的空内存，之后不能再插入数据
+    pub fn reserve_blank_at_bottom(&mut self,len:usize){
+        if len > 0{
+            self.mem.push(MemSegment { op_symidx: None, len })
+        }
+    }
     /// 会在末尾填充足够的空内存以对齐给定align
     pub fn align_mem_with_blank(&mut self,align:usize){
         if self.get_mem_len()%align !=0{
@@ -131,6 +137,44 @@ impl MemLayout{
         }
     }
 }
+/// struct 和 union 的成员布局，offsets 与成员的声明顺序一一对应
+#[derive(Clone,Debug)]
+pub struct AggregateLayout{
+    pub offsets:Vec<usize>,
+    pub size:usize,
+    pub align:usize,
+}
+impl AggregateLayout{
+    /// 按声明顺序依次放置成员，每个成员前按自身的对齐填充 padding ，最后把总长度填充到最大对齐
+    pub fn new_struct<'a>(member_tys:impl Iterator<Item = &'a Type>) -> Self{
+        let mut offsets = vec![];
+        let mut cur_mem_pos = 0;
+        let mut align = 1;
+        for member_ty in member_tys{
+            let member_align = member_ty.get_align();
+            cur_mem_pos = align_up(cur_mem_pos, member_align);
+            offsets.push(cur_mem_pos);
+            cur_mem_pos += member_ty.get_mem_len();
+            align = align.max(member_align);
+        }
+        Self { offsets, size: align_up(cur_mem_pos, align), align }
+    }
+    /// 所有成员的偏移都为 0 ，长度为最长的成员填充到最大对齐
+    pub fn new_union<'a>(member_tys:impl Iterator<Item = &'a Type>) -> Self{
+        let mut offsets = vec![];
+        let mut max_len = 0;
+        let mut align = 1;
+        for member_ty in member_tys{
+            offsets.push(0);
+            max_len = max_len.max(member_ty.get_mem_len());
+            align = align.max(member_ty.get_align());
+        }
+        Self { offsets, size: align_up(max_len, align), align }
+    }
+}
+pub fn align_up(mem_pos:usize, align:usize) -> usize{
+    (mem_pos + align - 1) / align * align
+}
 impl Debug for MemLayout{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
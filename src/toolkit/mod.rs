@@ -8,7 +8,14 @@ pub mod et_node;
 pub mod etc;
 pub mod eval_et;
 pub mod field;
+pub mod literal;
 pub mod gen_ast;
+pub mod sysy_lexer;
+pub mod sysy_parser;
+pub mod preprocess;
+pub mod diagnostic;
+pub mod semantic_check;
+pub mod warning;
 pub mod gen_cfg;
 pub mod gen_et;
 pub mod gen_nhwc_cfg;
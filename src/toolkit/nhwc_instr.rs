@@ -96,12 +96,52 @@ pub enum ArithOp {
         a:RcSymIdx,
         vartype:Type,
     },
+    //cond 为 i1 ，cond 为真时取 a 否则取 b ，用于两侧都没有副作用的条件运算符
+    Select {
+        cond:RcSymIdx,
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
+    //位运算，按位取反用 xor -1 表示
+    BitAnd {
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
+    BitOr {
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
+    BitXor {
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
+    //移位运算，有符号数右移是算术右移，无符号数右移是逻辑右移
+    Shl {
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
+    Ashr {
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
+    Lshr {
+        a:RcSymIdx,
+        b:RcSymIdx,
+        vartype:Type,
+    },
 }
 #[derive(Clone)]
 pub struct FuncOp {
-    pub rc_func_symidx:RcSymIdx,
+    pub rc_func_symidx:RcSymIdx, //间接调用时是存放函数地址的指针变量
     pub actual_arg_symidx_vec:Vec<RcSymIdx>, //存储所有的实参
-    pub ret_type:Type
+    pub ret_type:Type,
+    pub is_indirect:bool,
 }
 #[derive(Clone,PartialEq,Eq)]
 pub struct PhiPair {
@@ -265,12 +305,20 @@ impl NhwcInstr {
                 ArithOp::LogicAnd { a, b, vartype:_ } => vec![a,b],
                 ArithOp::LogicOr { a, b, vartype:_ } => vec![a,b],
                 ArithOp::LogicNot { a, vartype:_ } => vec![a],
+                ArithOp::Select { cond, a, b, vartype:_ } => vec![cond,a,b],
+                ArithOp::BitAnd { a, b, vartype:_ } => vec![a,b],
+                ArithOp::BitOr { a, b, vartype:_ } => vec![a,b],
+                ArithOp::BitXor { a, b, vartype:_ } => vec![a,b],
+                ArithOp::Shl { a, b, vartype:_ } => vec![a,b],
+                ArithOp::Ashr { a, b, vartype:_ } => vec![a,b],
+                ArithOp::Lshr { a, b, vartype:_ } => vec![a,b],
             }},
             NhwcInstrType::SimpleAssign { lhs:_, rhs, vartype: _ } => {
                  vec![rhs] 
             },
             NhwcInstrType::Call { op_lhs: _assigned, func_op } => {
-                func_op.actual_arg_symidx_vec.iter().collect_vec()
+                // 间接调用时函数指针本身也是一个 use
+                func_op.actual_arg_symidx_vec.iter().chain(func_op.is_indirect.then_some(&func_op.rc_func_symidx)).collect_vec()
             },
             NhwcInstrType::Jump { jump_op } => {
                 match jump_op{
@@ -285,7 +333,19 @@ impl NhwcInstr {
             NhwcInstrType::TranType { lhs:_, op } => match op{
                 Trans::Fptosi { float_symidx } => vec![float_symidx],
                 Trans::Sitofp { int_symidx } => vec![int_symidx],
+                Trans::Fptoui { float_symidx } => vec![float_symidx],
+                Trans::Uitofp { int_symidx } => vec![int_symidx],
                 Trans::Zext { bool_symidx } => vec![bool_symidx],
+                Trans::Sext { int_symidx } => vec![int_symidx],
+                Trans::ZextU32 { uint_symidx } => vec![uint_symidx],
+                Trans::Trunc { long_symidx } => vec![long_symidx],
+                Trans::TruncI8 { int_symidx } => vec![int_symidx],
+                Trans::Fptosi64 { float_symidx } => vec![float_symidx],
+                Trans::Sitofp64 { long_symidx } => vec![long_symidx],
+                Trans::Fpext { float_symidx } => vec![float_symidx],
+                Trans::Fptrunc { double_symidx } => vec![double_symidx],
+                Trans::FptosiF64 { double_symidx } => vec![double_symidx],
+                Trans::SitofpF64 { int_symidx } => vec![int_symidx],
                 Trans::Bitcast { rptr_symidx, rptr_type:_, lptr_type:_ } => vec![rptr_symidx],
             }
             ,
@@ -366,12 +426,20 @@ impl NhwcInstr {
                 ArithOp::LogicAnd { a, b, vartype:_ } => vec![a,b],
                 ArithOp::LogicOr { a, b, vartype:_ } => vec![a,b],
                 ArithOp::LogicNot { a, vartype :_} => vec![a],
+                ArithOp::Select { cond, a, b, vartype:_ } => vec![cond,a,b],
+                ArithOp::BitAnd { a, b, vartype:_ } => vec![a,b],
+                ArithOp::BitOr { a, b, vartype:_ } => vec![a,b],
+                ArithOp::BitXor { a, b, vartype:_ } => vec![a,b],
+                ArithOp::Shl { a, b, vartype:_ } => vec![a,b],
+                ArithOp::Ashr { a, b, vartype:_ } => vec![a,b],
+                ArithOp::Lshr { a, b, vartype:_ } => vec![a,b],
             }},
             NhwcInstrType::SimpleAssign { lhs:_, rhs, vartype: _ } => {
                  vec![rhs] 
             },
             NhwcInstrType::Call { op_lhs: _assigned, func_op } => {
-                func_op.actual_arg_symidx_vec.iter_mut().collect_vec()
+                let FuncOp { rc_func_symidx, actual_arg_symidx_vec, ret_type:_, is_indirect } = func_op;
+                actual_arg_symidx_vec.iter_mut().chain(is_indirect.then_some(rc_func_symidx)).collect_vec()
             },
             NhwcInstrType::Jump { jump_op } => {
                 match jump_op{
@@ -386,7 +454,19 @@ impl NhwcInstr {
             NhwcInstrType::TranType { lhs:_, op } => match op{
                 Trans::Fptosi { float_symidx } => vec![float_symidx],
                 Trans::Sitofp { int_symidx } => vec![int_symidx],
+                Trans::Fptoui { float_symidx } => vec![float_symidx],
+                Trans::Uitofp { int_symidx } => vec![int_symidx],
                 Trans::Zext { bool_symidx } => vec![bool_symidx],
+                Trans::Sext { int_symidx } => vec![int_symidx],
+                Trans::ZextU32 { uint_symidx } => vec![uint_symidx],
+                Trans::Trunc { long_symidx } => vec![long_symidx],
+                Trans::TruncI8 { int_symidx } => vec![int_symidx],
+                Trans::Fptosi64 { float_symidx } => vec![float_symidx],
+                Trans::Sitofp64 { long_symidx } => vec![long_symidx],
+                Trans::Fpext { float_symidx } => vec![float_symidx],
+                Trans::Fptrunc { double_symidx } => vec![double_symidx],
+                Trans::FptosiF64 { double_symidx } => vec![double_symidx],
+                Trans::SitofpF64 { int_symidx } => vec![int_symidx],
                 Trans::Bitcast { rptr_symidx, rptr_type:_, lptr_type:_ } => vec![rptr_symidx],
             }
             ,
@@ -431,6 +511,16 @@ impl CmpPlan{
             CmpPlan::Le => IcmpPlan::Sle,
         }
     }
+    pub fn to_unsigned_icmp_plan(&self) -> IcmpPlan{
+        match self{
+            CmpPlan::Eq => IcmpPlan::Eq,
+            CmpPlan::Ne => IcmpPlan::Ne,
+            CmpPlan::Gt => IcmpPlan::Ugt,
+            CmpPlan::Ge => IcmpPlan::Uge,
+            CmpPlan::Lt => IcmpPlan::Ult,
+            CmpPlan::Le => IcmpPlan::Ule,
+        }
+    }
     pub fn to_fcmp_plan(&self) -> FcmpPlan{
         match self{
             CmpPlan::Eq => FcmpPlan::Oeq,
@@ -467,8 +557,16 @@ pub enum FcmpPlan {
 
 #[derive(Clone, Debug)]
 pub struct ComparedPair {
-    compared:RcSymIdx,
-    label:RcSymIdx,
+    pub compared:RcSymIdx,
+    pub label:RcSymIdx,
+}
+impl ComparedPair{
+    pub fn new(compared:RcSymIdx, label:RcSymIdx)->Self{
+        Self{
+            compared,
+            label,
+        }
+    }
 }
 #[derive(Clone,EnumIs)]
 pub enum JumpOp {
@@ -495,8 +593,32 @@ pub enum Trans {
     Fptosi { float_symidx:RcSymIdx }, 
     /// 整数转浮点数
     Sitofp { int_symidx:RcSymIdx },   
+    /// 浮点转无符号整数
+    Fptoui { float_symidx:RcSymIdx }, 
+    /// 无符号整数转浮点数
+    Uitofp { int_symidx:RcSymIdx },   
     /// I1转整数
     Zext { bool_symidx:RcSymIdx },    
+    /// I32 符号扩展为 I64
+    Sext { int_symidx:RcSymIdx },     
+    /// U32 零扩展为 I64
+    ZextU32 { uint_symidx:RcSymIdx }, 
+    /// I64 截断为 I32 或 U32
+    Trunc { long_symidx:RcSymIdx },   
+    /// I32 截断为 I8 ，截断后仍保持符号扩展
+    TruncI8 { int_symidx:RcSymIdx },  
+    /// 浮点转 I64
+    Fptosi64 { float_symidx:RcSymIdx }, 
+    /// I64 转浮点
+    Sitofp64 { long_symidx:RcSymIdx }, 
+    /// F32 扩展为 F64
+    Fpext { float_symidx:RcSymIdx },  
+    /// F64 截断为 F32
+    Fptrunc { double_symidx:RcSymIdx }, 
+    /// F64 转整数，目标可能是 I32 U32 或 I64
+    FptosiF64 { double_symidx:RcSymIdx }, 
+    /// 整数转 F64，源可能是 I32 U32 或 I64
+    SitofpF64 { int_symidx:RcSymIdx }, 
     /// 指针类型转指针类型，比如I32指针转F32指针  
     /// 其他类型转I1通过变量和0进行比较得到  
     /// I1转f32分两步，先转I32，后转F32  
@@ -507,7 +629,19 @@ impl Debug for Trans {
         match self {
             Self::Sitofp { int_symidx } => write!(f, "sitofp i32 {:?} to f32", int_symidx.as_ref_borrow()),
             Self::Fptosi { float_symidx } => write!(f, "fptosi f32 {:?} to i32", float_symidx.as_ref_borrow()),
+            Self::Uitofp { int_symidx } => write!(f, "uitofp u32 {:?} to f32", int_symidx.as_ref_borrow()),
+            Self::Fptoui { float_symidx } => write!(f, "fptoui f32 {:?} to u32", float_symidx.as_ref_borrow()),
             Self::Zext { bool_symidx } => write!(f, "zext i1 {:?} to i32", bool_symidx.as_ref_borrow()),
+            Self::Sext { int_symidx } => write!(f, "sext i32 {:?} to i64", int_symidx.as_ref_borrow()),
+            Self::ZextU32 { uint_symidx } => write!(f, "zext u32 {:?} to i64", uint_symidx.as_ref_borrow()),
+            Self::Trunc { long_symidx } => write!(f, "trunc i64 {:?} to i32", long_symidx.as_ref_borrow()),
+            Self::TruncI8 { int_symidx } => write!(f, "trunc i32 {:?} to i8", int_symidx.as_ref_borrow()),
+            Self::Fptosi64 { float_symidx } => write!(f, "fptosi f32 {:?} to i64", float_symidx.as_ref_borrow()),
+            Self::Sitofp64 { long_symidx } => write!(f, "sitofp i64 {:?} to f32", long_symidx.as_ref_borrow()),
+            Self::Fpext { float_symidx } => write!(f, "fpext f32 {:?} to f64", float_symidx.as_ref_borrow()),
+            Self::Fptrunc { double_symidx } => write!(f, "fptrunc f64 {:?} to f32", double_symidx.as_ref_borrow()),
+            Self::FptosiF64 { double_symidx } => write!(f, "fptosi f64 {:?}", double_symidx.as_ref_borrow()),
+            Self::SitofpF64 { int_symidx } => write!(f, "sitofp {:?} to f64", int_symidx.as_ref_borrow()),
             Self::Bitcast { lptr_type, rptr_symidx, rptr_type } => {
                 write!(f, "bitcast {:?} {:?} to {:?}", rptr_type, rptr_symidx.as_ref_borrow(), lptr_type)
             }
@@ -541,6 +675,13 @@ impl NhwcInstrType {
     pub fn new_logic_and(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::LogicAnd { a, b, vartype } } }
     pub fn new_logic_or(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::LogicOr { a, b, vartype } } }
     pub fn new_logic_not(lhs:RcSymIdx, a:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::LogicNot { a, vartype } } }
+    pub fn new_select(lhs:RcSymIdx, cond:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::Select { cond, a, b, vartype } } }
+    pub fn new_bit_and(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::BitAnd { a, b, vartype } } }
+    pub fn new_bit_or(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::BitOr { a, b, vartype } } }
+    pub fn new_bit_xor(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::BitXor { a, b, vartype } } }
+    pub fn new_shl(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::Shl { a, b, vartype } } }
+    pub fn new_ashr(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::Ashr { a, b, vartype } } }
+    pub fn new_lshr(lhs:RcSymIdx, a:RcSymIdx, b:RcSymIdx, vartype:Type) -> Self { Self::Arith { lhs, rhs:ArithOp::Lshr { a, b, vartype } } }
     
     pub fn new_assign(lhs:RcSymIdx, rhs:RcSymIdx, vartype:Type) -> Self { Self::SimpleAssign { lhs, rhs, vartype } }
 
@@ -551,7 +692,11 @@ impl NhwcInstrType {
     // Instruction -> Call -> FuncOp
     pub fn new_func_call(assigned:Option<RcSymIdx>, func:RcSymIdx, args:Vec<RcSymIdx>,ret_type:Type) -> Self {
         //也许可以直接传入一个Func结构体
-        Self::Call { op_lhs: assigned, func_op:FuncOp { rc_func_symidx: func, actual_arg_symidx_vec: args,ret_type } }
+        Self::Call { op_lhs: assigned, func_op:FuncOp { rc_func_symidx: func, actual_arg_symidx_vec: args,ret_type, is_indirect:false } }
+    }
+    /// 通过函数指针 fn_ptr 调用，被调用的函数在运行时才能确定
+    pub fn new_indirect_func_call(assigned:Option<RcSymIdx>, fn_ptr:RcSymIdx, args:Vec<RcSymIdx>,ret_type:Type) -> Self {
+        Self::Call { op_lhs: assigned, func_op:FuncOp { rc_func_symidx: fn_ptr, actual_arg_symidx_vec: args,ret_type, is_indirect:true } }
     }
     // Instruction -> Jump ->JumpOp
     pub fn new_ret(op_ret_sym:Option<RcSymIdx>) -> Self { Self::Jump { jump_op:JumpOp::Ret { op_ret_sym } } }
@@ -575,7 +720,19 @@ impl NhwcInstrType {
     //自动类型转换
     pub fn new_int2float(int_symidx:RcSymIdx, float_symidx:RcSymIdx) -> Self { Self::TranType { lhs:float_symidx, op:Trans::Sitofp { int_symidx } } }
     pub fn new_float2int(float_symidx:RcSymIdx, int_symidx:RcSymIdx) -> Self { Self::TranType { lhs:int_symidx, op:Trans::Fptosi { float_symidx } } }
+    pub fn new_uint2float(int_symidx:RcSymIdx, float_symidx:RcSymIdx) -> Self { Self::TranType { lhs:float_symidx, op:Trans::Uitofp { int_symidx } } }
+    pub fn new_float2uint(float_symidx:RcSymIdx, int_symidx:RcSymIdx) -> Self { Self::TranType { lhs:int_symidx, op:Trans::Fptoui { float_symidx } } }
     pub fn new_bool2int(bool_symidx:RcSymIdx, int_symidx:RcSymIdx) -> Self { Self::TranType { lhs:int_symidx, op:Trans::Zext { bool_symidx } } }
+    pub fn new_int2long(int_symidx:RcSymIdx, long_symidx:RcSymIdx) -> Self { Self::TranType { lhs:long_symidx, op:Trans::Sext { int_symidx } } }
+    pub fn new_uint2long(uint_symidx:RcSymIdx, long_symidx:RcSymIdx) -> Self { Self::TranType { lhs:long_symidx, op:Trans::ZextU32 { uint_symidx } } }
+    pub fn new_long2int(long_symidx:RcSymIdx, int_symidx:RcSymIdx) -> Self { Self::TranType { lhs:int_symidx, op:Trans::Trunc { long_symidx } } }
+    pub fn new_int2char(int_symidx:RcSymIdx, char_symidx:RcSymIdx) -> Self { Self::TranType { lhs:char_symidx, op:Trans::TruncI8 { int_symidx } } }
+    pub fn new_long2float(long_symidx:RcSymIdx, float_symidx:RcSymIdx) -> Self { Self::TranType { lhs:float_symidx, op:Trans::Sitofp64 { long_symidx } } }
+    pub fn new_float2long(float_symidx:RcSymIdx, long_symidx:RcSymIdx) -> Self { Self::TranType { lhs:long_symidx, op:Trans::Fptosi64 { float_symidx } } }
+    pub fn new_float2double(float_symidx:RcSymIdx, double_symidx:RcSymIdx) -> Self { Self::TranType { lhs:double_symidx, op:Trans::Fpext { float_symidx } } }
+    pub fn new_double2float(double_symidx:RcSymIdx, float_symidx:RcSymIdx) -> Self { Self::TranType { lhs:float_symidx, op:Trans::Fptrunc { double_symidx } } }
+    pub fn new_double2int(double_symidx:RcSymIdx, int_symidx:RcSymIdx) -> Self { Self::TranType { lhs:int_symidx, op:Trans::FptosiF64 { double_symidx } } }
+    pub fn new_int2double(int_symidx:RcSymIdx, double_symidx:RcSymIdx) -> Self { Self::TranType { lhs:double_symidx, op:Trans::SitofpF64 { int_symidx } } }
     pub fn new_ptr2ptr(lptr:RcSymIdx, lptr_type:Type, rptr:RcSymIdx, rptr_type:Type) -> Self { Self::TranType { lhs:lptr, op:Trans::Bitcast { rptr_symidx:rptr, rptr_type, lptr_type } } }
 
     pub fn get_lhs(&self)->Option<RcSymIdx>{
@@ -625,6 +782,13 @@ impl Debug for ArithOp {
             Self::LogicAnd { a, b, vartype } => write!(f, "And {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
             Self::LogicOr { a, b, vartype } => write!(f, "Or {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
             Self::LogicNot { a, vartype } => write!(f, "xor {:?} {:?}, true", vartype, a.as_ref_borrow()),
+            Self::Select { cond, a, b, vartype } => write!(f, "select i1 {:?}, {:?} {:?}, {:?}", cond.as_ref_borrow(), vartype, a.as_ref_borrow(), b.as_ref_borrow()),
+            Self::BitAnd { a, b, vartype } => write!(f, "BitAnd {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
+            Self::BitOr { a, b, vartype } => write!(f, "BitOr {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
+            Self::BitXor { a, b, vartype } => write!(f, "BitXor {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
+            Self::Shl { a, b, vartype } => write!(f, "Shl {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
+            Self::Ashr { a, b, vartype } => write!(f, "Ashr {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
+            Self::Lshr { a, b, vartype } => write!(f, "Lshr {:?} {:?}, {:?}", vartype, a.as_ref_borrow(), b.as_ref_borrow()),
         }
     }
 }
@@ -633,7 +797,11 @@ impl Debug for FuncOp {
         let new_str_vec = self.actual_arg_symidx_vec.iter().map(|x| format!("{:?}",x.as_ref_borrow())).collect_vec();
         let arg = new_str_vec.join(", ");
 
-        write!(f, " Call {:?} {:?}({})", self.ret_type,self.rc_func_symidx.as_ref_borrow(), arg)
+        if self.is_indirect{
+            write!(f, " Call {:?} indirect {:?}({})", self.ret_type,self.rc_func_symidx.as_ref_borrow(), arg)
+        }else{
+            write!(f, " Call {:?} {:?}({})", self.ret_type,self.rc_func_symidx.as_ref_borrow(), arg)
+        }
     }
 }
 impl Debug for JumpOp {
@@ -646,7 +814,10 @@ impl Debug for JumpOp {
                 write!(f, "br i1 {:?}, label {:?}, label {:?}", cond.as_ref_borrow(), t1.as_ref_borrow(), t2.as_ref_borrow())
             }
 
-            Self::Switch { cond: _, default: _, compared: _ } => write!(f, "还没见过"),
+            Self::Switch { cond, default, compared } => {
+                let compared_str = compared.iter().map(|pair| format!("i32 {:?}, label {:?}",pair.compared.as_ref_borrow(),pair.label.as_ref_borrow())).collect_vec().join("  ");
+                write!(f, "switch i32 {:?}, label {:?} [ {} ]", cond.as_ref_borrow(), default.as_ref_borrow(), compared_str)
+            }
 
             Self::DirectJump { label_symidx } => write!(f, "jump label: {:?}",label_symidx.as_ref_borrow()),
         }
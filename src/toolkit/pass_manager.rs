@@ -27,12 +27,15 @@ impl PassManager {
         for pass in &mut self.passes {
             let name = pass.get_pass_name();
             let may_err = panic::catch_unwind(AssertUnwindSafe(||{
-                pass.run(&mut self.ctx).unwrap();
+                pass.run(&mut self.ctx)
             }));
             pass.when_finish_or_panic(&mut self.ctx);
-            if may_err.is_err(){
-                may_err.unwrap();
-                return Err(anyhow::anyhow!("Error occurred when running Pass {}",name ));
+            match may_err {
+                Ok(Ok(())) => {},
+                // pass 返回的错误(例如源码中的错误)已经输出过诊断信息，交给调用者决定退出码
+                Ok(Err(e)) => return Err(e.context(format!("Error occurred when running Pass {}",name))),
+                // 编译器自身的 bug ，panic 信息已经输出过了，继续向上抛
+                Err(panic_payload) => panic::resume_unwind(panic_payload),
             }
         }
             //println!("{}", format!("Pass {} run successfully", pass.get_pass_name()).green());
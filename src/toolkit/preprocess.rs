@@ -0,0 +1,677 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+
+/// include 的最大嵌套层数，防止没有 include guard 的头文件无限展开
+const MAX_INCLUDE_DEPTH:usize = 200;
+
+/// 宏定义，params 为 None 时是对象宏，否则是函数宏
+#[derive(Debug, Clone)]
+pub struct Macro {
+    pub params:Option<Vec<String>>,
+    pub body:String,
+}
+
+/// 预处理之后代码的每一行对应的原始 (文件名, 行号)，行号从 1 开始
+#[derive(Debug, Clone, Default)]
+pub struct LineMap {
+    pub lines:Vec<(Arc<str>, usize)>,
+}
+impl LineMap {
+    pub fn new() -> Self { LineMap { lines:vec![] } }
+    pub fn push(&mut self, file:&Arc<str>, line:usize) { self.lines.push((file.clone(), line)); }
+    /// 输入预处理之后代码的行号(从 1 开始)，返回原始的文件名和行号
+    pub fn get_src_line(&self, line:usize) -> Option<&(Arc<str>, usize)> {
+        if line == 0 { None } else { self.lines.get(line - 1) }
+    }
+}
+
+/// #if 嵌套时每一层的状态
+#[derive(Debug, Clone)]
+struct CondState {
+    /// 当前分支的代码是否保留
+    is_active:bool,
+    /// 这一组 #if/#elif/#else 中是否已经有分支被选中
+    is_taken:bool,
+    /// 外层是否保留，外层不保留时里面所有分支都不保留
+    is_parent_active:bool,
+}
+
+pub struct Preprocessor {
+    pub macros:HashMap<String, Macro>,
+    pub include_dirs:Vec<PathBuf>,
+    include_depth:usize,
+    /// 预处理之后的代码
+    pub code:String,
+    pub line_map:LineMap,
+}
+impl Preprocessor {
+    pub fn new(include_dirs:Vec<PathBuf>) -> Self {
+        Preprocessor { macros:HashMap::new(), include_dirs, include_depth:0, code:String::new(), line_map:LineMap::new() }
+    }
+    /// 处理命令行中的 -D ，形如 NAME 、 NAME=VALUE 或者 NAME(a,b)=BODY
+    pub fn define_from_cmdline(&mut self, def:&str) -> Result<()> {
+        match def.split_once('=') {
+            Some((name, value)) => self.parse_define(&format!("{} {}", name, value)),
+            None => self.parse_define(&format!("{} 1", def)),
+        }
+    }
+    pub fn process_file(&mut self, path:&Path) -> Result<()> {
+        let text = fs::read_to_string(path).map_err(|e| anyhow!("无法读取文件 {} : {}", path.display(), e))?;
+        self.process_text(&text, &path.to_string_lossy(), path.parent())
+    }
+    /// 预处理一段代码，file 用于 __FILE__ 和行号映射， dir 是 #include "..." 首先查找的目录
+    pub fn process_text(&mut self, text:&str, file:&str, dir:Option<&Path>) -> Result<()> {
+        let mut cond_stack:Vec<CondState> = vec![];
+        let rc_file:Arc<str> = file.into();
+        // 实参还没有写完的函数宏调用所在的 (起始行号, 已经合并的代码)
+        let mut pending:Option<(usize, String)> = None;
+        for (line_no, line) in split_logical_lines(&strip_comments(text)) {
+            let is_active = cond_stack.last().is_none_or(|c| c.is_active);
+            let trimmed = line.trim_start();
+            if let Some(directive) = trimmed.strip_prefix('#') {
+                if let Some((start_line_no, _)) = pending {
+                    return Err(anyhow!("{}:{}: 宏调用缺少 ')'", file, start_line_no));
+                }
+                let directive = directive.trim_start();
+                let name_len = directive.find(|c:char| !is_ident_char(c)).unwrap_or(directive.len());
+                let (name, rest) = (&directive[..name_len], directive[name_len..].trim());
+                match name {
+                    "ifdef" | "ifndef" => {
+                        let is_defined = self.macros.contains_key(rest.split_whitespace().next().unwrap_or(""));
+                        let is_true = is_active && (is_defined == (name == "ifdef"));
+                        cond_stack.push(CondState { is_active:is_true, is_taken:is_true, is_parent_active:is_active });
+                    }
+                    "if" => {
+                        let is_true = is_active && self.eval_cond(rest, file, line_no)?;
+                        cond_stack.push(CondState { is_active:is_true, is_taken:is_true, is_parent_active:is_active });
+                    }
+                    "elif" => {
+                        let cond = cond_stack.last().cloned().ok_or(anyhow!("{}:{}: #elif 没有对应的 #if", file, line_no))?;
+                        let is_true = cond.is_parent_active && !cond.is_taken && self.eval_cond(rest, file, line_no)?;
+                        let top = cond_stack.last_mut().unwrap();
+                        top.is_active = is_true;
+                        top.is_taken |= is_true;
+                    }
+                    "else" => {
+                        let top = cond_stack.last_mut().ok_or(anyhow!("{}:{}: #else 没有对应的 #if", file, line_no))?;
+                        top.is_active = top.is_parent_active && !top.is_taken;
+                        top.is_taken = true;
+                    }
+                    "endif" => {
+                        cond_stack.pop().ok_or(anyhow!("{}:{}: #endif 没有对应的 #if", file, line_no))?;
+                    }
+                    _ if !is_active => {}
+                    "define" => self.parse_define(rest).map_err(|e| anyhow!("{}:{}: {}", file, line_no, e))?,
+                    "undef" => { self.macros.remove(rest); }
+                    "include" => self.process_include(rest, file, line_no, dir)?,
+                    "error" => return Err(anyhow!("{}:{}: #error {}", file, line_no, rest)),
+                    "pragma" | "line" | "" => {}
+                    _ => return Err(anyhow!("{}:{}: 不支持的预处理指令 #{}", file, line_no, name)),
+                }
+                continue;
+            }
+            if !is_active { continue; }
+            // 函数宏的实参可以跨越多行，把后面的行合并进来直到括号闭合，合并之后的代码都算在起始行上
+            let (line_no, line) = match pending.take() {
+                Some((start_line_no, mut joined)) => {
+                    joined.push(' ');
+                    joined.push_str(&line);
+                    (start_line_no, joined)
+                }
+                None => (line_no, line),
+            };
+            if self.has_unterminated_macro_call(&line) {
+                pending = Some((line_no, line));
+                continue;
+            }
+            let expanded = self.expand(&line, file, line_no, &mut vec![]).map_err(|e| anyhow!("{}:{}: {}", file, line_no, e))?;
+            self.code.push_str(&expanded);
+            self.code.push('\n');
+            self.line_map.push(&rc_file, line_no);
+        }
+        if let Some((start_line_no, _)) = pending {
+            return Err(anyhow!("{}:{}: 宏调用缺少 ')'", file, start_line_no));
+        }
+        if !cond_stack.is_empty() {
+            return Err(anyhow!("{}: #if 缺少对应的 #endif", file));
+        }
+        Ok(())
+    }
+    /// 一行代码中是否有实参还没有写完的函数宏调用，有的话需要和后面的行合并之后再展开
+    fn has_unterminated_macro_call(&self, text:&str) -> bool {
+        let chars:Vec<char> = text.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '"' || c == '\'' {
+                i = skip_quoted(&chars, i);
+            }else if c.is_ascii_digit() {
+                i += chars[i..].iter().take_while(|&&c| is_ident_char(c) || c == '.').count();
+            }else if is_ident_char(c) {
+                let end = i + chars[i..].iter().take_while(|&&c| is_ident_char(c)).count();
+                let ident:String = chars[i..end].iter().collect();
+                i = end;
+                if self.macros.get(&ident).is_some_and(|m| m.params.is_some()) {
+                    let args_start = i + chars[i..].iter().take_while(|c| c.is_whitespace()).count();
+                    if chars.get(args_start) == Some(&'(') {
+                        match split_macro_args(&chars, args_start) {
+                            Ok((_, args_end)) => i = args_end,
+                            Err(_) => return true,
+                        }
+                    }
+                }
+            }else {
+                i += 1;
+            }
+        }
+        false
+    }
+    /// 处理 #include "file" 和 #include <file>，前者先在当前文件所在目录查找，然后都在 -I 指定的目录中查找
+    fn process_include(&mut self, rest:&str, file:&str, line_no:usize, dir:Option<&Path>) -> Result<()> {
+        let (header, is_quoted) = if let Some(header) = rest.strip_prefix('"').and_then(|s| s.split('"').next()) {
+            (header, true)
+        }else if let Some(header) = rest.strip_prefix('<').and_then(|s| s.split('>').next()) {
+            (header, false)
+        }else {
+            return Err(anyhow!("{}:{}: #include 后面应该是 \"file\" 或者 <file>", file, line_no));
+        };
+        let cur_dir = if is_quoted { dir.map(|d| d.to_path_buf()) } else { None };
+        let path = cur_dir.into_iter().chain(self.include_dirs.iter().cloned())
+            .map(|d| d.join(header))
+            .find(|p| p.is_file())
+            .ok_or(anyhow!("{}:{}: 找不到头文件 {}", file, line_no, header))?;
+        if self.include_depth >= MAX_INCLUDE_DEPTH {
+            return Err(anyhow!("{}:{}: #include 嵌套过深，可能缺少 include guard", file, line_no));
+        }
+        self.include_depth += 1;
+        let rst = self.process_file(&path);
+        self.include_depth -= 1;
+        rst
+    }
+    /// 解析 #define 之后的部分，名字后面紧跟 '(' 的是函数宏
+    fn parse_define(&mut self, rest:&str) -> Result<()> {
+        let name_len = rest.find(|c:char| !is_ident_char(c)).unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        if name.is_empty() || name.starts_with(|c:char| c.is_ascii_digit()) {
+            return Err(anyhow!("#define 后面应该是宏的名字 而不是 {}", rest));
+        }
+        let after_name = &rest[name_len..];
+        let r#macro = if let Some(params_and_body) = after_name.strip_prefix('(') {
+            let (params, body) = params_and_body.split_once(')').ok_or(anyhow!("函数宏 {} 的参数列表缺少 ')'", name))?;
+            let params:Vec<String> = params.split(',').map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect();
+            Macro { params:Some(params), body:body.trim().to_string() }
+        }else {
+            Macro { params:None, body:after_name.trim().to_string() }
+        };
+        self.macros.insert(name.to_string(), r#macro);
+        Ok(())
+    }
+    /// 对一行代码做宏展开，disabled 中是正在展开的宏，防止递归展开
+    fn expand(&self, text:&str, file:&str, line_no:usize, disabled:&mut Vec<String>) -> Result<String> {
+        let chars:Vec<char> = text.chars().collect();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '"' || c == '\'' {
+                let end = skip_quoted(&chars, i);
+                out.extend(&chars[i..end]);
+                i = end;
+            }else if c.is_ascii_digit() {
+                // pp-number ，避免把 0x1f 中的 x1f 当成标识符
+                let end = i + chars[i..].iter().take_while(|&&c| is_ident_char(c) || c == '.').count();
+                out.extend(&chars[i..end]);
+                i = end;
+            }else if is_ident_char(c) {
+                let end = i + chars[i..].iter().take_while(|&&c| is_ident_char(c)).count();
+                let ident:String = chars[i..end].iter().collect();
+                i = end;
+                match (ident.as_str(), self.macros.get(&ident)) {
+                    ("__LINE__", _) => out.push_str(&line_no.to_string()),
+                    ("__FILE__", _) => out.push_str(&format!("\"{}\"", file.replace('\\', "\\\\"))),
+                    (_, Some(r#macro)) if !disabled.contains(&ident) => {
+                        match &r#macro.params {
+                            None => {
+                                disabled.push(ident);
+                                out.push_str(&self.expand(&r#macro.body, file, line_no, disabled)?);
+                                disabled.pop();
+                            }
+                            Some(params) => {
+                                let args_start = i + chars[i..].iter().take_while(|c| c.is_whitespace()).count();
+                                if chars.get(args_start) != Some(&'(') {
+                                    // 函数宏后面没有括号时不展开
+                                    out.push_str(&ident);
+                                    continue;
+                                }
+                                let (args, args_end) = split_macro_args(&chars, args_start)?;
+                                let args = if params.is_empty() && args.len() == 1 && args[0].is_empty() { vec![] } else { args };
+                                if args.len() != params.len() {
+                                    return Err(anyhow!("宏 {} 需要 {} 个参数，但是传入了 {} 个", ident, params.len(), args.len()));
+                                }
+                                let substituted = self.substitute(params, &args, &r#macro.body, file, line_no, disabled)?;
+                                disabled.push(ident);
+                                out.push_str(&self.expand(&substituted, file, line_no, disabled)?);
+                                disabled.pop();
+                                i = args_end;
+                            }
+                        }
+                    }
+                    _ => out.push_str(&ident),
+                }
+            }else {
+                out.push(c);
+                i += 1;
+            }
+        }
+        Ok(out)
+    }
+    /// 把函数宏的形参替换为实参，处理 #param 字符串化和 a ## b 拼接
+    fn substitute(&self, params:&[String], args:&[String], body:&str, file:&str, line_no:usize, disabled:&mut Vec<String>) -> Result<String> {
+        let tokens = tokenize(body);
+        let is_paste = |idx:Option<usize>| idx.and_then(|idx| tokens.get(idx)).is_some_and(|t| t == "##");
+        let prev_non_space = |idx:usize| (0..idx).rev().find(|&j| !tokens[j].trim().is_empty());
+        let next_non_space = |idx:usize| (idx + 1..tokens.len()).find(|&j| !tokens[j].trim().is_empty());
+        let mut pieces:Vec<String> = vec![];
+        let mut idx = 0;
+        while idx < tokens.len() {
+            let token = &tokens[idx];
+            if token == "#" {
+                if let Some(param_idx) = next_non_space(idx).and_then(|j| params.iter().position(|p| p == &tokens[j])) {
+                    pieces.push(format!("\"{}\"", args[param_idx].replace('\\', "\\\\").replace('"', "\\\"")));
+                    idx = next_non_space(idx).unwrap() + 1;
+                    continue;
+                }
+            }
+            if let Some(param_idx) = params.iter().position(|p| p == token) {
+                // ## 两侧的实参不做宏展开
+                if is_paste(prev_non_space(idx)) || is_paste(next_non_space(idx)) {
+                    pieces.push(args[param_idx].clone());
+                }else {
+                    pieces.push(self.expand(&args[param_idx], file, line_no, disabled)?);
+                }
+            }else {
+                pieces.push(token.clone());
+            }
+            idx += 1;
+        }
+        // 去掉 ## 以及它两侧的空白，让两边的记号拼接在一起
+        let mut out = String::new();
+        let mut idx = 0;
+        while idx < pieces.len() {
+            if pieces[idx] == "##" {
+                out.truncate(out.trim_end().len());
+                idx += 1;
+                while idx < pieces.len() && pieces[idx].trim().is_empty() { idx += 1; }
+                continue;
+            }
+            out.push_str(&pieces[idx]);
+            idx += 1;
+        }
+        Ok(out)
+    }
+    /// 计算 #if 和 #elif 的条件，先处理 defined ，再做宏展开，剩下的标识符都当作 0
+    fn eval_cond(&self, expr:&str, file:&str, line_no:usize) -> Result<bool> {
+        let tokens = tokenize(expr);
+        let mut replaced = String::new();
+        let mut idx = 0;
+        while idx < tokens.len() {
+            if tokens[idx] == "defined" {
+                let rest:Vec<&String> = tokens[idx + 1..].iter().filter(|t| !t.trim().is_empty()).collect();
+                let (name, skip) = match rest.as_slice() {
+                    [lp, name, rp, ..] if lp.as_str() == "(" && rp.as_str() == ")" => (name.as_str(), 3),
+                    [name, ..] => (name.as_str(), 1),
+                    [] => return Err(anyhow!("{}:{}: defined 后面缺少宏的名字", file, line_no)),
+                };
+                replaced.push_str(if self.macros.contains_key(name) { " 1 " } else { " 0 " });
+                let mut remain = skip;
+                idx += 1;
+                while remain > 0 {
+                    if !tokens[idx].trim().is_empty() { remain -= 1; }
+                    idx += 1;
+                }
+                continue;
+            }
+            replaced.push_str(&tokens[idx]);
+            idx += 1;
+        }
+        let expanded = self.expand(&replaced, file, line_no, &mut vec![])?;
+        let tokens:Vec<String> = tokenize(&expanded).into_iter()
+            .filter(|t| !t.trim().is_empty())
+            .map(|t| if t.starts_with(|c:char| c.is_alphabetic() || c == '_') { "0".to_string() } else { t })
+            .collect();
+        let mut parser = CondExprParser { tokens:&tokens, pos:0 };
+        let value = parser.parse_ternary().map_err(|e| anyhow!("{}:{}: #if {} : {}", file, line_no, expr, e))?;
+        if parser.pos != tokens.len() {
+            return Err(anyhow!("{}:{}: #if {} 中有多余的记号 {}", file, line_no, expr, tokens[parser.pos]));
+        }
+        Ok(value != 0)
+    }
+}
+
+/// #if 条件表达式的求值，按照 C 的优先级做 precedence climbing
+struct CondExprParser<'a> {
+    tokens:&'a [String],
+    pos:usize,
+}
+impl<'a> CondExprParser<'a> {
+    fn peek(&self) -> Option<&str> { self.tokens.get(self.pos).map(|t| t.as_str()) }
+    fn expect(&mut self, token:&str) -> Result<()> {
+        if self.peek() == Some(token) { self.pos += 1; Ok(()) } else { Err(anyhow!("缺少 {}", token)) }
+    }
+    fn parse_ternary(&mut self) -> Result<i64> {
+        let cond = self.parse_binary(1)?;
+        if self.peek() == Some("?") {
+            self.pos += 1;
+            let then_value = self.parse_ternary()?;
+            self.expect(":")?;
+            let else_value = self.parse_ternary()?;
+            return Ok(if cond != 0 { then_value } else { else_value });
+        }
+        Ok(cond)
+    }
+    fn parse_binary(&mut self, min_prec:u8) -> Result<i64> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(prec) = self.peek().and_then(binary_prec).filter(|&p| p >= min_prec) {
+            let op = self.tokens[self.pos].clone();
+            self.pos += 1;
+            let rhs = self.parse_binary(prec + 1)?;
+            lhs = match op.as_str() {
+                "||" => ((lhs != 0) || (rhs != 0)) as i64,
+                "&&" => ((lhs != 0) && (rhs != 0)) as i64,
+                "|" => lhs | rhs,
+                "^" => lhs ^ rhs,
+                "&" => lhs & rhs,
+                "==" => (lhs == rhs) as i64,
+                "!=" => (lhs != rhs) as i64,
+                "<" => (lhs < rhs) as i64,
+                "<=" => (lhs <= rhs) as i64,
+                ">" => (lhs > rhs) as i64,
+                ">=" => (lhs >= rhs) as i64,
+                "<<" => lhs.wrapping_shl(rhs as u32),
+                ">>" => lhs.wrapping_shr(rhs as u32),
+                "+" => lhs.wrapping_add(rhs),
+                "-" => lhs.wrapping_sub(rhs),
+                "*" => lhs.wrapping_mul(rhs),
+                "/" | "%" if rhs == 0 => return Err(anyhow!("除以 0")),
+                "/" => lhs.wrapping_div(rhs),
+                "%" => lhs.wrapping_rem(rhs),
+                _ => unreachable!(),
+            };
+        }
+        Ok(lhs)
+    }
+    fn parse_unary(&mut self) -> Result<i64> {
+        match self.peek() {
+            Some("!") => { self.pos += 1; Ok((self.parse_unary()? == 0) as i64) }
+            Some("~") => { self.pos += 1; Ok(!self.parse_unary()?) }
+            Some("-") => { self.pos += 1; Ok(self.parse_unary()?.wrapping_neg()) }
+            Some("+") => { self.pos += 1; self.parse_unary() }
+            Some("(") => {
+                self.pos += 1;
+                let value = self.parse_ternary()?;
+                self.expect(")")?;
+                Ok(value)
+            }
+            Some(token) => {
+                let value = parse_pp_int(token).ok_or(anyhow!("无法识别的记号 {}", token))?;
+                self.pos += 1;
+                Ok(value)
+            }
+            None => Err(anyhow!("表达式不完整")),
+        }
+    }
+}
+
+fn binary_prec(op:&str) -> Option<u8> {
+    match op {
+        "||" => Some(1),
+        "&&" => Some(2),
+        "|" => Some(3),
+        "^" => Some(4),
+        "&" => Some(5),
+        "==" | "!=" => Some(6),
+        "<" | "<=" | ">" | ">=" => Some(7),
+        "<<" | ">>" => Some(8),
+        "+" | "-" => Some(9),
+        "*" | "/" | "%" => Some(10),
+        _ => None,
+    }
+}
+
+/// 解析 #if 中的整数，支持十进制、十六进制、八进制以及 u/l 后缀和字符字面量
+fn parse_pp_int(token:&str) -> Option<i64> {
+    if let Some(ch) = token.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        return match ch {
+            "\\n" => Some(10),
+            "\\t" => Some(9),
+            "\\0" => Some(0),
+            "\\\\" => Some(92),
+            "\\'" => Some(39),
+            _ => ch.chars().next().filter(|_| ch.chars().count() == 1).map(|c| c as i64),
+        };
+    }
+    let digits = token.trim_end_matches(['u', 'U', 'l', 'L']);
+    if let Some(hex) = digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()
+    }else if digits.len() > 1 && digits.starts_with('0') {
+        i64::from_str_radix(&digits[1..], 8).ok()
+    }else {
+        digits.parse().ok()
+    }
+}
+
+fn is_ident_char(c:char) -> bool { c.is_alphanumeric() || c == '_' }
+
+/// 从引号处开始，返回字符串或字符字面量结束之后的位置
+fn skip_quoted(chars:&[char], start:usize) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() && chars[i] != quote {
+        if chars[i] == '\\' { i += 1; }
+        i += 1;
+    }
+    (i + 1).min(chars.len())
+}
+
+/// 从 '(' 开始切分函数宏的实参，返回实参以及 ')' 之后的位置
+fn split_macro_args(chars:&[char], start:usize) -> Result<(Vec<String>, usize)> {
+    let mut args = vec![];
+    let mut cur = String::new();
+    let mut depth = 0;
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                let end = skip_quoted(chars, i);
+                cur.extend(&chars[i..end]);
+                i = end;
+                continue;
+            }
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                args.push(cur.trim().to_string());
+                return Ok((args, i + 1));
+            }
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(cur.trim().to_string());
+                cur.clear();
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        cur.push(c);
+        i += 1;
+    }
+    Err(anyhow!("宏调用缺少 ')'"))
+}
+
+/// 把一段文本切成预处理记号：标识符、数字、字面量、空白以及运算符
+fn tokenize(text:&str) -> Vec<String> {
+    let chars:Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let end = if c == '"' || c == '\'' {
+            skip_quoted(&chars, i)
+        }else if c.is_whitespace() {
+            i + chars[i..].iter().take_while(|c| c.is_whitespace()).count()
+        }else if c.is_ascii_digit() {
+            i + chars[i..].iter().take_while(|&&c| is_ident_char(c) || c == '.').count()
+        }else if is_ident_char(c) {
+            i + chars[i..].iter().take_while(|&&c| is_ident_char(c)).count()
+        }else {
+            let two:String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if ["##", "||", "&&", "==", "!=", "<=", ">=", "<<", ">>"].contains(&two.as_str()) { i + 2 } else { i + 1 }
+        };
+        tokens.push(chars[i..end].iter().collect());
+        i = end;
+    }
+    tokens
+}
+
+/// 去掉注释，块注释中的换行保留下来，这样行号不会错位
+fn strip_comments(text:&str) -> String {
+    let chars:Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' || c == '\'' {
+            let end = skip_quoted(&chars, i);
+            out.extend(&chars[i..end]);
+            i = end;
+        }else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' { i += 1; }
+            out.push(' ');
+        }else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' { out.push('\n'); }
+                i += 1;
+            }
+            i += 2;
+            out.push(' ');
+        }else {
+            out.push(c);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// 把以 '\' 结尾的续行合并成一个逻辑行，返回 (起始行号, 逻辑行)
+fn split_logical_lines(text:&str) -> Vec<(usize, String)> {
+    let mut logical_lines = vec![];
+    let mut cur:Option<(usize, String)> = None;
+    for (idx, line) in text.lines().enumerate() {
+        let (line_no, buf) = cur.get_or_insert((idx + 1, String::new()));
+        match line.strip_suffix('\\') {
+            Some(line) => buf.push_str(line),
+            None => {
+                buf.push_str(line);
+                logical_lines.push((*line_no, std::mem::take(buf)));
+                cur = None;
+            }
+        }
+    }
+    if let Some(last) = cur { logical_lines.push(last); }
+    logical_lines
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn preprocess(text:&str) -> Result<Preprocessor> {
+        let mut preprocessor = Preprocessor::new(vec![]);
+        preprocessor.process_text(text, "t.c", None)?;
+        Ok(preprocessor)
+    }
+    /// 找到预处理之后内容为 line 的行，返回它对应的原始行号
+    fn src_line_of(preprocessor:&Preprocessor, line:&str) -> usize {
+        let idx = preprocessor.code.lines().position(|l| l == line).unwrap_or_else(|| panic!("预处理结果中没有 {} :\n{}", line, preprocessor.code));
+        preprocessor.line_map.get_src_line(idx + 1).unwrap().1
+    }
+    fn temp_dir(name:&str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("preprocess_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_include_and_include_dirs() {
+        let (src_dir, inc_dir) = (temp_dir("src"), temp_dir("inc"));
+        fs::write(src_dir.join("a.h"), "#define A 1\n").unwrap();
+        fs::write(inc_dir.join("b.h"), "#ifndef B_H\n#define B_H\n#define B 2\nint b;\n#endif\n").unwrap();
+        fs::write(src_dir.join("main.c"), "#include \"a.h\"\n#include <b.h>\n#include \"b.h\"\nint x = A + B;\n").unwrap();
+        let mut preprocessor = Preprocessor::new(vec![inc_dir.clone()]);
+        preprocessor.process_file(&src_dir.join("main.c")).unwrap();
+        // "b.h" 在当前目录找不到时去 -I 中找，并且 include guard 让它只展开一次
+        assert_eq!(preprocessor.code, "int b;\nint x = 1 + 2;\n");
+        assert_eq!(preprocessor.line_map.get_src_line(1).unwrap(), &(inc_dir.join("b.h").to_string_lossy().into(), 4));
+        assert_eq!(preprocessor.line_map.get_src_line(2).unwrap(), &(src_dir.join("main.c").to_string_lossy().into(), 4));
+        // <a.h> 不在当前文件所在目录中查找
+        fs::write(src_dir.join("angle.c"), "#include <a.h>\n").unwrap();
+        let err = Preprocessor::new(vec![inc_dir.clone()]).process_file(&src_dir.join("angle.c")).unwrap_err();
+        assert!(err.to_string().contains("找不到头文件 a.h"));
+        fs::remove_dir_all(src_dir).unwrap();
+        fs::remove_dir_all(inc_dir).unwrap();
+    }
+
+    #[test]
+    fn test_cmdline_define() {
+        let mut preprocessor = Preprocessor::new(vec![]);
+        preprocessor.define_from_cmdline("N=10").unwrap();
+        preprocessor.define_from_cmdline("DEBUG").unwrap();
+        preprocessor.define_from_cmdline("SQ(x)=((x)*(x))").unwrap();
+        preprocessor.process_text("#if DEBUG\nint a = SQ(N);\n#endif\n", "t.c", None).unwrap();
+        assert_eq!(preprocessor.code, "int a = ((10)*(10));\n");
+        assert!(Preprocessor::new(vec![]).define_from_cmdline("1X=2").is_err());
+    }
+
+    #[test]
+    fn test_if_elif() {
+        let text = "#define V 2\n#if V == 1\none\n#elif V == 2 && defined(V)\ntwo\n#if 0\nnested\n#elif 1\nnested_elif\n#endif\n#elif 1\nthree\n#else\nfour\n#endif\n";
+        assert_eq!(preprocess(text).unwrap().code, "two\nnested_elif\n");
+        // 外层不保留时，里面的 #elif 和 #else 也都不保留
+        let text = "#ifdef UNDEFINED\n#if 0\n#elif 1\na\n#else\nb\n#endif\n#elif !defined UNDEFINED\nc\n#endif\n";
+        assert_eq!(preprocess(text).unwrap().code, "c\n");
+        assert!(preprocess("#if 1\n#else\n").is_err());
+        assert!(preprocess("#elif 1\n").is_err());
+    }
+
+    #[test]
+    fn test_line_mapping() {
+        let text = "int a; /* x\n y */\n#define L __LINE__\nint b = L;\nint c = \\\n __LINE__;\nint d = __LINE__;\n";
+        let preprocessor = preprocess(text).unwrap();
+        assert_eq!(src_line_of(&preprocessor, "int b = 4;"), 4);
+        // 续行合并成一个逻辑行，算在起始行上
+        assert_eq!(src_line_of(&preprocessor, "int c =  5;"), 5);
+        assert_eq!(src_line_of(&preprocessor, "int d = 7;"), 7);
+    }
+
+    #[test]
+    fn test_multi_line_macro_call() {
+        let text = "#define ADD(a, b) ((a) + (b))\nint x = ADD(1,\n    ADD(2,\n  3));\nint y = ADD(\n \")\",\n 4);\nint z = __LINE__;\n";
+        let preprocessor = preprocess(text).unwrap();
+        assert_eq!(src_line_of(&preprocessor, "int x = ((1) + (((2) + (3))));"), 2);
+        assert_eq!(src_line_of(&preprocessor, "int y = ((\")\") + (4));"), 5);
+        assert_eq!(src_line_of(&preprocessor, "int z = 8;"), 8);
+        // 到文件末尾或者遇到预处理指令时括号还没有闭合就报告起始行
+        let err = preprocess("#define F(a) a\nint x;\nint y = F(1,\n2;\n").err().unwrap();
+        assert!(err.to_string().contains("t.c:3: 宏调用缺少 ')'"));
+        let err = preprocess("#define F(a) a\nint y = F(1\n#define G\n);\n").err().unwrap();
+        assert!(err.to_string().contains("t.c:2: 宏调用缺少 ')'"));
+    }
+}
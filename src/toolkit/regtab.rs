@@ -375,7 +375,7 @@ impl RegTab{
     pub fn find_avail_reg_for_ty(&mut self, sym_ty:&TypeDiscriminants) -> Register {
         // find tail in priority 
         // only use s register when meet i32 or ptr or i1
-        if sym_ty.is_i_32() || sym_ty.is_i_1() || sym_ty.is_ptr_64()|| sym_ty.is_array(){
+        if sym_ty.is_i_32() || sym_ty.is_u_32() || sym_ty.is_i_8() || sym_ty.is_i_64() || sym_ty.is_i_1() || sym_ty.is_ptr_64()|| sym_ty.is_array() || sym_ty.is_struct() || sym_ty.is_union(){
             // alloc priority:
             // 1. released reg
             // 2. temp freed reg
@@ -395,7 +395,7 @@ impl RegTab{
             for i in REG_S_RANGE.clone(){ if self.is_freed(&Register::new_s(i)){ return Register::new_s(i) } }
 
             panic!("no avail(released or freed) reg fo ty {:?},{:?}",sym_ty,self)
-        }else if sym_ty.is_f_32(){
+        }else if sym_ty.is_f_32() || sym_ty.is_f_64(){
             if self.fpr_released_reg_count>0{
                 for i in REG_FA_RANGE.clone(){ if self.is_released(&Register::new_fa(i)){ return Register::new_fa(i) } }
                 for i in REG_FS_RANGE.clone(){ if self.is_released(&Register::new_fs(i)){ return Register::new_fs(i) } }
@@ -580,7 +580,7 @@ impl RegTab{
     ,) -> Result<Register>{
         // assert!(symidx.index_ssa.is_none());
         match sym_ty{
-            TypeDiscriminants::F32 => {
+            TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                 assert!(reg.is_fpr())
             },
             _ => {}
@@ -598,7 +598,7 @@ impl RegTab{
     ,) -> Result<Register>{
         // assert!(symidx.index_ssa.is_none());
         match sym_ty{
-            TypeDiscriminants::F32 => {
+            TypeDiscriminants::F32 | TypeDiscriminants::F64 => {
                 assert!(reg.is_fpr())
             },
             _ => {}
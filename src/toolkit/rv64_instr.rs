@@ -7,7 +7,7 @@ use strum_macros::EnumIs;
 
 use crate::{passes::ast2st_pass::Ast2StPass, toolkit::{field::{Type, Value}, symtab::WithBorrow}};
 
-use super::{field::TypeDiscriminants, symtab::{RcSymIdx, SymIdx}};
+use super::{field::TypeDiscriminants, scope_node::ST_ROOT, symtab::{RcSymIdx, SymIdx}};
 
 
 // #[derive(Clone)]
@@ -70,13 +70,22 @@ impl Debug for Imm{
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::GlobalLabel {symidx} =>{
-                write!(f,"{}",&symidx.as_ref_borrow().symbol_name)
+                // 只有 static 局部变量不在 ST_ROOT 中，不同函数里可能有同名的 static 局部变量，因此在标签后加上 scope
+                let symidx = symidx.as_ref_borrow();
+                if symidx.scope_node == ST_ROOT { write!(f,"{}",symidx.symbol_name) } else { write!(f,"{}.{}",symidx.symbol_name,symidx.scope_node) }
             },
             Self::Literal { symidx } => {
                 match &TypeDiscriminants::new_from_const_str(&symidx.as_ref_borrow().symbol_name) {
                     TypeDiscriminants::I32 => {
                         write!(f,"{}", symidx.as_ref_borrow())
                     },
+                    TypeDiscriminants::U32 => {
+                        // u32 在寄存器中同样是符号扩展的，所以按 i32 的比特位输出
+                        write!(f,"{}", Value::from_string_with_specific_type(symidx.as_ref_borrow().symbol_name, &Type::U32).as_i32())
+                    },
+                    TypeDiscriminants::I64 => {
+                        write!(f,"{}", Value::from_string_with_specific_type(symidx.as_ref_borrow().symbol_name, &Type::I64).as_i64())
+                    },
                     TypeDiscriminants::F32 => {
                         let f_val:f32 = match Value::from_string_with_specific_type(&symidx.as_ref_borrow().symbol_name, &Type::F32){
                             Value::F32(Some(f_val)) => f_val,
@@ -84,6 +93,14 @@ impl Debug for Imm{
                         };
                         write!(f,"{}", f_val.to_bits())
                     },
+                    TypeDiscriminants::F64 => {
+                        let f_val:f64 = match Value::from_string_with_specific_type(symidx.as_ref_borrow().symbol_name, &Type::F64){
+                            Value::F64(Some(f_val)) => f_val,
+                            _ => panic!()
+                        };
+                        // li 接受有符号的 64 位立即数
+                        write!(f,"{}", f_val.to_bits() as i64)
+                    },
                     TypeDiscriminants::I1 => {
                         if symidx.as_ref_borrow().symbol_name == "true"{
                             write!(f,"{}", 1)
@@ -176,6 +193,8 @@ pub enum PseudoInstr {
 
     Fmv_s {rd:Register ,rs:Register},//单精度符点移动
     Fmv_w_x {rd:Register ,rs:Register},//单精度符点移动
+    Fmv_d_x {rd:Register ,rs:Register},//整数寄存器的 64 位按位移动到浮点寄存器
+    Fmv_x_d {rd:Register ,rs:Register},//浮点寄存器的 64 位按位移动到整数寄存器
     Fabs_s {rd:Register ,rs:Register},//单精度取绝对值
     Fneg_s {rd:Register ,rs:Register},//单精度取反
     Fmv_d {rd:Register ,rs:Register},//双精度移动
@@ -201,7 +220,8 @@ impl PseudoInstr{
                 Self::new_mv(rd, rs)
             },
             (false,false)=> {
-                Self::new_fmv_s(rd, rs)
+                // fmv.d 会原样搬运 64 位，对 NaN-boxing 的 f32 同样适用
+                Self::new_fmv_d(rd, rs)
             },
             (false,true)=> {
                 Self::new_fmv_w_x(rd, rs)
@@ -269,6 +289,8 @@ impl Debug for PseudoInstr {
             PseudoInstr::Fabs_d { rd, rs } => write!(f, "{:7} {:?}, {:?}","fabs.d", rd, rs),
             PseudoInstr::Fneg_d { rd, rs } => write!(f, "{:7} {:?}, {:?}","fneg.d", rd, rs),
             PseudoInstr::Fmv_w_x { rd, rs } => write!(f, "{:7} {:?}, {:?}","fmv.w.x", rd, rs),
+            PseudoInstr::Fmv_d_x { rd, rs } => write!(f, "{:7} {:?}, {:?}","fmv.d.x", rd, rs),
+            PseudoInstr::Fmv_x_d { rd, rs } => write!(f, "{:7} {:?}, {:?}","fmv.x.d", rd, rs),
 
             PseudoInstr::Bgt { rs1: rs, rs2: rd, offset } => write!(f, "{:7} {:?}, {:?}, {:?}","bgt", rs, rd, offset),
             PseudoInstr::Ble { rs1: rs, rs2: rd, offset } => write!(f, "{:7} {:?}, {:?}, {:?}","ble", rs, rd, offset),
@@ -550,12 +572,19 @@ pub enum Arithmetic {
     DIVW { rd: Register, rs1: Register, rs2: Register },
     REM { rd: Register, rs1: Register, rs2: Register },
     REMW { rd: Register, rs1: Register, rs2: Register },
+    DIVUW { rd: Register, rs1: Register, rs2: Register },
+    REMUW { rd: Register, rs1: Register, rs2: Register },
     /// float Arithmetic
     FADDS { rd:Register, rs1:Register, rs2:Register },
     FSUBS { rd:Register, rs1:Register, rs2:Register },
     FMULS { rd:Register, rs1:Register, rs2:Register },
     FDIVS { rd:Register, rs1:Register, rs2:Register },
     FSQRTS { rd:Register, rs1:Register },
+    /// double Arithmetic
+    FADDD { rd:Register, rs1:Register, rs2:Register },
+    FSUBD { rd:Register, rs1:Register, rs2:Register },
+    FMULD { rd:Register, rs1:Register, rs2:Register },
+    FDIVD { rd:Register, rs1:Register, rs2:Register },
 }
 impl Arithmetic {
 }
@@ -577,11 +606,17 @@ impl Debug for Arithmetic {
             Arithmetic::DIVW { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","divw", rd, rs1, rs2),
             Arithmetic::REM { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","rem", rd, rs1, rs2),
             Arithmetic::REMW { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","remw", rd, rs1, rs2),
+            Arithmetic::DIVUW { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","divuw", rd, rs1, rs2),
+            Arithmetic::REMUW { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","remuw", rd, rs1, rs2),
             Arithmetic::FADDS { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fadd.s", rd, rs1, rs2),
             Arithmetic::FSUBS { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fsub.s", rd, rs1, rs2),
             Arithmetic::FMULS { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fmul.s", rd, rs1, rs2),
             Arithmetic::FDIVS { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fdiv.s", rd, rs1, rs2),   
             Arithmetic::FSQRTS { rd, rs1 } => write!(f, "{:7} {:?},{:?}","fsqrt.s", rd, rs1),
+            Arithmetic::FADDD { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fadd.d", rd, rs1, rs2),
+            Arithmetic::FSUBD { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fsub.d", rd, rs1, rs2),
+            Arithmetic::FMULD { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fmul.d", rd, rs1, rs2),
+            Arithmetic::FDIVD { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fdiv.d", rd, rs1, rs2),
         }
     }
 }
@@ -607,7 +642,7 @@ impl Debug for Logical {
             Logical::Xori { rd, rs1, imm } => write!(f, "{:7} {:?},{:?},{:?}","xori", rd, rs1, imm),
             Logical::Or { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","or", rd, rs1, rs2),
             Logical::Ori { rd, rs1, imm } => {
-                write!(f, "{:7} {:?},{:?},{:?}","ORI", rd, rs1, imm)
+                write!(f, "{:7} {:?},{:?},{:?}","ori", rd, rs1, imm)
             }
             Logical::And { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","and", rd, rs1, rs2),
             Logical::Andi { rd, rs1, imm } => write!(f, "{:7} {:?},{:?},{:?}","andi", rd, rs1, imm),
@@ -627,6 +662,9 @@ pub enum Compare {
     Feq_s{rd:Register,rs1:Register,rs2:Register},
     Flt_s{rd:Register,rs1:Register,rs2:Register},
     Fle_s{rd:Register,rs1:Register,rs2:Register},
+    Feq_d{rd:Register,rs1:Register,rs2:Register},
+    Flt_d{rd:Register,rs1:Register,rs2:Register},
+    Fle_d{rd:Register,rs1:Register,rs2:Register},
 }
 impl Debug for Compare {
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -638,6 +676,9 @@ impl Debug for Compare {
             Compare::Feq_s { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","feq.s" , rd, rs1, rs2),
             Compare::Flt_s { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","flt.s" , rd, rs1, rs2),
             Compare::Fle_s { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fle.s" , rd, rs1, rs2),
+            Compare::Feq_d { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","feq.d" , rd, rs1, rs2),
+            Compare::Flt_d { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","flt.d" , rd, rs1, rs2),
+            Compare::Fle_d { rd, rs1, rs2 } => write!(f, "{:7} {:?},{:?},{:?}","fle.d" , rd, rs1, rs2),
         }
     }
 }
@@ -854,12 +895,38 @@ impl Debug for Stores {
 pub enum Trans{
     Fcvt_w_s{rd:Register,rs1:Register},
     Fcvt_s_w{rd:Register,rs1:Register},
+    Fcvt_wu_s{rd:Register,rs1:Register},
+    Fcvt_s_wu{rd:Register,rs1:Register},
+    Fcvt_l_s{rd:Register,rs1:Register},
+    Fcvt_s_l{rd:Register,rs1:Register},
+    Fcvt_d_s{rd:Register,rs1:Register},
+    Fcvt_s_d{rd:Register,rs1:Register},
+    Fcvt_d_w{rd:Register,rs1:Register},
+    Fcvt_d_wu{rd:Register,rs1:Register},
+    Fcvt_d_l{rd:Register,rs1:Register},
+    Fcvt_w_d{rd:Register,rs1:Register},
+    Fcvt_wu_d{rd:Register,rs1:Register},
+    Fcvt_l_d{rd:Register,rs1:Register},
 }
 impl Debug for Trans{
     fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self{
             Trans::Fcvt_w_s { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.w.s", rd, rs1),
             Trans::Fcvt_s_w { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.s.w", rd, rs1),
+            Trans::Fcvt_wu_s { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.wu.s", rd, rs1),
+            Trans::Fcvt_s_wu { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.s.wu", rd, rs1),
+            Trans::Fcvt_l_s { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.l.s", rd, rs1),
+            Trans::Fcvt_s_l { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.s.l", rd, rs1),
+            // f32 与 i32 u32 到 f64 的转换是精确的，不需要舍入模式
+            Trans::Fcvt_d_s { rd, rs1 } => write!(f, "{:5} {:?},{:?}","fcvt.d.s", rd, rs1),
+            Trans::Fcvt_d_w { rd, rs1 } => write!(f, "{:5} {:?},{:?}","fcvt.d.w", rd, rs1),
+            Trans::Fcvt_d_wu { rd, rs1 } => write!(f, "{:5} {:?},{:?}","fcvt.d.wu", rd, rs1),
+            // 按 C 的语义舍入到最近
+            Trans::Fcvt_s_d { rd, rs1 } => write!(f, "{:5} {:?},{:?}","fcvt.s.d", rd, rs1),
+            Trans::Fcvt_d_l { rd, rs1 } => write!(f, "{:5} {:?},{:?}","fcvt.d.l", rd, rs1),
+            Trans::Fcvt_w_d { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.w.d", rd, rs1),
+            Trans::Fcvt_wu_d { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.wu.d", rd, rs1),
+            Trans::Fcvt_l_d { rd, rs1 } => write!(f, "{:5} {:?},{:?},rtz","fcvt.l.d", rd, rs1),
         }
     }
 }
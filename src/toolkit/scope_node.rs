@@ -57,7 +57,7 @@ impl Debug for ScopeNode {
                 ScopeType::Normal => "Normal",
                 ScopeType::Terminal => "Terminal",
                 ScopeType::If => "If",
-                ScopeType::Switch => "Switch",
+                ScopeType::Switch{ op_cfg_switch_node:_ } => "Switch",
                 ScopeType::Case => "Case",
             },
             self.ast_node,
@@ -77,6 +77,8 @@ pub enum ScopeType {
     Normal,
     Terminal,
     If,
-    Switch,
+    Switch{
+        op_cfg_switch_node:Option<u32>
+    },
     Case,
 }
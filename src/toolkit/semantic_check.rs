@@ -0,0 +1,777 @@
+use ahash::{HashMap, HashMapExt};
+
+use crate::antlr_parser::clexer::{
+    And, AndAnd, Const, Constant, Default, DigitSequence, Double, Ellipsis, Equal, Extern, Float, Greater, GreaterEqual, Identifier, LeftBracket, LeftParen, Less, LessEqual, MinusMinus, Not, NotEqual, OrOr, PlusPlus, Star, Static, StringLiteral, Void
+};
+use crate::antlr_parser::cparser::{
+    RULE_abstractDeclarator, RULE_additiveExpression, RULE_andExpression, RULE_argumentExpressionList, RULE_assignmentExpression, RULE_blockItem, RULE_blockItemList, RULE_breakStatement, RULE_compilationUnit, RULE_compoundStatement, RULE_conditionalExpression, RULE_constantExpression, RULE_continueStatement, RULE_declaration, RULE_declarationSpecifier, RULE_declarationSpecifiers, RULE_declarationSpecifiers2, RULE_declarator, RULE_directDeclarator, RULE_doWhileIterationStatement, RULE_enumSpecifier, RULE_enumerationConstant, RULE_enumerator, RULE_enumeratorList, RULE_equalityExpression, RULE_exclusiveOrExpression, RULE_expression, RULE_expressionStatement, RULE_externalDeclaration, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forCondition, RULE_forDeclaration, RULE_forIterationStatement, RULE_forMidExpression, RULE_functionDefinition, RULE_inclusiveOrExpression, RULE_initDeclarator, RULE_initDeclaratorList, RULE_initializer, RULE_initializerList, RULE_iterationStatement, RULE_jumpStatement, RULE_labeledStatement, RULE_logicalAndExpression, RULE_logicalOrExpression, RULE_multiplicativeExpression, RULE_parameterDeclaration, RULE_parameterList, RULE_parameterTypeList, RULE_pointer, RULE_postfixExpression, RULE_primaryExpression, RULE_relationalExpression, RULE_returnStatement, RULE_selectionStatement, RULE_shiftExpression, RULE_statement, RULE_structOrUnionSpecifier, RULE_switchSelection, RULE_translationUnit, RULE_typeQualifier, RULE_typeSpecifier, RULE_typedefName, RULE_unaryExpression, RULE_unaryOperator, RULE_whileIterationStatement
+};
+use crate::node;
+
+use super::ast_node::{get_storage_class_of_decl, is_typedef_decl, AstTree, SrcLoc};
+use super::diagnostic::Diagnostic;
+use super::field::Value;
+use super::literal::parse_num_literal;
+
+/// 语义检查只需要粗粒度的类型：基本类型加上指针层数(数组也算一层)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SemaBase {
+    Int,
+    Float,
+    Void,
+    Struct,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SemaTy {
+    Known { base:SemaBase, ptr_depth:usize },
+    /// 无法推断的类型，和任何类型都兼容，避免误报
+    Unknown,
+}
+impl SemaTy {
+    pub fn new_scalar(base:SemaBase) -> Self { SemaTy::Known { base, ptr_depth:0 } }
+    pub fn is_void_value(&self) -> bool { matches!(self, SemaTy::Known { base:SemaBase::Void, ptr_depth:0 }) }
+    pub fn is_float_value(&self) -> bool { matches!(self, SemaTy::Known { base:SemaBase::Float, ptr_depth:0 }) }
+    pub fn is_ptr(&self) -> bool { matches!(self, SemaTy::Known { ptr_depth, .. } if *ptr_depth > 0) }
+    fn with_depth(&self, f:impl Fn(usize) -> Option<usize>) -> Self {
+        match self {
+            SemaTy::Known { base, ptr_depth } => f(*ptr_depth).map_or(SemaTy::Unknown, |ptr_depth| SemaTy::Known { base:*base, ptr_depth }),
+            SemaTy::Unknown => SemaTy::Unknown,
+        }
+    }
+    /// 实参能否传给这个类型的形参
+    fn accepts(&self, arg_ty:&SemaTy) -> bool {
+        match (self, arg_ty) {
+            (SemaTy::Known { base:param_base, ptr_depth:param_depth }, SemaTy::Known { base:arg_base, ptr_depth:arg_depth }) => match (*param_depth, *arg_depth) {
+                (0, 0) => (*param_base == SemaBase::Struct) == (*arg_base == SemaBase::Struct),
+                (0, _) | (_, 0) => false,
+                // void * 可以接受任何指针
+                (1, _) if *param_base == SemaBase::Void => true,
+                (_, 1) if *arg_base == SemaBase::Void => true,
+                // 多维数组传给低维的形参在 SysY 中很常见(如 putfarray)，只要元素类型一致就允许
+                (param_depth, arg_depth) => param_depth <= arg_depth && param_base == arg_base,
+            },
+            _ => true,
+        }
+    }
+}
+impl std::fmt::Display for SemaTy {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SemaTy::Known { base, ptr_depth } => {
+                let base = match base { SemaBase::Int => "int", SemaBase::Float => "float", SemaBase::Void => "void", SemaBase::Struct => "struct" };
+                write!(f, "{}{}", base, "*".repeat(*ptr_depth))
+            }
+            SemaTy::Unknown => write!(f, "<unknown>"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FuncSig {
+    pub param_tys:Vec<SemaTy>,
+    pub is_variadic:bool,
+    /// `f()` 这种没有参数列表的声明，参数个数和类型都未指定，调用时不做检查
+    pub has_prototype:bool,
+    pub ret_ty:SemaTy,
+}
+#[derive(Clone, Debug)]
+pub struct SemaSym {
+    pub ty:SemaTy,
+    pub is_const:bool,
+    pub op_func_sig:Option<FuncSig>,
+    pub is_defined:bool,
+    /// typedef 定义的类型名，ty 是它所代表的类型
+    pub is_typedef:bool,
+    pub src_loc:Option<SrcLoc>,
+}
+
+/// declarator 中解析出来的信息
+struct DeclaratorInfo {
+    op_name:Option<String>,
+    op_src_loc:Option<SrcLoc>,
+    ptr_depth:usize,
+    /// 每一维的长度表达式，形如 `a[]` 的维度为 None
+    dim_nodes:Vec<Option<u32>>,
+    /// 函数声明的参数列表，Some(None) 表示 `()` 这种空参数列表
+    op_params:Option<Option<u32>>,
+    /// 形如 `(*f)(int)` 这种嵌套的 declarator ，类型不去推断
+    is_nested:bool,
+}
+
+/// 在 ast 上做语义检查，收集所有错误而不是遇到第一个就退出
+pub struct SemanticChecker<'a> {
+    ast_tree:&'a AstTree,
+    scope_stack:Vec<HashMap<String, SemaSym>>,
+    /// 后端允许调用定义在后面的函数，所以先收集所有函数定义，在作用域中找不到时再查这里
+    hoisted_funcs:HashMap<String, SemaSym>,
+    loop_depth:usize,
+    /// 每一层 switch 是否已经出现过 default 标签
+    switch_has_default:Vec<bool>,
+    op_cur_ret_ty:Option<SemaTy>,
+    pub diagnostics:Vec<Diagnostic>,
+}
+
+/// 对整棵 ast 做语义检查，返回收集到的诊断信息
+pub fn check_semantics(ast_tree:&AstTree) -> Vec<Diagnostic> {
+    let mut checker = SemanticChecker::new(ast_tree);
+    checker.check_compilation_unit();
+    checker.diagnostics
+}
+
+impl<'a> SemanticChecker<'a> {
+    pub fn new(ast_tree:&'a AstTree) -> Self { SemanticChecker { ast_tree, scope_stack:vec![HashMap::new()], hoisted_funcs:HashMap::new(), loop_depth:0, switch_has_default:vec![], op_cur_ret_ty:None, diagnostics:vec![] } }
+
+    fn children(&self, ast_node:u32) -> Vec<u32> { let ast_tree = self.ast_tree; node!(at ast_node in ast_tree).child_vec.clone() }
+    fn is_rule(&self, ast_node:u32, rule_id:usize) -> bool { let ast_tree = self.ast_tree; let n = node!(at ast_node in ast_tree); !n.is_terminal && n.rule_id == rule_id }
+    fn is_term(&self, ast_node:u32, term_id:isize) -> bool { let ast_tree = self.ast_tree; let n = node!(at ast_node in ast_tree); n.is_terminal && n.rule_id == term_id as usize }
+    fn children_of_rule(&self, ast_node:u32, rule_id:usize) -> Vec<u32> { self.children(ast_node).into_iter().filter(|&c| self.is_rule(c, rule_id)).collect() }
+    fn child_rule(&self, ast_node:u32, rule_id:usize) -> Option<u32> { self.children(ast_node).into_iter().find(|&c| self.is_rule(c, rule_id)) }
+    fn has_child_term(&self, ast_node:u32, term_id:isize) -> bool { self.children(ast_node).into_iter().any(|c| self.is_term(c, term_id)) }
+    fn text(&self, ast_node:u32) -> String { let ast_tree = self.ast_tree; node!(at ast_node in ast_tree).op_text.clone().unwrap_or_default() }
+    fn src_loc(&self, ast_node:u32) -> Option<SrcLoc> { let ast_tree = self.ast_tree; node!(at ast_node in ast_tree).src_loc.clone() }
+    /// dfs 找出 ast_node 之下所有满足条件的节点(包括自己)
+    fn dfs_nodes(&self, ast_node:u32, f:&impl Fn(u32) -> bool, found:&mut Vec<u32>) {
+        if f(ast_node) { found.push(ast_node) }
+        for child in self.children(ast_node) { self.dfs_nodes(child, f, found) }
+    }
+
+    fn error(&mut self, ast_node:u32, msg:String) { let src_loc = self.src_loc(ast_node); self.diagnostics.push(Diagnostic::new_error(src_loc, msg)); }
+    fn lookup(&self, name:&str) -> Option<&SemaSym> { self.scope_stack.iter().rev().find_map(|scope| scope.get(name)).or_else(|| self.hoisted_funcs.get(name)) }
+    fn push_scope(&mut self) { self.scope_stack.push(HashMap::new()) }
+    fn pop_scope(&mut self) { self.scope_stack.pop(); }
+    /// 在当前作用域中声明一个符号，同一作用域中重复声明则报错，函数的多次声明是允许的
+    fn declare(&mut self, name:String, ast_node:u32, sym:SemaSym) {
+        let cur_scope = self.scope_stack.last_mut().unwrap();
+        match cur_scope.get_mut(&name) {
+            Some(old_sym) if old_sym.op_func_sig.is_some() && sym.op_func_sig.is_some() && !(old_sym.is_defined && sym.is_defined) => {
+                let is_defined = old_sym.is_defined || sym.is_defined;
+                *old_sym = SemaSym { is_defined, ..sym };
+            }
+            // extern 声明的变量可以再次声明或者定义
+            Some(old_sym) if old_sym.op_func_sig.is_none() && sym.op_func_sig.is_none() && !old_sym.is_typedef && !sym.is_typedef && !(old_sym.is_defined && sym.is_defined) => {
+                let is_defined = old_sym.is_defined || sym.is_defined;
+                *old_sym = SemaSym { is_defined, ..sym };
+            }
+            Some(old_sym) => {
+                let msg = format!("重复定义 `{}`，之前的定义在 {}", name, SrcLoc::fmt_op(&old_sym.src_loc));
+                self.error(ast_node, msg);
+            }
+            None => { cur_scope.insert(name, sym); }
+        }
+    }
+
+    fn check_compilation_unit(&mut self) {
+        let root = 0;
+        if !self.is_rule(root, RULE_compilationUnit) { return }
+        for translation_unit in self.children_of_rule(root, RULE_translationUnit) {
+            for external_decl in self.children_of_rule(translation_unit, RULE_externalDeclaration) {
+                for func_def in self.children_of_rule(external_decl, RULE_functionDefinition) {
+                    if let Some((name, sym)) = self.parse_func_def_sym(func_def) {
+                        self.hoisted_funcs.insert(name, sym);
+                    }
+                }
+            }
+        }
+        for translation_unit in self.children_of_rule(root, RULE_translationUnit) {
+            for external_decl in self.children_of_rule(translation_unit, RULE_externalDeclaration) {
+                for decl in self.children(external_decl) {
+                    if self.is_rule(decl, RULE_functionDefinition) {
+                        self.check_function_definition(decl);
+                    } else if self.is_rule(decl, RULE_declaration) {
+                        self.check_declaration(decl);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 解析 declarationSpecifiers ，返回 (基本类型, 是否 const)
+    fn parse_decl_specs(&self, decl_specs_node:u32) -> (SemaTy, bool) {
+        let mut ty = SemaTy::new_scalar(SemaBase::Int);
+        let mut is_const = false;
+        for decl_spec in self.children_of_rule(decl_specs_node, RULE_declarationSpecifier) {
+            for spec in self.children(decl_spec) {
+                if self.is_rule(spec, RULE_typeQualifier) {
+                    is_const |= self.has_child_term(spec, Const);
+                } else if self.is_rule(spec, RULE_typeSpecifier) {
+                    let type_node = self.children(spec)[0];
+                    if self.is_term(type_node, Void) {
+                        ty = SemaTy::new_scalar(SemaBase::Void);
+                    } else if self.is_term(type_node, Float) || self.is_term(type_node, Double) {
+                        ty = SemaTy::new_scalar(SemaBase::Float);
+                    } else if self.is_rule(type_node, RULE_structOrUnionSpecifier) {
+                        ty = SemaTy::new_scalar(SemaBase::Struct);
+                    } else if self.is_rule(type_node, RULE_typedefName) {
+                        // typedef 名按作用域查找，找不到的在 check_type_names 中报告
+                        let name = self.text(self.children(type_node)[0]);
+                        ty = self.lookup(&name).filter(|sym| sym.is_typedef).map_or(SemaTy::Unknown, |sym| sym.ty);
+                    } else if self.is_rule(type_node, RULE_enumSpecifier) {
+                        ty = SemaTy::new_scalar(SemaBase::Int);
+                    }
+                }
+            }
+        }
+        (ty, is_const)
+    }
+
+    /// declarationSpecifiers 中用到的 typedef 名必须是当前可见的类型名
+    fn check_type_names(&mut self, decl_specs_node:u32) {
+        for decl_spec in self.children_of_rule(decl_specs_node, RULE_declarationSpecifier) {
+            let Some(type_spec) = self.child_rule(decl_spec, RULE_typeSpecifier) else { continue };
+            let Some(typedef_name) = self.child_rule(type_spec, RULE_typedefName) else { continue };
+            let ident = self.children(typedef_name)[0];
+            let name = self.text(ident);
+            if !self.lookup(&name).is_some_and(|sym| sym.is_typedef) {
+                self.error(ident, format!("`{}` 不是类型名", name));
+            }
+        }
+    }
+
+    fn parse_declarator(&self, declarator_node:u32) -> DeclaratorInfo {
+        let mut info = DeclaratorInfo { op_name:None, op_src_loc:None, ptr_depth:0, dim_nodes:vec![], op_params:None, is_nested:false };
+        if let Some(pointer_node) = self.child_rule(declarator_node, RULE_pointer) {
+            info.ptr_depth = self.children(pointer_node).into_iter().filter(|&c| self.is_term(c, Star)).count();
+        }
+        if let Some(direct_declarator) = self.child_rule(declarator_node, RULE_directDeclarator) {
+            self.parse_direct_declarator(direct_declarator, &mut info);
+        }
+        info
+    }
+    fn parse_direct_declarator(&self, direct_declarator:u32, info:&mut DeclaratorInfo) {
+        let children = self.children(direct_declarator);
+        let first = children[0];
+        if self.is_term(first, Identifier) {
+            info.op_name = Some(self.text(first));
+            info.op_src_loc = self.src_loc(first);
+        } else if self.is_term(first, LeftParen) {
+            if let Some(&inner_declarator) = children.get(1).filter(|&&c| self.is_rule(c, RULE_declarator)) {
+                let inner_info = self.parse_declarator(inner_declarator);
+                info.op_name = inner_info.op_name;
+                info.op_src_loc = inner_info.op_src_loc;
+                info.is_nested = true;
+            }
+        } else if self.is_rule(first, RULE_directDeclarator) {
+            self.parse_direct_declarator(first, info);
+            if children.len() > 1 && self.is_term(children[1], LeftBracket) {
+                info.dim_nodes.push(children.iter().copied().find(|&c| self.is_rule(c, RULE_assignmentExpression)));
+            } else if children.len() > 1 && self.is_term(children[1], LeftParen) {
+                info.op_params = Some(children.iter().copied().find(|&c| self.is_rule(c, RULE_parameterTypeList)));
+            }
+        } else {
+            // Identifier ':' DigitSequence 这种 bit field 以及其他扩展写法
+            if let Some(ident) = children.iter().copied().find(|&c| self.is_term(c, Identifier)) {
+                info.op_name = Some(self.text(ident));
+                info.op_src_loc = self.src_loc(ident);
+            }
+        }
+    }
+    /// declarator 所声明的变量的类型
+    fn ty_of_declarator(base_ty:SemaTy, info:&DeclaratorInfo) -> SemaTy {
+        if info.is_nested { SemaTy::Unknown } else { base_ty.with_depth(|d| Some(d + info.ptr_depth + info.dim_nodes.len())) }
+    }
+
+    /// 解析函数的参数列表，返回 (参数的 ast 节点以及 declarator 信息, 是否变长参数)
+    fn parse_params(&mut self, op_param_type_list:Option<u32>) -> (Vec<(u32, SemaTy, Option<DeclaratorInfo>)>, bool) {
+        let Some(param_type_list) = op_param_type_list else { return (vec![], false) };
+        let is_variadic = self.has_child_term(param_type_list, Ellipsis);
+        let mut params = vec![];
+        if let Some(param_list) = self.child_rule(param_type_list, RULE_parameterList) {
+            for param_decl in self.children_of_rule(param_list, RULE_parameterDeclaration) {
+                let decl_specs = self.child_rule(param_decl, RULE_declarationSpecifiers).or(self.child_rule(param_decl, RULE_declarationSpecifiers2)).unwrap();
+                self.check_type_names(decl_specs);
+                let (base_ty, _) = self.parse_decl_specs(decl_specs);
+                match self.child_rule(param_decl, RULE_declarator) {
+                    Some(declarator) => {
+                        let info = self.parse_declarator(declarator);
+                        // 形参的第一维可以省略，其余维度必须是常量
+                        for &dim_node in info.dim_nodes.iter().flatten() {
+                            self.check_const_expr(dim_node, "数组维度");
+                        }
+                        let ty = Self::ty_of_declarator(base_ty, &info);
+                        params.push((param_decl, ty, Some(info)));
+                    }
+                    None => {
+                        // 没有名字的形参，如 `int []` 或者 `void *`
+                        let ptr_depth = self.child_rule(param_decl, RULE_abstractDeclarator).map_or(0, |abstract_declarator| {
+                            let mut found = vec![];
+                            self.dfs_nodes(abstract_declarator, &|n| self.is_term(n, Star) || self.is_term(n, LeftBracket), &mut found);
+                            found.len()
+                        });
+                        params.push((param_decl, base_ty.with_depth(|_| Some(ptr_depth)), None));
+                    }
+                }
+            }
+        }
+        // `f(void)` 表示没有参数
+        if params.len() == 1 && params[0].1.is_void_value() && params[0].2.is_none() {
+            params.clear();
+        }
+        (params, is_variadic)
+    }
+
+    /// 函数定义对应的符号，诊断信息会在 check_function_definition 中报告，这里只取签名
+    fn parse_func_def_sym(&self, func_def:u32) -> Option<(String, SemaSym)> {
+        let mut checker = SemanticChecker::new(self.ast_tree);
+        let decl_specs = self.child_rule(func_def, RULE_declarationSpecifiers)?;
+        let (ret_ty, _) = self.parse_decl_specs(decl_specs);
+        let info = self.parse_declarator(self.child_rule(func_def, RULE_declarator)?);
+        let (params, is_variadic) = checker.parse_params(info.op_params.flatten());
+        let func_sig = FuncSig { param_tys:params.iter().map(|(_, ty, _)| *ty).collect(), is_variadic, has_prototype:info.op_params != Some(None), ret_ty:ret_ty.with_depth(|d| Some(d + info.ptr_depth)) };
+        Some((info.op_name?, SemaSym { ty:SemaTy::Unknown, is_const:false, op_func_sig:Some(func_sig), is_defined:true, is_typedef:false, src_loc:info.op_src_loc }))
+    }
+    fn check_function_definition(&mut self, func_def:u32) {
+        let declarator = self.child_rule(func_def, RULE_declarator).unwrap();
+        if let Some(decl_specs) = self.child_rule(func_def, RULE_declarationSpecifiers) {
+            self.check_type_names(decl_specs);
+        }
+        let info = self.parse_declarator(declarator);
+        let ret_ty = match self.parse_func_def_sym(func_def) {
+            Some((name, sym)) => {
+                let ret_ty = sym.op_func_sig.as_ref().unwrap().ret_ty;
+                self.declare(name, declarator, sym);
+                ret_ty
+            }
+            None => SemaTy::Unknown,
+        };
+        let (params, _) = self.parse_params(info.op_params.flatten());
+        // 形参和函数体最外层的 block 在同一个作用域中
+        self.push_scope();
+        for (param_decl, ty, op_info) in params {
+            if let Some(DeclaratorInfo { op_name:Some(name), op_src_loc, .. }) = op_info {
+                self.declare(name, param_decl, SemaSym { ty, is_const:false, op_func_sig:None, is_defined:true, is_typedef:false, src_loc:op_src_loc });
+            }
+        }
+        self.op_cur_ret_ty = Some(ret_ty);
+        if let Some(compound) = self.child_rule(func_def, RULE_compoundStatement) {
+            self.check_block_items(compound);
+        }
+        self.op_cur_ret_ty = None;
+        self.pop_scope();
+    }
+
+    /// 检查 declaration 或 forDeclaration ，它们都是 declarationSpecifiers 加上 initDeclaratorList
+    fn check_declaration(&mut self, decl:u32) {
+        let Some(decl_specs) = self.child_rule(decl, RULE_declarationSpecifiers) else { return };
+        self.check_type_names(decl_specs);
+        let (base_ty, is_const) = self.parse_decl_specs(decl_specs);
+        let is_typedef = is_typedef_decl(self.ast_tree, decl);
+        let storage_class = get_storage_class_of_decl(self.ast_tree, decl);
+        let is_local = self.scope_stack.len() > 1;
+        self.declare_enumerators(decl_specs);
+        let Some(init_decl_list) = self.child_rule(decl, RULE_initDeclaratorList) else { return };
+        for init_decl in self.children_of_rule(init_decl_list, RULE_initDeclarator) {
+            let Some(declarator) = self.child_rule(init_decl, RULE_declarator) else { continue };
+            let info = self.parse_declarator(declarator);
+            let Some(name) = info.op_name.clone() else { continue };
+            let sym = match info.op_params {
+                // 函数类型的 typedef 只记录类型名，不产生函数声明
+                Some(_) if is_typedef => SemaSym { ty:SemaTy::Unknown, is_const:false, op_func_sig:None, is_defined:true, is_typedef, src_loc:info.op_src_loc.clone() },
+                Some(op_param_type_list) if !info.is_nested => {
+                    let (params, is_variadic) = self.parse_params(op_param_type_list);
+                    let func_sig = FuncSig { param_tys:params.iter().map(|(_, ty, _)| *ty).collect(), is_variadic, has_prototype:op_param_type_list.is_some(), ret_ty:base_ty.with_depth(|d| Some(d + info.ptr_depth)) };
+                    SemaSym { ty:SemaTy::Unknown, is_const:false, op_func_sig:Some(func_sig), is_defined:false, is_typedef:false, src_loc:info.op_src_loc.clone() }
+                }
+                _ => {
+                    for &dim_node in info.dim_nodes.iter().flatten() {
+                        self.check_const_expr(dim_node, "数组维度");
+                    }
+                    // `const int *p` 中 const 修饰的是 p 指向的值，p 本身可以被赋值
+                    // 没有初值的 extern 变量只是声明，定义在别处
+                    let is_defined = storage_class != Some(Extern) || self.child_rule(init_decl, RULE_initializer).is_some();
+                    SemaSym { ty:Self::ty_of_declarator(base_ty, &info), is_const:is_const && info.ptr_depth == 0, op_func_sig:None, is_defined, is_typedef, src_loc:info.op_src_loc.clone() }
+                }
+            };
+            // 先声明再检查初值，这和 C 的作用域规则一致
+            self.declare(name.clone(), declarator, sym);
+            if let Some(initializer) = self.child_rule(init_decl, RULE_initializer) {
+                if is_typedef {
+                    self.error(initializer, format!("typedef `{}` 不能有初值", name));
+                }
+                match storage_class {
+                    Some(Extern) if is_local => self.error(initializer, format!("块作用域中的 extern 变量 `{}` 不能有初值", name)),
+                    // static 局部变量只在程序开始前初始化一次，初值必须是常量
+                    Some(Static) if is_local => self.check_no_var_or_call(initializer, &format!("static 局部变量 `{}` 的初值", name)),
+                    _ => {}
+                }
+                self.check_initializer(initializer);
+            }
+        }
+    }
+    /// `enum E { A, B = A + 2 };` 中的枚举常量都是 const int ，每个枚举常量在自己的初值之后才可见
+    fn declare_enumerators(&mut self, decl_specs:u32) {
+        for decl_spec in self.children_of_rule(decl_specs, RULE_declarationSpecifier) {
+            let Some(type_spec) = self.child_rule(decl_spec, RULE_typeSpecifier) else { continue };
+            let Some(enum_spec) = self.child_rule(type_spec, RULE_enumSpecifier) else { continue };
+            let Some(enumerator_list) = self.child_rule(enum_spec, RULE_enumeratorList) else { continue };
+            for enumerator in self.children_of_rule(enumerator_list, RULE_enumerator) {
+                if let Some(const_expr) = self.child_rule(enumerator, RULE_constantExpression) {
+                    self.check_const_expr(const_expr, "枚举常量的值");
+                }
+                let Some(enum_const) = self.child_rule(enumerator, RULE_enumerationConstant) else { continue };
+                let name = self.text(self.children(enum_const)[0]);
+                let src_loc = self.src_loc(enum_const);
+                self.declare(name, enum_const, SemaSym { ty:SemaTy::new_scalar(SemaBase::Int), is_const:true, op_func_sig:None, is_defined:true, is_typedef:false, src_loc });
+            }
+        }
+    }
+    fn check_initializer(&mut self, initializer:u32) {
+        for child in self.children(initializer) {
+            if self.is_rule(child, RULE_assignmentExpression) {
+                self.check_value_expr(child);
+            } else if self.is_rule(child, RULE_initializerList) {
+                for sub_initializer in self.children_of_rule(child, RULE_initializer) {
+                    self.check_initializer(sub_initializer);
+                }
+            }
+        }
+    }
+
+    /// 数组的维度和枚举常量的值只能由字面量和 const 变量组成，不能调用函数，what 用于报错信息
+    fn check_const_expr(&mut self, expr_node:u32, what:&str) {
+        self.expr_ty(expr_node);
+        self.check_no_var_or_call(expr_node, what);
+    }
+    /// 只检查 ast_node 之下没有函数调用和非 const 的变量，不推断类型
+    fn check_no_var_or_call(&mut self, expr_node:u32, what:&str) {
+        let mut found = vec![];
+        self.dfs_nodes(expr_node, &|n| {
+            (self.is_rule(n, RULE_primaryExpression) && self.has_child_term(n, Identifier)) || (self.is_rule(n, RULE_postfixExpression) && self.has_child_term(n, LeftParen))
+        }, &mut found);
+        for node in found {
+            if self.is_rule(node, RULE_postfixExpression) {
+                self.error(node, format!("{}必须是常量表达式，不能包含函数调用", what));
+                return;
+            }
+            let name = self.text(self.children(node)[0]);
+            // 未声明的标识符已经报过错了
+            if self.lookup(&name).is_some_and(|sym| !sym.is_const) {
+                self.error(node, format!("{}必须是常量表达式，`{}` 不是常量", what, name));
+                return;
+            }
+        }
+    }
+
+    fn check_block_items(&mut self, compound:u32) {
+        let Some(block_item_list) = self.child_rule(compound, RULE_blockItemList) else { return };
+        for block_item in self.children_of_rule(block_item_list, RULE_blockItem) {
+            let item = self.children(block_item)[0];
+            if self.is_rule(item, RULE_declaration) {
+                self.check_declaration(item);
+            } else if self.is_rule(item, RULE_statement) {
+                self.check_statement(item);
+            }
+        }
+    }
+
+    fn check_statement(&mut self, stmt:u32) {
+        let inner = self.children(stmt)[0];
+        if node_is_terminal(self.ast_tree, inner) { return }
+        let ast_tree = self.ast_tree;
+        match node!(at inner in ast_tree).rule_id {
+            RULE_compoundStatement => {
+                self.push_scope();
+                self.check_block_items(inner);
+                self.pop_scope();
+            }
+            RULE_expressionStatement => {
+                if let Some(expr) = self.child_rule(inner, RULE_expression) { self.expr_ty(expr); }
+            }
+            RULE_selectionStatement => {
+                let selection = self.children(inner)[0];
+                let cond = self.child_rule(selection, RULE_expression).unwrap();
+                self.check_value_expr(cond);
+                let is_switch = self.is_rule(selection, RULE_switchSelection);
+                if is_switch { self.switch_has_default.push(false) }
+                for sub_stmt in self.children_of_rule(selection, RULE_statement) {
+                    self.check_statement(sub_stmt);
+                }
+                if is_switch { self.switch_has_default.pop(); }
+            }
+            RULE_iterationStatement => self.check_iteration(self.children(inner)[0]),
+            RULE_jumpStatement => self.check_jump(inner),
+            RULE_labeledStatement => {
+                if let Some(const_expr) = self.child_rule(inner, RULE_constantExpression) {
+                    self.check_value_expr(const_expr);
+                }
+                let is_case_or_default = self.child_rule(inner, RULE_constantExpression).is_some() || self.has_child_term(inner, Default);
+                if is_case_or_default && self.switch_has_default.is_empty() {
+                    self.error(inner, "case 或 default 标签不在 switch 语句中".to_string());
+                }
+                if self.has_child_term(inner, Default) {
+                    match self.switch_has_default.last_mut() {
+                        Some(has_default) if *has_default => self.error(inner, "switch 中出现了多个 default 标签".to_string()),
+                        Some(has_default) => *has_default = true,
+                        None => {}
+                    }
+                }
+                if let Some(sub_stmt) = self.child_rule(inner, RULE_statement) { self.check_statement(sub_stmt) }
+            }
+            _ => {}
+        }
+    }
+    fn check_iteration(&mut self, iteration:u32) {
+        let ast_tree = self.ast_tree;
+        match node!(at iteration in ast_tree).rule_id {
+            RULE_whileIterationStatement | RULE_doWhileIterationStatement => {
+                let cond = self.child_rule(iteration, RULE_expression).unwrap();
+                self.check_value_expr(cond);
+                self.loop_depth += 1;
+                self.check_statement(self.child_rule(iteration, RULE_statement).unwrap());
+                self.loop_depth -= 1;
+            }
+            RULE_forIterationStatement => {
+                // for 的初始化语句中声明的变量只在 for 中可见
+                self.push_scope();
+                let for_cond = self.child_rule(iteration, RULE_forCondition).unwrap();
+                for part in self.children(for_cond) {
+                    if self.is_rule(part, RULE_forBeforeExpression) {
+                        if let Some(for_decl) = self.child_rule(part, RULE_forDeclaration) {
+                            self.check_declaration(for_decl);
+                        } else if let Some(expr) = self.child_rule(part, RULE_expression) {
+                            self.expr_ty(expr);
+                        }
+                    } else if self.is_rule(part, RULE_forMidExpression) {
+                        let expr = self.child_rule(part, RULE_expression).unwrap();
+                        self.check_value_expr(expr);
+                    } else if self.is_rule(part, RULE_forAfterExpression) {
+                        let expr = self.child_rule(part, RULE_expression).unwrap();
+                        self.expr_ty(expr);
+                    }
+                }
+                self.loop_depth += 1;
+                self.check_statement(self.child_rule(iteration, RULE_statement).unwrap());
+                self.loop_depth -= 1;
+                self.pop_scope();
+            }
+            _ => {}
+        }
+    }
+    fn check_jump(&mut self, jump:u32) {
+        let inner = self.children(jump)[0];
+        if self.is_rule(inner, RULE_breakStatement) {
+            if self.loop_depth == 0 && self.switch_has_default.is_empty() {
+                self.error(inner, "break 语句不在循环或 switch 语句中".to_string());
+            }
+        } else if self.is_rule(inner, RULE_continueStatement) {
+            if self.loop_depth == 0 {
+                self.error(inner, "continue 语句不在循环语句中".to_string());
+            }
+        } else if self.is_rule(inner, RULE_returnStatement) {
+            let op_expr = self.child_rule(inner, RULE_expression);
+            let op_expr_ty = op_expr.map(|expr| self.expr_ty(expr));
+            match (self.op_cur_ret_ty, op_expr_ty) {
+                (Some(ret_ty), Some(_)) if ret_ty.is_void_value() => self.error(inner, "void 函数不能返回值".to_string()),
+                (Some(ret_ty), None) if !ret_ty.is_void_value() => self.error(inner, format!("返回类型为 {} 的函数必须返回一个值", ret_ty)),
+                (Some(ret_ty), Some(expr_ty)) if expr_ty.is_void_value() => self.error(inner, format!("不能把 void 值作为 {} 返回", ret_ty)),
+                _ => {}
+            }
+        }
+    }
+
+    /// 检查一个需要用到值的表达式，void 值会报错
+    fn check_value_expr(&mut self, expr:u32) -> SemaTy {
+        let ty = self.expr_ty(expr);
+        if ty.is_void_value() {
+            self.error(expr, "void 值不能参与运算".to_string());
+            return SemaTy::Unknown;
+        }
+        ty
+    }
+
+    /// 推断表达式的类型，顺便检查其中的标识符、函数调用以及赋值
+    fn expr_ty(&mut self, expr:u32) -> SemaTy {
+        let ast_tree = self.ast_tree;
+        let expr_struct = node!(at expr in ast_tree);
+        if expr_struct.is_terminal {
+            return if self.is_term(expr, Constant) {
+                let text = self.text(expr);
+                if text.starts_with('\'') {
+                    return SemaTy::new_scalar(SemaBase::Int);
+                }
+                // 作为负号操作数时才合法的字面量(例如 9223372036854775808)也要放过
+                match parse_num_literal(&text, false).or_else(|_| parse_num_literal(&text, true)) {
+                    Ok(Value::F32(_) | Value::F64(_)) => SemaTy::new_scalar(SemaBase::Float),
+                    Ok(_) => SemaTy::new_scalar(SemaBase::Int),
+                    Err(e) => {
+                        self.error(expr, e.to_string());
+                        SemaTy::Unknown
+                    }
+                }
+            } else if self.is_term(expr, StringLiteral) {
+                SemaTy::Known { base:SemaBase::Int, ptr_depth:1 }
+            } else if self.is_term(expr, DigitSequence) {
+                SemaTy::new_scalar(SemaBase::Int)
+            } else {
+                SemaTy::Unknown
+            };
+        }
+        let children = expr_struct.child_vec.clone();
+        match expr_struct.rule_id {
+            RULE_primaryExpression => {
+                let first = children[0];
+                if self.is_term(first, Identifier) {
+                    let name = self.text(first);
+                    match self.lookup(&name) {
+                        Some(sym) if sym.op_func_sig.is_some() => SemaTy::Unknown,
+                        Some(sym) if sym.is_typedef => {
+                            self.error(first, format!("`{}` 是类型名，不能当作值使用", name));
+                            SemaTy::Unknown
+                        }
+                        Some(sym) => sym.ty,
+                        None => {
+                            self.error(first, format!("使用了未声明的标识符 `{}`", name));
+                            SemaTy::Unknown
+                        }
+                    }
+                } else if self.is_term(first, LeftParen) {
+                    self.expr_ty(children[1])
+                } else {
+                    self.expr_ty(first)
+                }
+            }
+            _ if children.len() == 1 => self.expr_ty(children[0]),
+            RULE_postfixExpression => self.postfix_ty(expr, &children),
+            RULE_unaryExpression => {
+                if self.is_rule(children[0], RULE_unaryOperator) {
+                    let op = self.children(children[0])[0];
+                    if self.is_term(op, PlusPlus) || self.is_term(op, MinusMinus) { self.check_assignable(children[1]) }
+                    let operand_ty = self.check_value_expr(children[1]);
+                    if self.is_term(op, And) {
+                        operand_ty.with_depth(|d| Some(d + 1))
+                    } else if self.is_term(op, Star) {
+                        operand_ty.with_depth(|d| d.checked_sub(1))
+                    } else if self.is_term(op, Not) {
+                        SemaTy::new_scalar(SemaBase::Int)
+                    } else {
+                        operand_ty
+                    }
+                } else {
+                    // sizeof(typeName)
+                    SemaTy::new_scalar(SemaBase::Int)
+                }
+            }
+            RULE_assignmentExpression => {
+                self.check_assignable(children[0]);
+                let lhs_ty = self.expr_ty(children[0]);
+                self.check_value_expr(children[2]);
+                lhs_ty
+            }
+            RULE_conditionalExpression => {
+                self.check_value_expr(children[0]);
+                let then_ty = self.expr_ty(children[2]);
+                self.expr_ty(children[4]);
+                then_ty
+            }
+            RULE_expression => {
+                let mut ty = SemaTy::Unknown;
+                for sub_expr in self.children_of_rule(expr, RULE_assignmentExpression) { ty = self.expr_ty(sub_expr) }
+                ty
+            }
+            RULE_multiplicativeExpression | RULE_additiveExpression | RULE_shiftExpression | RULE_relationalExpression | RULE_equalityExpression | RULE_andExpression | RULE_exclusiveOrExpression | RULE_inclusiveOrExpression | RULE_logicalAndExpression | RULE_logicalOrExpression => {
+                let operand_tys:Vec<SemaTy> = children.iter().step_by(2).map(|&operand| self.check_value_expr(operand)).collect();
+                let is_bool_op = children.iter().skip(1).step_by(2).any(|&op| [Less, Greater, LessEqual, GreaterEqual, Equal, NotEqual, AndAnd, OrOr].iter().any(|&term| self.is_term(op, term)));
+                if is_bool_op {
+                    SemaTy::new_scalar(SemaBase::Int)
+                } else if operand_tys.contains(&SemaTy::Unknown) {
+                    SemaTy::Unknown
+                } else if let Some(ptr_ty) = operand_tys.iter().find(|ty| ty.is_ptr()) {
+                    *ptr_ty
+                } else if operand_tys.iter().any(|ty| ty.is_float_value()) {
+                    SemaTy::new_scalar(SemaBase::Float)
+                } else {
+                    SemaTy::new_scalar(SemaBase::Int)
+                }
+            }
+            _ => {
+                // 其他不关心的节点，只检查其中的标识符
+                for child in children { self.expr_ty(child); }
+                SemaTy::Unknown
+            }
+        }
+    }
+    fn postfix_ty(&mut self, expr:u32, children:&[u32]) -> SemaTy {
+        let base = children[0];
+        let op = children[1];
+        if self.is_term(op, LeftBracket) {
+            let base_ty = self.expr_ty(base);
+            self.check_value_expr(children[2]);
+            base_ty.with_depth(|d| d.checked_sub(1))
+        } else if self.is_term(op, LeftParen) {
+            let arg_nodes = self.child_rule(expr, RULE_argumentExpressionList).map_or(vec![], |arg_list| self.children_of_rule(arg_list, RULE_assignmentExpression));
+            let arg_tys:Vec<SemaTy> = arg_nodes.iter().map(|&arg| self.expr_ty(arg)).collect();
+            let op_func_name = self.single_identifier(base);
+            let op_func_sig = op_func_name.as_ref().and_then(|name| self.lookup(name)).and_then(|sym| sym.op_func_sig.clone());
+            match (op_func_name, op_func_sig) {
+                (Some(func_name), Some(func_sig)) => {
+                    let param_count = func_sig.param_tys.len();
+                    if func_sig.has_prototype && (arg_tys.len() < param_count || (arg_tys.len() > param_count && !func_sig.is_variadic)) {
+                        self.error(expr, format!("调用函数 `{}` 需要 {} 个参数，但是传入了 {} 个", func_name, param_count, arg_tys.len()));
+                    }
+                    for (idx, (&arg_node, arg_ty)) in arg_nodes.iter().zip(arg_tys.iter()).enumerate() {
+                        if arg_ty.is_void_value() {
+                            self.error(arg_node, format!("函数 `{}` 的第 {} 个参数是 void 值", func_name, idx + 1));
+                        } else if let Some(param_ty) = func_sig.param_tys.get(idx) {
+                            if !param_ty.accepts(arg_ty) {
+                                self.error(arg_node, format!("函数 `{}` 的第 {} 个参数类型不匹配，需要 {}，传入的是 {}", func_name, idx + 1, param_ty, arg_ty));
+                            }
+                        }
+                    }
+                    func_sig.ret_ty
+                }
+                (Some(_), None) => {
+                    // 不是函数名的标识符，也要检查它是否声明过
+                    self.expr_ty(base);
+                    SemaTy::Unknown
+                }
+                (None, _) => {
+                    self.expr_ty(base);
+                    SemaTy::Unknown
+                }
+            }
+        } else if self.is_term(op, PlusPlus) || self.is_term(op, MinusMinus) {
+            self.check_assignable(base);
+            self.check_value_expr(base)
+        } else {
+            // `.` 和 `->` 访问成员
+            self.expr_ty(base);
+            SemaTy::Unknown
+        }
+    }
+    /// 如果表达式只是一个单独的标识符，返回它的名字
+    fn single_identifier(&self, expr:u32) -> Option<String> {
+        let children = self.children(expr);
+        if self.is_rule(expr, RULE_primaryExpression) {
+            children.first().filter(|&&c| self.is_term(c, Identifier)).map(|&c| self.text(c))
+        } else if children.len() == 1 && !node_is_terminal(self.ast_tree, expr) {
+            self.single_identifier(children[0])
+        } else {
+            None
+        }
+    }
+    /// 赋值、自增自减的对象不能是 const 变量或者 const 数组的元素
+    fn check_assignable(&mut self, lhs:u32) {
+        let mut cur = lhs;
+        loop {
+            let children = self.children(cur);
+            if self.is_rule(cur, RULE_postfixExpression) && children.len() > 1 && self.is_term(children[1], LeftBracket) {
+                cur = children[0];
+            } else if self.is_rule(cur, RULE_primaryExpression) && self.is_term(children[0], LeftParen) {
+                cur = children[1];
+            } else if self.is_rule(cur, RULE_primaryExpression) {
+                break;
+            } else if children.len() == 1 && !node_is_terminal(self.ast_tree, cur) {
+                cur = children[0];
+            } else {
+                return;
+            }
+        }
+        let ident = self.children(cur)[0];
+        if !self.is_term(ident, Identifier) { return }
+        let name = self.text(ident);
+        if self.lookup(&name).is_some_and(|sym| sym.is_const) {
+            self.error(ident, format!("不能给 const 变量 `{}` 赋值", name));
+        }
+    }
+}
+
+fn node_is_terminal(ast_tree:&AstTree, ast_node:u32) -> bool { node!(at ast_node in ast_tree).is_terminal }
@@ -10,7 +10,7 @@ use anyhow::*;
 use crate::{add_symbol, debug_info_red, debug_info_yellow, instr, make_field_trait_for_struct, reg_field_for_struct};
 use super::cfg_node::InstrList;
 
-use super::field::Type::{self, F32, I32};
+use super::field::Type::{self, F32, F64, I32, I64, U32};
 
 use super::nhwc_instr::{BreakpointArg, NhwcInstr};
 use super::symtab::{RcSymIdx, SymIdx, WithBorrow};
@@ -424,13 +424,13 @@ impl Simulator{
                     let result = a_val.clone() * b_val.clone();
                     self.simu_add_value(&lhs,result)?;
                 },
-                Div { a, b, vartype: _ } => {
+                Div { a, b, vartype } => {
                     let a = a.as_ref_borrow();
                     let b = b.as_ref_borrow();
 
                     let a_val=self.simu_symtab.get(&a).get_simu_val();
                     let b_val = self.simu_symtab.get(&b).get_simu_val();
-                    let result = a_val.clone() / b_val.clone();
+                    let result = if vartype.is_u_32() { a_val.trans_to_specific_type(&U32) / b_val.trans_to_specific_type(&U32) } else { a_val.clone() / b_val.clone() };
                     self.simu_add_value(&lhs,result)?;
                 },
                 Sub { a, b, vartype: _ } => {
@@ -442,13 +442,13 @@ impl Simulator{
                     let result = a_val.clone() - b_val.clone();
                     self.simu_add_value(&lhs,result)?;
                 },
-                Mod { a, b, vartype: _ } => {
+                Mod { a, b, vartype } => {
                     let a = a.as_ref_borrow();
                     let b = b.as_ref_borrow();
 
                     let a_val=self.simu_symtab.get(&a).get_simu_val();
                     let b_val = self.simu_symtab.get(&b).get_simu_val();
-                    let result = a_val.clone() % b_val.clone();
+                    let result = if vartype.is_u_32() { a_val.trans_to_specific_type(&U32) % b_val.trans_to_specific_type(&U32) } else { a_val.clone() % b_val.clone() };
                     self.simu_add_value(&lhs,result)?;
                 },
                 Icmp { plan, a, b, vartype: _ } => {
@@ -471,19 +471,19 @@ impl Simulator{
                             self.simu_add_value(&lhs,result)?;
                         }
                         super::nhwc_instr::IcmpPlan::Ugt => {
-                            let result = Value::new_i1(a_val.clone() > b_val.clone());
+                            let result = a_val.trans_to_specific_type(&U32).greater_than(&b_val.trans_to_specific_type(&U32));
                             self.simu_add_value(&lhs,result)?;
                         },
                         super::nhwc_instr::IcmpPlan::Uge => {
-                            let result = Value::new_i1(a_val.clone() >= b_val.clone());
+                            let result = a_val.trans_to_specific_type(&U32).greater_than_or_equal(&b_val.trans_to_specific_type(&U32));
                             self.simu_add_value(&lhs,result)?;
                         },
                         super::nhwc_instr::IcmpPlan::Ult => {
-                            let result = Value::new_i1(a_val.clone() < b_val.clone());
+                            let result = a_val.trans_to_specific_type(&U32).less_than(&b_val.trans_to_specific_type(&U32));
                             self.simu_add_value(&lhs,result)?;
                         },
                         super::nhwc_instr::IcmpPlan::Ule => {
-                            let result = Value::new_i1(a_val.clone() <= b_val.clone());
+                            let result = a_val.trans_to_specific_type(&U32).less_than_or_equal(&b_val.trans_to_specific_type(&U32));
                             self.simu_add_value(&lhs,result)?;
                         },
                         super::nhwc_instr::IcmpPlan::Sgt => {
@@ -558,13 +558,72 @@ impl Simulator{
                     let result = !a_val.clone();
                     self.simu_add_value(&lhs,result)?;
                 },
+                Select { cond, a, b, vartype: _ } => {
+                    let cond_val = self.simu_symtab.get(&cond.as_ref_borrow()).get_simu_val().trans_to_specific_type(&Type::I1);
+                    let picked = if matches!(cond_val, Value::I1(Some(true))) { a } else { b };
+                    let result = self.simu_symtab.get(&picked.as_ref_borrow()).get_simu_val().clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
+                BitAnd { a, b, vartype: _ } => {
+                    let a = a.as_ref_borrow();
+                    let b = b.as_ref_borrow();
+                    let a_val = self.simu_symtab.get(&a).get_simu_val();
+                    let b_val = self.simu_symtab.get(&b).get_simu_val();
+                    let result = a_val.clone() & b_val.clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
+                BitOr { a, b, vartype: _ } => {
+                    let a = a.as_ref_borrow();
+                    let b = b.as_ref_borrow();
+                    let a_val = self.simu_symtab.get(&a).get_simu_val();
+                    let b_val = self.simu_symtab.get(&b).get_simu_val();
+                    let result = a_val.clone() | b_val.clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
+                BitXor { a, b, vartype: _ } => {
+                    let a = a.as_ref_borrow();
+                    let b = b.as_ref_borrow();
+                    let a_val = self.simu_symtab.get(&a).get_simu_val();
+                    let b_val = self.simu_symtab.get(&b).get_simu_val();
+                    let result = a_val.clone() ^ b_val.clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
+                Shl { a, b, vartype: _ } => {
+                    let a = a.as_ref_borrow();
+                    let b = b.as_ref_borrow();
+                    let a_val = self.simu_symtab.get(&a).get_simu_val();
+                    let b_val = self.simu_symtab.get(&b).get_simu_val();
+                    let result = a_val.clone() << b_val.clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
+                Ashr { a, b, vartype: _ } => {
+                    let a = a.as_ref_borrow();
+                    let b = b.as_ref_borrow();
+                    let a_val = self.simu_symtab.get(&a).get_simu_val();
+                    let b_val = self.simu_symtab.get(&b).get_simu_val();
+                    let result = a_val.clone() >> b_val.clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
+                Lshr { a, b, vartype: _ } => {
+                    let a = a.as_ref_borrow();
+                    let b = b.as_ref_borrow();
+                    let a_val = self.simu_symtab.get(&a).get_simu_val();
+                    let b_val = self.simu_symtab.get(&b).get_simu_val();
+                    let result = a_val.trans_to_specific_type(&U32) >> b_val.clone();
+                    self.simu_add_value(&lhs,result)?;
+                },
             }}
-            SimpleAssign { lhs, rhs, vartype: _ } => {
+            SimpleAssign { lhs, rhs, vartype } => {
                 let rhs = rhs.as_ref_borrow();
                 let lhs = lhs.as_ref_borrow();
                 let rhs_val = self.simu_symtab.get(&rhs).get_simu_val().clone();
+                // char 与 int 之间的赋值按目标类型截断或符号扩展
+                let rhs_val = if (rhs_val.is_i_8() || vartype.is_i_8()) && (rhs_val.is_i_8() || rhs_val.is_i_32()) { rhs_val.force_to_ty(vartype) } else { rhs_val };
                 self.simu_add_value(&lhs,rhs_val)?;
             },
+            Call { op_lhs:_, func_op } if func_op.is_indirect => {
+                return Err(anyhow!("模拟器暂不支持通过函数指针 {:?} 的间接调用",func_op.rc_func_symidx))
+            },
             Call { op_lhs: op_assigned_symidx, func_op } => {
                 self.push_func_call(op_assigned_symidx.as_ref(),&func_op.rc_func_symidx, &func_op.actual_arg_symidx_vec,  src_symtab)?;
             },
@@ -587,7 +646,22 @@ impl Simulator{
                             self.cur_instr_pos = *self.simu_symtab.get_mut(&t2).get_simu_label_pos();
                         }
                     },
-                    super::nhwc_instr::JumpOp::Switch { cond: _, default: _, compared: _ } => todo!(),
+                    super::nhwc_instr::JumpOp::Switch { cond, default, compared } => {
+                        let cond = cond.as_ref_borrow();
+                        // case 的值在生成时已经转换成了条件表达式的类型
+                        let cond_val = self.simu_symtab.get(&cond).get_simu_val().clone();
+                        // 没有匹配的 case 就跳到 default
+                        let mut target = default.clone();
+                        for pair in compared.iter(){
+                            let compared_val = self.simu_symtab.get(&pair.compared.as_ref_borrow()).get_simu_val().clone().trans_to_specific_type(&cond_val.to_type());
+                            if compared_val == cond_val{
+                                target = pair.label.clone();
+                                break;
+                            }
+                        }
+                        let target = target.as_ref_borrow();
+                        self.cur_instr_pos = *self.simu_symtab.get_mut(&target).get_simu_label_pos();
+                    },
                     super::nhwc_instr::JumpOp::DirectJump { label_symidx } => {
                         let label_symidx = label_symidx.as_ref_borrow();
                         self.cur_instr_pos = *self.simu_symtab.get_mut(&label_symidx).get_simu_label_pos();
@@ -633,6 +707,106 @@ impl Simulator{
                         self.simu_add_value(&lhs,result.clone())?;
                         result
                     },
+                    super::nhwc_instr::Trans::Fptoui { float_symidx } => {
+                        let float_symidx = float_symidx.as_ref_borrow();
+
+                        let float_val = self.simu_symtab.get(&float_symidx).get_simu_val();
+                        let result = float_val.clone().trans_to_specific_type(&U32);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Uitofp { int_symidx } => {
+                        let int_symidx = int_symidx.as_ref_borrow();
+
+                        let int_val = self.simu_symtab.get(&int_symidx).get_simu_val();
+                        let result = int_val.clone().trans_to_specific_type(&U32).trans_to_specific_type(&F32);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Sext { int_symidx } => {
+                        let int_symidx = int_symidx.as_ref_borrow();
+
+                        let int_val = self.simu_symtab.get(&int_symidx).get_simu_val();
+                        let result = int_val.clone().trans_to_specific_type(&I64);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::ZextU32 { uint_symidx } => {
+                        let uint_symidx = uint_symidx.as_ref_borrow();
+
+                        let uint_val = self.simu_symtab.get(&uint_symidx).get_simu_val();
+                        let result = uint_val.clone().trans_to_specific_type(&U32).trans_to_specific_type(&I64);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Trunc { long_symidx } => {
+                        let long_symidx = long_symidx.as_ref_borrow();
+
+                        // 截断的目标可能是 I32 也可能是 U32
+                        let lhs_ty = src_symtab.get(&lhs.to_src_symidx()).get_type().clone();
+                        let long_val = self.simu_symtab.get(&long_symidx).get_simu_val();
+                        let result = long_val.clone().trans_to_specific_type(&lhs_ty);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::TruncI8 { int_symidx } => {
+                        let int_symidx = int_symidx.as_ref_borrow();
+
+                        let int_val = self.simu_symtab.get(&int_symidx).get_simu_val();
+                        let result = int_val.clone().trans_to_specific_type(&Type::I8);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Fptosi64 { float_symidx } => {
+                        let float_symidx = float_symidx.as_ref_borrow();
+
+                        let float_val = self.simu_symtab.get(&float_symidx).get_simu_val();
+                        let result = float_val.clone().trans_to_specific_type(&I64);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Sitofp64 { long_symidx } => {
+                        let long_symidx = long_symidx.as_ref_borrow();
+
+                        let long_val = self.simu_symtab.get(&long_symidx).get_simu_val();
+                        let result = long_val.clone().trans_to_specific_type(&F32);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Fpext { float_symidx } => {
+                        let float_symidx = float_symidx.as_ref_borrow();
+
+                        let float_val = self.simu_symtab.get(&float_symidx).get_simu_val();
+                        let result = float_val.clone().trans_to_specific_type(&F64);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::Fptrunc { double_symidx } => {
+                        let double_symidx = double_symidx.as_ref_borrow();
+
+                        let double_val = self.simu_symtab.get(&double_symidx).get_simu_val();
+                        let result = double_val.clone().trans_to_specific_type(&F32);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::FptosiF64 { double_symidx } => {
+                        let double_symidx = double_symidx.as_ref_borrow();
+
+                        // 目标可能是 I32 U32 或 I64
+                        let lhs_ty = src_symtab.get(&lhs.to_src_symidx()).get_type().clone();
+                        let double_val = self.simu_symtab.get(&double_symidx).get_simu_val();
+                        let result = double_val.clone().trans_to_specific_type(&lhs_ty);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
+                    super::nhwc_instr::Trans::SitofpF64 { int_symidx } => {
+                        let int_symidx = int_symidx.as_ref_borrow();
+
+                        let int_val = self.simu_symtab.get(&int_symidx).get_simu_val();
+                        let result = int_val.clone().trans_to_specific_type(&F64);
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
+                    },
                     super::nhwc_instr::Trans::Zext { bool_symidx } => {
                         let bool_symidx = bool_symidx.as_ref_borrow();
 
@@ -641,8 +815,12 @@ impl Simulator{
                         self.simu_add_value(&lhs,result.clone())?;
                         result
                     },
-                    super::nhwc_instr::Trans::Bitcast { rptr_symidx: _, rptr_type: _, lptr_type: _ } => {
-                        panic!("bitcast不处理")
+                    super::nhwc_instr::Trans::Bitcast { rptr_symidx, rptr_type: _, lptr_type: _ } => {
+                        let rptr_symidx = rptr_symidx.as_ref_borrow();
+
+                        let result = self.simu_symtab.get(&rptr_symidx).get_simu_val().clone();
+                        self.simu_add_value(&lhs,result.clone())?;
+                        result
                     },
                 };
             },
@@ -670,8 +848,13 @@ impl Simulator{
                 let src_var_symidx = var_symidx.to_src_symidx();
                 let rc_src_var_symidx = &src_var_symidx.clone().as_rc();
                 if !simu_symtab.has_symbol(&src_var_symidx){
+                    // 字符串字面量的内容在生成时就已经确定
+                    let init_val = match src_symtab.try_get(&src_var_symidx).ok().and_then(|sym| sym.get_op_str_literal_val()){
+                        Some(str_val) => str_val.clone(),
+                        None => Value::new_unsure_from_specific_type(&vartype),
+                    };
                     add_symbol!({src_var_symidx.clone().into_symbol()}
-                        with_field SIMU_VAL:{Value::new_unsure_from_specific_type(&vartype)}
+                        with_field SIMU_VAL:{init_val}
                         with_field SIMU_OP_LAST_DEF_INSTR:{Some(instr)}
                         to simu_symtab
                     );
@@ -728,7 +911,7 @@ impl Simulator{
                     None => {},
                 }
             },
-            Store { val_symidx: value_symidx, value_ty: _, ptr_symidx, ptr_ty: _ } => {
+            Store { val_symidx: value_symidx, value_ty, ptr_symidx, ptr_ty } => {
                 debug_info_red!("store {value_symidx:?} to ptr {ptr_symidx:?}");
                 let ptr_symidx = ptr_symidx.as_ref_borrow();
                 let value_symidx = value_symidx.as_ref_borrow();
@@ -740,6 +923,8 @@ impl Simulator{
                                 // 这里要分两种情况，一种是数组，另一种是普通指针
                                 let pointed_symidx = pointed_symidx.as_ref_borrow();
                                 let val = self.simu_symtab.get(&value_symidx).get_simu_val().clone();
+                                // 存入 char 时截断到 1 字节，这样全局数组也能按 .byte 输出
+                                let val = if (value_ty.is_i_8() || ptr_ty.to_deref_ptr_type().is_i_8()) && val.is_i_32() { val.force_to_ty(&Type::I8) } else { val };
                                 let var_be_assigned = self.simu_symtab.get_mut(&pointed_symidx).get_mut_simu_val();
                                 debug_info_red!("assigned val is {val:?}");
                                 match var_be_assigned{
@@ -0,0 +1,245 @@
+use anyhow::{anyhow, Result};
+
+use crate::antlr_parser::clexer::*;
+
+/// 手写词法分析器产生的 token ，token_type 与 CLexer 的 token 类型一致，EOF 的类型是 -1
+#[derive(Clone, Debug)]
+pub struct Token<'a> {
+    pub token_type:isize,
+    pub text:&'a str,
+    /// 行号从 1 开始，列号从 0 开始并且按字符计数，与 antlr 相同
+    pub line:usize,
+    pub col:usize,
+}
+pub const TOKEN_EOF:isize = -1;
+
+/// 关键字，与 CLexer 一样，长度相同时关键字优先于 Identifier
+const KEYWORDS:&[(&str, isize)] = &[
+    ("__m128", T__0), ("__m128d", T__1), ("__m128i", T__2), ("__extension__", T__3), ("__typeof__", T__4), ("__inline__", T__5), ("__stdcall", T__6), ("__declspec", T__7),
+    ("__cdecl", T__8), ("__clrcall", T__9), ("__fastcall", T__10), ("__thiscall", T__11), ("__vectorcall", T__12), ("__asm", T__13), ("__asm__", T__14), ("__volatile__", T__15),
+    ("auto", Auto), ("break", Break), ("case", Case), ("char", Char), ("const", Const), ("continue", Continue), ("default", Default), ("do", Do), ("double", Double),
+    ("else", Else), ("enum", Enum), ("extern", Extern), ("float", Float), ("for", For), ("goto", Goto), ("if", If), ("breakpoint", Breakpoint), ("inline", Inline),
+    ("int", Int), ("long", Long), ("register", Register), ("restrict", Restrict), ("return", Return), ("short", Short), ("signed", Signed), ("sizeof", Sizeof),
+    ("static", Static), ("struct", Struct), ("switch", Switch), ("typedef", Typedef), ("union", Union), ("unsigned", Unsigned), ("void", Void), ("volatile", Volatile),
+    ("while", While), ("_Alignas", Alignas), ("_Alignof", Alignof), ("_Atomic", Atomic), ("_Bool", Bool), ("_Complex", Complex), ("_Generic", Generic),
+    ("_Imaginary", Imaginary), ("_Noreturn", Noreturn), ("_Static_assert", StaticAssert), ("_Thread_local", ThreadLocal),
+];
+/// 标点符号，按最长匹配选择
+const PUNCTUATORS:&[(&str, isize)] = &[
+    ("...", Ellipsis), ("<<=", LeftShiftAssign), (">>=", RightShiftAssign),
+    ("->", Arrow), ("++", PlusPlus), ("--", MinusMinus), ("<<", LeftShift), (">>", RightShift), ("<=", LessEqual), (">=", GreaterEqual), ("==", Equal), ("!=", NotEqual),
+    ("&&", AndAnd), ("||", OrOr), ("*=", MulAssign), ("/=", DivAssign), ("%=", ModAssign), ("+=", PlusAssign), ("-=", MinusAssign), ("&=", AndAssign), ("^=", XorAssign), ("|=", OrAssign),
+    ("(", LeftParen), (")", RightParen), ("[", LeftBracket), ("]", RightBracket), ("{", LeftBrace), ("}", RightBrace), ("<", Less), (">", Greater), ("+", Plus), ("-", Minus),
+    ("*", Star), ("/", Div), ("%", Mod), ("&", And), ("|", Or), ("^", Caret), ("!", Not), ("~", Tilde), ("?", Question), (":", Colon), (";", Semi), (",", Comma), ("=", Assign), (".", Dot),
+];
+
+/// 把代码切分成 token ，丢弃空白、注释和预处理指令这些 antlr 中放在 HIDDEN channel 的 token ，最后附加一个 EOF
+/// 每个位置都按 CLexer 的规则取最长匹配，因此切分结果与 CLexer 相同
+pub fn tokenize(code:&str) -> Result<Vec<Token<'_>>> {
+    let bytes = code.as_bytes();
+    let mut tokens = vec![];
+    let (mut pos, mut line, mut col) = (0, 1, 0);
+    while pos < bytes.len() {
+        let (len, op_token_type) = match_token(bytes, pos).ok_or_else(|| anyhow!("{}:{} 无法识别的字符 `{}`", line, col, code[pos..].chars().next().unwrap()))?;
+        if let Some(token_type) = op_token_type {
+            tokens.push(Token { token_type, text:&code[pos..pos + len], line, col });
+        }
+        for &b in &bytes[pos..pos + len] {
+            if b == b'\n' {
+                line += 1;
+                col = 0;
+            }else if b & 0xC0 != 0x80 {
+                // utf-8 的后续字节不单独计一列
+                col += 1;
+            }
+        }
+        pos += len;
+    }
+    tokens.push(Token { token_type:TOKEN_EOF, text:"<EOF>", line, col });
+    Ok(tokens)
+}
+
+/// 如果代码以被丢弃的 token 开头，返回这个 token 的文本
+pub fn leading_hidden_text(code:&str) -> Option<&str> {
+    if code.is_empty() { return None }
+    match match_token(code.as_bytes(), 0) {
+        Some((len, None)) => Some(&code[..len]),
+        _ => None,
+    }
+}
+
+/// 返回 pos 处最长匹配的长度和 token 类型，token 类型为 None 表示这是被丢弃的 token
+fn match_token(bytes:&[u8], pos:usize) -> Option<(usize, Option<isize>)> {
+    let rest = &bytes[pos..];
+    let c = rest[0];
+    match c {
+        b' ' | b'\t' => return Some((rest.iter().take_while(|&&b| b == b' ' || b == b'\t').count(), None)),
+        b'\r' => return Some((if rest.get(1) == Some(&b'\n') { 2 } else { 1 }, None)),
+        b'\n' => return Some((1, None)),
+        b'#' => return Some((directive_len(rest), None)),
+        _ => {}
+    }
+    if rest.starts_with(b"/*") {
+        // 没有闭合的 /* 不是注释，此时 / 会被当作除号
+        if let Some(end) = find_bytes(&rest[2..], b"*/") {
+            return Some((end + 4, None))
+        }
+    }
+    if rest.starts_with(b"//") {
+        return Some((rest.iter().take_while(|&&b| b != b'\r' && b != b'\n').count(), None))
+    }
+    // 各个规则分别取最长匹配，长度相同时取规则靠前的
+    let mut best:Option<(usize, Option<isize>)> = None;
+    let mut consider = |len:usize, token_type:Option<isize>| {
+        if len > 0 && best.is_none_or(|(best_len, _)| len > best_len) {
+            best = Some((len, token_type));
+        }
+    };
+    if is_ident_start(c) {
+        let len = rest.iter().take_while(|&&b| is_ident_continue(b)).count();
+        let word = &rest[..len];
+        let keyword = KEYWORDS.iter().find(|(kw, _)| kw.as_bytes() == word);
+        consider(len, Some(keyword.map_or(Identifier, |&(_, token_type)| token_type)));
+        if word.starts_with(b"asm") {
+            // 'asm' ~'{'* '{' ~'}'* '}' 整块都被丢弃
+            if let Some(left_brace) = rest.iter().position(|&b| b == b'{') {
+                if let Some(right_brace) = rest[left_brace..].iter().position(|&b| b == b'}') {
+                    consider(left_brace + right_brace + 1, None);
+                }
+            }
+        }
+    }
+    for &(punct, token_type) in PUNCTUATORS {
+        if rest.starts_with(punct.as_bytes()) {
+            consider(punct.len(), Some(token_type));
+            break
+        }
+    }
+    consider(num_constant_len(rest), Some(Constant));
+    consider(quoted_len(rest, b'\''), Some(Constant));
+    consider(rest.iter().take_while(|b| b.is_ascii_digit()).count(), Some(DigitSequence));
+    consider(quoted_len(rest, b'"'), Some(StringLiteral));
+    best
+}
+
+fn is_ident_start(b:u8) -> bool { b.is_ascii_alphabetic() || b == b'_' }
+fn is_ident_continue(b:u8) -> bool { b.is_ascii_alphanumeric() || b == b'_' }
+fn find_bytes(haystack:&[u8], needle:&[u8]) -> Option<usize> { haystack.windows(needle.len()).position(|w| w == needle) }
+fn count_while(bytes:&[u8], pred:impl Fn(u8) -> bool) -> usize { bytes.iter().take_while(|&&b| pred(b)).count() }
+
+/// # 开头的预处理指令，行尾是 \ 时延续到下一行
+fn directive_len(rest:&[u8]) -> usize {
+    let mut len = 0;
+    loop {
+        let line_len = count_while(&rest[len..], |b| b != b'\n');
+        let line = &rest[len..len + line_len];
+        let is_continued = line.strip_suffix(b"\r").unwrap_or(line).ends_with(b"\\") && len + line_len + 1 < rest.len() && rest[len + line_len + 1] != b'\n';
+        if !is_continued {
+            return len + line_len
+        }
+        len += line_len + 1;
+    }
+}
+
+/// 数值常量 IntegerConstant 与 FloatingConstant 中最长的匹配
+fn num_constant_len(rest:&[u8]) -> usize {
+    let digits = |from:usize| count_while(&rest[from.min(rest.len())..], |b| b.is_ascii_digit());
+    let hex_digits = |from:usize| count_while(&rest[from.min(rest.len())..], |b| b.is_ascii_hexdigit());
+    let at = |idx:usize| rest.get(idx).copied().unwrap_or(0);
+    let mut max_len = 0;
+    // 十进制与八进制整数，八进制只有 0 开头的 [0-7]*
+    if at(0).is_ascii_digit() {
+        let int_len = if at(0) == b'0' { 1 + count_while(&rest[1..], |b| (b'0'..=b'7').contains(&b)) } else { digits(0) };
+        max_len = max_len.max(int_len + int_suffix_len(&rest[int_len..]));
+    }
+    if at(0) == b'0' && (at(1) == b'x' || at(1) == b'X') {
+        let int_digits = hex_digits(2);
+        if int_digits > 0 {
+            max_len = max_len.max(2 + int_digits + int_suffix_len(&rest[2 + int_digits..]));
+        }
+        // 十六进制浮点数必须有 p 指数
+        let frac_len = if at(2 + int_digits) == b'.' { 1 + hex_digits(3 + int_digits) } else { 0 };
+        if int_digits + frac_len > 0 && !(int_digits == 0 && frac_len == 1) {
+            let mantissa_len = 2 + int_digits + frac_len;
+            let exp_len = exponent_len(&rest[mantissa_len..], b"pP");
+            if exp_len > 0 {
+                max_len = max_len.max(mantissa_len + exp_len + float_suffix_len(&rest[mantissa_len + exp_len..]));
+            }
+        }
+    }
+    if at(0) == b'0' && (at(1) == b'b' || at(1) == b'B') && matches!(at(2), b'0' | b'1') {
+        max_len = max_len.max(2 + count_while(&rest[2..], |b| b == b'0' || b == b'1'));
+    }
+    // 十进制浮点数 1.5 .5 5. 以及 1e3
+    let int_digits = digits(0);
+    let frac_len = if at(int_digits) == b'.' { 1 + digits(int_digits + 1) } else { 0 };
+    if frac_len > 0 && !(int_digits == 0 && frac_len == 1) {
+        let mantissa_len = int_digits + frac_len;
+        let exp_len = exponent_len(&rest[mantissa_len..], b"eE");
+        max_len = max_len.max(mantissa_len + exp_len + float_suffix_len(&rest[mantissa_len + exp_len..]));
+    }
+    if int_digits > 0 {
+        let exp_len = exponent_len(&rest[int_digits..], b"eE");
+        if exp_len > 0 {
+            max_len = max_len.max(int_digits + exp_len + float_suffix_len(&rest[int_digits + exp_len..]));
+        }
+    }
+    max_len
+}
+/// [uU] ([lL] | ll | LL)? 或者 ([lL] | ll | LL) [uU]?
+fn int_suffix_len(rest:&[u8]) -> usize {
+    let long_len = |rest:&[u8]| if rest.starts_with(b"ll") || rest.starts_with(b"LL") { 2 } else if matches!(rest.first(), Some(b'l' | b'L')) { 1 } else { 0 };
+    match rest.first() {
+        Some(b'u' | b'U') => 1 + long_len(&rest[1..]),
+        _ => {
+            let len = long_len(rest);
+            if len > 0 && matches!(rest.get(len), Some(b'u' | b'U')) { len + 1 } else { len }
+        }
+    }
+}
+fn float_suffix_len(rest:&[u8]) -> usize { if matches!(rest.first(), Some(b'f' | b'l' | b'F' | b'L')) { 1 } else { 0 } }
+/// 指数部分 [eE] [+-]? 数字+ ，不完整时返回 0
+fn exponent_len(rest:&[u8], markers:&[u8]) -> usize {
+    if !rest.first().is_some_and(|b| markers.contains(b)) { return 0 }
+    let sign_len = if matches!(rest.get(1), Some(b'+' | b'-')) { 1 } else { 0 };
+    let digits = count_while(&rest[(1 + sign_len).min(rest.len())..], |b| b.is_ascii_digit());
+    if digits > 0 { 1 + sign_len + digits } else { 0 }
+}
+/// 字符常量与字符串字面量，包括 L u U 以及字符串的 u8 前缀，不完整时返回 0
+fn quoted_len(rest:&[u8], quote:u8) -> usize {
+    let prefixes:&[&[u8]] = if quote == b'"' { &[b"u8", b"u", b"U", b"L", b""] } else { &[b"u", b"U", b"L", b""] };
+    let Some(prefix) = prefixes.iter().find(|prefix| rest.starts_with(prefix) && rest.get(prefix.len()) == Some(&quote)) else { return 0 };
+    let mut idx = prefix.len() + 1;
+    while idx < rest.len() {
+        match rest[idx] {
+            b if b == quote => {
+                // 字符常量中至少要有一个字符
+                return if quote == b'\'' && idx == prefix.len() + 1 { 0 } else { idx + 1 }
+            }
+            b'\r' | b'\n' => return 0,
+            b'\\' => {
+                let escape_len = escape_len(&rest[idx..], quote == b'"');
+                if escape_len == 0 { return 0 }
+                idx += escape_len;
+            }
+            _ => idx += 1,
+        }
+    }
+    0
+}
+/// 转义序列的长度，字符串中允许用 \ 续行
+fn escape_len(rest:&[u8], allow_line_continuation:bool) -> usize {
+    match rest.get(1) {
+        Some(b'\'' | b'"' | b'?' | b'a' | b'b' | b'f' | b'n' | b'r' | b't' | b'v' | b'\\') => 2,
+        Some(b'0'..=b'7') => 1 + count_while(&rest[1..rest.len().min(4)], |b| (b'0'..=b'7').contains(&b)),
+        Some(b'x') => {
+            let digits = count_while(&rest[2..], |b| b.is_ascii_hexdigit());
+            if digits > 0 { 2 + digits } else { 0 }
+        }
+        Some(b'u') if rest.len() >= 6 && rest[2..6].iter().all(u8::is_ascii_hexdigit) => 6,
+        Some(b'U') if rest.len() >= 10 && rest[2..10].iter().all(u8::is_ascii_hexdigit) => 10,
+        Some(b'\n') if allow_line_continuation => 2,
+        Some(b'\r') if allow_line_continuation && rest.get(2) == Some(&b'\n') => 3,
+        _ => 0,
+    }
+}
@@ -0,0 +1,951 @@
+use ahash::HashSet;
+use anyhow::{anyhow, Result};
+use petgraph::stable_graph::NodeIndex;
+
+use crate::antlr_parser::clexer::*;
+use crate::antlr_parser::cparser::{RULE_abstractDeclarator, RULE_additiveExpression, RULE_andExpression, RULE_argumentExpressionList, RULE_assignmentExpression, RULE_assignmentOperator, RULE_blockItem, RULE_blockItemList, RULE_breakStatement, RULE_breakpointArg, RULE_breakpointStatement, RULE_castExpression, RULE_compilationUnit, RULE_compoundStatement, RULE_conditionalExpression, RULE_constantExpression, RULE_continueStatement, RULE_declaration, RULE_declarationSpecifier, RULE_declarationSpecifiers, RULE_declarationSpecifiers2, RULE_declarator, RULE_designation, RULE_designator, RULE_designatorList, RULE_directAbstractDeclarator, RULE_directDeclarator, RULE_doWhileIterationStatement, RULE_enumSpecifier, RULE_enumerationConstant, RULE_enumerator, RULE_enumeratorList, RULE_equalityExpression, RULE_exclusiveOrExpression, RULE_expression, RULE_expressionStatement, RULE_externalDeclaration, RULE_forAfterExpression, RULE_forBeforeExpression, RULE_forCondition, RULE_forDeclaration, RULE_forIterationStatement, RULE_forMidExpression, RULE_functionDefinition, RULE_functionSpecifier, RULE_ifSelection, RULE_inclusiveOrExpression, RULE_initDeclarator, RULE_initDeclaratorList, RULE_initializer, RULE_initializerList, RULE_iterationStatement, RULE_jumpStatement, RULE_labeledStatement, RULE_logicalAndExpression, RULE_logicalOrExpression, RULE_multiplicativeExpression, RULE_parameterDeclaration, RULE_parameterList, RULE_parameterTypeList, RULE_pointer, RULE_postfixExpression, RULE_primaryExpression, RULE_relationalExpression, RULE_returnStatement, RULE_selectionStatement, RULE_shiftExpression, RULE_specifierQualifierList, RULE_statement, RULE_storageClassSpecifier, RULE_structDeclaration, RULE_structDeclarationList, RULE_structDeclarator, RULE_structDeclaratorList, RULE_structOrUnion, RULE_structOrUnionSpecifier, RULE_switchSelection, RULE_translationUnit, RULE_typeName, RULE_typeQualifier, RULE_typeQualifierList, RULE_typeSpecifier, RULE_typedefName, RULE_unaryExpression, RULE_unaryOperator, RULE_whileIterationStatement};
+use crate::node_mut;
+
+use super::ast_node::{AstNode, AstTree, SrcLoc};
+use super::gen_ast::{is_typedef_ptr_decl, scan_typedef_names};
+use super::preprocess::LineMap;
+use super::sysy_lexer::{leading_hidden_text, tokenize, Token, TOKEN_EOF};
+
+/// 手写的递归下降 parser 先生成这样的树，回溯时直接丢弃即可，最后再按先序一次性加入 AstTree
+enum ParseNode {
+    /// start 是这个规则第一个 token 的下标，空规则的 start 是它后面的 token
+    Rule { rule_id:usize, start:usize, children:Vec<ParseNode> },
+    Term(usize),
+}
+impl ParseNode {
+    fn start(&self) -> usize {
+        match self {
+            ParseNode::Rule { start, .. } => *start,
+            ParseNode::Term(token_idx) => *token_idx,
+        }
+    }
+}
+fn rule(rule_id:usize, start:usize, children:Vec<ParseNode>) -> ParseNode { ParseNode::Rule { rule_id, start, children } }
+
+const STORAGE_CLASS_TOKENS:&[isize] = &[Typedef, Extern, Static, ThreadLocal, Auto, Register];
+const TYPE_KEYWORD_TOKENS:&[isize] = &[Void, Char, Short, Int, Long, Float, Double, Signed, Unsigned, Bool, Complex];
+const TYPE_QUALIFIER_TOKENS:&[isize] = &[Const, Restrict, Volatile, Atomic];
+const FUNCTION_SPECIFIER_TOKENS:&[isize] = &[Inline, Noreturn, T__5, T__6];
+const UNARY_OPERATOR_TOKENS:&[isize] = &[And, Star, Plus, Minus, Tilde, Not, PlusPlus, MinusMinus];
+const ASSIGNMENT_OPERATOR_TOKENS:&[isize] = &[Assign, MulAssign, DivAssign, ModAssign, PlusAssign, MinusAssign, LeftShiftAssign, RightShiftAssign, AndAssign, XorAssign, OrAssign];
+/// 从 multiplicativeExpression 到 logicalOrExpression ，每一层的规则和运算符
+const BINARY_LEVELS:&[(usize, &[isize])] = &[
+    (RULE_multiplicativeExpression, &[Star, Div, Mod]),
+    (RULE_additiveExpression, &[Plus, Minus]),
+    (RULE_shiftExpression, &[LeftShift, RightShift]),
+    (RULE_relationalExpression, &[Less, Greater, LessEqual, GreaterEqual]),
+    (RULE_equalityExpression, &[Equal, NotEqual]),
+    (RULE_andExpression, &[And]),
+    (RULE_exclusiveOrExpression, &[Caret]),
+    (RULE_inclusiveOrExpression, &[Or]),
+    (RULE_logicalAndExpression, &[AndAnd]),
+    (RULE_logicalOrExpression, &[OrOr]),
+];
+
+/// SysY 子集的递归下降 parser ，生成的 AstTree 与 CParser 经 TerminalRuleListener 转换得到的完全相同
+/// 包括左递归规则的嵌套方式、空规则节点、节点的先序编号和位置，这样 gen_scope gen_cfg gen_et 不需要区分两种前端
+/// 文法有歧义的地方按 antlr 的方式选择: 多个分支都可行时选择靠前的分支
+struct SysyParser<'a> {
+    tokens:Vec<Token<'a>>,
+    pos:usize,
+    typedef_names:HashSet<String>,
+    line_map:&'a LineMap,
+    /// antlr 的 compilationUnit 从第一个 token 开始，即使它是被丢弃的空白或注释，这时它的位置和文本都包含这个 token
+    op_leading_hidden:Option<&'a str>,
+}
+
+/// 用手写的 parser 把代码解析为 AstTree ，debug 时和 antlr 一样给非终结符也记录文本
+pub fn parse_by_native(code:&str, line_map:&LineMap, debug:bool) -> Result<AstTree> {
+    let tokens = tokenize(code).map_err(|e| map_err_line(line_map, e))?;
+    let typedef_names = scan_typedef_names(&tokens.iter().map(|token| (token.token_type, token.text)).collect::<Vec<_>>());
+    let mut parser = SysyParser { tokens, pos:0, typedef_names, line_map, op_leading_hidden:leading_hidden_text(code) };
+    let root = parser.compilation_unit()?;
+    let mut ast_tree = AstTree::default();
+    parser.add_to_ast_tree(&root, None, &mut ast_tree, debug);
+    Ok(ast_tree)
+}
+/// 词法错误中的行号是预处理之后的，换算回原始文件
+fn map_err_line(line_map:&LineMap, e:anyhow::Error) -> anyhow::Error {
+    let msg = e.to_string();
+    let Some((line, rest)) = msg.split_once(':') else { return e };
+    match line.parse().ok().and_then(|line| line_map.get_src_line(line)) {
+        Some((file, src_line)) => anyhow!("{}:{}:{}", file, src_line, rest),
+        None => e,
+    }
+}
+
+impl<'a> SysyParser<'a> {
+    fn la(&self, k:usize) -> isize { self.tokens.get(self.pos + k).map_or(TOKEN_EOF, |token| token.token_type) }
+    fn term(&mut self) -> ParseNode {
+        let node = ParseNode::Term(self.pos);
+        if self.la(0) != TOKEN_EOF { self.pos += 1; }
+        node
+    }
+    fn expect(&mut self, token_type:isize) -> Result<ParseNode> {
+        if self.la(0) == token_type { Ok(self.term()) } else { Err(self.error(&format!("期望 {}", token_name(token_type)))) }
+    }
+    fn error(&self, msg:&str) -> anyhow::Error {
+        let token = &self.tokens[self.pos.min(self.tokens.len() - 1)];
+        let loc = match self.line_map.get_src_line(token.line) {
+            Some((file, src_line)) => format!("{}:{}:{}", file, src_line, token.col + 1),
+            None => format!("line {}:{}", token.line, token.col + 1),
+        };
+        anyhow!("{} {} 但遇到 `{}`", loc, msg, token.text)
+    }
+    /// 先尝试 f ，失败时回到原来的位置
+    fn try_parse<T>(&mut self, f:impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let saved_pos = self.pos;
+        let rst = f(self);
+        if rst.is_err() { self.pos = saved_pos; }
+        rst
+    }
+
+    // ---------------- 外部定义 ----------------
+    fn compilation_unit(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        if self.la(0) != TOKEN_EOF {
+            children.push(self.translation_unit()?);
+        }
+        if self.la(0) != TOKEN_EOF {
+            return Err(self.error("期望文件结束"))
+        }
+        Ok(rule(RULE_compilationUnit, start, children))
+    }
+    fn translation_unit(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        while self.la(0) != TOKEN_EOF {
+            children.push(self.external_declaration()?);
+        }
+        Ok(rule(RULE_translationUnit, start, children))
+    }
+    fn external_declaration(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let child = if self.la(0) == Semi {
+            self.term()
+        }else if self.is_function_definition() {
+            self.function_definition()?
+        }else {
+            self.declaration()?
+        };
+        Ok(rule(RULE_externalDeclaration, start, vec![child]))
+    }
+    /// 说明符和 declarator 之后是 { 的是函数定义
+    fn is_function_definition(&mut self) -> bool {
+        let saved_pos = self.pos;
+        let is_func_def = self.declaration_specifiers(RULE_declarationSpecifiers).and_then(|_| self.declarator()).is_ok() && self.la(0) == LeftBrace;
+        self.pos = saved_pos;
+        is_func_def
+    }
+    fn function_definition(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = vec![self.declaration_specifiers(RULE_declarationSpecifiers)?, self.declarator()?, self.compound_statement()?];
+        Ok(rule(RULE_functionDefinition, start, children))
+    }
+
+    // ---------------- 声明 ----------------
+    fn declaration(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        if self.la(0) == StaticAssert {
+            return Err(self.error("手写 parser 不支持 _Static_assert"))
+        }
+        let mut children = vec![self.declaration_specifiers(RULE_declarationSpecifiers)?];
+        if self.la(0) != Semi {
+            children.push(self.init_declarator_list()?);
+        }
+        children.push(self.expect(Semi)?);
+        Ok(rule(RULE_declaration, start, children))
+    }
+    fn is_declaration_start(&self, token_type:isize) -> bool {
+        STORAGE_CLASS_TOKENS.contains(&token_type) || TYPE_KEYWORD_TOKENS.contains(&token_type) || TYPE_QUALIFIER_TOKENS.contains(&token_type) || FUNCTION_SPECIFIER_TOKENS.contains(&token_type)
+            || [Struct, Union, Enum, Alignas, StaticAssert, T__0, T__1, T__2, T__3, T__4, T__7].contains(&token_type)
+    }
+    /// 说明符中的标识符是否是 typedefName
+    /// 第一个说明符一定是类型名，已经有类型说明符之后的标识符是 declarator ，否则后面还能接 declarator 或说明符时才是类型名
+    fn is_typedef_name_specifier(&self, has_specifier:bool, has_type:bool) -> bool {
+        self.la(0) == Identifier && (!has_specifier || (!has_type && (self.is_declaration_start(self.la(1)) || [Identifier, Star, LeftParen].contains(&self.la(1)))))
+    }
+    /// declarationSpecifiers 与 declarationSpecifiers2 的结构相同，只是规则不同
+    fn declaration_specifiers(&mut self, rule_id:usize) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        let mut has_type = false;
+        loop {
+            let specifier_start = self.pos;
+            let la0 = self.la(0);
+            let child = if STORAGE_CLASS_TOKENS.contains(&la0) {
+                rule(RULE_storageClassSpecifier, specifier_start, vec![self.term()])
+            }else if TYPE_QUALIFIER_TOKENS.contains(&la0) && !(la0 == Atomic && self.la(1) == LeftParen) {
+                rule(RULE_typeQualifier, specifier_start, vec![self.term()])
+            }else if FUNCTION_SPECIFIER_TOKENS.contains(&la0) {
+                rule(RULE_functionSpecifier, specifier_start, vec![self.term()])
+            }else if TYPE_KEYWORD_TOKENS.contains(&la0) || [Struct, Union, Enum].contains(&la0) || self.is_typedef_name_specifier(!children.is_empty(), has_type) {
+                has_type = true;
+                self.type_specifier()?
+            }else if self.is_declaration_start(la0) {
+                return Err(self.error("手写 parser 不支持这种说明符"))
+            }else {
+                break
+            };
+            children.push(rule(RULE_declarationSpecifier, specifier_start, vec![child]));
+        }
+        if children.is_empty() {
+            return Err(self.error("期望声明说明符"))
+        }
+        Ok(rule(rule_id, start, children))
+    }
+    fn type_specifier(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let child = match self.la(0) {
+            Struct | Union => self.struct_or_union_specifier()?,
+            Enum => self.enum_specifier()?,
+            Identifier => rule(RULE_typedefName, start, vec![self.term()]),
+            _ => self.term(),
+        };
+        Ok(rule(RULE_typeSpecifier, start, vec![child]))
+    }
+    fn struct_or_union_specifier(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![rule(RULE_structOrUnion, start, vec![self.term()])];
+        if self.la(0) == Identifier {
+            children.push(self.term());
+        }
+        if self.la(0) == LeftBrace || children.len() == 1 {
+            children.push(self.expect(LeftBrace)?);
+            children.push(self.struct_declaration_list()?);
+            children.push(self.expect(RightBrace)?);
+        }
+        Ok(rule(RULE_structOrUnionSpecifier, start, children))
+    }
+    fn struct_declaration_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.struct_declaration()?];
+        while self.la(0) != RightBrace && self.la(0) != TOKEN_EOF {
+            children.push(self.struct_declaration()?);
+        }
+        Ok(rule(RULE_structDeclarationList, start, children))
+    }
+    fn struct_declaration(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.specifier_qualifier_list(false, false)?];
+        if self.la(0) != Semi {
+            children.push(self.struct_declarator_list()?);
+        }
+        children.push(self.expect(Semi)?);
+        Ok(rule(RULE_structDeclaration, start, children))
+    }
+    /// specifierQualifierList 是右递归的，每一层只有一个说明符
+    fn specifier_qualifier_list(&mut self, has_specifier:bool, has_type:bool) -> Result<ParseNode> {
+        let start = self.pos;
+        let la0 = self.la(0);
+        let (child, has_type) = if TYPE_QUALIFIER_TOKENS.contains(&la0) && !(la0 == Atomic && self.la(1) == LeftParen) {
+            (rule(RULE_typeQualifier, start, vec![self.term()]), has_type)
+        }else if TYPE_KEYWORD_TOKENS.contains(&la0) || [Struct, Union, Enum].contains(&la0) || self.is_typedef_name_specifier(has_specifier, has_type) {
+            (self.type_specifier()?, true)
+        }else {
+            return Err(self.error("期望类型说明符"))
+        };
+        let mut children = vec![child];
+        let la0 = self.la(0);
+        if TYPE_QUALIFIER_TOKENS.contains(&la0) || TYPE_KEYWORD_TOKENS.contains(&la0) || [Struct, Union, Enum].contains(&la0) || self.is_typedef_name_specifier(true, has_type) {
+            children.push(self.specifier_qualifier_list(true, has_type)?);
+        }
+        Ok(rule(RULE_specifierQualifierList, start, children))
+    }
+    fn struct_declarator_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.struct_declarator()?];
+        while self.la(0) == Comma {
+            children.push(self.term());
+            children.push(self.struct_declarator()?);
+        }
+        Ok(rule(RULE_structDeclaratorList, start, children))
+    }
+    fn struct_declarator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        if self.la(0) != Colon {
+            children.push(self.declarator()?);
+        }
+        if self.la(0) == Colon {
+            children.push(self.term());
+            children.push(self.constant_expression()?);
+        }
+        Ok(rule(RULE_structDeclarator, start, children))
+    }
+    fn enum_specifier(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.expect(Enum)?];
+        if self.la(0) == Identifier {
+            children.push(self.term());
+        }
+        if self.la(0) == LeftBrace || children.len() == 1 {
+            children.push(self.expect(LeftBrace)?);
+            children.push(self.enumerator_list()?);
+            if self.la(0) == Comma {
+                children.push(self.term());
+            }
+            children.push(self.expect(RightBrace)?);
+        }
+        Ok(rule(RULE_enumSpecifier, start, children))
+    }
+    fn enumerator_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.enumerator()?];
+        while self.la(0) == Comma && self.la(1) != RightBrace {
+            children.push(self.term());
+            children.push(self.enumerator()?);
+        }
+        Ok(rule(RULE_enumeratorList, start, children))
+    }
+    fn enumerator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![rule(RULE_enumerationConstant, start, vec![self.expect(Identifier)?])];
+        if self.la(0) == Assign {
+            children.push(self.term());
+            children.push(self.constant_expression()?);
+        }
+        Ok(rule(RULE_enumerator, start, children))
+    }
+    fn init_declarator_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.init_declarator()?];
+        while self.la(0) == Comma {
+            children.push(self.term());
+            children.push(self.init_declarator()?);
+        }
+        Ok(rule(RULE_initDeclaratorList, start, children))
+    }
+    fn init_declarator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.declarator()?];
+        if self.la(0) == Assign {
+            children.push(self.term());
+            children.push(self.initializer()?);
+        }
+        Ok(rule(RULE_initDeclarator, start, children))
+    }
+    fn declarator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        if self.la(0) == Star || self.la(0) == Caret {
+            children.push(self.pointer());
+        }
+        children.push(self.direct_declarator()?);
+        Ok(rule(RULE_declarator, start, children))
+    }
+    fn pointer(&mut self) -> ParseNode {
+        let start = self.pos;
+        let mut children = vec![];
+        while self.la(0) == Star || self.la(0) == Caret {
+            children.push(self.term());
+            if TYPE_QUALIFIER_TOKENS.contains(&self.la(0)) {
+                children.push(self.type_qualifier_list());
+            }
+        }
+        rule(RULE_pointer, start, children)
+    }
+    fn type_qualifier_list(&mut self) -> ParseNode {
+        let start = self.pos;
+        let mut children = vec![];
+        while TYPE_QUALIFIER_TOKENS.contains(&self.la(0)) {
+            let qualifier_start = self.pos;
+            children.push(rule(RULE_typeQualifier, qualifier_start, vec![self.term()]));
+        }
+        rule(RULE_typeQualifierList, start, children)
+    }
+    /// directDeclarator 是左递归的，每个 [] 或 () 后缀都把之前的 directDeclarator 作为第一个孩子包一层
+    fn direct_declarator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = match self.la(0) {
+            Identifier if self.la(1) == Colon && self.la(2) == DigitSequence => vec![self.term(), self.term(), self.term()],
+            Identifier => vec![self.term()],
+            LeftParen => vec![self.term(), self.declarator()?, self.expect(RightParen)?],
+            _ => return Err(self.error("期望标识符或者 (")),
+        };
+        let mut node = rule(RULE_directDeclarator, start, children);
+        loop {
+            let mut children = vec![node];
+            match self.la(0) {
+                LeftBracket => {
+                    children.push(self.term());
+                    if TYPE_QUALIFIER_TOKENS.contains(&self.la(0)) {
+                        children.push(self.type_qualifier_list());
+                    }
+                    if self.la(0) == Star && self.la(1) == RightBracket {
+                        children.push(self.term());
+                    }else if self.la(0) != RightBracket {
+                        children.push(self.assignment_expression()?);
+                    }
+                    children.push(self.expect(RightBracket)?);
+                }
+                // 参数列表在前，因此 f(a, b) 中的 a b 都被当作类型名而不是 identifierList
+                LeftParen => {
+                    children.push(self.term());
+                    if self.la(0) != RightParen {
+                        children.push(self.parameter_type_list()?);
+                    }
+                    children.push(self.expect(RightParen)?);
+                }
+                _ => return Ok(children.pop().unwrap()),
+            }
+            node = rule(RULE_directDeclarator, start, children);
+        }
+    }
+    fn parameter_type_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.parameter_list()?];
+        if self.la(0) == Comma && self.la(1) == Ellipsis {
+            children.push(self.term());
+            children.push(self.term());
+        }
+        Ok(rule(RULE_parameterTypeList, start, children))
+    }
+    fn parameter_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.parameter_declaration()?];
+        while self.la(0) == Comma && self.la(1) != Ellipsis {
+            children.push(self.term());
+            children.push(self.parameter_declaration()?);
+        }
+        Ok(rule(RULE_parameterList, start, children))
+    }
+    /// 有名字的参数是 declarationSpecifiers declarator ，否则是 declarationSpecifiers2 abstractDeclarator?
+    fn parameter_declaration(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let named = self.try_parse(|parser| {
+            let children = vec![parser.declaration_specifiers(RULE_declarationSpecifiers)?, parser.declarator()?];
+            if parser.la(0) == Comma || parser.la(0) == RightParen { Ok(children) } else { Err(parser.error("期望 , 或者 )")) }
+        });
+        let children = match named {
+            Ok(children) => children,
+            Err(_) => {
+                let mut children = vec![self.declaration_specifiers(RULE_declarationSpecifiers2)?];
+                if self.la(0) != Comma && self.la(0) != RightParen {
+                    children.push(self.abstract_declarator()?);
+                }
+                children
+            }
+        };
+        Ok(rule(RULE_parameterDeclaration, start, children))
+    }
+    fn type_name(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.specifier_qualifier_list(false, false)?];
+        if [Star, Caret, LeftParen, LeftBracket].contains(&self.la(0)) {
+            children.push(self.abstract_declarator()?);
+        }
+        Ok(rule(RULE_typeName, start, children))
+    }
+    fn abstract_declarator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        if self.la(0) == Star || self.la(0) == Caret {
+            children.push(self.pointer());
+        }
+        if self.la(0) == LeftParen || self.la(0) == LeftBracket || children.is_empty() {
+            children.push(self.direct_abstract_declarator()?);
+        }
+        Ok(rule(RULE_abstractDeclarator, start, children))
+    }
+    /// 与 directDeclarator 一样是左递归的
+    fn direct_abstract_declarator(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut node = None;
+        loop {
+            let mut children:Vec<ParseNode> = node.into_iter().collect();
+            let is_first = children.is_empty();
+            match self.la(0) {
+                LeftParen if is_first && [Star, Caret, LeftParen, LeftBracket].contains(&self.la(1)) => {
+                    children.push(self.term());
+                    children.push(self.abstract_declarator()?);
+                    children.push(self.expect(RightParen)?);
+                }
+                LeftParen => {
+                    children.push(self.term());
+                    if self.la(0) != RightParen {
+                        children.push(self.parameter_type_list()?);
+                    }
+                    children.push(self.expect(RightParen)?);
+                }
+                LeftBracket => {
+                    children.push(self.term());
+                    if self.la(0) == Static {
+                        return Err(self.error("手写 parser 不支持 [static]"))
+                    }
+                    if TYPE_QUALIFIER_TOKENS.contains(&self.la(0)) {
+                        children.push(self.type_qualifier_list());
+                    }
+                    if self.la(0) == Star && self.la(1) == RightBracket {
+                        children.push(self.term());
+                    }else if self.la(0) != RightBracket {
+                        children.push(self.assignment_expression()?);
+                    }
+                    children.push(self.expect(RightBracket)?);
+                }
+                _ if is_first => return Err(self.error("期望 ( 或者 [")),
+                _ => return Ok(children.pop().unwrap()),
+            }
+            node = Some(rule(RULE_directAbstractDeclarator, start, children));
+        }
+    }
+    fn initializer(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = if self.la(0) == LeftBrace {
+            let mut children = vec![self.term(), self.initializer_list()?];
+            if self.la(0) == Comma {
+                children.push(self.term());
+            }
+            children.push(self.expect(RightBrace)?);
+            children
+        }else {
+            vec![self.assignment_expression()?]
+        };
+        Ok(rule(RULE_initializer, start, children))
+    }
+    fn initializer_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        // {} 在文法中是错误的，antlr 报错恢复之后得到空的 initializerList ，这里直接生成同样的树
+        if self.la(0) == RightBrace {
+            return Ok(rule(RULE_initializerList, start, children))
+        }
+        loop {
+            if self.la(0) == LeftBracket || self.la(0) == Dot {
+                children.push(self.designation()?);
+            }
+            children.push(self.initializer()?);
+            if self.la(0) == Comma && self.la(1) != RightBrace {
+                children.push(self.term());
+            }else {
+                break
+            }
+        }
+        Ok(rule(RULE_initializerList, start, children))
+    }
+    fn designation(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut designators = vec![];
+        while self.la(0) == LeftBracket || self.la(0) == Dot {
+            let designator_start = self.pos;
+            let children = if self.la(0) == LeftBracket {
+                vec![self.term(), self.constant_expression()?, self.expect(RightBracket)?]
+            }else {
+                vec![self.term(), self.expect(Identifier)?]
+            };
+            designators.push(rule(RULE_designator, designator_start, children));
+        }
+        let designator_list = rule(RULE_designatorList, start, designators);
+        Ok(rule(RULE_designation, start, vec![designator_list, self.expect(Assign)?]))
+    }
+
+    // ---------------- 语句 ----------------
+    fn statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let child = match self.la(0) {
+            Identifier if self.la(1) == Colon => self.labeled_statement()?,
+            Case | Default => self.labeled_statement()?,
+            LeftBrace => self.compound_statement()?,
+            If | Switch => {
+                let child = if self.la(0) == If { self.if_selection()? } else { self.switch_selection()? };
+                rule(RULE_selectionStatement, start, vec![child])
+            }
+            While | Do | For => {
+                let child = match self.la(0) {
+                    While => self.while_iteration_statement()?,
+                    Do => self.do_while_iteration_statement()?,
+                    _ => self.for_iteration_statement()?,
+                };
+                rule(RULE_iterationStatement, start, vec![child])
+            }
+            Continue | Break | Return | Goto => self.jump_statement()?,
+            Breakpoint => self.breakpoint_statement()?,
+            T__13 | T__14 => return Err(self.error("手写 parser 不支持内联汇编")),
+            _ => self.expression_statement()?,
+        };
+        Ok(rule(RULE_statement, start, vec![child]))
+    }
+    fn labeled_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        match self.la(0) {
+            Case => {
+                children.push(self.term());
+                children.push(self.constant_expression()?);
+            }
+            _ => children.push(self.term()),
+        }
+        children.push(self.expect(Colon)?);
+        children.push(self.statement()?);
+        Ok(rule(RULE_labeledStatement, start, children))
+    }
+    fn compound_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let left_brace = self.expect(LeftBrace)?;
+        let block_item_list = self.block_item_list()?;
+        Ok(rule(RULE_compoundStatement, start, vec![left_brace, block_item_list, self.expect(RightBrace)?]))
+    }
+    fn block_item_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        while self.la(0) != RightBrace && self.la(0) != TOKEN_EOF {
+            children.push(self.block_item()?);
+        }
+        Ok(rule(RULE_blockItemList, start, children))
+    }
+    /// 语句在前，因此既能当作语句也能当作声明时选择语句，例外是 `T *p;` (T 是 typedef 名)，与 antlr 解析之后的修正相同
+    fn block_item(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let child = if is_typedef_ptr_decl(|idx| self.la(idx), self.tokens.get(self.pos).map(|token| token.text), &self.typedef_names) || self.is_declaration_start(self.la(0)) {
+            self.declaration()?
+        }else if self.la(0) == Identifier {
+            match self.try_parse(Self::statement) {
+                Ok(stmt) => stmt,
+                Err(stmt_err) => self.try_parse(Self::declaration).map_err(|_| stmt_err)?,
+            }
+        }else {
+            self.statement()?
+        };
+        Ok(rule(RULE_blockItem, start, vec![child]))
+    }
+    fn expression_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        if self.la(0) != Semi {
+            children.push(self.expression()?);
+        }
+        children.push(self.expect(Semi)?);
+        Ok(rule(RULE_expressionStatement, start, children))
+    }
+    fn if_selection(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.term(), self.expect(LeftParen)?, self.expression()?, self.expect(RightParen)?, self.statement()?];
+        if self.la(0) == Else {
+            children.push(self.term());
+            children.push(self.statement()?);
+        }
+        Ok(rule(RULE_ifSelection, start, children))
+    }
+    fn switch_selection(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = vec![self.term(), self.expect(LeftParen)?, self.expression()?, self.expect(RightParen)?, self.statement()?];
+        Ok(rule(RULE_switchSelection, start, children))
+    }
+    fn while_iteration_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = vec![self.term(), self.expect(LeftParen)?, self.expression()?, self.expect(RightParen)?, self.statement()?];
+        Ok(rule(RULE_whileIterationStatement, start, children))
+    }
+    fn do_while_iteration_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = vec![self.term(), self.statement()?, self.expect(While)?, self.expect(LeftParen)?, self.expression()?, self.expect(RightParen)?, self.expect(Semi)?];
+        Ok(rule(RULE_doWhileIterationStatement, start, children))
+    }
+    fn for_iteration_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = vec![self.term(), self.expect(LeftParen)?, self.for_condition()?, self.expect(RightParen)?, self.statement()?];
+        Ok(rule(RULE_forIterationStatement, start, children))
+    }
+    fn for_condition(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.for_before_expression()?, self.expect(Semi)?];
+        if self.la(0) != Semi {
+            let mid_start = self.pos;
+            children.push(rule(RULE_forMidExpression, mid_start, vec![self.expression()?]));
+        }
+        children.push(self.expect(Semi)?);
+        if self.la(0) != RightParen {
+            let after_start = self.pos;
+            children.push(rule(RULE_forAfterExpression, after_start, vec![self.expression()?]));
+        }
+        Ok(rule(RULE_forCondition, start, children))
+    }
+    /// forDeclaration 在前，以标识符开头时先尝试当作声明
+    fn for_before_expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = if self.is_declaration_start(self.la(0)) {
+            vec![self.for_declaration()?]
+        }else if self.la(0) == Semi {
+            vec![]
+        }else if let Ok(for_decl) = self.try_parse(|parser| parser.for_declaration().and_then(|for_decl| if parser.la(0) == Semi { Ok(for_decl) } else { Err(parser.error("期望 ;")) })) {
+            vec![for_decl]
+        }else {
+            vec![self.expression()?]
+        };
+        Ok(rule(RULE_forBeforeExpression, start, children))
+    }
+    fn for_declaration(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.declaration_specifiers(RULE_declarationSpecifiers)?];
+        if self.la(0) != Semi {
+            children.push(self.init_declarator_list()?);
+        }
+        Ok(rule(RULE_forDeclaration, start, children))
+    }
+    fn jump_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = match self.la(0) {
+            Continue => vec![rule(RULE_continueStatement, start, vec![self.term(), self.expect(Semi)?])],
+            Break => vec![rule(RULE_breakStatement, start, vec![self.term(), self.expect(Semi)?])],
+            Return => {
+                let mut children = vec![self.term()];
+                if self.la(0) != Semi {
+                    children.push(self.expression()?);
+                }
+                children.push(self.expect(Semi)?);
+                vec![rule(RULE_returnStatement, start, children)]
+            }
+            _ => vec![self.term(), self.expect(Identifier)?, self.expect(Semi)?],
+        };
+        Ok(rule(RULE_jumpStatement, start, children))
+    }
+    fn breakpoint_statement(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.term(), self.expect(Identifier)?];
+        if self.la(0) == LeftParen {
+            children.push(self.term());
+            if self.la(0) != RightParen {
+                loop {
+                    let arg_start = self.pos;
+                    let mut arg_children = vec![self.expect(Identifier)?];
+                    if self.la(0) == Dot {
+                        arg_children.push(self.term());
+                        arg_children.push(self.expect(Identifier)?);
+                    }
+                    children.push(rule(RULE_breakpointArg, arg_start, arg_children));
+                    if self.la(0) != Comma { break }
+                    children.push(self.term());
+                }
+            }
+            children.push(self.expect(RightParen)?);
+        }
+        children.push(self.expect(Semi)?);
+        Ok(rule(RULE_breakpointStatement, start, children))
+    }
+
+    // ---------------- 表达式 ----------------
+    fn expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![self.assignment_expression()?];
+        while self.la(0) == Comma {
+            children.push(self.term());
+            children.push(self.assignment_expression()?);
+        }
+        Ok(rule(RULE_expression, start, children))
+    }
+    fn constant_expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        Ok(rule(RULE_constantExpression, start, vec![self.conditional_expression(None)?]))
+    }
+    /// 两个分支都以 unaryExpression 开头，先解析 unaryExpression ，后面不是赋值运算符时再把它作为 conditionalExpression 的最左操作数，避免回溯
+    fn assignment_expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        if self.la(0) == DigitSequence {
+            return Ok(rule(RULE_assignmentExpression, start, vec![self.term()]))
+        }
+        let unary = self.unary_expression()?;
+        let children = if ASSIGNMENT_OPERATOR_TOKENS.contains(&self.la(0)) {
+            let op_start = self.pos;
+            let op = rule(RULE_assignmentOperator, op_start, vec![self.term()]);
+            vec![unary, op, self.assignment_expression()?]
+        }else {
+            vec![self.conditional_expression(Some(unary))?]
+        };
+        Ok(rule(RULE_assignmentExpression, start, children))
+    }
+    /// op_first_unary 是已经解析好的最左边的 unaryExpression
+    fn conditional_expression(&mut self, op_first_unary:Option<ParseNode>) -> Result<ParseNode> {
+        let logical_or = self.binary_expression(BINARY_LEVELS.len() - 1, op_first_unary)?;
+        let start = logical_or.start();
+        let mut children = vec![logical_or];
+        if self.la(0) == Question {
+            children.push(self.term());
+            children.push(self.expression()?);
+            children.push(self.expect(Colon)?);
+            children.push(self.conditional_expression(None)?);
+        }
+        Ok(rule(RULE_conditionalExpression, start, children))
+    }
+    fn binary_expression(&mut self, level:usize, mut op_first_unary:Option<ParseNode>) -> Result<ParseNode> {
+        let (rule_id, ops) = BINARY_LEVELS[level];
+        let operand = |parser:&mut Self, op_first_unary:Option<ParseNode>| if level == 0 { parser.cast_expression(op_first_unary) } else { parser.binary_expression(level - 1, op_first_unary) };
+        let first = operand(self, op_first_unary.take())?;
+        let start = first.start();
+        let mut children = vec![first];
+        while ops.contains(&self.la(0)) {
+            children.push(self.term());
+            children.push(operand(self, None)?);
+        }
+        Ok(rule(rule_id, start, children))
+    }
+    fn cast_expression(&mut self, op_first_unary:Option<ParseNode>) -> Result<ParseNode> {
+        let unary = match op_first_unary {
+            Some(unary) => unary,
+            None => self.unary_expression()?,
+        };
+        let start = unary.start();
+        Ok(rule(RULE_castExpression, start, vec![unary]))
+    }
+    fn unary_expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = match self.la(0) {
+            la0 if UNARY_OPERATOR_TOKENS.contains(&la0) => {
+                let op = rule(RULE_unaryOperator, start, vec![self.term()]);
+                vec![op, self.cast_expression(None)?]
+            }
+            Sizeof | Alignof => vec![self.term(), self.expect(LeftParen)?, self.type_name()?, self.expect(RightParen)?],
+            _ => vec![self.postfix_expression()?],
+        };
+        Ok(rule(RULE_unaryExpression, start, children))
+    }
+    /// postfixExpression 是左递归的，每个后缀都把之前的 postfixExpression 作为第一个孩子包一层
+    fn postfix_expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut node = rule(RULE_postfixExpression, start, vec![self.primary_expression()?]);
+        loop {
+            let mut children = vec![node];
+            match self.la(0) {
+                LeftBracket => {
+                    children.push(self.term());
+                    children.push(self.expression()?);
+                    children.push(self.expect(RightBracket)?);
+                }
+                LeftParen => {
+                    children.push(self.term());
+                    children.push(self.argument_expression_list()?);
+                    children.push(self.expect(RightParen)?);
+                }
+                Dot | Arrow => {
+                    children.push(self.term());
+                    children.push(self.expect(Identifier)?);
+                }
+                PlusPlus | MinusMinus => children.push(self.term()),
+                _ => return Ok(children.pop().unwrap()),
+            }
+            node = rule(RULE_postfixExpression, start, children);
+        }
+    }
+    /// 没有实参时也有一个空的 argumentExpressionList
+    fn argument_expression_list(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let mut children = vec![];
+        if self.la(0) != RightParen {
+            children.push(self.assignment_expression()?);
+            while self.la(0) == Comma {
+                children.push(self.term());
+                children.push(self.assignment_expression()?);
+            }
+        }
+        Ok(rule(RULE_argumentExpressionList, start, children))
+    }
+    fn primary_expression(&mut self) -> Result<ParseNode> {
+        let start = self.pos;
+        let children = match self.la(0) {
+            Identifier | Constant => vec![self.term()],
+            StringLiteral => {
+                let mut children = vec![];
+                while self.la(0) == StringLiteral {
+                    children.push(self.term());
+                }
+                children
+            }
+            LeftParen => vec![self.term(), self.expression()?, self.expect(RightParen)?],
+            _ => return Err(self.error("期望表达式")),
+        };
+        Ok(rule(RULE_primaryExpression, start, children))
+    }
+
+    /// 按先序把 node 加入 ast_tree ，返回这个节点的文本，即所有 token 文本的拼接
+    fn add_to_ast_tree(&self, node:&ParseNode, op_parent:Option<u32>, ast_tree:&mut AstTree, debug:bool) -> String {
+        let (rule_id, is_terminal, token) = match node {
+            ParseNode::Rule { rule_id, start, .. } => (*rule_id, false, &self.tokens[*start]),
+            ParseNode::Term(token_idx) => (self.tokens[*token_idx].token_type as usize, true, &self.tokens[*token_idx]),
+        };
+        let op_text = if is_terminal { Some(token.text.to_string()) } else { None };
+        let mut ast_node = AstNode::new(rule_id, op_text, is_terminal);
+        ast_node.node_index = ast_tree.node_count() as u32;
+        let (line, col) = if op_parent.is_none() && self.op_leading_hidden.is_some() { (1, 0) } else { (token.line, token.col) };
+        ast_node.src_loc = self.line_map.get_src_line(line).map(|(file, src_line)| SrcLoc::new(file.clone(), *src_line, col + 1));
+        let node_id = ast_tree.add_node(ast_node).index() as u32;
+        if let Some(parent) = op_parent {
+            node_mut!(at parent in ast_tree).child_vec.push(node_id);
+            ast_tree.add_edge(NodeIndex::from(parent), NodeIndex::from(node_id), ());
+        }
+        match node {
+            ParseNode::Rule { rule_id, children, .. } => {
+                let mut text = if op_parent.is_none() { self.op_leading_hidden.unwrap_or_default().to_string() } else { String::new() };
+                for child in children {
+                    let child_text = self.add_to_ast_tree(child, Some(node_id), ast_tree, debug);
+                    if debug { text.push_str(&child_text); }
+                }
+                // antlr 的 compilationUnit 包含 EOF ，它的文本是 <EOF>
+                if debug && *rule_id == RULE_compilationUnit { text.push_str("<EOF>"); }
+                if debug { node_mut!(at node_id in ast_tree).op_text = Some(text.clone()); }
+                text
+            }
+            ParseNode::Term(_) => token.text.to_string(),
+        }
+    }
+}
+
+fn token_name(token_type:isize) -> String {
+    match _LITERAL_NAMES.get(token_type as usize).copied().flatten() {
+        Some(literal_name) => literal_name.to_string(),
+        None => _SYMBOLIC_NAMES.get(token_type as usize).copied().flatten().unwrap_or("<EOF>").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use petgraph::stable_graph::NodeIndex;
+
+    use super::parse_by_native;
+    use crate::{antlr_parser::cparser::{RULE_blockItem, RULE_declaration, RULE_statement}, toolkit::{ast_node::find_dfs_rule_ast, gen_ast::{cross_check_ast_tree, parse_by_antlr}, preprocess::Preprocessor}};
+
+    /// 前两个 demo 中有 C 的类型转换和 case 之后直接声明，文法本身不接受，antlr 也会报错  
+    /// antlr 在未优化编译时解析 33_multi_branch.sy 需要几分钟，它只用 --parser check 在 release 下检查
+    const SKIPPED_FILES:&[&str] = &["demo_break.c", "demo_cast.c", "33_multi_branch.sy"];
+
+    #[test]
+    fn test_cross_check_with_antlr() {
+        // 未优化编译时 antlr 在长代码上递归很深，默认的测试线程栈不够
+        std::thread::Builder::new().stack_size(256 << 20).spawn(cross_check_corpus).unwrap().join().unwrap();
+    }
+    /// `T *p;` 与 `T *a[2], *q;` 中 T 是 typedef 名时是声明，antlr 解析成乘法表达式之后要换成 declaration ，并与手写 parser 一致
+    #[test]
+    fn test_typedef_ptr_decl() {
+        let mut preprocessor = Preprocessor::new(vec![]);
+        preprocessor.process_text("typedef int T;\nint main(){ int T2 = 2; T *p; T *a[2], *q; T2 * T2; return 0; }\n", "typedef_ptr_decl.c", None).unwrap();
+        let (code, line_map) = (&preprocessor.code, &preprocessor.line_map);
+        let antlr_ast_tree = parse_by_antlr(code, line_map, false);
+        let native_ast_tree = parse_by_native(code, line_map, false).unwrap();
+        cross_check_ast_tree(&antlr_ast_tree, &native_ast_tree).unwrap();
+        let block_item_kinds = find_dfs_rule_ast(&antlr_ast_tree, 0, RULE_blockItem).map(|block_item_node| antlr_ast_tree[NodeIndex::from(antlr_ast_tree[NodeIndex::from(block_item_node)].child_vec[0])].rule_id).collect::<Vec<_>>();
+        assert_eq!(block_item_kinds, vec![RULE_declaration, RULE_declaration, RULE_declaration, RULE_statement, RULE_statement]);
+    }
+    fn cross_check_corpus() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let mut paths:Vec<PathBuf> = [root.join("for_auto_test/00sy"), root.join("demos")].iter()
+            .flat_map(|dir| fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sy" || ext == "c"))
+            .filter(|path| !SKIPPED_FILES.contains(&path.file_name().unwrap().to_str().unwrap()))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let mut preprocessor = Preprocessor::new(vec![]);
+            preprocessor.process_file(&path).unwrap();
+            let (code, line_map) = (&preprocessor.code, &preprocessor.line_map);
+            let antlr_ast_tree = parse_by_antlr(code, line_map, false);
+            let native_ast_tree = parse_by_native(code, line_map, false).unwrap_or_else(|e| panic!("{} 解析失败 {}", path.display(), e));
+            cross_check_ast_tree(&antlr_ast_tree, &native_ast_tree).unwrap_or_else(|e| panic!("{} {}", path.display(), e));
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
+use anyhow::{anyhow, Result};
+
+use crate::{instr, node};
+
+use super::cfg_node::{CfgGraph, CfgNodeType, CFG_ROOT};
+use super::diagnostic::{Diagnostic, Severity, WarnKind};
+use super::etc::dfs;
+use super::nhwc_instr::{ArithOp, InstrSlab, NhwcInstr, NhwcInstrType};
+use super::symtab::{SymIdx, SymTab, WithBorrow};
+
+/// 由命令行中的 `-W` 选项决定打开哪些警告，以及是否把警告当作错误
+#[derive(Clone, Debug, Default)]
+pub struct WarnConfig {
+    pub enabled:HashSet<WarnKind>,
+    pub is_werror:bool,
+}
+impl WarnConfig {
+    /// 解析 `-Wall` `-Werror` `-W<kind>` 以及 `-Wno-<kind>` ，按出现顺序生效
+    pub fn from_flags(flags:&[String]) -> Result<Self> {
+        let mut config = WarnConfig { enabled:HashSet::new(), is_werror:false };
+        for flag in flags {
+            match flag.as_str() {
+                "all" => config.enabled.extend(WarnKind::ALL),
+                "error" => config.is_werror = true,
+                _ => match flag.strip_prefix("no-") {
+                    Some(name) => { config.enabled.remove(&WarnKind::from_flag_name(name).ok_or_else(|| anyhow!("未知的警告选项 -W{}", flag))?); }
+                    None => { config.enabled.insert(WarnKind::from_flag_name(flag).ok_or_else(|| anyhow!("未知的警告选项 -W{}", flag))?); }
+                },
+            }
+        }
+        Ok(config)
+    }
+    pub fn is_any_enabled(&self) -> bool { !self.enabled.is_empty() }
+    /// 过滤掉没有打开的警告，按位置排序去重，-Werror 时把警告提升为错误
+    pub fn apply(&self, diagnostics:Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let mut diagnostics:Vec<Diagnostic> = diagnostics.into_iter().filter(|d| d.op_warn_kind.is_some_and(|kind| self.enabled.contains(&kind))).map(|mut d| {
+            if self.is_werror { d.severity = Severity::Error }
+            d
+        }).collect();
+        diagnostics.sort_by_key(|d| (d.src_loc.as_ref().map(|l| (l.file.clone(), l.line, l.col)), d.op_warn_kind, d.msg.clone()));
+        diagnostics.dedup_by(|a, b| a.render_line() == b.render_line());
+        diagnostics
+    }
+}
+
+/// 在 return 、break 、continue 或 goto 之后的语句所在的 cfg 节点没有前驱，从 CFG_ROOT 出发无法到达
+/// 必须在 Ncfg2DjgPass 删除这些孤立节点之前调用，每一段不可达代码只报告开头的第一个节点
+pub fn collect_unreachable_warnings(cfg_graph:&CfgGraph) -> Vec<Diagnostic> {
+    let reachable:HashSet<u32> = dfs(cfg_graph, CFG_ROOT).into_iter().collect();
+    let mut unreachable_nodes:Vec<u32> = cfg_graph.node_indices().map(|n| n.index() as u32).filter(|cfg_node| !reachable.contains(cfg_node)).filter(|&cfg_node| {
+        let cfg_node_struct = node!(at cfg_node in cfg_graph);
+        let has_code = match &cfg_node_struct.cfg_node_type {
+            CfgNodeType::BasicBlock { ast_nodes } => !ast_nodes.is_empty(),
+            CfgNodeType::Branch { .. } | CfgNodeType::Switch { .. } | CfgNodeType::ForLoop { .. } | CfgNodeType::WhileLoop { .. } | CfgNodeType::DoWhileLoop { .. } => true,
+            CfgNodeType::Entry { .. } | CfgNodeType::Exit { .. } | CfgNodeType::Gather {} | CfgNodeType::Root { .. } => false,
+        };
+        has_code && cfg_node_struct.get_op_src_loc().is_some()
+    }).collect();
+    unreachable_nodes.sort_by_key(|&cfg_node| node!(at cfg_node in cfg_graph).get_src_loc().clone().line);
+    let mut covered = HashSet::new();
+    let mut diagnostics = vec![];
+    for cfg_node in unreachable_nodes {
+        if covered.contains(&cfg_node) { continue }
+        covered.extend(dfs(cfg_graph, cfg_node));
+        diagnostics.push(Diagnostic::new_warning(WarnKind::Unreachable, Some(node!(at cfg_node in cfg_graph).get_src_loc().clone()), "这段代码永远不会被执行".to_string()));
+    }
+    diagnostics
+}
+
+/// 在 SsaPass 之后、gvn 之前调用，此时每个变量的每次定值都有自己的 ssa 版本，并且还没有被常量传播改写
+/// 收集未使用的局部变量、未初始化就被读取的变量以及除以常量 0 的警告
+pub fn collect_ssa_warnings(cfg_graph:&CfgGraph, instr_slab:&InstrSlab<NhwcInstr>, symtab:&SymTab) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for (_func_symidx, cfg_entry) in symtab.get_global_info().get_all_cfg_func_symidx_entry_tuples().iter() {
+        let instrs:Vec<usize> = dfs(cfg_graph, *cfg_entry).into_iter().flat_map(|cfg_node| node!(at cfg_node in cfg_graph).iter_all_instrs().cloned().collect::<Vec<_>>()).collect();
+        let src_loc_of = |instr:usize| instr!(at instr in instr_slab).get_op_src_loc().cloned();
+
+        // 源码中声明的标量局部变量，不包括临时变量、形参和全局变量
+        let is_local_var = |symidx:&SymIdx| {
+            if symidx.is_temp() || symidx.is_literal() || symidx.is_global_ptr() { return false }
+            let symbol = symtab.get(&symidx.to_src_symidx());
+            !symbol.get_op_is_temp().is_some_and(|&b| b) && !symbol.get_op_is_global().is_some_and(|&b| b) && !symbol.get_op_is_func_para().is_some_and(|&b| b)
+        };
+        // 所有局部变量都在函数入口处 alloc ，带初值的声明不会生成 DefineVar ，所以从 alloc 中收集
+        let mut declared_vars:Vec<SymIdx> = vec![];
+        // 每个变量第一次被定值的指令，用于定位变量的声明
+        let mut first_def_instr_map:HashMap<SymIdx, usize> = HashMap::new();
+        let mut undef_versions = HashSet::new();
+        let mut read_vars = HashSet::new();
+        for &instr in instrs.iter() {
+            let instr_struct = instr!(at instr in instr_slab);
+            for def_symidx in instr_struct.get_ssa_direct_def_symidx_vec() {
+                first_def_instr_map.entry(def_symidx.as_ref_borrow().to_src_symidx()).or_insert(instr);
+            }
+            match &instr_struct.instr_type {
+                NhwcInstrType::Alloc { var_symidx_vec, vartype:_ } => {
+                    for var_symidx in var_symidx_vec.iter().map(|var_symidx| var_symidx.as_ref_borrow().to_src_symidx()) {
+                        if is_local_var(&var_symidx) && !declared_vars.contains(&var_symidx) {
+                            declared_vars.push(var_symidx);
+                        }
+                    }
+                }
+                NhwcInstrType::DefineVar { var_symidx, vartype, op_value:None } => {
+                    let var_symidx = var_symidx.as_ref_borrow();
+                    if is_local_var(&var_symidx) && !vartype.is_aggregate() {
+                        undef_versions.insert(var_symidx.clone());
+                    }
+                }
+                NhwcInstrType::Arith { lhs:_, rhs:ArithOp::Div { a:_, b, vartype:_ } | ArithOp::Mod { a:_, b, vartype:_ } } if is_zero_int_literal(&b.as_ref_borrow()) => {
+                    diagnostics.push(Diagnostic::new_warning(WarnKind::DivByZero, src_loc_of(instr), "除数为 0".to_string()));
+                }
+                _ => {}
+            }
+            // phi 只是把不同路径上的版本合并起来，不算读取，mu 和 chi 也不是源码中的读取
+            if !matches!(instr_struct.instr_type, NhwcInstrType::Phi { .. } | NhwcInstrType::Mu { .. } | NhwcInstrType::Chi { .. }) {
+                for use_symidx in instr_struct.get_ssa_direct_use_symidx_vec() {
+                    read_vars.insert(use_symidx.as_ref_borrow().to_src_symidx());
+                }
+            }
+        }
+
+        // 只要 phi 的某一个来源可能未初始化，phi 的结果就可能未初始化
+        let mut maybe_undef_versions = undef_versions.clone();
+        let mut phi_sources:HashMap<SymIdx, Vec<SymIdx>> = HashMap::new();
+        for &instr in instrs.iter() {
+            if let NhwcInstrType::Phi { lhs, rhs } = &instr!(at instr in instr_slab).instr_type {
+                phi_sources.insert(lhs.as_ref_borrow().clone(), rhs.phi_pairs.iter().map(|pair| pair.symidx.as_ref_borrow().clone()).collect());
+            }
+        }
+        loop {
+            let newly_undef:Vec<SymIdx> = phi_sources.iter().filter(|(lhs, srcs)| !maybe_undef_versions.contains(*lhs) && srcs.iter().any(|src| maybe_undef_versions.contains(src))).map(|(lhs, _)| lhs.clone()).collect();
+            if newly_undef.is_empty() { break }
+            maybe_undef_versions.extend(newly_undef);
+        }
+        for &instr in instrs.iter() {
+            let instr_struct = instr!(at instr in instr_slab);
+            if matches!(instr_struct.instr_type, NhwcInstrType::Phi { .. } | NhwcInstrType::Mu { .. } | NhwcInstrType::Chi { .. }) { continue }
+            for use_symidx in instr_struct.get_ssa_direct_use_symidx_vec() {
+                let use_symidx = use_symidx.as_ref_borrow();
+                let msg = if undef_versions.contains(&*use_symidx) {
+                    format!("变量 `{}` 未初始化就被使用", use_symidx.symbol_name)
+                } else if maybe_undef_versions.contains(&*use_symidx) {
+                    format!("变量 `{}` 可能未初始化就被使用", use_symidx.symbol_name)
+                } else {
+                    continue
+                };
+                diagnostics.push(Diagnostic::new_warning(WarnKind::Uninitialized, src_loc_of(instr), msg));
+            }
+        }
+
+        for var_symidx in declared_vars {
+            if !read_vars.contains(&var_symidx) {
+                let op_src_loc = first_def_instr_map.get(&var_symidx).and_then(|&def_instr| src_loc_of(def_instr));
+                diagnostics.push(Diagnostic::new_warning(WarnKind::Unused, op_src_loc, format!("变量 `{}` 定义了但从未被读取", var_symidx.symbol_name)));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// 整数字面量 0 ，包括 `00` `0x0` 这样的写法，浮点数除以 0 不报警告
+fn is_zero_int_literal(symidx:&SymIdx) -> bool {
+    if !symidx.is_literal() { return false }
+    let text = symidx.symbol_name;
+    let digits = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text);
+    !digits.is_empty() && digits.chars().all(|c| c == '0')
+}